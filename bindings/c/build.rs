@@ -4,6 +4,12 @@ use std::path::PathBuf;
 /// The directory where the generated header file will be written.
 const DIR_FOR_HEADER: &str = "build";
 
+/// The prefix used for the macros that are added to the generated header.
+const MACRO_PREFIX: &str = "ETH_KZG";
+
+/// The header-only C++ wrapper, which is written next to the generated header.
+const CPP_WRAPPER: &str = include_str!("cpp/c_eth_kzg.hpp");
+
 fn main() {
     // linker flags
     // Link libm on Unix-like systems (needed due to use of num_cpus crate)
@@ -11,21 +17,59 @@ fn main() {
     println!("cargo:rustc-link-lib=m");
 
     println!("cargo:rerun-if-changed=src/");
+    println!("cargo:rerun-if-changed=cpp/");
     let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     let package_name = env::var("CARGO_PKG_NAME").unwrap();
 
     let path_to_crate_dir = PathBuf::from(&crate_dir);
 
-    let output_file = PathBuf::from(&path_to_crate_dir)
-        .join(DIR_FOR_HEADER)
+    let output_dir = path_to_crate_dir.join(DIR_FOR_HEADER);
+    let output_file = output_dir
         .join(format!("{}.h", package_name))
         .display()
         .to_string();
 
+    // Doc comments on the exported items are copied into the header, so that
+    // it can be used as the reference for the C API.
+    let mut config = cbindgen::Config::default();
+    config.language = cbindgen::Language::C;
+    config.include_guard = Some(format!("{}_H", package_name.to_uppercase()));
+    config.autogen_warning = Some(
+        "/* Warning: This file is generated by cbindgen. Do not modify it manually. */".to_string(),
+    );
+    config.after_includes = Some(version_macros());
+    config.documentation = true;
+    // Wraps the declarations in `extern "C"` when the header is included from C++.
+    config.cpp_compat = true;
+
     cbindgen::Builder::new()
         .with_crate(crate_dir)
-        .with_language(cbindgen::Language::C)
+        .with_config(config)
         .generate()
         .unwrap()
         .write_to_file(output_file);
+
+    std::fs::write(
+        output_dir.join(format!("{}.hpp", package_name)),
+        format!(
+            "/* Warning: This file is copied from `cpp/{package_name}.hpp` by build.rs. Do not modify it manually. */\n\n{CPP_WRAPPER}"
+        ),
+    )
+    .unwrap();
+}
+
+/// Returns the macros that describe the version of the library that the header was generated for.
+fn version_macros() -> String {
+    let version = env::var("CARGO_PKG_VERSION").unwrap();
+    let major = env::var("CARGO_PKG_VERSION_MAJOR").unwrap();
+    let minor = env::var("CARGO_PKG_VERSION_MINOR").unwrap();
+    let patch = env::var("CARGO_PKG_VERSION_PATCH").unwrap();
+
+    format!(
+        "\n\
+         #define {MACRO_PREFIX}_VERSION_MAJOR {major}\n\
+         #define {MACRO_PREFIX}_VERSION_MINOR {minor}\n\
+         #define {MACRO_PREFIX}_VERSION_PATCH {patch}\n\
+         #define {MACRO_PREFIX}_VERSION \"{version}\""
+    )
 }
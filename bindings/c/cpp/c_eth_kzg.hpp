@@ -0,0 +1,261 @@
+#ifndef C_ETH_KZG_HPP
+#define C_ETH_KZG_HPP
+
+/*
+ * A header-only C++20 wrapper around the C API in `c_eth_kzg.h`.
+ *
+ * - `eth_kzg::PeerDASContext` owns a reference to a context and frees it when it is destroyed.
+ * - Inputs and outputs are passed as `std::span`s of fixed size byte arrays, so their sizes
+ *   are checked by the compiler where possible and the library writes into them directly.
+ * - Every error code is mapped to a subclass of `eth_kzg::Error`, which is thrown instead
+ *   of returning a `CResult`.
+ */
+
+#include <array>
+#include <cstdint>
+#include <span>
+#include <stdexcept>
+#include <string>
+#include <string_view>
+#include <utility>
+#include <vector>
+
+#include "c_eth_kzg.h"
+
+namespace eth_kzg {
+
+using Blob = std::array<uint8_t, BYTES_PER_BLOB>;
+using Cell = std::array<uint8_t, BYTES_PER_CELL>;
+using KZGCommitment = std::array<uint8_t, BYTES_PER_COMMITMENT>;
+using KZGProof = std::array<uint8_t, BYTES_PER_PROOF>;
+
+/// The base class of every exception thrown by the wrapper.
+class Error : public std::runtime_error {
+public:
+    Error(CResultCode code, const std::string &message) : std::runtime_error(message), code_(code) {}
+
+    /// The code that the library returned.
+    CResultCode code() const noexcept { return code_; }
+
+private:
+    CResultCode code_;
+};
+
+/// Thrown for `CResultCode_InvalidProof`.
+class InvalidProofError : public Error {
+    using Error::Error;
+};
+
+/// Thrown for `CResultCode_BadLength`.
+class BadLengthError : public Error {
+    using Error::Error;
+};
+
+/// Thrown for `CResultCode_BadEncoding`.
+class BadEncodingError : public Error {
+    using Error::Error;
+};
+
+/// Thrown for `CResultCode_BadArgument`.
+class BadArgumentError : public Error {
+    using Error::Error;
+};
+
+/// Thrown for `CResultCode_Internal`.
+class InternalError : public Error {
+    using Error::Error;
+};
+
+/// Thrown for `CResultCode_Io`.
+class IoError : public Error {
+    using Error::Error;
+};
+
+/// Thrown for `CResultCode_BufferTooSmall`.
+class BufferTooSmallError : public Error {
+    using Error::Error;
+};
+
+namespace detail {
+
+[[noreturn]] inline void throw_error(CResultCode code, const std::string &message) {
+    switch (code) {
+    case CResultCode_InvalidProof:
+        throw InvalidProofError(code, message);
+    case CResultCode_BadLength:
+        throw BadLengthError(code, message);
+    case CResultCode_BadEncoding:
+        throw BadEncodingError(code, message);
+    case CResultCode_BadArgument:
+        throw BadArgumentError(code, message);
+    case CResultCode_Io:
+        throw IoError(code, message);
+    case CResultCode_BufferTooSmall:
+        throw BufferTooSmallError(code, message);
+    default:
+        throw InternalError(code, message);
+    }
+}
+
+/// Throws the exception for `result` if it is an error, freeing the message that the library allocated.
+inline void check(CResult result) {
+    if (result.status == Ok) {
+        return;
+    }
+    std::string message = result.error_msg != nullptr ? result.error_msg : eth_kzg_error_to_string(result.code);
+    eth_kzg_free_error_message(result.error_msg);
+    throw_error(result.code, message);
+}
+
+/// Returns a pointer to the first byte of a span of byte arrays.
+///
+/// The arrays are laid out contiguously, so this is also the layout of the flat methods of the C API.
+template <typename T> const uint8_t *bytes(std::span<const T> items) noexcept {
+    return items.empty() ? nullptr : items.front().data();
+}
+
+template <typename T> uint8_t *bytes(std::span<T> items) noexcept {
+    return items.empty() ? nullptr : items.front().data();
+}
+
+} // namespace detail
+
+/// All of the cells in an extended blob, along with a KZG proof for each of them.
+struct CellsAndProofs {
+    std::vector<Cell> cells;
+    std::vector<KZGProof> proofs;
+};
+
+/// A context that is used to create and verify the KZG proofs of cells.
+///
+/// The context is expensive to create, so it should be created once and reused. Copies share
+/// the same underlying context, and it is safe to use from multiple threads.
+class PeerDASContext {
+public:
+    /// Creates a context that uses the embedded trusted setup.
+    ///
+    /// `num_threads` can be set to `0` to let the library pick a sensible default.
+    explicit PeerDASContext(bool use_precomp = true, uint32_t num_threads = 1) {
+        CResultCode code = CResultCode_Ok;
+        ctx_ = eth_kzg_das_context_new(use_precomp, num_threads, &code);
+        if (ctx_ == nullptr) {
+            detail::throw_error(code, eth_kzg_error_to_string(code));
+        }
+    }
+
+    /// Creates a context that uses the embedded trusted setup with the given options.
+    explicit PeerDASContext(const DASContextConfig &config) {
+        CResultCode code = CResultCode_Ok;
+        ctx_ = eth_kzg_das_context_new_with_config(&config, &code);
+        if (ctx_ == nullptr) {
+            detail::throw_error(code, eth_kzg_error_to_string(code));
+        }
+    }
+
+    /// Creates a context from a trusted setup in the JSON format used by the consensus specs.
+    static PeerDASContext from_trusted_setup(std::string_view json, const DASContextConfig &config) {
+        DASContext *ctx = nullptr;
+        detail::check(eth_kzg_das_context_new_from_setup(json.data(), json.size(), &config, &ctx));
+        return PeerDASContext(ctx);
+    }
+
+    /// Creates a context from the trusted setup stored in the file at `path`.
+    static PeerDASContext from_trusted_setup_file(const std::string &path, const DASContextConfig &config) {
+        DASContext *ctx = nullptr;
+        detail::check(eth_kzg_das_context_new_from_setup_file(path.c_str(), &config, &ctx));
+        return PeerDASContext(ctx);
+    }
+
+    PeerDASContext(const PeerDASContext &other) : ctx_(eth_kzg_das_context_clone(other.ctx_)) {}
+
+    PeerDASContext(PeerDASContext &&other) noexcept : ctx_(std::exchange(other.ctx_, nullptr)) {}
+
+    PeerDASContext &operator=(PeerDASContext other) noexcept {
+        std::swap(ctx_, other.ctx_);
+        return *this;
+    }
+
+    ~PeerDASContext() { eth_kzg_das_context_free(ctx_); }
+
+    /// Returns the underlying context, for calling the C API directly.
+    ///
+    /// The pointer is owned by this object and must not be freed.
+    DASContext *get() const noexcept { return ctx_; }
+
+    /// Computes the KZG commitment for the blob.
+    KZGCommitment blob_to_kzg_commitment(std::span<const uint8_t, BYTES_PER_BLOB> blob) const {
+        KZGCommitment commitment;
+        detail::check(eth_kzg_blob_to_kzg_commitment(ctx_, blob.data(), commitment.data()));
+        return commitment;
+    }
+
+    /// Computes the cells and the KZG proofs for the blob.
+    CellsAndProofs compute_cells_and_kzg_proofs(std::span<const uint8_t, BYTES_PER_BLOB> blob) const {
+        CellsAndProofs result{std::vector<Cell>(CELLS_PER_EXT_BLOB), std::vector<KZGProof>(CELLS_PER_EXT_BLOB)};
+        compute_cells_and_kzg_proofs(blob, result.cells, result.proofs);
+        return result;
+    }
+
+    /// Computes the cells and the KZG proofs for the blob, writing them to `cells` and `proofs`.
+    ///
+    /// Each output must have room for at least `CELLS_PER_EXT_BLOB` items.
+    void compute_cells_and_kzg_proofs(std::span<const uint8_t, BYTES_PER_BLOB> blob, std::span<Cell> cells,
+                                      std::span<KZGProof> proofs) const {
+        detail::check(eth_kzg_compute_cells_and_kzg_proofs_flat(ctx_, blob.data(), cells.size(), detail::bytes(cells),
+                                                                proofs.size(), detail::bytes(proofs)));
+    }
+
+    /// Computes the cells for the blob, without computing their KZG proofs.
+    std::vector<Cell> compute_cells(std::span<const uint8_t, BYTES_PER_BLOB> blob) const {
+        std::vector<Cell> cells(CELLS_PER_EXT_BLOB);
+        compute_cells(blob, cells);
+        return cells;
+    }
+
+    /// Computes the cells for the blob, writing them to `cells`.
+    ///
+    /// `cells` must have room for at least `CELLS_PER_EXT_BLOB` items.
+    void compute_cells(std::span<const uint8_t, BYTES_PER_BLOB> blob, std::span<Cell> cells) const {
+        detail::check(eth_kzg_compute_cells_flat(ctx_, blob.data(), cells.size(), detail::bytes(cells)));
+    }
+
+    /// Verifies a batch of cell proofs.
+    ///
+    /// Returns false if any of the proofs is invalid, and throws if an input is malformed.
+    bool verify_cell_kzg_proof_batch(std::span<const KZGCommitment> commitments,
+                                     std::span<const uint64_t> cell_indices, std::span<const Cell> cells,
+                                     std::span<const KZGProof> proofs) const {
+        bool verified = false;
+        detail::check(eth_kzg_verify_cell_kzg_proof_batch_flat(
+            ctx_, commitments.size(), detail::bytes(commitments), cell_indices.size(), cell_indices.data(),
+            cells.size(), detail::bytes(cells), proofs.size(), detail::bytes(proofs), &verified));
+        return verified;
+    }
+
+    /// Recovers all of the cells and computes their KZG proofs, given at least half of the cells.
+    CellsAndProofs recover_cells_and_kzg_proofs(std::span<const uint64_t> cell_indices,
+                                                std::span<const Cell> cells) const {
+        CellsAndProofs result{std::vector<Cell>(CELLS_PER_EXT_BLOB), std::vector<KZGProof>(CELLS_PER_EXT_BLOB)};
+        recover_cells_and_kzg_proofs(cell_indices, cells, result.cells, result.proofs);
+        return result;
+    }
+
+    /// Recovers all of the cells and computes their KZG proofs, writing them to `out_cells` and `out_proofs`.
+    ///
+    /// Each output must have room for at least `CELLS_PER_EXT_BLOB` items.
+    void recover_cells_and_kzg_proofs(std::span<const uint64_t> cell_indices, std::span<const Cell> cells,
+                                      std::span<Cell> out_cells, std::span<KZGProof> out_proofs) const {
+        detail::check(eth_kzg_recover_cells_and_proofs_flat(
+            ctx_, cells.size(), detail::bytes(cells), cell_indices.size(), cell_indices.data(), out_cells.size(),
+            detail::bytes(out_cells), out_proofs.size(), detail::bytes(out_proofs)));
+    }
+
+private:
+    explicit PeerDASContext(DASContext *ctx) noexcept : ctx_(ctx) {}
+
+    DASContext *ctx_ = nullptr;
+};
+
+} // namespace eth_kzg
+
+#endif /* C_ETH_KZG_HPP */
@@ -0,0 +1,81 @@
+use crate::{CResult, CResultCode};
+use std::ffi::{c_void, CString};
+use std::os::raw::c_char;
+use std::sync::OnceLock;
+
+/// The allocator used for the buffers that are returned to the caller.
+///
+/// This is chosen the first time a buffer is allocated, and cannot be changed afterwards,
+/// so that every buffer is freed by the same allocator that allocated it.
+static ALLOCATOR: OnceLock<Allocator> = OnceLock::new();
+
+enum Allocator {
+    /// Buffers are allocated by Rust's global allocator.
+    Rust,
+    /// Buffers are allocated by the callbacks that the caller registered.
+    Custom {
+        malloc_fn: unsafe extern "C" fn(size: usize) -> *mut c_void,
+        free_fn: unsafe extern "C" fn(ptr: *mut c_void),
+    },
+}
+
+fn allocator() -> &'static Allocator {
+    ALLOCATOR.get_or_init(|| Allocator::Rust)
+}
+
+pub(crate) fn _set_allocator(
+    malloc_fn: Option<unsafe extern "C" fn(size: usize) -> *mut c_void>,
+    free_fn: Option<unsafe extern "C" fn(ptr: *mut c_void)>,
+) -> Result<(), CResult> {
+    let (Some(malloc_fn), Some(free_fn)) = (malloc_fn, free_fn) else {
+        return Err(CResult::with_error(
+            CResultCode::BadArgument,
+            "both the malloc and free callbacks must be set",
+        ));
+    };
+
+    ALLOCATOR
+        .set(Allocator::Custom { malloc_fn, free_fn })
+        .map_err(|_| {
+            CResult::with_error(
+                CResultCode::BadArgument,
+                "the allocator can only be set once, before the library allocates any buffers",
+            )
+        })
+}
+
+/// Copies `message` into a null-terminated string that is owned by the caller.
+///
+/// Returns a null pointer if the custom allocator could not allocate the string.
+pub(crate) fn alloc_c_string(message: &str) -> *mut c_char {
+    let message = CString::new(message).unwrap();
+
+    match allocator() {
+        Allocator::Rust => message.into_raw(),
+        Allocator::Custom { malloc_fn, .. } => {
+            let bytes = message.as_bytes_with_nul();
+            // Safety: The callback was registered by the caller, who guarantees that it behaves like `malloc`.
+            let ptr = unsafe { malloc_fn(bytes.len()) }.cast::<u8>();
+            if ptr.is_null() {
+                return std::ptr::null_mut();
+            }
+            // Safety: `ptr` points to a newly allocated region of memory that is at least `bytes.len()` bytes.
+            unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len()) };
+            ptr.cast()
+        }
+    }
+}
+
+/// Frees a string that was returned by `alloc_c_string`.
+///
+/// # Safety
+///
+/// - The caller must ensure that `ptr` was returned by `alloc_c_string` and has not been freed.
+pub(crate) unsafe fn free_c_string(ptr: *mut c_char) {
+    match allocator() {
+        Allocator::Rust => {
+            let _ = CString::from_raw(ptr);
+        }
+        Allocator::Custom { free_fn, .. } => free_fn(ptr.cast()),
+    }
+}
@@ -1,7 +1,9 @@
 use rust_eth_kzg::constants::{BYTES_PER_BLOB, BYTES_PER_COMMITMENT};
 
-use crate::pointer_utils::{create_array_ref, deref_const, write_to_slice};
-use crate::{CResult, DASContext};
+use crate::pointer_utils::{
+    create_array_ref, deref_const, flat_ptr_to_vec_slice_const, write_to_flat_slice, write_to_slice,
+};
+use crate::{check_output_capacity, CResult, DASContext};
 
 pub(crate) fn _blob_to_kzg_commitment(
     ctx: *const DASContext,
@@ -17,9 +19,7 @@ pub(crate) fn _blob_to_kzg_commitment(
 
     // Computation
     //
-    let commitment = ctx
-        .blob_to_kzg_commitment(blob)
-        .map_err(|err| CResult::with_error(&format!("{:?}", err)))?;
+    let commitment = ctx.blob_to_kzg_commitment(blob).map_err(CResult::from)?;
 
     assert!(
         commitment.len() == BYTES_PER_COMMITMENT,
@@ -34,3 +34,37 @@ pub(crate) fn _blob_to_kzg_commitment(
 
     Ok(())
 }
+
+pub(crate) fn _blobs_to_kzg_commitments(
+    ctx: *const DASContext,
+    blobs_length: u64,
+    blobs: *const u8,
+    out_length: u64,
+    out: *mut u8,
+) -> Result<(), CResult> {
+    assert!(!ctx.is_null(), "context pointer is null");
+
+    // Output capacity checks
+    //
+    check_output_capacity("out", out_length, blobs_length as usize)?;
+
+    // Dereference the input pointers
+    //
+    let ctx = deref_const(ctx);
+    let blobs = flat_ptr_to_vec_slice_const::<BYTES_PER_BLOB>(blobs, blobs_length as usize)?;
+
+    // Computation
+    //
+    let commitments = ctx
+        .blob_to_kzg_commitment_batch(blobs)
+        .map_err(CResult::from)?;
+
+    // Write output to slice
+    //
+    write_to_flat_slice(
+        out,
+        commitments.iter().map(|commitment| commitment.as_slice()),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::pointer_utils::{create_array_ref, deref_const, write_to_2d_slice, write_to_flat_slice};
+use crate::{check_output_capacity, CResult, DASContext};
+use rust_eth_kzg::constants::{BYTES_PER_BLOB, CELLS_PER_EXT_BLOB};
+use rust_eth_kzg::Cell;
+
+pub(crate) fn _compute_cells(
+    ctx: *const DASContext,
+    blob: *const u8,
+    out_cells_length: u64,
+    out_cells: *mut *mut u8,
+) -> Result<(), CResult> {
+    let cells = compute_cells(ctx, blob, out_cells_length)?;
+    let cells_unboxed = cells.map(|cell| cell.to_vec());
+
+    // Write to output
+    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_cells, cells_unboxed);
+
+    Ok(())
+}
+
+pub(crate) fn _compute_cells_flat(
+    ctx: *const DASContext,
+    blob: *const u8,
+    out_cells_length: u64,
+    out_cells: *mut u8,
+) -> Result<(), CResult> {
+    let cells = compute_cells(ctx, blob, out_cells_length)?;
+
+    // Write to output
+    write_to_flat_slice(out_cells, cells.iter().map(|cell| cell.as_slice()));
+
+    Ok(())
+}
+
+fn compute_cells(
+    ctx: *const DASContext,
+    blob: *const u8,
+    out_cells_length: u64,
+) -> Result<[Cell; CELLS_PER_EXT_BLOB], CResult> {
+    assert!(!ctx.is_null(), "context pointer is null");
+
+    // Output capacity checks
+    //
+    check_output_capacity("out_cells", out_cells_length, CELLS_PER_EXT_BLOB)?;
+
+    // Pointer checks
+    //
+    let ctx = deref_const(ctx);
+    let blob = create_array_ref::<BYTES_PER_BLOB, _>(blob);
+
+    // Computation
+    //
+    ctx.compute_cells(blob).map_err(CResult::from)
+}
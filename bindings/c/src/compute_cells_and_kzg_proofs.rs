@@ -1,15 +1,58 @@
-use crate::pointer_utils::{create_array_ref, deref_const, write_to_2d_slice};
-use crate::{CResult, DASContext};
+use crate::pointer_utils::{create_array_ref, deref_const, write_to_2d_slice, write_to_flat_slice};
+use crate::{check_output_capacity, CResult, DASContext};
 use rust_eth_kzg::constants::{BYTES_PER_BLOB, CELLS_PER_EXT_BLOB};
+use rust_eth_kzg::CellsAndKZGProofs;
 
 pub(crate) fn _compute_cells_and_kzg_proofs(
     ctx: *const DASContext,
     blob: *const u8,
+    out_cells_length: u64,
     out_cells: *mut *mut u8,
+    out_proofs_length: u64,
     out_proofs: *mut *mut u8,
 ) -> Result<(), CResult> {
+    let (cells, proofs) =
+        compute_cells_and_kzg_proofs(ctx, blob, out_cells_length, out_proofs_length)?;
+    let cells_unboxed = cells.map(|cell| cell.to_vec());
+
+    // Write to output
+    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_cells, cells_unboxed);
+    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_proofs, proofs);
+
+    Ok(())
+}
+
+pub(crate) fn _compute_cells_and_kzg_proofs_flat(
+    ctx: *const DASContext,
+    blob: *const u8,
+    out_cells_length: u64,
+    out_cells: *mut u8,
+    out_proofs_length: u64,
+    out_proofs: *mut u8,
+) -> Result<(), CResult> {
+    let (cells, proofs) =
+        compute_cells_and_kzg_proofs(ctx, blob, out_cells_length, out_proofs_length)?;
+
+    // Write to output
+    write_to_flat_slice(out_cells, cells.iter().map(|cell| cell.as_slice()));
+    write_to_flat_slice(out_proofs, proofs.iter().map(|proof| proof.as_slice()));
+
+    Ok(())
+}
+
+fn compute_cells_and_kzg_proofs(
+    ctx: *const DASContext,
+    blob: *const u8,
+    out_cells_length: u64,
+    out_proofs_length: u64,
+) -> Result<CellsAndKZGProofs, CResult> {
     assert!(!ctx.is_null(), "context pointer is null");
 
+    // Output capacity checks
+    //
+    check_output_capacity("out_cells", out_cells_length, CELLS_PER_EXT_BLOB)?;
+    check_output_capacity("out_proofs", out_proofs_length, CELLS_PER_EXT_BLOB)?;
+
     // Pointer checks
     //
     let ctx = deref_const(ctx);
@@ -17,14 +60,6 @@ pub(crate) fn _compute_cells_and_kzg_proofs(
 
     // Computation
     //
-    let (cells, proofs) = ctx
-        .compute_cells_and_kzg_proofs(blob)
-        .map_err(|err| CResult::with_error(&format!("{:?}", err)))?;
-    let cells_unboxed = cells.map(|cell| cell.to_vec());
-
-    // Write to output
-    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_cells, cells_unboxed);
-    write_to_2d_slice::<_, CELLS_PER_EXT_BLOB>(out_proofs, proofs);
-
-    Ok(())
+    ctx.compute_cells_and_kzg_proofs(blob)
+        .map_err(CResult::from)
 }
@@ -0,0 +1,153 @@
+use crate::pointer_utils::deref_mut;
+use crate::{
+    free_c_string, into_raw_context, CResult, CResultCode, DASContext, DASContextConfig,
+    TrustedSetup,
+};
+
+pub(crate) fn _das_context_new_with_config(
+    config: *const DASContextConfig,
+) -> Result<DASContext, CResult> {
+    // Dereference the input pointers
+    //
+    let config = DASContextConfig::from_ptr(config);
+    let use_precomp = config.use_precomp()?;
+    let mode = config.mode()?;
+
+    // Computation
+    //
+    DASContext::try_with_trusted_setup(
+        &TrustedSetup::default(),
+        config.num_threads as usize,
+        use_precomp,
+        mode,
+    )
+}
+
+/// Converts the result of creating a context into a pointer to the context, or a null
+/// pointer if it could not be created.
+///
+/// The result code is written to `out_code` if it is not null. These functions do not
+/// return a `CResult`, so the error message is freed here.
+pub(crate) fn context_or_null(
+    ctx: Result<DASContext, CResult>,
+    out_code: *mut CResultCode,
+) -> *mut DASContext {
+    let (ctx, code) = match ctx {
+        Ok(ctx) => (into_raw_context(ctx), CResultCode::Ok),
+        Err(err) => {
+            if !err.error_msg.is_null() {
+                // Safety: The message was allocated by `CResult::with_error` and is not used again.
+                unsafe { free_c_string(err.error_msg) };
+            }
+            (std::ptr::null_mut(), err.code)
+        }
+    };
+
+    if !out_code.is_null() {
+        *deref_mut(out_code) = code;
+    }
+
+    ctx
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eth_kzg_blob_to_kzg_commitment, eth_kzg_das_context_free,
+        eth_kzg_das_context_new_with_config, eth_kzg_free_error_message,
+        eth_kzg_verify_cell_kzg_proof_batch, CResult, CResultCode, DASContextConfig,
+        DASContextMode, BYTES_PER_BLOB, BYTES_PER_COMMITMENT,
+    };
+
+    /// Returns the code of the result, freeing its error message.
+    fn code(result: CResult) -> CResultCode {
+        unsafe { eth_kzg_free_error_message(result.error_msg) };
+        result.code
+    }
+
+    /// Creates a context with the given mode, and returns the result codes of a proving
+    /// and a verification call on it.
+    fn prove_and_verify(mode: DASContextMode) -> (CResultCode, CResultCode) {
+        let config = DASContextConfig {
+            num_threads: 1,
+            precomp_width: 0,
+            mode: mode as u32,
+        };
+        let ctx = unsafe { eth_kzg_das_context_new_with_config(&config, std::ptr::null_mut()) };
+        assert!(!ctx.is_null());
+
+        let blob = vec![0u8; BYTES_PER_BLOB];
+        let mut commitment = [0u8; BYTES_PER_COMMITMENT];
+        let prove = code(eth_kzg_blob_to_kzg_commitment(
+            ctx,
+            blob.as_ptr(),
+            commitment.as_mut_ptr(),
+        ));
+
+        // An empty batch is valid, so this only fails if the context cannot verify proofs
+        let mut verified = false;
+        let null = std::ptr::null();
+        let verify = code(eth_kzg_verify_cell_kzg_proof_batch(
+            ctx,
+            0,
+            null,
+            0,
+            null.cast(),
+            0,
+            null,
+            0,
+            null,
+            &mut verified,
+        ));
+        assert_eq!(verified, verify == CResultCode::Ok);
+
+        eth_kzg_das_context_free(ctx);
+        (prove, verify)
+    }
+
+    #[test]
+    fn null_config_uses_the_defaults() {
+        let mut out_code = CResultCode::Internal;
+        let ctx = unsafe { eth_kzg_das_context_new_with_config(std::ptr::null(), &mut out_code) };
+        assert!(!ctx.is_null());
+        assert_eq!(out_code, CResultCode::Ok);
+        eth_kzg_das_context_free(ctx);
+    }
+
+    #[test]
+    fn out_of_range_mode_is_rejected() {
+        let config = DASContextConfig {
+            num_threads: 1,
+            precomp_width: 0,
+            mode: 3,
+        };
+        let mut out_code = CResultCode::Ok;
+        let ctx = unsafe { eth_kzg_das_context_new_with_config(&config, &mut out_code) };
+        assert!(ctx.is_null());
+        assert_eq!(out_code, CResultCode::BadArgument);
+    }
+
+    #[test]
+    fn both_mode_proves_and_verifies() {
+        assert_eq!(
+            prove_and_verify(DASContextMode::Both),
+            (CResultCode::Ok, CResultCode::Ok)
+        );
+    }
+
+    #[test]
+    fn prover_only_mode_does_not_verify() {
+        assert_eq!(
+            prove_and_verify(DASContextMode::ProverOnly),
+            (CResultCode::Ok, CResultCode::BadArgument)
+        );
+    }
+
+    #[test]
+    fn verifier_only_mode_does_not_prove() {
+        assert_eq!(
+            prove_and_verify(DASContextMode::VerifierOnly),
+            (CResultCode::BadArgument, CResultCode::Ok)
+        );
+    }
+}
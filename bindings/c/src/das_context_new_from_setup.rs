@@ -0,0 +1,81 @@
+use crate::pointer_utils::{create_slice_view, deref_mut};
+use crate::{into_raw_context, CResult, CResultCode, DASContext, DASContextConfig, TrustedSetup};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+pub(crate) fn _das_context_new_from_setup(
+    json: *const c_char,
+    json_length: usize,
+    config: *const DASContextConfig,
+    out: *mut *mut DASContext,
+) -> Result<(), CResult> {
+    assert!(!out.is_null(), "out pointer is null");
+
+    // Dereference the input pointers
+    //
+    let json = create_slice_view(json.cast::<u8>(), json_length);
+    let json = std::str::from_utf8(json).map_err(|err| {
+        CResult::with_error(
+            CResultCode::BadEncoding,
+            &format!("trusted setup is not valid utf-8: {err}"),
+        )
+    })?;
+
+    write_das_context(json, config, out)
+}
+
+pub(crate) fn _das_context_new_from_setup_file(
+    path: *const c_char,
+    config: *const DASContextConfig,
+    out: *mut *mut DASContext,
+) -> Result<(), CResult> {
+    assert!(!path.is_null(), "path pointer is null");
+    assert!(!out.is_null(), "out pointer is null");
+
+    // Dereference the input pointers
+    //
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_str().map_err(|err| {
+        CResult::with_error(
+            CResultCode::BadEncoding,
+            &format!("path is not valid utf-8: {err}"),
+        )
+    })?;
+
+    let json = std::fs::read_to_string(path).map_err(|err| {
+        CResult::with_error(
+            CResultCode::Io,
+            &format!("could not read trusted setup file {path}: {err}"),
+        )
+    })?;
+
+    write_das_context(&json, config, out)
+}
+
+/// Creates a DASContext from the trusted setup and writes a pointer to it to `out`.
+fn write_das_context(
+    json: &str,
+    config: *const DASContextConfig,
+    out: *mut *mut DASContext,
+) -> Result<(), CResult> {
+    let config = DASContextConfig::from_ptr(config);
+    let use_precomp = config.use_precomp()?;
+    let mode = config.mode()?;
+
+    // Computation
+    //
+    let trusted_setup = TrustedSetup::try_from_json(json)
+        .map_err(|err| CResult::from(rust_eth_kzg::Error::from(err)))?;
+    let ctx = DASContext::try_with_trusted_setup(
+        &trusted_setup,
+        config.num_threads as usize,
+        use_precomp,
+        mode,
+    )?;
+
+    // Write output
+    //
+    *deref_mut(out) = into_raw_context(ctx);
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::panic::catch_panic;
+use crate::pointer_utils::deref_const;
+use crate::{CResult, DASContext};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+thread_local! {
+    /// The error message of the most recent failed call made from the current thread on each
+    /// context, keyed by the id of the context.
+    ///
+    /// The messages are freed when the thread exits, so hosts that create and destroy many
+    /// threads do not accumulate messages. The message for a context is also removed when the
+    /// context is freed from this thread.
+    static LAST_ERRORS: RefCell<HashMap<u64, CString>> = RefCell::new(HashMap::new());
+}
+
+/// Identifies a context in the error messages of the current thread.
+///
+/// An id is used instead of the address of the context, since a context that is created after
+/// another one is freed can have the same address.
+#[derive(Debug)]
+pub(crate) struct LastErrors {
+    context_id: u64,
+}
+
+impl Default for LastErrors {
+    fn default() -> Self {
+        static NEXT_CONTEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self {
+            context_id: NEXT_CONTEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl LastErrors {
+    /// Records the error message of `err` as the last error of this context for the current thread.
+    fn record(&self, err: &CResult) {
+        if err.error_msg.is_null() {
+            return;
+        }
+        // Safety: The error message was created by `CResult::with_error` and has not been
+        // returned to the caller yet, so it is a valid null-terminated string.
+        let message = unsafe { CStr::from_ptr(err.error_msg) }.to_owned();
+
+        LAST_ERRORS.with(|last_errors| {
+            last_errors.borrow_mut().insert(self.context_id, message);
+        });
+    }
+
+    /// Returns the last error of this context for the current thread, or a null pointer if
+    /// there is none.
+    ///
+    /// The pointer stays valid until the next error is recorded for this context on the
+    /// current thread.
+    fn get(&self) -> *const c_char {
+        LAST_ERRORS.with(|last_errors| {
+            last_errors
+                .borrow()
+                .get(&self.context_id)
+                .map_or(std::ptr::null(), |message| message.as_ptr())
+        })
+    }
+}
+
+impl Drop for LastErrors {
+    fn drop(&mut self) {
+        // The messages recorded by other threads cannot be reached from here, they are freed
+        // when those threads exit. This fails if the context is freed while the current thread
+        // is exiting, in which case the message has already been freed.
+        let _ = LAST_ERRORS.try_with(|last_errors| {
+            last_errors.borrow_mut().remove(&self.context_id);
+        });
+    }
+}
+
+/// Runs the body of a call on `ctx` and converts its result into a `CResult`, recording the
+/// error for the current thread if the call failed or panicked.
+pub(crate) fn with_last_error(
+    ctx: *const DASContext,
+    body: impl FnOnce() -> Result<(), CResult>,
+) -> CResult {
+    match catch_panic(body) {
+        Ok(_) => CResult::with_ok(),
+        Err(err) => {
+            if !ctx.is_null() {
+                deref_const(ctx).last_errors.record(&err);
+            }
+            err
+        }
+    }
+}
+
+pub(crate) fn _das_context_last_error(ctx: *const DASContext) -> *const c_char {
+    if ctx.is_null() {
+        return std::ptr::null();
+    }
+    deref_const(ctx).last_errors.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LastErrors;
+    use crate::{eth_kzg_free_error_message, CResult, CResultCode};
+    use std::ffi::CStr;
+
+    /// Records an error with the given message for `last_errors`.
+    fn record(last_errors: &LastErrors, message: &str) {
+        let err = CResult::with_error(CResultCode::BadArgument, message);
+        last_errors.record(&err);
+        unsafe { eth_kzg_free_error_message(err.error_msg) };
+    }
+
+    fn message(last_errors: &LastErrors) -> Option<String> {
+        let message = last_errors.get();
+        (!message.is_null()).then(|| {
+            unsafe { CStr::from_ptr(message) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    #[test]
+    fn each_context_keeps_its_own_error() {
+        let first = LastErrors::default();
+        let second = LastErrors::default();
+        assert_eq!(message(&first), None);
+
+        record(&first, "first");
+        record(&second, "second");
+        assert_eq!(message(&first).as_deref(), Some("first"));
+        assert_eq!(message(&second).as_deref(), Some("second"));
+
+        // Errors are recorded per thread
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert_eq!(message(&first), None));
+        });
+
+        let id = first.context_id;
+        drop(first);
+        super::LAST_ERRORS.with(|last_errors| assert!(!last_errors.borrow().contains_key(&id)));
+    }
+}
@@ -1,25 +1,92 @@
+mod allocator;
+use allocator::{_set_allocator, alloc_c_string, free_c_string};
+
 mod blob_to_kzg_commitment;
-use blob_to_kzg_commitment::_blob_to_kzg_commitment;
+use blob_to_kzg_commitment::{_blob_to_kzg_commitment, _blobs_to_kzg_commitments};
 
 mod compute_cells_and_kzg_proofs;
-use compute_cells_and_kzg_proofs::_compute_cells_and_kzg_proofs;
+use compute_cells_and_kzg_proofs::{
+    _compute_cells_and_kzg_proofs, _compute_cells_and_kzg_proofs_flat,
+};
+
+mod last_error;
+use last_error::{_das_context_last_error, with_last_error, LastErrors};
+
+mod logging;
+use logging::_set_log_callback;
+
+mod panic;
+use panic::{catch_panic, panic_message};
+
+mod compute_cells;
+use compute_cells::{_compute_cells, _compute_cells_flat};
 
 mod verify_cells_and_kzg_proofs_batch;
-use rust_eth_kzg::constants::RECOMMENDED_PRECOMP_WIDTH;
+pub use rust_eth_kzg::constants::RECOMMENDED_PRECOMP_WIDTH;
 use rust_eth_kzg::ThreadCount;
-use verify_cells_and_kzg_proofs_batch::_verify_cell_kzg_proof_batch;
+use verify_cells_and_kzg_proofs_batch::{
+    _verify_cell_kzg_proof_batch, _verify_cell_kzg_proof_batch_flat,
+};
+
+mod das_context_new;
+use das_context_new::{_das_context_new_with_config, context_or_null};
+
+mod das_context_new_from_setup;
+use das_context_new_from_setup::{_das_context_new_from_setup, _das_context_new_from_setup_file};
 
 mod recover_cells_and_kzg_proofs;
-use recover_cells_and_kzg_proofs::_recover_cells_and_proofs;
+use recover_cells_and_kzg_proofs::{_recover_cells_and_proofs, _recover_cells_and_proofs_flat};
 
 pub(crate) mod pointer_utils;
+use pointer_utils::deref_const;
 
-pub use rust_eth_kzg::constants::{
-    BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT,
-    CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_BLOB,
+use rust_eth_kzg::ContextMode;
+pub use rust_eth_kzg::{
+    verification_result_to_bool, CellIndex, Error, ErrorKind, TrustedSetup, UsePrecomp,
+    EMBEDDED_TRUSTED_SETUP_ID,
 };
-pub use rust_eth_kzg::Error;
 use std::ops::Deref;
+use std::sync::Arc;
+
+// The constants below are defined here, rather than re-exported from the eip7594 library,
+// so that cbindgen adds them to the generated header. The assertions at the end of this
+// block check that they match the values used by the library.
+
+/// The number of bytes needed to represent a field element.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+
+/// The number of field elements in a blob.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+
+/// The number of field elements in a cell.
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
+
+/// The number of bytes in a blob.
+pub const BYTES_PER_BLOB: usize = 131072;
+
+/// The number of bytes in a cell.
+pub const BYTES_PER_CELL: usize = 2048;
+
+/// The number of bytes in a KZG commitment.
+pub const BYTES_PER_COMMITMENT: usize = 48;
+
+/// The number of bytes in a KZG proof.
+pub const BYTES_PER_PROOF: usize = 48;
+
+/// The number of cells in an extended blob.
+pub const CELLS_PER_EXT_BLOB: usize = 128;
+
+const _: () = {
+    use rust_eth_kzg::constants;
+    assert!(BYTES_PER_FIELD_ELEMENT == constants::BYTES_PER_FIELD_ELEMENT);
+    assert!(FIELD_ELEMENTS_PER_BLOB == constants::FIELD_ELEMENTS_PER_BLOB);
+    assert!(FIELD_ELEMENTS_PER_CELL == constants::FIELD_ELEMENTS_PER_CELL);
+    assert!(BYTES_PER_BLOB == constants::BYTES_PER_BLOB);
+    assert!(BYTES_PER_CELL == constants::BYTES_PER_CELL);
+    assert!(BYTES_PER_COMMITMENT == constants::BYTES_PER_COMMITMENT);
+    assert!(BYTES_PER_PROOF == constants::BYTES_PER_PROOF);
+    assert!(CELLS_PER_EXT_BLOB == constants::CELLS_PER_EXT_BLOB);
+};
 
 /*
  * Note: All methods in this file have been prefixed with `eth_kzg`.
@@ -36,9 +103,70 @@ use std::ops::Deref;
 #[derive(Default)]
 pub struct DASContext {
     inner: rust_eth_kzg::DASContext,
+    last_errors: LastErrors,
 }
 
 impl DASContext {
+    /// Creates a DASContext using the default trusted setup.
+    ///
+    /// `num_threads`: set to `0` to indicate that the library should pick a sensible default.
+    pub fn with_threads(num_threads: usize, use_precomp: UsePrecomp) -> Self {
+        Self::with_trusted_setup(&TrustedSetup::default(), num_threads, use_precomp)
+    }
+
+    /// Creates a DASContext using the given trusted setup.
+    ///
+    /// `num_threads`: set to `0` to indicate that the library should pick a sensible default.
+    pub fn with_trusted_setup(
+        trusted_setup: &TrustedSetup,
+        num_threads: usize,
+        use_precomp: UsePrecomp,
+    ) -> Self {
+        Self::with_mode(trusted_setup, num_threads, use_precomp, ContextMode::Both)
+    }
+
+    /// Creates a DASContext using the given trusted setup, with only the parts that are
+    /// needed for `mode`.
+    ///
+    /// `num_threads`: set to `0` to indicate that the library should pick a sensible default.
+    pub fn with_mode(
+        trusted_setup: &TrustedSetup,
+        num_threads: usize,
+        use_precomp: UsePrecomp,
+        mode: ContextMode,
+    ) -> Self {
+        DASContext {
+            inner: rust_eth_kzg::DASContext::with_threads_and_mode(
+                trusted_setup,
+                ThreadCount::Multi(num_threads),
+                use_precomp,
+                mode,
+            ),
+            last_errors: LastErrors::default(),
+        }
+    }
+
+    /// Creates a DASContext using the given trusted setup, returning an error instead of
+    /// panicking if the context could not be created.
+    ///
+    /// This happens, for example, if the thread pool could not be started.
+    pub(crate) fn try_with_trusted_setup(
+        trusted_setup: &TrustedSetup,
+        num_threads: usize,
+        use_precomp: UsePrecomp,
+        mode: ContextMode,
+    ) -> Result<Self, CResult> {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            Self::with_mode(trusted_setup, num_threads, use_precomp, mode)
+        }))
+        .map_err(|payload| {
+            CResult::with_error(
+                CResultCode::Internal,
+                &format!("could not create the context: {}", panic_message(&*payload)),
+            )
+        })
+    }
+
     pub fn inner(&self) -> &rust_eth_kzg::DASContext {
         &self.inner
     }
@@ -56,30 +184,267 @@ impl Deref for DASContext {
 ///
 /// `num_threads`: set to `0`` to indicate that the library should pick a sensible default.
 ///
+/// Returns a null pointer if the context could not be created, for example because the
+/// thread pool could not be started. If `out_code` is not null, the result code is written
+/// to it, so that callers can tell why the context was not created.
+///
+/// # Safety
+///
+/// - The caller must ensure that `out_code` is either null or a valid pointer.
+///
 /// # Memory faults
 ///
 /// To avoid memory leaks, one should ensure that the pointer is freed after use
 /// by calling `eth_kzg_das_context_free`.
 #[no_mangle]
-pub extern "C" fn eth_kzg_das_context_new(use_precomp: bool, num_threads: u32) -> *mut DASContext {
-    let use_precomp = if use_precomp {
-        rust_eth_kzg::UsePrecomp::Yes {
+pub unsafe extern "C" fn eth_kzg_das_context_new(
+    use_precomp: bool,
+    num_threads: u32,
+    out_code: *mut CResultCode,
+) -> *mut DASContext {
+    let ctx = catch_panic(|| {
+        DASContext::try_with_trusted_setup(
+            &TrustedSetup::default(),
+            num_threads as usize,
+            recommended_precomp(use_precomp),
+            ContextMode::Both,
+        )
+    });
+    context_or_null(ctx, out_code)
+}
+
+/// Returns the precomputation setting that is used when the caller only
+/// chooses whether precomputation should be used.
+pub const fn recommended_precomp(use_precomp: bool) -> UsePrecomp {
+    if use_precomp {
+        UsePrecomp::Yes {
             width: RECOMMENDED_PRECOMP_WIDTH,
         }
     } else {
-        rust_eth_kzg::UsePrecomp::No
+        UsePrecomp::No
+    }
+}
+
+/// Moves the context into a reference counted allocation and returns a pointer to it.
+///
+/// The pointer is released by `eth_kzg_das_context_free`.
+pub(crate) fn into_raw_context(ctx: DASContext) -> *mut DASContext {
+    Arc::into_raw(Arc::new(ctx)).cast_mut()
+}
+
+/// The smallest precomputation width that can be used when creating a context.
+pub const MIN_PRECOMP_WIDTH: usize = 2;
+
+/// The largest precomputation width that can be used when creating a context.
+///
+/// The memory needed for the precomputed tables doubles with every increase in the width.
+pub const MAX_PRECOMP_WIDTH: usize = 16;
+
+/// The operations that a DASContext is created for.
+///
+/// Creating the prover is much more expensive than creating the verifier, in both time and
+/// memory, so callers that only verify proofs should use `DASContextMode_VerifierOnly`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DASContextMode {
+    /// The context supports both proving and verification.
+    Both = 0,
+    /// The context only supports the functions that compute commitments, cells and proofs,
+    /// and recovery. The verification functions return `BadArgument`.
+    ProverOnly = 1,
+    /// The context only supports the verification functions. The functions that compute
+    /// commitments, cells and proofs, and recovery, return `BadArgument`.
+    VerifierOnly = 2,
+}
+
+/// The options used to create a DASContext with `eth_kzg_das_context_new_with_config`
+/// or with a custom trusted setup.
+///
+/// All of the functions that accept a config use `DASContextConfig::DEFAULT` if it is null,
+/// that is pre-computation and a single thread, with support for both proving and verification.
+#[repr(C)]
+pub struct DASContextConfig {
+    /// The number of threads to use. Set to `0` to indicate that the library should pick a sensible default.
+    pub num_threads: u32,
+    /// The width of the precomputed tables used to speed up proof creation.
+    ///
+    /// Set to `0` to disable precomputation, otherwise this must be between
+    /// `MIN_PRECOMP_WIDTH` and `MAX_PRECOMP_WIDTH` inclusive. This is ignored if
+    /// the context is created without a prover.
+    pub precomp_width: u32,
+    /// Whether the context supports proving, verification or both.
+    ///
+    /// This must be one of the `DASContextMode` values, any other value is rejected with
+    /// `BadArgument`. It is not declared as a `DASContextMode`, because an enum that holds
+    /// any other value cannot be read safely.
+    pub mode: u32,
+}
+
+impl DASContextConfig {
+    /// The options used when a null config is passed to a function that accepts one.
+    ///
+    /// This uses pre-computation and a single thread, and supports both proving and verification.
+    const DEFAULT: Self = Self {
+        num_threads: 1,
+        precomp_width: RECOMMENDED_PRECOMP_WIDTH as u32,
+        mode: DASContextMode::Both as u32,
     };
 
-    let ctx = Box::new(DASContext {
-        inner: rust_eth_kzg::DASContext::with_threads(
-            &rust_eth_kzg::TrustedSetup::default(),
-            ThreadCount::Multi(num_threads as usize),
-            use_precomp,
-        ),
-    });
-    Box::into_raw(ctx)
+    /// Returns the config that `config` points to, or the default config if it is null.
+    fn from_ptr<'a>(config: *const Self) -> &'a Self {
+        if config.is_null() {
+            &Self::DEFAULT
+        } else {
+            deref_const(config)
+        }
+    }
+
+    /// Returns the mode of this config, or an error if it is not a `DASContextMode` value.
+    fn mode(&self) -> Result<ContextMode, CResult> {
+        match self.mode {
+            mode if mode == DASContextMode::Both as u32 => Ok(ContextMode::Both),
+            mode if mode == DASContextMode::ProverOnly as u32 => Ok(ContextMode::ProverOnly),
+            mode if mode == DASContextMode::VerifierOnly as u32 => Ok(ContextMode::VerifierOnly),
+            mode => Err(CResult::with_error(
+                CResultCode::BadArgument,
+                &format!("invalid context mode {mode}"),
+            )),
+        }
+    }
+
+    /// Returns the precomputation setting for this config, or an error if the width is invalid.
+    fn use_precomp(&self) -> Result<UsePrecomp, CResult> {
+        if self.mode == DASContextMode::VerifierOnly as u32 {
+            return Ok(UsePrecomp::No);
+        }
+        match self.precomp_width as usize {
+            0 => Ok(UsePrecomp::No),
+            width if (MIN_PRECOMP_WIDTH..=MAX_PRECOMP_WIDTH).contains(&width) => {
+                Ok(UsePrecomp::Yes { width })
+            }
+            _ => Err(CResult::with_error(
+                CResultCode::BadArgument,
+                &format!("invalid precomputation width {}", self.precomp_width),
+            )),
+        }
+    }
 }
 
+/// Create a new DASContext using the given options and return a pointer to it.
+///
+/// If `config` is null, pre-computation and a single thread are used. Returns a null pointer
+/// if `config` contains an invalid option or if the context could not be created. If `out_code`
+/// is not null, the result code is written to it.
+///
+/// # Safety
+///
+/// - The caller must ensure that `config` is either null or points to a valid `DASContextConfig`.
+/// - The caller must ensure that `out_code` is either null or a valid pointer.
+///
+/// # Memory faults
+///
+/// To avoid memory leaks, one should ensure that the pointer is freed after use
+/// by calling `eth_kzg_das_context_free`.
+#[no_mangle]
+pub unsafe extern "C" fn eth_kzg_das_context_new_with_config(
+    config: *const DASContextConfig,
+    out_code: *mut CResultCode,
+) -> *mut DASContext {
+    context_or_null(
+        catch_panic(|| _das_context_new_with_config(config)),
+        out_code,
+    )
+}
+
+/// Create a new DASContext that uses the given trusted setup instead of the embedded one.
+///
+/// The trusted setup must be in the JSON format used by the consensus specs. All of its
+/// points are checked to be valid and in the correct subgroup.
+///
+/// `json` does not need to be null-terminated. If `config` is null, pre-computation and a
+/// single thread are used.
+///
+/// # Safety
+///
+/// - The caller must ensure that `json` points to a region of memory that is at least `json_length` bytes.
+/// - The caller must ensure that `config` is either null or points to a valid `DASContextConfig`.
+/// - The caller must ensure that `out` is a valid pointer. A pointer to the new context is written to it on success.
+///
+/// # Memory faults
+///
+/// To avoid memory leaks, one should ensure that the pointer written to `out` is freed after use
+/// by calling `eth_kzg_das_context_free`.
+#[no_mangle]
+#[must_use]
+pub unsafe extern "C" fn eth_kzg_das_context_new_from_setup(
+    json: *const std::os::raw::c_char,
+    json_length: usize,
+    config: *const DASContextConfig,
+    out: *mut *mut DASContext,
+) -> CResult {
+    match catch_panic(|| _das_context_new_from_setup(json, json_length, config, out)) {
+        Ok(_) => CResult::with_ok(),
+        Err(err) => err,
+    }
+}
+
+/// Create a new DASContext that uses the trusted setup stored in the file at `path`.
+///
+/// This is the same as `eth_kzg_das_context_new_from_setup`, except that the trusted setup is
+/// read from a file.
+///
+/// # Safety
+///
+/// - The caller must ensure that `path` is a valid null-terminated string.
+/// - The caller must ensure that `config` is either null or points to a valid `DASContextConfig`.
+/// - The caller must ensure that `out` is a valid pointer. A pointer to the new context is written to it on success.
+///
+/// # Memory faults
+///
+/// To avoid memory leaks, one should ensure that the pointer written to `out` is freed after use
+/// by calling `eth_kzg_das_context_free`.
+#[no_mangle]
+#[must_use]
+pub unsafe extern "C" fn eth_kzg_das_context_new_from_setup_file(
+    path: *const std::os::raw::c_char,
+    config: *const DASContextConfig,
+    out: *mut *mut DASContext,
+) -> CResult {
+    match catch_panic(|| _das_context_new_from_setup_file(path, config, out)) {
+        Ok(_) => CResult::with_ok(),
+        Err(err) => err,
+    }
+}
+
+/// Returns a new reference to the context, so that it can be shared without
+/// coordinating which owner frees it.
+///
+/// Contexts are reference counted. Every pointer returned by this method, or by one of the
+/// methods that create a context, must be freed by calling `eth_kzg_das_context_free`. The
+/// context is destroyed once all of them have been freed. Returns a null pointer if `ctx` is null.
+///
+/// It is safe to clone and free references to the same context from multiple threads.
+///
+/// # Safety
+///
+/// - The caller must ensure that the pointer is valid and has not been freed.
+///
+/// # Undefined behavior
+///
+/// - Since the `ctx` is created in Rust, we can only get undefined behavior, if the caller passes in
+///   a pointer that was not created by one of the methods that create or clone a context.
+#[no_mangle]
+pub unsafe extern "C" fn eth_kzg_das_context_clone(ctx: *const DASContext) -> *mut DASContext {
+    if ctx.is_null() {
+        return std::ptr::null_mut();
+    }
+    // Safety: The pointer was created by `Arc::into_raw` and the caller holds a reference to it.
+    unsafe { Arc::increment_strong_count(ctx) };
+    ctx.cast_mut()
+}
+
+/// Releases a reference to the context, destroying it once all references have been released.
+///
 /// # Safety
 ///
 /// - The caller must ensure that the pointer is valid. If the pointer is null, this method will return early.
@@ -87,21 +452,50 @@ pub extern "C" fn eth_kzg_das_context_new(use_precomp: bool, num_threads: u32) -
 ///
 /// # Memory faults
 ///
-/// - If this method is called twice on the same pointer, it will result in a double-free.
+/// - If this method is called more times than there are references to the context, it will result in a double-free.
 ///
 /// # Undefined behavior
 ///
 /// - Since the `ctx` is created in Rust, we can only get undefined behavior, if the caller passes in
-///   a pointer that was not created by `eth_kzg_das_context_new`.
+///   a pointer that was not created by one of the methods that create or clone a context.
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn eth_kzg_das_context_free(ctx: *mut DASContext) {
     if ctx.is_null() {
         return;
     }
-    unsafe {
-        let _ = Box::from_raw(ctx);
-    }
+    // Safety: The pointer was created by `Arc::into_raw`, and the caller gives up their reference to it.
+    let ctx = unsafe { Arc::from_raw(ctx.cast_const()) };
+    // A panic while the context is dropped, such as when its thread pool is shut down, cannot be
+    // reported to the caller, so it is ignored.
+    let _ = catch_panic(|| {
+        drop(ctx);
+        Ok(())
+    });
+}
+
+/// Returns the error message of the most recent failed call on `ctx` that was made from the
+/// current thread, or a null pointer if there has not been one.
+///
+/// This is the same message that was returned in the `CResult` of that call. It allows callers
+/// that have already freed, or did not keep, that message to report the details of an error,
+/// such as the lengths or indices that were invalid. Calls that succeed do not clear the message.
+///
+/// Each thread keeps the most recent message of every context separately, so a failed call on
+/// another context, or from another thread, does not replace it.
+///
+/// The returned string is owned by the library and must not be freed by the caller. It stays
+/// valid until the next failed call on `ctx` from the current thread, until the context is
+/// destroyed, or until the thread exits.
+///
+/// # Safety
+///
+/// - The caller must ensure that `ctx` is either null or a valid pointer to a context that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn eth_kzg_das_context_last_error(
+    ctx: *const DASContext,
+) -> *const std::os::raw::c_char {
+    _das_context_last_error(ctx)
 }
 
 /// A C-style enum to indicate whether a function call was a success or not.
@@ -111,13 +505,82 @@ pub enum CResultStatus {
     Err,
 }
 
+/// A C-style enum to indicate why a function call failed.
+///
+/// The values of this enum are stable, so callers can branch on them and use
+/// `eth_kzg_error_to_string` to describe them.
+///
+/// cbindgen:prefix-with-name
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CResultCode {
+    /// The function call was successful.
+    Ok = 0,
+    /// A proof failed verification.
+    ///
+    /// Note: `eth_kzg_verify_cell_kzg_proof_batch` does not return this code and
+    /// instead reports an invalid proof by setting `verified` to false.
+    InvalidProof = 1,
+    /// An input, or the number of inputs, has the wrong length.
+    BadLength = 2,
+    /// An input could not be deserialized, for example because it does not
+    /// encode a valid field element or point.
+    BadEncoding = 3,
+    /// An input is well formed but not valid for the function, for example
+    /// because a cell index is out of range or is repeated, or because the
+    /// context was created without the prover or verifier that the function needs.
+    BadArgument = 4,
+    /// An error that was not caused by the inputs.
+    ///
+    /// This is also returned if the library panicked, instead of unwinding into the caller.
+    Internal = 5,
+    /// A file could not be read.
+    Io = 6,
+    /// An output buffer does not have enough capacity for the result.
+    ///
+    /// The required capacity can be queried with functions such as `eth_kzg_cells_length`.
+    BufferTooSmall = 7,
+}
+
+impl From<ErrorKind> for CResultCode {
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::InvalidProof => Self::InvalidProof,
+            ErrorKind::BadLength => Self::BadLength,
+            ErrorKind::BadEncoding | ErrorKind::BadTrustedSetup => Self::BadEncoding,
+            ErrorKind::BadArgument => Self::BadArgument,
+            ErrorKind::Internal => Self::Internal,
+        }
+    }
+}
+
+/// Returns a static, null-terminated description of the result code.
+///
+/// The returned string must not be freed by the caller.
+#[no_mangle]
+pub extern "C" fn eth_kzg_error_to_string(code: CResultCode) -> *const std::os::raw::c_char {
+    let description: &'static [u8] = match code {
+        CResultCode::Ok => b"ok\0",
+        CResultCode::InvalidProof => b"invalid proof\0",
+        CResultCode::BadLength => b"input has the wrong length\0",
+        CResultCode::BadEncoding => b"input could not be deserialized\0",
+        CResultCode::BadArgument => b"input is not a valid argument\0",
+        CResultCode::Internal => b"internal error\0",
+        CResultCode::Io => b"file could not be read\0",
+        CResultCode::BufferTooSmall => b"output buffer is too small\0",
+    };
+    description.as_ptr().cast()
+}
+
 /// A C-style struct to represent the success result of a function call.
 ///
-/// This includes the status of the call and an error message, if the status was an error.
+/// This includes the status of the call, a code indicating why the call failed
+/// and an error message, if the status was an error.
 #[repr(C)]
 pub struct CResult {
     pub status: CResultStatus,
     pub error_msg: *mut std::os::raw::c_char,
+    pub code: CResultCode,
 }
 
 impl CResult {
@@ -128,29 +591,64 @@ impl CResult {
     /// - Ownership of the error message is transferred to the caller.
     ///   The caller is responsible for freeing the memory allocated for the error message.
     ///   This can be done by calling `eth_kzg_free_error_message`.
+    /// - If a custom allocator was registered and it fails to allocate the error message,
+    ///   the error message will be null.
     ///
     /// # Memory faults
     ///
     /// - If this method is called twice on the same pointer, it will result in a double-free.
-    pub fn with_error(error_msg: &str) -> Self {
-        let error_msg = std::ffi::CString::new(error_msg).unwrap();
+    pub fn with_error(code: CResultCode, error_msg: &str) -> Self {
         CResult {
             status: CResultStatus::Err,
-            error_msg: error_msg.into_raw(),
+            error_msg: alloc_c_string(error_msg),
+            code,
         }
     }
 
+    /// Creates a new CResult from an error returned by the underlying library.
+    ///
+    /// This is the only place where library errors are converted into C results, so
+    /// every error variant is reported to C callers in the same way.
+    pub fn with_library_error(err: &Error) -> Self {
+        Self::with_error(CResultCode::from(err.kind()), &err.to_string())
+    }
+
     /// Creates a new CResult with an Ok status indicating a function has returned successfully.
     pub fn with_ok() -> Self {
         CResult {
             status: CResultStatus::Ok,
             error_msg: std::ptr::null_mut(),
+            code: CResultCode::Ok,
         }
     }
 }
 
+/// Checks that an output buffer, which was given with a capacity of `capacity` elements,
+/// can hold the `required` number of elements that a function will write to it.
+pub(crate) fn check_output_capacity(
+    name: &str,
+    capacity: u64,
+    required: usize,
+) -> Result<(), CResult> {
+    if capacity < required as u64 {
+        return Err(CResult::with_error(
+            CResultCode::BufferTooSmall,
+            &format!("{name} has capacity for {capacity} elements, but {required} are required"),
+        ));
+    }
+    Ok(())
+}
+
+impl From<Error> for CResult {
+    fn from(err: Error) -> Self {
+        Self::with_library_error(&err)
+    }
+}
+
 /// Free the memory allocated for the error message.
 ///
+/// If an allocator was registered with `eth_kzg_set_allocator`, its `free_fn` is used.
+///
 /// # Safety
 ///
 /// - The caller must ensure that the pointer is valid. If the pointer is null, this method will return early.
@@ -162,9 +660,79 @@ pub unsafe extern "C" fn eth_kzg_free_error_message(c_message: *mut std::os::raw
         return;
     }
     // Safety: Deallocate the memory allocated for the C-style string
-    unsafe {
-        let _ = std::ffi::CString::from_raw(c_message);
-    };
+    unsafe { free_c_string(c_message) };
+}
+
+/// Registers the callbacks used to allocate and free the buffers that the library returns
+/// to the caller, such as the error message in a `CResult`.
+///
+/// This allows callers to track or pool the memory that the library hands to them.
+/// Memory that the library uses internally, and the memory used by a `DASContext`,
+/// is not allocated with these callbacks.
+///
+/// The allocator can only be set once, and must be set before any other function in
+/// this library is called. Otherwise a `BadArgument` error is returned.
+///
+/// # Safety
+///
+/// - `malloc_fn` must behave like `malloc`. It must return either null or a pointer to
+///   at least `size` bytes of memory, that is valid until it is passed to `free_fn`.
+/// - `free_fn` must behave like `free` for the pointers returned by `malloc_fn`.
+/// - Both callbacks must be safe to call from multiple threads.
+#[no_mangle]
+#[must_use]
+pub unsafe extern "C" fn eth_kzg_set_allocator(
+    malloc_fn: Option<unsafe extern "C" fn(size: usize) -> *mut std::ffi::c_void>,
+    free_fn: Option<unsafe extern "C" fn(ptr: *mut std::ffi::c_void)>,
+) -> CResult {
+    match catch_panic(|| _set_allocator(malloc_fn, free_fn)) {
+        Ok(_) => CResult::with_ok(),
+        Err(err) => err,
+    }
+}
+
+/// The level of a log message.
+///
+/// Levels are ordered from the least to the most verbose.
+///
+/// cbindgen:prefix-with-name
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CLogLevel {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+/// Registers a callback that receives the library's log messages, such as the timings of
+/// the spans for each operation.
+///
+/// Only messages that are at most as verbose as `level` are passed to the callback. Setting
+/// `callback` to null stops messages from being passed on. Calling this method again replaces
+/// the previous callback and level.
+///
+/// The library uses the `tracing` crate for its logs. This method installs a global `tracing`
+/// subscriber the first time it is called, and returns an `Internal` error if a global subscriber
+/// had already been installed by another part of the process.
+///
+/// # Safety
+///
+/// - `callback` must be safe to call from multiple threads, since messages are logged from the
+///   threads that run the computation.
+/// - The `message` passed to `callback` is a null-terminated string that is only valid for the
+///   duration of the call. The callback must copy it if it is needed afterwards.
+#[no_mangle]
+#[must_use]
+pub unsafe extern "C" fn eth_kzg_set_log_callback(
+    callback: Option<extern "C" fn(level: CLogLevel, message: *const std::os::raw::c_char)>,
+    level: CLogLevel,
+) -> CResult {
+    match catch_panic(|| _set_log_callback(callback, level)) {
+        Ok(_) => CResult::with_ok(),
+        Err(err) => err,
+    }
 }
 
 /// Compute a commitment from a Blob
@@ -188,10 +756,50 @@ pub extern "C" fn eth_kzg_blob_to_kzg_commitment(
 
     out: *mut u8,
 ) -> CResult {
-    match _blob_to_kzg_commitment(ctx, blob, out) {
-        Ok(_) => CResult::with_ok(),
-        Err(err) => err,
-    }
+    with_last_error(ctx, || _blob_to_kzg_commitment(ctx, blob, out))
+}
+
+/// Compute a commitment for each blob in a batch of blobs.
+///
+/// The blobs are processed in parallel using the context's thread pool.
+///
+/// The blobs are read from a single buffer, where each blob directly follows the previous one,
+/// and the commitments are written in the same order to `out`, with commitment `i` starting
+/// at byte `i * BYTES_PER_COMMITMENT`.
+///
+/// # Safety
+///
+/// - If the length parameter for a pointer is set to zero, then this implementation will not check if its pointer is
+///   null. This is because the caller might have passed in a null pointer, if the length is zero. Instead an empty slice
+///   will be created.
+///
+/// - The caller must ensure that the pointers are valid.
+/// - The caller must ensure that `blobs` points to a region of memory that is at least `blobs_length * BYTES_PER_BLOB` bytes.
+/// - The caller must ensure that `out` points to a region of memory that is at least `out_length * BYTES_PER_COMMITMENT` bytes.
+///
+/// # Errors
+///
+/// - If `out_length` is less than `blobs_length`, a `BufferTooSmall` error is returned and nothing is written.
+/// - If any of the blobs is invalid, an error is returned and nothing is written.
+///
+/// # Undefined behavior
+///
+/// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+///   If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+#[no_mangle]
+#[must_use]
+pub extern "C" fn eth_kzg_blobs_to_kzg_commitments(
+    ctx: *const DASContext,
+
+    blobs_length: u64,
+    blobs: *const u8,
+
+    out_length: u64,
+    out: *mut u8,
+) -> CResult {
+    with_last_error(ctx, || {
+        _blobs_to_kzg_commitments(ctx, blobs_length, blobs, out_length, out)
+    })
 }
 
 /// Computes the cells and KZG proofs for a given blob.
@@ -200,11 +808,16 @@ pub extern "C" fn eth_kzg_blob_to_kzg_commitment(
 ///
 /// - The caller must ensure that the pointers are valid. If pointers are null.
 /// - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
-/// - The caller must ensure that `out_cells` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` elements
+/// - The caller must ensure that `out_cells` points to a region of memory that is at least `out_cells_length` elements
 ///   and that each element is at least `BYTES_PER_CELL` bytes.
-/// - The caller must ensure that `out_proofs` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` elements
+/// - The caller must ensure that `out_proofs` points to a region of memory that is at least `out_proofs_length` elements
 ///   and that each element is at least `BYTES_PER_COMMITMENT` bytes.
 ///
+/// # Errors
+///
+/// - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+///   `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+///
 /// # Undefined behavior
 ///
 /// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
@@ -216,27 +829,155 @@ pub extern "C" fn eth_kzg_compute_cells_and_kzg_proofs(
 
     blob: *const u8,
 
+    out_cells_length: u64,
     out_cells: *mut *mut u8,
+
+    out_proofs_length: u64,
     out_proofs: *mut *mut u8,
 ) -> CResult {
-    match _compute_cells_and_kzg_proofs(ctx, blob, out_cells, out_proofs) {
-        Ok(_) => CResult::with_ok(),
-        Err(err) => err,
-    }
+    with_last_error(ctx, || {
+        _compute_cells_and_kzg_proofs(
+            ctx,
+            blob,
+            out_cells_length,
+            out_cells,
+            out_proofs_length,
+            out_proofs,
+        )
+    })
+}
+
+/// Computes the cells and KZG proofs for a given blob, writing them to contiguous buffers.
+///
+/// This is the same as `eth_kzg_compute_cells_and_kzg_proofs`, except that the cells and proofs
+/// are written one after the other into a single buffer each, rather than into an array of pointers.
+/// Cell `i` starts at byte `i * BYTES_PER_CELL` of `out_cells` and proof `i` starts at byte
+/// `i * BYTES_PER_PROOF` of `out_proofs`.
+///
+/// # Safety
+///
+/// - The caller must ensure that the pointers are valid.
+/// - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
+/// - The caller must ensure that `out_cells` points to a region of memory that is at least
+///   `out_cells_length * BYTES_PER_CELL` bytes.
+/// - The caller must ensure that `out_proofs` points to a region of memory that is at least
+///   `out_proofs_length * BYTES_PER_PROOF` bytes.
+///
+/// # Errors
+///
+/// - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+///   `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+///
+/// # Undefined behavior
+///
+/// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+///   If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+#[no_mangle]
+#[must_use]
+pub extern "C" fn eth_kzg_compute_cells_and_kzg_proofs_flat(
+    ctx: *const DASContext,
+
+    blob: *const u8,
+
+    out_cells_length: u64,
+    out_cells: *mut u8,
+
+    out_proofs_length: u64,
+    out_proofs: *mut u8,
+) -> CResult {
+    with_last_error(ctx, || {
+        _compute_cells_and_kzg_proofs_flat(
+            ctx,
+            blob,
+            out_cells_length,
+            out_cells,
+            out_proofs_length,
+            out_proofs,
+        )
+    })
+}
+
+/// Computes the cells for a given blob, without computing their KZG proofs.
+///
+/// This is cheaper than `eth_kzg_compute_cells_and_kzg_proofs` and can be used when only the
+/// extended blob is needed.
+///
+/// # Safety
+///
+/// - The caller must ensure that the pointers are valid.
+/// - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
+/// - The caller must ensure that `out_cells` points to a region of memory that is at least `out_cells_length` elements
+///   and that each element is at least `BYTES_PER_CELL` bytes.
+///
+/// # Errors
+///
+/// - If `out_cells_length` is less than `eth_kzg_cells_length()`, a `BufferTooSmall` error is returned
+///   and nothing is written.
+///
+/// # Undefined behavior
+///
+/// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+///   If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+#[no_mangle]
+#[must_use]
+pub extern "C" fn eth_kzg_compute_cells(
+    ctx: *const DASContext,
+
+    blob: *const u8,
+
+    out_cells_length: u64,
+    out_cells: *mut *mut u8,
+) -> CResult {
+    with_last_error(ctx, || {
+        _compute_cells(ctx, blob, out_cells_length, out_cells)
+    })
+}
+
+/// Computes the cells for a given blob, without computing their KZG proofs, writing them to a contiguous buffer.
+///
+/// This is the same as `eth_kzg_compute_cells`, except that cell `i` is written starting at byte
+/// `i * BYTES_PER_CELL` of `out_cells`, rather than to an array of pointers.
+///
+/// # Safety
+///
+/// - The caller must ensure that the pointers are valid.
+/// - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
+/// - The caller must ensure that `out_cells` points to a region of memory that is at least
+///   `out_cells_length * BYTES_PER_CELL` bytes.
+///
+/// # Errors
+///
+/// - If `out_cells_length` is less than `eth_kzg_cells_length()`, a `BufferTooSmall` error is returned
+///   and nothing is written.
+///
+/// # Undefined behavior
+///
+/// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+///   If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+#[no_mangle]
+#[must_use]
+pub extern "C" fn eth_kzg_compute_cells_flat(
+    ctx: *const DASContext,
+
+    blob: *const u8,
+
+    out_cells_length: u64,
+    out_cells: *mut u8,
+) -> CResult {
+    with_last_error(ctx, || {
+        _compute_cells_flat(ctx, blob, out_cells_length, out_cells)
+    })
 }
 
 // The underlying cryptography library, uses a Result enum to indicate a proof failed verification.
 //
 // From the callers perspective, as long as the verification procedure is invalid, it doesn't matter why it is invalid.
-// We need to unwrap it here because the FFI API is not rich enough to distinguish invalid proof vs invalid input.
+// We unwrap it here so that an invalid proof is reported through `verified`, while invalid inputs are reported
+// as errors with a `CResultCode` describing them.
 fn verification_result_to_bool_cresult(
     verification_result: Result<(), Error>,
 ) -> Result<bool, CResult> {
-    match verification_result {
-        Ok(_) => Ok(true),
-        Err(x) if x.invalid_proof() => Ok(false),
-        Err(err) => Err(CResult::with_error(&format!("{:?}", err))),
-    }
+    verification_result_to_bool(verification_result).map_err(CResult::from)
 }
 
 /// Verifies a batch of cells and their KZG proofs.
@@ -283,21 +1024,80 @@ pub extern "C" fn eth_kzg_verify_cell_kzg_proof_batch(
 
     verified: *mut bool,
 ) -> CResult {
-    match _verify_cell_kzg_proof_batch(
-        ctx,
-        commitments_length,
-        commitments,
-        cell_indices_length,
-        cell_indices,
-        cells_length,
-        cells,
-        proofs_length,
-        proofs,
-        verified,
-    ) {
-        Ok(_) => CResult::with_ok(),
-        Err(err) => err,
-    }
+    with_last_error(ctx, || {
+        _verify_cell_kzg_proof_batch(
+            ctx,
+            commitments_length,
+            commitments,
+            cell_indices_length,
+            cell_indices,
+            cells_length,
+            cells,
+            proofs_length,
+            proofs,
+            verified,
+        )
+    })
+}
+
+/// Verifies a batch of cells and their KZG proofs, that are given as contiguous buffers.
+///
+/// This is the same as `eth_kzg_verify_cell_kzg_proof_batch`, except that the commitments, cells
+/// and proofs are each read from a single buffer, where each item directly follows the previous one,
+/// rather than from an array of pointers.
+///
+/// # Safety
+///
+/// - If the length parameter for a pointer is set to zero, then this implementation will not check if its pointer is
+///   null. This is because the caller might have passed in a null pointer, if the length is zero. Instead an empty slice
+///   will be created.
+///
+/// - The caller must ensure that the pointers are valid.
+/// - The caller must ensure that `commitments` points to a region of memory that is at least
+///   `commitments_length * BYTES_PER_COMMITMENT` bytes.
+/// - The caller must ensure that `cell_indices` points to a region of memory that is at least `cell_indices_length` elements
+///   and that each element is 8 bytes.
+/// - The caller must ensure that `cells` points to a region of memory that is at least `cells_length * BYTES_PER_CELL` bytes.
+/// - The caller must ensure that `proofs` points to a region of memory that is at least `proofs_length * BYTES_PER_PROOF` bytes.
+/// - The caller must ensure that `verified` points to a region of memory that is at least 1 byte.
+///
+/// # Undefined behavior
+///
+/// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+///   If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+#[no_mangle]
+#[must_use]
+pub extern "C" fn eth_kzg_verify_cell_kzg_proof_batch_flat(
+    ctx: *const DASContext,
+
+    commitments_length: u64,
+    commitments: *const u8,
+
+    cell_indices_length: u64,
+    cell_indices: *const u64,
+
+    cells_length: u64,
+    cells: *const u8,
+
+    proofs_length: u64,
+    proofs: *const u8,
+
+    verified: *mut bool,
+) -> CResult {
+    with_last_error(ctx, || {
+        _verify_cell_kzg_proof_batch_flat(
+            ctx,
+            commitments_length,
+            commitments,
+            cell_indices_length,
+            cell_indices,
+            cells_length,
+            cells,
+            proofs_length,
+            proofs,
+            verified,
+        )
+    })
 }
 
 /// Recovers all cells and their KZG proofs from the given cell indices and cells
@@ -312,11 +1112,16 @@ pub extern "C" fn eth_kzg_verify_cell_kzg_proof_batch(
 ///   and that each cell is at least `BYTES_PER_CELL` bytes.
 /// - The caller must ensure that `cell_indices` points to a region of memory that is at least `cell_indices_length` cell indices
 ///   and that each cell id is 8 bytes.
-/// - The caller must ensure that `out_cells` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` cells
+/// - The caller must ensure that `out_cells` points to a region of memory that is at least `out_cells_length` cells
 ///   and that each cell is at least `BYTES_PER_CELL` bytes.
-/// - The caller must ensure that `out_proofs` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` proofs
+/// - The caller must ensure that `out_proofs` points to a region of memory that is at least `out_proofs_length` proofs
 ///   and that each proof is at least `BYTES_PER_COMMITMENT` bytes.
 ///
+/// # Errors
+///
+/// - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+///   `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+///
 /// # Undefined behavior
 ///
 /// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
@@ -332,21 +1137,105 @@ pub extern "C" fn eth_kzg_recover_cells_and_proofs(
     cell_indices_length: u64,
     cell_indices: *const u64,
 
+    out_cells_length: u64,
     out_cells: *mut *mut u8,
+
+    out_proofs_length: u64,
     out_proofs: *mut *mut u8,
 ) -> CResult {
-    match _recover_cells_and_proofs(
-        ctx,
-        cells_length,
-        cells,
-        cell_indices_length,
-        cell_indices,
-        out_cells,
-        out_proofs,
-    ) {
-        Ok(_) => CResult::with_ok(),
-        Err(err) => err,
-    }
+    with_last_error(ctx, || {
+        _recover_cells_and_proofs(
+            ctx,
+            cells_length,
+            cells,
+            cell_indices_length,
+            cell_indices,
+            out_cells_length,
+            out_cells,
+            out_proofs_length,
+            out_proofs,
+        )
+    })
+}
+
+/// Recovers all cells and their KZG proofs from the given cell indices and cells, that are given and
+/// returned as contiguous buffers.
+///
+/// This is the same as `eth_kzg_recover_cells_and_proofs`, except that the cells and proofs are read
+/// from and written to a single buffer each, where each item directly follows the previous one,
+/// rather than an array of pointers.
+///
+/// # Safety
+///
+///  - If the length parameter for a pointer is set to zero, then this implementation will not check if its pointer is
+///    null. This is because the caller might have passed in a null pointer, if the length is zero. Instead an empty slice will be created.
+///
+/// - The caller must ensure that the pointers are valid.
+/// - The caller must ensure that `cells` points to a region of memory that is at least `cells_length * BYTES_PER_CELL` bytes.
+/// - The caller must ensure that `cell_indices` points to a region of memory that is at least `cell_indices_length` cell indices
+///   and that each cell id is 8 bytes.
+/// - The caller must ensure that `out_cells` points to a region of memory that is at least
+///   `out_cells_length * BYTES_PER_CELL` bytes.
+/// - The caller must ensure that `out_proofs` points to a region of memory that is at least
+///   `out_proofs_length * BYTES_PER_PROOF` bytes.
+///
+/// # Errors
+///
+/// - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+///   `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+///
+/// # Undefined behavior
+///
+/// - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+///   If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+#[no_mangle]
+#[must_use]
+pub extern "C" fn eth_kzg_recover_cells_and_proofs_flat(
+    ctx: *const DASContext,
+
+    cells_length: u64,
+    cells: *const u8,
+
+    cell_indices_length: u64,
+    cell_indices: *const u64,
+
+    out_cells_length: u64,
+    out_cells: *mut u8,
+
+    out_proofs_length: u64,
+    out_proofs: *mut u8,
+) -> CResult {
+    with_last_error(ctx, || {
+        _recover_cells_and_proofs_flat(
+            ctx,
+            cells_length,
+            cells,
+            cell_indices_length,
+            cell_indices,
+            out_cells_length,
+            out_cells,
+            out_proofs_length,
+            out_proofs,
+        )
+    })
+}
+
+/// Returns the number of cells that `eth_kzg_compute_cells`, `eth_kzg_compute_cells_and_kzg_proofs` and
+/// `eth_kzg_recover_cells_and_proofs` write to `out_cells`.
+///
+/// This is the smallest value that can be passed as `out_cells_length`.
+#[no_mangle]
+pub extern "C" fn eth_kzg_cells_length() -> u64 {
+    CELLS_PER_EXT_BLOB as u64
+}
+
+/// Returns the number of proofs that `eth_kzg_compute_cells_and_kzg_proofs` and
+/// `eth_kzg_recover_cells_and_proofs` write to `out_proofs`.
+///
+/// This is the smallest value that can be passed as `out_proofs_length`.
+#[no_mangle]
+pub extern "C" fn eth_kzg_proofs_length() -> u64 {
+    CELLS_PER_EXT_BLOB as u64
 }
 
 // Expose the constants to the C API so that languages that have to define them
@@ -357,9 +1246,24 @@ pub extern "C" fn eth_kzg_constant_bytes_per_cell() -> u64 {
 }
 #[no_mangle]
 pub extern "C" fn eth_kzg_constant_bytes_per_proof() -> u64 {
-    BYTES_PER_COMMITMENT as u64
+    BYTES_PER_PROOF as u64
 }
 #[no_mangle]
 pub extern "C" fn eth_kzg_constant_cells_per_ext_blob() -> u64 {
     CELLS_PER_EXT_BLOB as u64
 }
+
+/// The version of the C ABI that this library implements.
+///
+/// This is increased whenever a change is made that is not backwards compatible, such as
+/// changing the signature of a function or the layout of a type.
+pub const ETH_KZG_ABI_VERSION: u32 = 3;
+
+/// Returns the version of the C ABI that the loaded library implements.
+///
+/// Hosts that load the library dynamically should check that this is equal to the
+/// `ETH_KZG_ABI_VERSION` they were built against, and refuse to use the library otherwise.
+#[no_mangle]
+pub extern "C" fn eth_kzg_abi_version() -> u32 {
+    ETH_KZG_ABI_VERSION
+}
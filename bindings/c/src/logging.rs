@@ -0,0 +1,142 @@
+use crate::{CLogLevel, CResult, CResultCode};
+use std::ffi::CString;
+use std::io::Write;
+use std::os::raw::c_char;
+use std::sync::{Once, RwLock};
+use tracing::level_filters::LevelFilter;
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+pub(crate) type LogCallback = extern "C" fn(level: CLogLevel, message: *const c_char);
+
+/// The callback that log messages are forwarded to, along with the most verbose level that is forwarded.
+static LOGGER: RwLock<Option<(LogCallback, LevelFilter)>> = RwLock::new(None);
+
+/// Guards the installation of the global tracing subscriber, which can only happen once per process.
+static INSTALL_SUBSCRIBER: Once = Once::new();
+
+pub(crate) fn _set_log_callback(
+    callback: Option<LogCallback>,
+    level: CLogLevel,
+) -> Result<(), CResult> {
+    let mut installed = true;
+    INSTALL_SUBSCRIBER.call_once(|| installed = install_subscriber());
+    if !installed {
+        return Err(CResult::with_error(
+            CResultCode::Internal,
+            "a global tracing subscriber has already been set in this process",
+        ));
+    }
+
+    *LOGGER.write().unwrap_or_else(|err| err.into_inner()) =
+        callback.map(|callback| (callback, LevelFilter::from_level(Level::from(level))));
+
+    Ok(())
+}
+
+/// Installs a global subscriber that formats the library's spans and events, and forwards
+/// them to the registered callback.
+///
+/// Returns false if a global subscriber was already installed by someone else.
+fn install_subscriber() -> bool {
+    let layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        // The host adds its own timestamps and levels. `without_time` is not used, since it
+        // also removes the timings of the spans.
+        .with_timer(())
+        .with_level(false)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(CallbackWriter)
+        .with_filter(tracing_subscriber::filter::filter_fn(is_enabled));
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::set_global_default(subscriber).is_ok()
+}
+
+fn is_enabled(metadata: &Metadata<'_>) -> bool {
+    match *LOGGER.read().unwrap_or_else(|err| err.into_inner()) {
+        Some((_, max_level)) => metadata.level() <= &max_level,
+        None => false,
+    }
+}
+
+/// Creates a `LogLine` for each formatted span or event.
+struct CallbackWriter;
+
+impl<'a> MakeWriter<'a> for CallbackWriter {
+    type Writer = LogLine;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogLine::new(Level::INFO)
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        LogLine::new(*meta.level())
+    }
+}
+
+/// Buffers a single formatted log line and passes it to the callback once it is complete.
+struct LogLine {
+    level: Level,
+    buffer: Vec<u8>,
+}
+
+impl LogLine {
+    const fn new(level: Level) -> Self {
+        Self {
+            level,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Write for LogLine {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LogLine {
+    fn drop(&mut self) {
+        let Some((callback, _)) = *LOGGER.read().unwrap_or_else(|err| err.into_inner()) else {
+            return;
+        };
+
+        let message = String::from_utf8_lossy(&self.buffer);
+        let message = CString::new(message.trim().replace('\0', ""))
+            .expect("null bytes were removed from the message");
+        callback(CLogLevel::from(self.level), message.as_ptr());
+    }
+}
+
+impl From<CLogLevel> for Level {
+    fn from(level: CLogLevel) -> Self {
+        match level {
+            CLogLevel::Error => Self::ERROR,
+            CLogLevel::Warn => Self::WARN,
+            CLogLevel::Info => Self::INFO,
+            CLogLevel::Debug => Self::DEBUG,
+            CLogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
+impl From<Level> for CLogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::ERROR => Self::Error,
+            Level::WARN => Self::Warn,
+            Level::INFO => Self::Info,
+            Level::DEBUG => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{CResult, CResultCode};
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+
+/// Runs `f`, converting a panic into an `Internal` error.
+///
+/// A panic that unwinds out of an `extern "C"` function is undefined behavior, and recent
+/// versions of Rust abort the whole process when it happens. Every function that is exported
+/// from this library therefore runs its body through this, so that the caller gets an error instead.
+///
+/// The state that `f` touches is either owned by the call, or guarded by locks that recover from
+/// being poisoned, so it is safe to keep using a context after one of its calls panicked.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> Result<T, CResult>) -> Result<T, CResult> {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        Err(CResult::with_error(
+            CResultCode::Internal,
+            &format!("the library panicked: {}", panic_message(&*payload)),
+        ))
+    })
+}
+
+/// Returns the message that a panic was started with.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown error")
+}
@@ -1,5 +1,7 @@
 // Helper methods for dereferencing raw pointers and writing to slices
 
+use crate::{CResult, CResultCode};
+
 /// Dereference a raw pointer to a mutable reference
 pub(crate) fn deref_mut<'a, T>(ptr: *mut T) -> &'a mut T {
     unsafe { &mut *ptr }
@@ -49,6 +51,39 @@ pub(crate) fn write_to_slice<T: Copy>(ptr: *mut T, data: &[T]) {
     slice.copy_from_slice(data);
 }
 
+/// Dereference a raw pointer to a contiguous array of `outer_len` items, that are each `INNER_LEN` bytes
+///
+/// Returns an error if the total length of the array does not fit in a `usize`.
+pub(crate) fn flat_ptr_to_vec_slice_const<'a, const INNER_LEN: usize>(
+    ptr: *const u8,
+    outer_len: usize,
+) -> Result<Vec<&'a [u8; INNER_LEN]>, CResult> {
+    let len = outer_len.checked_mul(INNER_LEN).ok_or_else(|| {
+        CResult::with_error(
+            CResultCode::BadArgument,
+            &format!("{outer_len} items of {INNER_LEN} bytes do not fit in memory"),
+        )
+    })?;
+    let slice = create_slice_view(ptr, len);
+
+    Ok(slice
+        .chunks_exact(INNER_LEN)
+        .map(|chunk| chunk.try_into().expect("chunk has the length of an item"))
+        .collect())
+}
+
+/// Write each item in `data` to a contiguous array starting at `ptr`, directly after the previous item
+pub(crate) fn write_to_flat_slice<'a, T: Copy + 'a>(
+    ptr: *mut T,
+    data: impl IntoIterator<Item = &'a [T]>,
+) {
+    let mut offset = 0;
+    for item in data {
+        write_to_slice(unsafe { ptr.add(offset) }, item);
+        offset += item.len();
+    }
+}
+
 /// Write `data` to a 2D slice starting at `ptr`
 pub(crate) fn write_to_2d_slice<T: Copy, const N: usize>(
     ptr: *mut *mut T,
@@ -66,7 +101,7 @@ pub(crate) fn write_to_2d_slice<T: Copy, const N: usize>(
 /// If the length is 0, an empty slice is returned regardless of the pointer.
 pub(crate) fn create_array_ref<'a, const LEN: usize, T>(ptr: *const T) -> &'a [T; LEN] {
     let slice = create_slice_view(ptr, LEN);
-    slice.try_into().expect("item should have length {LEN}")
+    slice.try_into().expect("slice has the length of the array")
 }
 pub(crate) fn create_slice_view<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
     if len == 0 {
@@ -75,3 +110,16 @@ pub(crate) fn create_slice_view<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
         unsafe { std::slice::from_raw_parts(ptr, len) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::flat_ptr_to_vec_slice_const;
+    use crate::{eth_kzg_free_error_message, CResultCode};
+
+    #[test]
+    fn flat_array_that_overflows_is_rejected() {
+        let err = flat_ptr_to_vec_slice_const::<48>(std::ptr::null(), usize::MAX).unwrap_err();
+        assert_eq!(err.code, CResultCode::BadArgument);
+        unsafe { eth_kzg_free_error_message(err.error_msg) };
+    }
+}
@@ -1,31 +1,37 @@
 use crate::pointer_utils::{
-    create_slice_view, deref_const, ptr_ptr_to_vec_slice_const, write_to_2d_slice,
+    create_slice_view, deref_const, flat_ptr_to_vec_slice_const, ptr_ptr_to_vec_slice_const,
+    write_to_2d_slice, write_to_flat_slice,
 };
-use crate::{CResult, DASContext};
+use crate::{check_output_capacity, CResult, DASContext, Error};
 use rust_eth_kzg::constants::{BYTES_PER_CELL, CELLS_PER_EXT_BLOB};
+use rust_eth_kzg::{CellIndex, CellRef, CellsAndKZGProofs, RecoveryError};
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn _recover_cells_and_proofs(
     ctx: *const DASContext,
     cells_length: u64,
     cells: *const *const u8,
     cell_indices_length: u64,
     cell_indices: *const u64,
+    out_cells_length: u64,
     out_cells: *mut *mut u8,
+    out_proofs_length: u64,
     out_proofs: *mut *mut u8,
 ) -> Result<(), CResult> {
-    assert!(!ctx.is_null(), "context pointer is null");
-
     // Dereference the input pointers
     //
-    let ctx = deref_const(ctx);
     let cells = ptr_ptr_to_vec_slice_const::<BYTES_PER_CELL>(cells, cells_length as usize);
     let cell_indices = create_slice_view(cell_indices, cell_indices_length as usize);
 
     // Computation
     //
-    let (recovered_cells, recovered_proofs) = ctx
-        .recover_cells_and_kzg_proofs(cell_indices.to_vec(), cells)
-        .map_err(|err| CResult::with_error(&format!("{:?}", err)))?;
+    let (recovered_cells, recovered_proofs) = recover_cells_and_proofs(
+        ctx,
+        cells,
+        cell_indices,
+        out_cells_length,
+        out_proofs_length,
+    )?;
     let recovered_cells_unboxed = recovered_cells.map(|cell| cell.to_vec());
 
     // Write to output
@@ -34,3 +40,65 @@ pub(crate) fn _recover_cells_and_proofs(
 
     Ok(())
 }
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn _recover_cells_and_proofs_flat(
+    ctx: *const DASContext,
+    cells_length: u64,
+    cells: *const u8,
+    cell_indices_length: u64,
+    cell_indices: *const u64,
+    out_cells_length: u64,
+    out_cells: *mut u8,
+    out_proofs_length: u64,
+    out_proofs: *mut u8,
+) -> Result<(), CResult> {
+    // Dereference the input pointers
+    //
+    let cells = flat_ptr_to_vec_slice_const::<BYTES_PER_CELL>(cells, cells_length as usize)?;
+    let cell_indices = create_slice_view(cell_indices, cell_indices_length as usize);
+
+    // Computation
+    //
+    let (recovered_cells, recovered_proofs) = recover_cells_and_proofs(
+        ctx,
+        cells,
+        cell_indices,
+        out_cells_length,
+        out_proofs_length,
+    )?;
+
+    // Write to output
+    write_to_flat_slice(
+        out_cells,
+        recovered_cells.iter().map(|cell| cell.as_slice()),
+    );
+    write_to_flat_slice(
+        out_proofs,
+        recovered_proofs.iter().map(|proof| proof.as_slice()),
+    );
+
+    Ok(())
+}
+
+fn recover_cells_and_proofs(
+    ctx: *const DASContext,
+    cells: Vec<CellRef>,
+    cell_indices: &[u64],
+    out_cells_length: u64,
+    out_proofs_length: u64,
+) -> Result<CellsAndKZGProofs, CResult> {
+    assert!(!ctx.is_null(), "context pointer is null");
+
+    // Output capacity checks
+    //
+    check_output_capacity("out_cells", out_cells_length, CELLS_PER_EXT_BLOB)?;
+    check_output_capacity("out_proofs", out_proofs_length, CELLS_PER_EXT_BLOB)?;
+
+    let cell_indices = CellIndex::try_from_u64s(cell_indices)
+        .map_err(|err| CResult::from(Error::from(RecoveryError::from(err))))?;
+
+    let ctx = deref_const(ctx);
+    ctx.recover_cells_and_kzg_proofs(cell_indices, cells)
+        .map_err(CResult::from)
+}
@@ -1,6 +1,10 @@
-use crate::pointer_utils::{create_slice_view, deref_const, deref_mut, ptr_ptr_to_vec_slice_const};
-use crate::{verification_result_to_bool_cresult, CResult, DASContext};
+use crate::pointer_utils::{
+    create_slice_view, deref_const, deref_mut, flat_ptr_to_vec_slice_const,
+    ptr_ptr_to_vec_slice_const,
+};
+use crate::{verification_result_to_bool_cresult, CResult, DASContext, Error};
 use rust_eth_kzg::constants::{BYTES_PER_CELL, BYTES_PER_COMMITMENT};
+use rust_eth_kzg::{Bytes48Ref, CellIndex, CellRef, VerifierError};
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn _verify_cell_kzg_proof_batch(
@@ -20,10 +24,8 @@ pub(crate) fn _verify_cell_kzg_proof_batch(
 
     verified: *mut bool,
 ) -> Result<(), CResult> {
-    assert!(!ctx.is_null(), "context pointer is null");
     // Dereference the input pointers
     //
-    let ctx = deref_const(ctx);
     let commitments = ptr_ptr_to_vec_slice_const::<BYTES_PER_COMMITMENT>(
         commitments,
         commitments_length as usize,
@@ -31,12 +33,62 @@ pub(crate) fn _verify_cell_kzg_proof_batch(
     let cell_indices = create_slice_view(cell_indices, cell_indices_length as usize);
     let cells = ptr_ptr_to_vec_slice_const::<BYTES_PER_CELL>(cells, cells_length as usize);
     let proofs = ptr_ptr_to_vec_slice_const::<BYTES_PER_COMMITMENT>(proofs, proofs_length as usize);
+
+    verify_cell_kzg_proof_batch(ctx, commitments, cell_indices, cells, proofs, verified)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn _verify_cell_kzg_proof_batch_flat(
+    ctx: *const DASContext,
+
+    commitments_length: u64,
+    commitments: *const u8,
+
+    cell_indices_length: u64,
+    cell_indices: *const u64,
+
+    cells_length: u64,
+    cells: *const u8,
+
+    proofs_length: u64,
+    proofs: *const u8,
+
+    verified: *mut bool,
+) -> Result<(), CResult> {
+    // Dereference the input pointers
+    //
+    let commitments = flat_ptr_to_vec_slice_const::<BYTES_PER_COMMITMENT>(
+        commitments,
+        commitments_length as usize,
+    )?;
+    let cell_indices = create_slice_view(cell_indices, cell_indices_length as usize);
+    let cells = flat_ptr_to_vec_slice_const::<BYTES_PER_CELL>(cells, cells_length as usize)?;
+    let proofs =
+        flat_ptr_to_vec_slice_const::<BYTES_PER_COMMITMENT>(proofs, proofs_length as usize)?;
+
+    verify_cell_kzg_proof_batch(ctx, commitments, cell_indices, cells, proofs, verified)
+}
+
+fn verify_cell_kzg_proof_batch(
+    ctx: *const DASContext,
+    commitments: Vec<Bytes48Ref>,
+    cell_indices: &[u64],
+    cells: Vec<CellRef>,
+    proofs: Vec<Bytes48Ref>,
+    verified: *mut bool,
+) -> Result<(), CResult> {
+    assert!(!ctx.is_null(), "context pointer is null");
+
+    let ctx = deref_const(ctx);
     let verified = deref_mut(verified);
 
+    let cell_indices = CellIndex::try_from_u64s(cell_indices)
+        .map_err(|err| CResult::from(Error::from(VerifierError::from(err))))?;
+
     // Computation
     //
     let verification_result =
-        ctx.verify_cell_kzg_proof_batch(commitments, cell_indices.to_vec(), cells, proofs);
+        ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs);
 
     // Write to output
     let proof_is_valid = verification_result_to_bool_cresult(verification_result)?;
@@ -24,11 +24,21 @@ public sealed unsafe class EthKZG : IDisposable
     // The number of bytes in a single cell.
     public const int BytesPerCell = 2048;
 
+    // The version of the C ABI that these bindings were written against.
+    // This must match `ETH_KZG_ABI_VERSION` in the native library.
+    private const uint AbiVersion = 3;
+
     private DASContext* _context;
 
     public EthKZG(bool usePrecomp = true, uint numThreads = 1)
     {
-        _context = eth_kzg_das_context_new(usePrecomp, numThreads);
+        CheckAbiVersion();
+        CResultCode code;
+        _context = eth_kzg_das_context_new(usePrecomp, numThreads, &code);
+        if (_context == null)
+        {
+            throw new InvalidOperationException($"could not create the context: {code}");
+        }
     }
 
     public void Dispose()
@@ -103,7 +113,7 @@ public sealed unsafe class EthKZG : IDisposable
                 }
             }
 
-            CResult result = eth_kzg_compute_cells_and_kzg_proofs(_context, blobPtr, outCellsPtrPtr, outProofsPtrPtr);
+            CResult result = eth_kzg_compute_cells_and_kzg_proofs(_context, blobPtr, Convert.ToUInt64(numCells), outCellsPtrPtr, Convert.ToUInt64(numProofs), outProofsPtrPtr);
             ThrowOnError(result);
         }
         return (outCells, outProofs);
@@ -242,13 +252,123 @@ public sealed unsafe class EthKZG : IDisposable
                 }
             }
 
-            CResult result = eth_kzg_recover_cells_and_proofs(_context, Convert.ToUInt64(numInputCells), inputCellsPtrPtr, Convert.ToUInt64(cellIds.Length), cellIdsPtr, outCellsPtrPtr, outProofsPtrPtr);
+            CResult result = eth_kzg_recover_cells_and_proofs(_context, Convert.ToUInt64(numInputCells), inputCellsPtrPtr, Convert.ToUInt64(cellIds.Length), cellIdsPtr, Convert.ToUInt64(numOutCells), outCellsPtrPtr, Convert.ToUInt64(numProofs), outProofsPtrPtr);
             ThrowOnError(result);
         }
 
         return (outCells, outProofs);
     }
 
+    // The methods below take and write to flat buffers, where each item directly follows the
+    // previous one, eg the cells are `CellsPerExtBlob * BytesPerCell` bytes. The buffers are pinned
+    // and passed to the native library as they are, so the caller decides where the memory lives
+    // and nothing is copied or marshaled per cell.
+
+    public void BlobToKzgCommitment(ReadOnlySpan<byte> blob, Span<byte> commitment)
+    {
+        CheckLength(blob, BytesPerBlob, nameof(blob));
+        CheckLength(commitment, BytesPerCommitment, nameof(commitment));
+
+        fixed (byte* blobPtr = blob)
+        fixed (byte* commitmentPtr = commitment)
+        {
+            CResult result = eth_kzg_blob_to_kzg_commitment(_context, blobPtr, commitmentPtr);
+            ThrowOnError(result);
+        }
+    }
+
+    public void ComputeCellsAndKZGProofs(ReadOnlySpan<byte> blob, Span<byte> cells, Span<byte> proofs)
+    {
+        CheckLength(blob, BytesPerBlob, nameof(blob));
+        CheckLength(cells, CellsPerExtBlob * BytesPerCell, nameof(cells));
+        CheckLength(proofs, CellsPerExtBlob * BytesPerProof, nameof(proofs));
+
+        fixed (byte* blobPtr = blob)
+        fixed (byte* cellsPtr = cells)
+        fixed (byte* proofsPtr = proofs)
+        {
+            CResult result = eth_kzg_compute_cells_and_kzg_proofs_flat(_context, blobPtr, CellsPerExtBlob, cellsPtr, CellsPerExtBlob, proofsPtr);
+            ThrowOnError(result);
+        }
+    }
+
+    public void ComputeCells(ReadOnlySpan<byte> blob, Span<byte> cells)
+    {
+        CheckLength(blob, BytesPerBlob, nameof(blob));
+        CheckLength(cells, CellsPerExtBlob * BytesPerCell, nameof(cells));
+
+        fixed (byte* blobPtr = blob)
+        fixed (byte* cellsPtr = cells)
+        {
+            CResult result = eth_kzg_compute_cells_flat(_context, blobPtr, CellsPerExtBlob, cellsPtr);
+            ThrowOnError(result);
+        }
+    }
+
+    public bool VerifyCellKZGProofBatch(ReadOnlySpan<byte> commitments, ReadOnlySpan<ulong> cellIndices, ReadOnlySpan<byte> cells, ReadOnlySpan<byte> proofs)
+    {
+        ulong numCommitments = CountItems(commitments, BytesPerCommitment, nameof(commitments));
+        ulong numCells = CountItems(cells, BytesPerCell, nameof(cells));
+        ulong numProofs = CountItems(proofs, BytesPerProof, nameof(proofs));
+
+        bool verified = false;
+
+        fixed (byte* commitmentsPtr = commitments)
+        fixed (ulong* cellIndicesPtr = cellIndices)
+        fixed (byte* cellsPtr = cells)
+        fixed (byte* proofsPtr = proofs)
+        {
+            CResult result = eth_kzg_verify_cell_kzg_proof_batch_flat(_context, numCommitments, commitmentsPtr, Convert.ToUInt64(cellIndices.Length), cellIndicesPtr, numCells, cellsPtr, numProofs, proofsPtr, &verified);
+            ThrowOnError(result);
+        }
+        return verified;
+    }
+
+    public void RecoverCellsAndKZGProofs(ReadOnlySpan<ulong> cellIndices, ReadOnlySpan<byte> cells, Span<byte> outCells, Span<byte> outProofs)
+    {
+        ulong numCells = CountItems(cells, BytesPerCell, nameof(cells));
+        CheckLength(outCells, CellsPerExtBlob * BytesPerCell, nameof(outCells));
+        CheckLength(outProofs, CellsPerExtBlob * BytesPerProof, nameof(outProofs));
+
+        fixed (ulong* cellIndicesPtr = cellIndices)
+        fixed (byte* cellsPtr = cells)
+        fixed (byte* outCellsPtr = outCells)
+        fixed (byte* outProofsPtr = outProofs)
+        {
+            CResult result = eth_kzg_recover_cells_and_proofs_flat(_context, numCells, cellsPtr, Convert.ToUInt64(cellIndices.Length), cellIndicesPtr, CellsPerExtBlob, outCellsPtr, CellsPerExtBlob, outProofsPtr);
+            ThrowOnError(result);
+        }
+    }
+
+    private static void CheckLength(ReadOnlySpan<byte> buffer, int expectedLength, string name)
+    {
+        if (buffer.Length != expectedLength)
+        {
+            throw new ArgumentException($"{name} has an invalid length, expected {expectedLength} bytes but got {buffer.Length}");
+        }
+    }
+
+    // Returns the number of items in a flat buffer of items that are each `itemLength` bytes.
+    private static ulong CountItems(ReadOnlySpan<byte> buffer, int itemLength, string name)
+    {
+        if (buffer.Length % itemLength != 0)
+        {
+            throw new ArgumentException($"{name} has an invalid length, {buffer.Length} bytes is not a multiple of {itemLength}");
+        }
+        return Convert.ToUInt64(buffer.Length / itemLength);
+    }
+
+    // Refuse to use a native library that implements a different C ABI, since calling
+    // its methods with the wrong signatures would corrupt memory instead of failing.
+    private static void CheckAbiVersion()
+    {
+        uint nativeAbiVersion = eth_kzg_abi_version();
+        if (nativeAbiVersion != AbiVersion)
+        {
+            throw new InvalidOperationException($"the native library implements ABI version {nativeAbiVersion}, but version {AbiVersion} is required");
+        }
+    }
+
     private static void ThrowOnError(CResult result)
     {
         switch (result.status)
@@ -21,15 +21,110 @@ namespace EthKZG.Native
         ///
         ///  `num_threads`: set to `0`` to indicate that the library should pick a sensible default.
         ///
+        ///  Returns a null pointer if the context could not be created, for example because the
+        ///  thread pool could not be started. If `out_code` is not null, the result code is written
+        ///  to it, so that callers can tell why the context was not created.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that `out_code` is either null or a valid pointer.
+        ///
         ///  # Memory faults
         ///
         ///  To avoid memory leaks, one should ensure that the pointer is freed after use
         ///  by calling `eth_kzg_das_context_free`.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "eth_kzg_das_context_new", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern DASContext* eth_kzg_das_context_new([MarshalAs(UnmanagedType.U1)] bool use_precomp, uint num_threads);
+        internal static extern DASContext* eth_kzg_das_context_new([MarshalAs(UnmanagedType.U1)] bool use_precomp, uint num_threads, CResultCode* out_code);
+
+        /// <summary>
+        ///  Create a new DASContext using the given options and return a pointer to it.
+        ///
+        ///  If `config` is null, pre-computation and a single thread are used. Returns a null pointer
+        ///  if `config` contains an invalid option or if the context could not be created. If `out_code`
+        ///  is not null, the result code is written to it.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that `config` is either null or points to a valid `DASContextConfig`.
+        ///  - The caller must ensure that `out_code` is either null or a valid pointer.
+        ///
+        ///  # Memory faults
+        ///
+        ///  To avoid memory leaks, one should ensure that the pointer is freed after use
+        ///  by calling `eth_kzg_das_context_free`.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_das_context_new_with_config", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern DASContext* eth_kzg_das_context_new_with_config(DASContextConfig* config, CResultCode* out_code);
 
         /// <summary>
+        ///  Create a new DASContext that uses the given trusted setup instead of the embedded one.
+        ///
+        ///  The trusted setup must be in the JSON format used by the consensus specs. All of its
+        ///  points are checked to be valid and in the correct subgroup.
+        ///
+        ///  `json` does not need to be null-terminated. If `config` is null, pre-computation and a
+        ///  single thread are used.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that `json` points to a region of memory that is at least `json_length` bytes.
+        ///  - The caller must ensure that `config` is either null or points to a valid `DASContextConfig`.
+        ///  - The caller must ensure that `out` is a valid pointer. A pointer to the new context is written to it on success.
+        ///
+        ///  # Memory faults
+        ///
+        ///  To avoid memory leaks, one should ensure that the pointer written to `out` is freed after use
+        ///  by calling `eth_kzg_das_context_free`.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_das_context_new_from_setup", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_das_context_new_from_setup(byte* json, UIntPtr json_length, DASContextConfig* config, DASContext** @out);
+
+        /// <summary>
+        ///  Create a new DASContext that uses the trusted setup stored in the file at `path`.
+        ///
+        ///  This is the same as `eth_kzg_das_context_new_from_setup`, except that the trusted setup is
+        ///  read from a file.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that `path` is a valid null-terminated string.
+        ///  - The caller must ensure that `config` is either null or points to a valid `DASContextConfig`.
+        ///  - The caller must ensure that `out` is a valid pointer. A pointer to the new context is written to it on success.
+        ///
+        ///  # Memory faults
+        ///
+        ///  To avoid memory leaks, one should ensure that the pointer written to `out` is freed after use
+        ///  by calling `eth_kzg_das_context_free`.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_das_context_new_from_setup_file", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_das_context_new_from_setup_file(byte* path, DASContextConfig* config, DASContext** @out);
+
+        /// <summary>
+        ///  Returns a new reference to the context, so that it can be shared without
+        ///  coordinating which owner frees it.
+        ///
+        ///  Contexts are reference counted. Every pointer returned by this method, or by one of the
+        ///  methods that create a context, must be freed by calling `eth_kzg_das_context_free`. The
+        ///  context is destroyed once all of them have been freed. Returns a null pointer if `ctx` is null.
+        ///
+        ///  It is safe to clone and free references to the same context from multiple threads.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that the pointer is valid and has not been freed.
+        ///
+        ///  # Undefined behavior
+        ///
+        ///  - Since the `ctx` is created in Rust, we can only get undefined behavior, if the caller passes in
+        ///    a pointer that was not created by one of the methods that create or clone a context.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_das_context_clone", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern DASContext* eth_kzg_das_context_clone(DASContext* ctx);
+
+        /// <summary>
+        ///  Releases a reference to the context, destroying it once all references have been released.
+        ///
         ///  # Safety
         ///
         ///  - The caller must ensure that the pointer is valid. If the pointer is null, this method will return early.
@@ -37,19 +132,51 @@ namespace EthKZG.Native
         ///
         ///  # Memory faults
         ///
-        ///  - If this method is called twice on the same pointer, it will result in a double-free.
+        ///  - If this method is called more times than there are references to the context, it will result in a double-free.
         ///
         ///  # Undefined behavior
         ///
         ///  - Since the `ctx` is created in Rust, we can only get undefined behavior, if the caller passes in
-        ///    a pointer that was not created by `eth_kzg_das_context_new`.
+        ///    a pointer that was not created by one of the methods that create or clone a context.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "eth_kzg_das_context_free", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void eth_kzg_das_context_free(DASContext* ctx);
 
+        /// <summary>
+        ///  Returns the error message of the most recent failed call on `ctx` that was made from the
+        ///  current thread, or a null pointer if there has not been one.
+        ///
+        ///  This is the same message that was returned in the `CResult` of that call. It allows callers
+        ///  that have already freed, or did not keep, that message to report the details of an error,
+        ///  such as the lengths or indices that were invalid. Calls that succeed do not clear the message.
+        ///
+        ///  Each thread keeps the most recent message of every context separately, so a failed call on
+        ///  another context, or from another thread, does not replace it.
+        ///
+        ///  The returned string is owned by the library and must not be freed by the caller. It stays
+        ///  valid until the next failed call on `ctx` from the current thread, until the context is
+        ///  destroyed, or until the thread exits.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that `ctx` is either null or a valid pointer to a context that has not been freed.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_das_context_last_error", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* eth_kzg_das_context_last_error(DASContext* ctx);
+
+        /// <summary>
+        ///  Returns a static, null-terminated description of the result code.
+        ///
+        ///  The returned string must not be freed by the caller.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_error_to_string", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* eth_kzg_error_to_string(CResultCode code);
+
         /// <summary>
         ///  Free the memory allocated for the error message.
         ///
+        ///  If an allocator was registered with `eth_kzg_set_allocator`, its `free_fn` is used.
+        ///
         ///  # Safety
         ///
         ///  - The caller must ensure that the pointer is valid. If the pointer is null, this method will return early.
@@ -58,6 +185,49 @@ namespace EthKZG.Native
         [DllImport(__DllName, EntryPoint = "eth_kzg_free_error_message", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void eth_kzg_free_error_message(byte* c_message);
 
+        /// <summary>
+        ///  Registers the callbacks used to allocate and free the buffers that the library returns
+        ///  to the caller, such as the error message in a `CResult`.
+        ///
+        ///  This allows callers to track or pool the memory that the library hands to them.
+        ///  Memory that the library uses internally, and the memory used by a `DASContext`,
+        ///  is not allocated with these callbacks.
+        ///
+        ///  The allocator can only be set once, and must be set before any other function in
+        ///  this library is called. Otherwise a `BadArgument` error is returned.
+        ///
+        ///  # Safety
+        ///
+        ///  - `malloc_fn` must behave like `malloc`. It must return either null or a pointer to
+        ///    at least `size` bytes of memory, that is valid until it is passed to `free_fn`.
+        ///  - `free_fn` must behave like `free` for the pointers returned by `malloc_fn`.
+        ///  - Both callbacks must be safe to call from multiple threads.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_set_allocator", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_set_allocator(delegate* unmanaged[Cdecl]<UIntPtr, void*> malloc_fn, delegate* unmanaged[Cdecl]<void*, void> free_fn);
+
+        /// <summary>
+        ///  Registers a callback that receives the library's log messages, such as the timings of
+        ///  the spans for each operation.
+        ///
+        ///  Only messages that are at most as verbose as `level` are passed to the callback. Setting
+        ///  `callback` to null stops messages from being passed on. Calling this method again replaces
+        ///  the previous callback and level.
+        ///
+        ///  The library uses the `tracing` crate for its logs. This method installs a global `tracing`
+        ///  subscriber the first time it is called, and returns an `Internal` error if a global subscriber
+        ///  had already been installed by another part of the process.
+        ///
+        ///  # Safety
+        ///
+        ///  - `callback` must be safe to call from multiple threads, since messages are logged from the
+        ///    threads that run the computation.
+        ///  - The `message` passed to `callback` is a null-terminated string that is only valid for the
+        ///    duration of the call. The callback must copy it if it is needed afterwards.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_set_log_callback", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_set_log_callback(delegate* unmanaged[Cdecl]<CLogLevel, byte*, void> callback, CLogLevel level);
+
         /// <summary>
         ///  Compute a commitment from a Blob
         ///
@@ -75,6 +245,38 @@ namespace EthKZG.Native
         [DllImport(__DllName, EntryPoint = "eth_kzg_blob_to_kzg_commitment", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern CResult eth_kzg_blob_to_kzg_commitment(DASContext* ctx, byte* blob, byte* @out);
 
+        /// <summary>
+        ///  Compute a commitment for each blob in a batch of blobs.
+        ///
+        ///  The blobs are processed in parallel using the context's thread pool.
+        ///
+        ///  The blobs are read from a single buffer, where each blob directly follows the previous one,
+        ///  and the commitments are written in the same order to `out`, with commitment `i` starting
+        ///  at byte `i * BYTES_PER_COMMITMENT`.
+        ///
+        ///  # Safety
+        ///
+        ///  - If the length parameter for a pointer is set to zero, then this implementation will not check if its pointer is
+        ///    null. This is because the caller might have passed in a null pointer, if the length is zero. Instead an empty slice
+        ///    will be created.
+        ///
+        ///  - The caller must ensure that the pointers are valid.
+        ///  - The caller must ensure that `blobs` points to a region of memory that is at least `blobs_length * BYTES_PER_BLOB` bytes.
+        ///  - The caller must ensure that `out` points to a region of memory that is at least `out_length * BYTES_PER_COMMITMENT` bytes.
+        ///
+        ///  # Errors
+        ///
+        ///  - If `out_length` is less than `blobs_length`, a `BufferTooSmall` error is returned and nothing is written.
+        ///  - If any of the blobs is invalid, an error is returned and nothing is written.
+        ///
+        ///  # Undefined behavior
+        ///
+        ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+        ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_blobs_to_kzg_commitments", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_blobs_to_kzg_commitments(DASContext* ctx, ulong blobs_length, byte* blobs, ulong out_length, byte* @out);
+
         /// <summary>
         ///  Computes the cells and KZG proofs for a given blob.
         ///
@@ -82,18 +284,105 @@ namespace EthKZG.Native
         ///
         ///  - The caller must ensure that the pointers are valid. If pointers are null.
         ///  - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
-        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` elements
+        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least `out_cells_length` elements
         ///    and that each element is at least `BYTES_PER_CELL` bytes.
-        ///  - The caller must ensure that `out_proofs` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` elements
+        ///  - The caller must ensure that `out_proofs` points to a region of memory that is at least `out_proofs_length` elements
         ///    and that each element is at least `BYTES_PER_COMMITMENT` bytes.
         ///
+        ///  # Errors
+        ///
+        ///  - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+        ///    `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+        ///
         ///  # Undefined behavior
         ///
         ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
         ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "eth_kzg_compute_cells_and_kzg_proofs", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern CResult eth_kzg_compute_cells_and_kzg_proofs(DASContext* ctx, byte* blob, byte** out_cells, byte** out_proofs);
+        internal static extern CResult eth_kzg_compute_cells_and_kzg_proofs(DASContext* ctx, byte* blob, ulong out_cells_length, byte** out_cells, ulong out_proofs_length, byte** out_proofs);
+
+        /// <summary>
+        ///  Computes the cells and KZG proofs for a given blob, writing them to contiguous buffers.
+        ///
+        ///  This is the same as `eth_kzg_compute_cells_and_kzg_proofs`, except that the cells and proofs
+        ///  are written one after the other into a single buffer each, rather than into an array of pointers.
+        ///  Cell `i` starts at byte `i * BYTES_PER_CELL` of `out_cells` and proof `i` starts at byte
+        ///  `i * BYTES_PER_PROOF` of `out_proofs`.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that the pointers are valid.
+        ///  - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
+        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least
+        ///    `out_cells_length * BYTES_PER_CELL` bytes.
+        ///  - The caller must ensure that `out_proofs` points to a region of memory that is at least
+        ///    `out_proofs_length * BYTES_PER_PROOF` bytes.
+        ///
+        ///  # Errors
+        ///
+        ///  - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+        ///    `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+        ///
+        ///  # Undefined behavior
+        ///
+        ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+        ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_compute_cells_and_kzg_proofs_flat", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_compute_cells_and_kzg_proofs_flat(DASContext* ctx, byte* blob, ulong out_cells_length, byte* out_cells, ulong out_proofs_length, byte* out_proofs);
+
+        /// <summary>
+        ///  Computes the cells for a given blob, without computing their KZG proofs.
+        ///
+        ///  This is cheaper than `eth_kzg_compute_cells_and_kzg_proofs` and can be used when only the
+        ///  extended blob is needed.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that the pointers are valid.
+        ///  - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
+        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least `out_cells_length` elements
+        ///    and that each element is at least `BYTES_PER_CELL` bytes.
+        ///
+        ///  # Errors
+        ///
+        ///  - If `out_cells_length` is less than `eth_kzg_cells_length()`, a `BufferTooSmall` error is returned
+        ///    and nothing is written.
+        ///
+        ///  # Undefined behavior
+        ///
+        ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+        ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_compute_cells", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_compute_cells(DASContext* ctx, byte* blob, ulong out_cells_length, byte** out_cells);
+
+        /// <summary>
+        ///  Computes the cells for a given blob, without computing their KZG proofs, writing them to a contiguous buffer.
+        ///
+        ///  This is the same as `eth_kzg_compute_cells`, except that cell `i` is written starting at byte
+        ///  `i * BYTES_PER_CELL` of `out_cells`, rather than to an array of pointers.
+        ///
+        ///  # Safety
+        ///
+        ///  - The caller must ensure that the pointers are valid.
+        ///  - The caller must ensure that `blob` points to a region of memory that is at least `BYTES_PER_BLOB` bytes.
+        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least
+        ///    `out_cells_length * BYTES_PER_CELL` bytes.
+        ///
+        ///  # Errors
+        ///
+        ///  - If `out_cells_length` is less than `eth_kzg_cells_length()`, a `BufferTooSmall` error is returned
+        ///    and nothing is written.
+        ///
+        ///  # Undefined behavior
+        ///
+        ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+        ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_compute_cells_flat", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_compute_cells_flat(DASContext* ctx, byte* blob, ulong out_cells_length, byte* out_cells);
 
         /// <summary>
         ///  Verifies a batch of cells and their KZG proofs.
@@ -125,6 +414,36 @@ namespace EthKZG.Native
         [DllImport(__DllName, EntryPoint = "eth_kzg_verify_cell_kzg_proof_batch", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern CResult eth_kzg_verify_cell_kzg_proof_batch(DASContext* ctx, ulong commitments_length, byte** commitments, ulong cell_indices_length, ulong* cell_indices, ulong cells_length, byte** cells, ulong proofs_length, byte** proofs, bool* verified);
 
+        /// <summary>
+        ///  Verifies a batch of cells and their KZG proofs, that are given as contiguous buffers.
+        ///
+        ///  This is the same as `eth_kzg_verify_cell_kzg_proof_batch`, except that the commitments, cells
+        ///  and proofs are each read from a single buffer, where each item directly follows the previous one,
+        ///  rather than from an array of pointers.
+        ///
+        ///  # Safety
+        ///
+        ///  - If the length parameter for a pointer is set to zero, then this implementation will not check if its pointer is
+        ///    null. This is because the caller might have passed in a null pointer, if the length is zero. Instead an empty slice
+        ///    will be created.
+        ///
+        ///  - The caller must ensure that the pointers are valid.
+        ///  - The caller must ensure that `commitments` points to a region of memory that is at least
+        ///    `commitments_length * BYTES_PER_COMMITMENT` bytes.
+        ///  - The caller must ensure that `cell_indices` points to a region of memory that is at least `cell_indices_length` elements
+        ///    and that each element is 8 bytes.
+        ///  - The caller must ensure that `cells` points to a region of memory that is at least `cells_length * BYTES_PER_CELL` bytes.
+        ///  - The caller must ensure that `proofs` points to a region of memory that is at least `proofs_length * BYTES_PER_PROOF` bytes.
+        ///  - The caller must ensure that `verified` points to a region of memory that is at least 1 byte.
+        ///
+        ///  # Undefined behavior
+        ///
+        ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+        ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_verify_cell_kzg_proof_batch_flat", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_verify_cell_kzg_proof_batch_flat(DASContext* ctx, ulong commitments_length, byte* commitments, ulong cell_indices_length, ulong* cell_indices, ulong cells_length, byte* cells, ulong proofs_length, byte* proofs, bool* verified);
+
         /// <summary>
         ///  Recovers all cells and their KZG proofs from the given cell indices and cells
         ///
@@ -138,18 +457,76 @@ namespace EthKZG.Native
         ///    and that each cell is at least `BYTES_PER_CELL` bytes.
         ///  - The caller must ensure that `cell_indices` points to a region of memory that is at least `cell_indices_length` cell indices
         ///    and that each cell id is 8 bytes.
-        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` cells
+        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least `out_cells_length` cells
         ///    and that each cell is at least `BYTES_PER_CELL` bytes.
-        ///  - The caller must ensure that `out_proofs` points to a region of memory that is at least `CELLS_PER_EXT_BLOB` proofs
+        ///  - The caller must ensure that `out_proofs` points to a region of memory that is at least `out_proofs_length` proofs
         ///    and that each proof is at least `BYTES_PER_COMMITMENT` bytes.
         ///
+        ///  # Errors
+        ///
+        ///  - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+        ///    `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+        ///
         ///  # Undefined behavior
         ///
         ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
         ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "eth_kzg_recover_cells_and_proofs", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern CResult eth_kzg_recover_cells_and_proofs(DASContext* ctx, ulong cells_length, byte** cells, ulong cell_indices_length, ulong* cell_indices, byte** out_cells, byte** out_proofs);
+        internal static extern CResult eth_kzg_recover_cells_and_proofs(DASContext* ctx, ulong cells_length, byte** cells, ulong cell_indices_length, ulong* cell_indices, ulong out_cells_length, byte** out_cells, ulong out_proofs_length, byte** out_proofs);
+
+        /// <summary>
+        ///  Recovers all cells and their KZG proofs from the given cell indices and cells, that are given and
+        ///  returned as contiguous buffers.
+        ///
+        ///  This is the same as `eth_kzg_recover_cells_and_proofs`, except that the cells and proofs are read
+        ///  from and written to a single buffer each, where each item directly follows the previous one,
+        ///  rather than an array of pointers.
+        ///
+        ///  # Safety
+        ///
+        ///   - If the length parameter for a pointer is set to zero, then this implementation will not check if its pointer is
+        ///     null. This is because the caller might have passed in a null pointer, if the length is zero. Instead an empty slice will be created.
+        ///
+        ///  - The caller must ensure that the pointers are valid.
+        ///  - The caller must ensure that `cells` points to a region of memory that is at least `cells_length * BYTES_PER_CELL` bytes.
+        ///  - The caller must ensure that `cell_indices` points to a region of memory that is at least `cell_indices_length` cell indices
+        ///    and that each cell id is 8 bytes.
+        ///  - The caller must ensure that `out_cells` points to a region of memory that is at least
+        ///    `out_cells_length * BYTES_PER_CELL` bytes.
+        ///  - The caller must ensure that `out_proofs` points to a region of memory that is at least
+        ///    `out_proofs_length * BYTES_PER_PROOF` bytes.
+        ///
+        ///  # Errors
+        ///
+        ///  - If `out_cells_length` is less than `eth_kzg_cells_length()` or `out_proofs_length` is less than
+        ///    `eth_kzg_proofs_length()`, a `BufferTooSmall` error is returned and nothing is written.
+        ///
+        ///  # Undefined behavior
+        ///
+        ///  - This implementation will check if the ctx pointer is null, but it will not check if the other arguments are null.
+        ///    If the other arguments are null, this method will dereference a null pointer and result in undefined behavior.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_recover_cells_and_proofs_flat", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern CResult eth_kzg_recover_cells_and_proofs_flat(DASContext* ctx, ulong cells_length, byte* cells, ulong cell_indices_length, ulong* cell_indices, ulong out_cells_length, byte* out_cells, ulong out_proofs_length, byte* out_proofs);
+
+        /// <summary>
+        ///  Returns the number of cells that `eth_kzg_compute_cells`, `eth_kzg_compute_cells_and_kzg_proofs` and
+        ///  `eth_kzg_recover_cells_and_proofs` write to `out_cells`.
+        ///
+        ///  This is the smallest value that can be passed as `out_cells_length`.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_cells_length", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong eth_kzg_cells_length();
+
+        /// <summary>
+        ///  Returns the number of proofs that `eth_kzg_compute_cells_and_kzg_proofs` and
+        ///  `eth_kzg_recover_cells_and_proofs` write to `out_proofs`.
+        ///
+        ///  This is the smallest value that can be passed as `out_proofs_length`.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_proofs_length", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong eth_kzg_proofs_length();
 
         [DllImport(__DllName, EntryPoint = "eth_kzg_constant_bytes_per_cell", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ulong eth_kzg_constant_bytes_per_cell();
@@ -160,6 +537,15 @@ namespace EthKZG.Native
         [DllImport(__DllName, EntryPoint = "eth_kzg_constant_cells_per_ext_blob", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ulong eth_kzg_constant_cells_per_ext_blob();
 
+        /// <summary>
+        ///  Returns the version of the C ABI that the loaded library implements.
+        ///
+        ///  Hosts that load the library dynamically should check that this is equal to the
+        ///  `ETH_KZG_ABI_VERSION` they were built against, and refuse to use the library otherwise.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "eth_kzg_abi_version", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern uint eth_kzg_abi_version();
+
 
     }
 
@@ -168,11 +554,20 @@ namespace EthKZG.Native
     {
     }
 
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct DASContextConfig
+    {
+        public uint num_threads;
+        public uint precomp_width;
+        public uint mode;
+    }
+
     [StructLayout(LayoutKind.Sequential)]
     internal unsafe partial struct CResult
     {
         public CResultStatus status;
         public byte* error_msg;
+        public CResultCode code;
     }
 
 
@@ -182,5 +577,33 @@ namespace EthKZG.Native
         Err,
     }
 
+    internal enum CResultCode : uint
+    {
+        Ok = 0,
+        InvalidProof = 1,
+        BadLength = 2,
+        BadEncoding = 3,
+        BadArgument = 4,
+        Internal = 5,
+        Io = 6,
+        BufferTooSmall = 7,
+    }
+
+    internal enum CLogLevel : uint
+    {
+        Error = 1,
+        Warn = 2,
+        Info = 3,
+        Debug = 4,
+        Trace = 5,
+    }
+
+    internal enum DASContextMode : uint
+    {
+        Both = 0,
+        ProverOnly = 1,
+        VerifierOnly = 2,
+    }
+
 
 }
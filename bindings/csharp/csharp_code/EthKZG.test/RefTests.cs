@@ -50,6 +50,12 @@ public class ReferenceTests
         return strings.Select(GetBytes).ToArray();
     }
 
+    // Concatenates the byte arrays into a single flat buffer, as used by the span based methods.
+    private static byte[] GetFlatBytes(List<string> strings)
+    {
+        return strings.SelectMany(GetBytes).ToArray();
+    }
+
     #endregion
 
     #region BlobToKzgCommitment
@@ -147,6 +153,40 @@ public class ReferenceTests
         }
     }
 
+    [TestCase]
+    public void TestComputeCellsAndKzgProofsSpan()
+    {
+        Matcher matcher = new();
+        matcher.AddIncludePatterns(new[] { "*/*/data.yaml" });
+
+        IEnumerable<string> testFiles = matcher.GetResultsInFullPath(_computeCellsAndKzgProofsTests);
+        Assert.That(testFiles.Count(), Is.GreaterThan(0));
+
+        byte[] cells = new byte[EthKZG.MaxNumColumns * EthKZG.BytesPerCell];
+        byte[] proofs = new byte[EthKZG.MaxNumColumns * EthKZG.BytesPerProof];
+
+        foreach (string testFile in testFiles)
+        {
+            string yaml = File.ReadAllText(testFile);
+            ComputeCellsAndKzgProofsTest test = _deserializer.Deserialize<ComputeCellsAndKzgProofsTest>(yaml);
+            Assert.That(test, Is.Not.EqualTo(null));
+
+            byte[] blob = GetBytes(test.Input.Blob);
+
+            try
+            {
+                _context.ComputeCellsAndKZGProofs(blob, cells, proofs);
+                Assert.That(test.Output, Is.Not.EqualTo(null));
+                Assert.That(cells, Is.EqualTo(GetFlatBytes(test.Output.ElementAt(0))));
+                Assert.That(proofs, Is.EqualTo(GetFlatBytes(test.Output.ElementAt(1))));
+            }
+            catch
+            {
+                Assert.That(test.Output, Is.EqualTo(null));
+            }
+        }
+    }
+
     #endregion
 
     #region VerifyCellKzgProofBatch
@@ -197,6 +237,38 @@ public class ReferenceTests
         }
     }
 
+    [TestCase]
+    public void TestVerifyCellKzgProofBatchSpan()
+    {
+        Matcher matcher = new();
+        matcher.AddIncludePatterns(new[] { "*/*/data.yaml" });
+
+        IEnumerable<string> testFiles = matcher.GetResultsInFullPath(_verifyCellKzgProofBatchTests);
+        Assert.That(testFiles.Count(), Is.GreaterThan(0));
+
+        foreach (string testFile in testFiles)
+        {
+            string yaml = File.ReadAllText(testFile);
+            VerifyCellKzgProofBatchTest test = _deserializerUnderscoreNaming.Deserialize<VerifyCellKzgProofBatchTest>(yaml);
+            Assert.That(test, Is.Not.EqualTo(null));
+
+            byte[] commitments = GetFlatBytes(test.Input.Commitments);
+            ulong[] cellIndices = test.Input.CellIndices.ToArray();
+            byte[] cells = GetFlatBytes(test.Input.Cells);
+            byte[] proofs = GetFlatBytes(test.Input.Proofs);
+
+            try
+            {
+                bool isCorrect = _context.VerifyCellKZGProofBatch(commitments, cellIndices, cells, proofs);
+                Assert.That(isCorrect, Is.EqualTo(test.Output));
+            }
+            catch
+            {
+                Assert.That(test.Output, Is.EqualTo(null));
+            }
+        }
+    }
+
     #endregion
 
     #region RecoverCellsAndKzgProofs
@@ -247,5 +319,40 @@ public class ReferenceTests
         }
     }
 
+    [TestCase]
+    public void TestRecoverCellsAndKzgProofsSpan()
+    {
+        Matcher matcher = new();
+        matcher.AddIncludePatterns(new[] { "*/*/data.yaml" });
+
+        IEnumerable<string> testFiles = matcher.GetResultsInFullPath(_recoverCellsAndKzgProofsTests);
+        Assert.That(testFiles.Count(), Is.GreaterThan(0));
+
+        byte[] recoveredCells = new byte[EthKZG.MaxNumColumns * EthKZG.BytesPerCell];
+        byte[] recoveredProofs = new byte[EthKZG.MaxNumColumns * EthKZG.BytesPerProof];
+
+        foreach (string testFile in testFiles)
+        {
+            string yaml = File.ReadAllText(testFile);
+            RecoverCellsAndKzgProofsTest test = _deserializerUnderscoreNaming.Deserialize<RecoverCellsAndKzgProofsTest>(yaml);
+            Assert.That(test, Is.Not.EqualTo(null));
+
+            ulong[] cellIndices = test.Input.CellIndices.ToArray();
+            byte[] cells = GetFlatBytes(test.Input.Cells);
+
+            try
+            {
+                _context.RecoverCellsAndKZGProofs(cellIndices, cells, recoveredCells, recoveredProofs);
+                Assert.That(test.Output, Is.Not.EqualTo(null));
+                Assert.That(recoveredCells, Is.EqualTo(GetFlatBytes(test.Output.ElementAt(0))));
+                Assert.That(recoveredProofs, Is.EqualTo(GetFlatBytes(test.Output.ElementAt(1))));
+            }
+            catch
+            {
+                Assert.That(test.Output, Is.EqualTo(null));
+            }
+        }
+    }
+
     #endregion
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+package eth_kzg
+
+// #include "./build/c_eth_kzg.h"
+import "C"
+import "fmt"
+
+// ErrorCode indicates why a call into the library failed.
+//
+// The values match the `CResultCode` enum of the C API.
+type ErrorCode int
+
+const (
+	// ErrInvalidProof is returned when a proof failed verification.
+	ErrInvalidProof ErrorCode = C.CResultCode_InvalidProof
+	// ErrBadLength is returned when an input, or the number of inputs, has the wrong length.
+	ErrBadLength ErrorCode = C.CResultCode_BadLength
+	// ErrBadEncoding is returned when an input could not be deserialized.
+	ErrBadEncoding ErrorCode = C.CResultCode_BadEncoding
+	// ErrBadArgument is returned when an input is well formed but not valid for the function.
+	ErrBadArgument ErrorCode = C.CResultCode_BadArgument
+	// ErrInternal is returned for an error that was not caused by the inputs.
+	ErrInternal ErrorCode = C.CResultCode_Internal
+	// ErrIo is returned when a file could not be read.
+	ErrIo ErrorCode = C.CResultCode_Io
+	// ErrBufferTooSmall is returned when an output slice is too short for the result.
+	ErrBufferTooSmall ErrorCode = C.CResultCode_BufferTooSmall
+)
+
+// String returns the description of the code from the library.
+func (code ErrorCode) String() string {
+	return C.GoString(C.eth_kzg_error_to_string(C.CResultCode(code)))
+}
+
+// Error is the error returned by every method that calls into the library.
+//
+// Callers can branch on the kind of failure with errors.As:
+//
+//	var kzgErr *eth_kzg.Error
+//	if errors.As(err, &kzgErr) && kzgErr.Code == eth_kzg.ErrBadEncoding { ... }
+type Error struct {
+	Code    ErrorCode
+	Message string
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("%s: %s", e.Code, e.Message)
+}
+
+// Is reports whether target is an *Error with the same code, so that
+// errors.Is(err, &eth_kzg.Error{Code: eth_kzg.ErrBadLength}) works.
+func (e *Error) Is(target error) bool {
+	t, ok := target.(*Error)
+	return ok && t.Code == e.Code
+}
+
+// fromCResult converts the result of a call into the library into an error,
+// freeing the error message that the library allocated.
+func fromCResult(result C.CResult) error {
+	if result.status == C.Ok {
+		return nil
+	}
+	message := "unknown error"
+	if result.error_msg != nil {
+		message = C.GoString(result.error_msg)
+		C.eth_kzg_free_error_message(result.error_msg)
+	}
+	return &Error{Code: ErrorCode(result.code), Message: message}
+}
@@ -10,60 +10,186 @@ package eth_kzg
 */
 import "C"
 import (
-	"errors"
 	"runtime"
+	"unsafe"
 )
 
 /*
 
-NOTICE: This binding will not be maintained and is only for demonstration purposes.
-		The main reason being that forcing downstream users and their dependents to install
-		a rust toolchain is not ideal.
+NOTICE: This package links the static library that is built by `scripts/compile.sh golang`,
+		so a rust toolchain is needed to build it from source.
 */
 
+// The constants are taken from the generated header, so that they always match the
+// values used by the library.
 const (
 	// BytesPerCommitment is the number of bytes in a KZG commitment.
-	BytesPerCommitment = 48
+	BytesPerCommitment = C.BYTES_PER_COMMITMENT
 
 	// BytesPerProof is the number of bytes in a KZG proof.
-	BytesPerProof = 48
+	BytesPerProof = C.BYTES_PER_PROOF
 
 	// BytesPerFieldElement is the number of bytes in a BLS scalar field element.
-	BytesPerFieldElement = 32
+	BytesPerFieldElement = C.BYTES_PER_FIELD_ELEMENT
 
 	// BytesPerBlob is the number of bytes in a blob.
-	BytesPerBlob = 131_072
+	BytesPerBlob = C.BYTES_PER_BLOB
 
 	// MaxNumColumns is the maximum number of columns in an extended blob.
-	MaxNumColumns = 128
+	MaxNumColumns = C.CELLS_PER_EXT_BLOB
 
 	// BytesPerCell is the number of bytes in a single cell.
-	BytesPerCell = 2048
+	BytesPerCell = C.BYTES_PER_CELL
 )
 
+// The types are fixed size arrays, so a slice of them is a single contiguous buffer
+// that is passed to the library without being copied.
+type (
+	Blob          [BytesPerBlob]byte
+	Cell          [BytesPerCell]byte
+	KZGCommitment [BytesPerCommitment]byte
+	KZGProof      [BytesPerProof]byte
+)
+
+// Config holds the options used to create a DASContext.
+type Config struct {
+	// UsePrecomp enables the precomputed tables, which speed up proof creation at the cost of memory.
+	UsePrecomp bool
+	// NumThreads is the number of threads to use. Set to 0 to let the library pick a sensible default.
+	NumThreads uint32
+}
+
+// DASContext is a handle to a context in the library, which is used to create and verify
+// the KZG proofs of cells.
+//
+// The context is expensive to create, so it should be created once and reused. It is safe
+// to use from multiple goroutines. It is freed when it is garbage collected, or earlier by calling Close.
 type DASContext struct {
 	_inner *C.DASContext
 }
 
-func NewProverContext() *DASContext {
-	self := &DASContext{_inner: C.eth_kzg_das_context_new(C._Bool(true), C.uint32_t(1))}
+// NewDASContext creates a context that uses the embedded trusted setup.
+func NewDASContext(config Config) (*DASContext, error) {
+	var code C.CResultCode
+	inner := C.eth_kzg_das_context_new(C._Bool(config.UsePrecomp), C.uint32_t(config.NumThreads), &code)
+	if inner == nil {
+		return nil, &Error{Code: ErrorCode(code), Message: "could not create the context"}
+	}
+	self := &DASContext{_inner: inner}
+
+	runtime.SetFinalizer(self, (*DASContext).Close)
 
-	runtime.SetFinalizer(self, func(self *DASContext) {
-		C.eth_kzg_das_context_free(self.inner())
-	})
+	return self, nil
+}
 
-	return self
+// NewProverContext creates a context that uses precomputed tables and a single thread.
+func NewProverContext() (*DASContext, error) {
+	return NewDASContext(Config{UsePrecomp: true, NumThreads: 1})
 }
 
-func (prover *DASContext) BlobToKZGCommitment(blob []byte) ([]byte, error) {
-	if len(blob) != BytesPerBlob {
-		return nil, errors.New("invalid blob size")
+// Close frees the context. The context must not be used after it has been closed.
+//
+// Calling Close more than once has no effect.
+func (ctx *DASContext) Close() {
+	if ctx._inner == nil {
+		return
 	}
-	out := make([]byte, 48)
-	C.eth_kzg_blob_to_kzg_commitment(prover.inner(), (*C.uint8_t)(&blob[0]), (*C.uint8_t)(&out[0]))
-	return out, nil
+	C.eth_kzg_das_context_free(ctx._inner)
+	ctx._inner = nil
+	runtime.SetFinalizer(ctx, nil)
+}
+
+// BlobToKZGCommitment computes the KZG commitment for the blob.
+func (ctx *DASContext) BlobToKZGCommitment(blob *Blob) (KZGCommitment, error) {
+	var out KZGCommitment
+	result := C.eth_kzg_blob_to_kzg_commitment(ctx.inner(), ptr(blob[:]), ptr(out[:]))
+	runtime.KeepAlive(ctx)
+	return out, fromCResult(result)
+}
+
+// ComputeCellsAndKZGProofs computes the cells and the KZG proofs for the blob.
+//
+// The results are written to cells and proofs, which must each have a length of at least MaxNumColumns.
+func (ctx *DASContext) ComputeCellsAndKZGProofs(blob *Blob, cells []Cell, proofs []KZGProof) error {
+	result := C.eth_kzg_compute_cells_and_kzg_proofs_flat(
+		ctx.inner(),
+		ptr(blob[:]),
+		C.uint64_t(len(cells)), ptr(cells),
+		C.uint64_t(len(proofs)), ptr(proofs),
+	)
+	runtime.KeepAlive(ctx)
+	return fromCResult(result)
+}
+
+// ComputeCells computes the cells for the blob, without computing their KZG proofs.
+//
+// The cells are written to cells, which must have a length of at least MaxNumColumns.
+func (ctx *DASContext) ComputeCells(blob *Blob, cells []Cell) error {
+	result := C.eth_kzg_compute_cells_flat(
+		ctx.inner(),
+		ptr(blob[:]),
+		C.uint64_t(len(cells)), ptr(cells),
+	)
+	runtime.KeepAlive(ctx)
+	return fromCResult(result)
 }
 
-func (prover *DASContext) inner() *C.DASContext {
-	return prover._inner
+// RecoverCellsAndKZGProofs recovers all of the cells and computes their KZG proofs, given at
+// least half of the cells.
+//
+// The results are written to outCells and outProofs, which must each have a length of at least MaxNumColumns.
+func (ctx *DASContext) RecoverCellsAndKZGProofs(cellIndices []uint64, cells []Cell, outCells []Cell, outProofs []KZGProof) error {
+	result := C.eth_kzg_recover_cells_and_proofs_flat(
+		ctx.inner(),
+		C.uint64_t(len(cells)), ptr(cells),
+		C.uint64_t(len(cellIndices)), indicesPtr(cellIndices),
+		C.uint64_t(len(outCells)), ptr(outCells),
+		C.uint64_t(len(outProofs)), ptr(outProofs),
+	)
+	runtime.KeepAlive(ctx)
+	return fromCResult(result)
+}
+
+// VerifyCellKZGProofBatch verifies a batch of cell proofs.
+//
+// Returns false if any of the proofs is invalid, and an error if an input is malformed.
+func (ctx *DASContext) VerifyCellKZGProofBatch(commitments []KZGCommitment, cellIndices []uint64, cells []Cell, proofs []KZGProof) (bool, error) {
+	var verified C._Bool
+	result := C.eth_kzg_verify_cell_kzg_proof_batch_flat(
+		ctx.inner(),
+		C.uint64_t(len(commitments)), ptr(commitments),
+		C.uint64_t(len(cellIndices)), indicesPtr(cellIndices),
+		C.uint64_t(len(cells)), ptr(cells),
+		C.uint64_t(len(proofs)), ptr(proofs),
+		&verified,
+	)
+	runtime.KeepAlive(ctx)
+	if err := fromCResult(result); err != nil {
+		return false, err
+	}
+	return bool(verified), nil
+}
+
+func (ctx *DASContext) inner() *C.DASContext {
+	if ctx._inner == nil {
+		panic("eth_kzg: the context has been closed")
+	}
+	return ctx._inner
+}
+
+// ptr returns a pointer to the first byte of the slice, or nil if it is empty.
+//
+// The library does not read a pointer whose length is zero, so nil is valid for empty inputs.
+func ptr[T any](s []T) *C.uint8_t {
+	if len(s) == 0 {
+		return nil
+	}
+	return (*C.uint8_t)(unsafe.Pointer(&s[0]))
+}
+
+func indicesPtr(s []uint64) *C.uint64_t {
+	if len(s) == 0 {
+		return nil
+	}
+	return (*C.uint64_t)(unsafe.Pointer(&s[0]))
 }
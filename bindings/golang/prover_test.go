@@ -1,15 +1,130 @@
 package eth_kzg
 
 import (
+	"errors"
 	"testing"
 )
 
+func newTestContext(t *testing.T) *DASContext {
+	ctx, err := NewProverContext()
+	if err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(ctx.Close)
+	return ctx
+}
+
+func testBlob() *Blob {
+	var blob Blob
+	// Each field element must be less than the modulus, so only the low bytes are set.
+	for i := 0; i < BytesPerBlob; i += BytesPerFieldElement {
+		blob[i+BytesPerFieldElement-1] = byte(i / BytesPerFieldElement)
+	}
+	return &blob
+}
+
 func TestBridgeNewProverCtx(t *testing.T) {
+	ctx := newTestContext(t)
+
+	commitment, err := ctx.BlobToKZGCommitment(testBlob())
+	if err != nil {
+		t.Fatal(err)
+	}
+	if commitment == (KZGCommitment{}) {
+		t.Fatal("expected a non-zero commitment")
+	}
+}
+
+func TestComputeVerifyAndRecover(t *testing.T) {
+	ctx := newTestContext(t)
+	blob := testBlob()
+
+	commitment, err := ctx.BlobToKZGCommitment(blob)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	cells := make([]Cell, MaxNumColumns)
+	proofs := make([]KZGProof, MaxNumColumns)
+	if err := ctx.ComputeCellsAndKZGProofs(blob, cells, proofs); err != nil {
+		t.Fatal(err)
+	}
+
+	onlyCells := make([]Cell, MaxNumColumns)
+	if err := ctx.ComputeCells(blob, onlyCells); err != nil {
+		t.Fatal(err)
+	}
+	for i := range cells {
+		if cells[i] != onlyCells[i] {
+			t.Fatalf("cell %d differs between ComputeCells and ComputeCellsAndKZGProofs", i)
+		}
+	}
+
+	commitments := make([]KZGCommitment, MaxNumColumns)
+	cellIndices := make([]uint64, MaxNumColumns)
+	for i := range commitments {
+		commitments[i] = commitment
+		cellIndices[i] = uint64(i)
+	}
+	valid, err := ctx.VerifyCellKZGProofBatch(commitments, cellIndices, cells, proofs)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !valid {
+		t.Fatal("expected the proofs to be valid")
+	}
+
+	// An invalid proof is reported as false rather than as an error.
+	proofs[0], proofs[1] = proofs[1], proofs[0]
+	valid, err = ctx.VerifyCellKZGProofBatch(commitments, cellIndices, cells, proofs)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if valid {
+		t.Fatal("expected the swapped proofs to be invalid")
+	}
+	proofs[0], proofs[1] = proofs[1], proofs[0]
+
+	half := MaxNumColumns / 2
+	recoveredCells := make([]Cell, MaxNumColumns)
+	recoveredProofs := make([]KZGProof, MaxNumColumns)
+	if err := ctx.RecoverCellsAndKZGProofs(cellIndices[half:], cells[half:], recoveredCells, recoveredProofs); err != nil {
+		t.Fatal(err)
+	}
+	for i := range cells {
+		if cells[i] != recoveredCells[i] || proofs[i] != recoveredProofs[i] {
+			t.Fatalf("recovered cell or proof %d does not match", i)
+		}
+	}
+}
+
+func TestErrors(t *testing.T) {
+	ctx := newTestContext(t)
+	blob := testBlob()
+
+	// The output slices are too short.
+	err := ctx.ComputeCellsAndKZGProofs(blob, make([]Cell, 1), make([]KZGProof, MaxNumColumns))
+	if !errors.Is(err, &Error{Code: ErrBufferTooSmall}) {
+		t.Fatalf("expected a buffer too small error, got %v", err)
+	}
+
+	// There are not enough cells to recover the blob.
+	cells := make([]Cell, 1)
+	err = ctx.RecoverCellsAndKZGProofs([]uint64{0}, cells, make([]Cell, MaxNumColumns), make([]KZGProof, MaxNumColumns))
+	var kzgErr *Error
+	if !errors.As(err, &kzgErr) || kzgErr.Code != ErrBadLength {
+		t.Fatalf("expected a bad length error, got %v", err)
+	}
+	if kzgErr.Message == "" {
+		t.Fatal("expected the error to have a message")
+	}
+}
 
-	blob := make([]byte, 4096*32)
-	blob[1] = 1
-	prover_ctx := NewProverContext()
-	comm, err := prover_ctx.BlobToKZGCommitment(blob)
-	_ = comm
-	_ = err
+func TestCloseIsIdempotent(t *testing.T) {
+	ctx, err := NewProverContext()
+	if err != nil {
+		t.Fatal(err)
+	}
+	ctx.Close()
+	ctx.Close()
 }
@@ -0,0 +1,47 @@
+package ethereum.cryptography;
+
+import java.util.concurrent.CompletableFuture;
+
+/**
+ * A future that is completed by the native library, once it has computed cells and proofs
+ * on its own thread pool.
+ *
+ * <p>The native library calls the methods of this class from its own threads, which means that
+ * the result is converted into a {@link CellsAndProofs} and exceptions are created on the Java
+ * side, where the classes of this package are always visible.
+ */
+final class CellsAndProofsFuture extends CompletableFuture<CellsAndProofs> {
+
+    /** Called by the native library when the task succeeds. */
+    void completeWithCellsAndProofs(byte[][] cells, byte[][] proofs) {
+        complete(new CellsAndProofs(cells, proofs));
+    }
+
+    /**
+     * Called by the native library when the task fails.
+     *
+     * @param exceptionClass The JNI name of the exception that would have been thrown by the
+     *     synchronous method, such as {@code ethereum/cryptography/KZGSerializationException}.
+     * @param message The reason for the failure.
+     */
+    void completeWithError(String exceptionClass, String message) {
+        completeExceptionally(createException(exceptionClass, message));
+    }
+
+    private static RuntimeException createException(String exceptionClass, String message) {
+        switch (exceptionClass) {
+            case "ethereum/cryptography/KZGSerializationException":
+                return new KZGSerializationException(message);
+            case "ethereum/cryptography/KZGProofException":
+                return new KZGProofException(message);
+            case "ethereum/cryptography/KZGRecoveryException":
+                return new KZGRecoveryException(message);
+            case "ethereum/cryptography/KZGTrustedSetupException":
+                return new KZGTrustedSetupException(message);
+            case "java/lang/IllegalStateException":
+                return new IllegalStateException(message);
+            default:
+                return new IllegalArgumentException(message);
+        }
+    }
+}
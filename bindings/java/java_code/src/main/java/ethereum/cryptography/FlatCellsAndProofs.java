@@ -0,0 +1,92 @@
+package ethereum.cryptography;
+
+import java.util.Arrays;
+
+/**
+ * Represents the cells of an extended blob and their corresponding proofs, with the cells
+ * and the proofs each stored contiguously in a single byte array.
+ *
+ * <p>Cell {@code i} occupies the bytes starting at {@code i * BYTES_PER_CELL} in {@link #cells},
+ * and its proof occupies the bytes starting at {@code i * BYTES_PER_PROOF} in {@link #proofs}.
+ * This avoids allocating a separate array for every cell and proof.
+ */
+public class FlatCellsAndProofs {
+
+    /** The concatenated cells. */
+    public final byte[] cells;
+
+    /** The concatenated proofs corresponding to the cells. */
+    public final byte[] proofs;
+
+    /**
+     * Constructs a FlatCellsAndProofs object with the given cells and proofs.
+     *
+     * @param cells  The concatenated cells.
+     * @param proofs The concatenated proofs corresponding to the cells.
+     */
+    public FlatCellsAndProofs(byte[] cells, byte[] proofs) {
+        this.cells = cells;
+        this.proofs = proofs;
+    }
+
+    /**
+     * Gets the number of cells.
+     *
+     * @return The number of cells.
+     */
+    public int numCells() {
+        return cells.length / LibEthKZG.BYTES_PER_CELL;
+    }
+
+    /**
+     * Gets a copy of the cell at the given index.
+     *
+     * @param index The index of the cell.
+     * @return The cell.
+     */
+    public byte[] getCell(int index) {
+        return Arrays.copyOfRange(cells, index * LibEthKZG.BYTES_PER_CELL, (index + 1) * LibEthKZG.BYTES_PER_CELL);
+    }
+
+    /**
+     * Gets a copy of the proof at the given index.
+     *
+     * @param index The index of the proof.
+     * @return The proof.
+     */
+    public byte[] getProof(int index) {
+        return Arrays.copyOfRange(proofs, index * LibEthKZG.BYTES_PER_PROOF, (index + 1) * LibEthKZG.BYTES_PER_PROOF);
+    }
+
+    /**
+     * Converts this object into a CellsAndProofs object, with one array per cell and proof.
+     *
+     * @return The equivalent CellsAndProofs object.
+     */
+    public CellsAndProofs toCellsAndProofs() {
+        byte[][] cellsArr = new byte[numCells()][];
+        byte[][] proofsArr = new byte[numCells()][];
+        for (int i = 0; i < cellsArr.length; i++) {
+            cellsArr[i] = getCell(i);
+            proofsArr[i] = getProof(i);
+        }
+        return new CellsAndProofs(cellsArr, proofsArr);
+    }
+
+    @Override
+    public int hashCode() {
+        return 31 * Arrays.hashCode(cells) + Arrays.hashCode(proofs);
+    }
+
+    @Override
+    public boolean equals(Object obj) {
+        if (this == obj) {
+            return true;
+        }
+        if (obj == null || getClass() != obj.getClass()) {
+            return false;
+        }
+        FlatCellsAndProofs other = (FlatCellsAndProofs) obj;
+        return Arrays.equals(cells, other.cells) && Arrays.equals(proofs, other.proofs);
+    }
+}
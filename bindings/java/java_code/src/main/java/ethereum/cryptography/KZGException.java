@@ -0,0 +1,20 @@
+package ethereum.cryptography;
+
+/**
+ * The base class for the exceptions thrown when the native library rejects its inputs.
+ *
+ * <p>This extends {@link IllegalArgumentException}, so code that catches
+ * {@code IllegalArgumentException} continues to handle all of these exceptions.
+ * Catch one of the subclasses to handle a specific category of failure.
+ */
+public class KZGException extends IllegalArgumentException {
+
+    /**
+     * Constructs a KZGException with the given message.
+     *
+     * @param message The reason for the failure.
+     */
+    public KZGException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,20 @@
+package ethereum.cryptography;
+
+/**
+ * Thrown when the inputs to proof verification are inconsistent, for example
+ * because a cell index is out of range or the input arrays have different lengths.
+ *
+ * <p>Note: A proof that is well-formed but fails verification is not an error, and is instead
+ * reported by the verification method returning {@code false}.
+ */
+public class KZGProofException extends KZGException {
+
+    /**
+     * Constructs a KZGProofException with the given message.
+     *
+     * @param message The reason for the failure.
+     */
+    public KZGProofException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,17 @@
+package ethereum.cryptography;
+
+/**
+ * Thrown when the cells given to recovery cannot be used to reconstruct the blob,
+ * for example because there are not enough of them or the cell indices are not unique.
+ */
+public class KZGRecoveryException extends KZGException {
+
+    /**
+     * Constructs a KZGRecoveryException with the given message.
+     *
+     * @param message The reason for the failure.
+     */
+    public KZGRecoveryException(String message) {
+        super(message);
+    }
+}
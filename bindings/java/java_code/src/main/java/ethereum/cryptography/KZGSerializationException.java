@@ -0,0 +1,17 @@
+package ethereum.cryptography;
+
+/**
+ * Thrown when a blob, cell, commitment or proof could not be deserialized,
+ * for example because it has the wrong length or does not encode a valid field element or point.
+ */
+public class KZGSerializationException extends KZGException {
+
+    /**
+     * Constructs a KZGSerializationException with the given message.
+     *
+     * @param message The reason for the failure.
+     */
+    public KZGSerializationException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,16 @@
+package ethereum.cryptography;
+
+/**
+ * Thrown when a trusted setup could not be parsed or contains invalid points.
+ */
+public class KZGTrustedSetupException extends KZGException {
+
+    /**
+     * Constructs a KZGTrustedSetupException with the given message.
+     *
+     * @param message The reason for the failure.
+     */
+    public KZGTrustedSetupException(String message) {
+        super(message);
+    }
+}
@@ -2,13 +2,19 @@ package ethereum.cryptography;
 import java.io.IOException;
 import java.io.InputStream;
 import java.io.UncheckedIOException;
+import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
 import java.nio.file.Files;
 import java.nio.file.Path;
 import java.nio.file.StandardCopyOption;
+import java.util.concurrent.CompletableFuture;
 
 /**
  * This class handles the loading of native libraries and provides methods for
  * Ethereum's DAS related cryptography.
+ *
+ * <p>Inputs that are rejected by the native library result in a subclass of
+ * {@link KZGException} being thrown, which indicates the category of the failure.
  */
 public class LibEthKZG implements AutoCloseable{
     /** The number of bytes in a KZG commitment. */
@@ -24,7 +30,13 @@ public class LibEthKZG implements AutoCloseable{
     /** The number of bytes in a single cell. */
     public static final int BYTES_PER_CELL = 2048;
 
-    private long contextPtr;
+    /**
+     * A handle to the native context.
+     *
+     * This is not a pointer, so using a handle after the context has been destroyed
+     * results in an IllegalStateException being thrown by the native code.
+     */
+    private long contextHandle;
 
     private static volatile boolean libraryLoaded = false;
     private static final Object libraryLock = new Object();
@@ -37,18 +49,67 @@ public class LibEthKZG implements AutoCloseable{
         ensureLibraryLoaded();
         boolean usePrecomp = true;
         long numThreads = 1;
-        this.contextPtr = DASContextNew(usePrecomp, numThreads);
+        this.contextHandle = DASContextNew(usePrecomp, numThreads);
     }
 
     /**
      * Constructs a LibEthKZG instance with specified parameters.
      *
+     * <p>Passing {@code false} for {@code usePrecomp} avoids allocating the pre-computed tables,
+     * at the cost of slower proof creation.
+     *
      * @param usePrecomp Whether to use pre-computation.
      * @param numThreads Number of threads to use.
      */
     public LibEthKZG(boolean usePrecomp, long numThreads) {
         ensureLibraryLoaded();
-        this.contextPtr = DASContextNew(usePrecomp, numThreads);
+        this.contextHandle = DASContextNew(usePrecomp, numThreads);
+    }
+
+    /**
+     * Constructs a LibEthKZG instance that uses pre-computation with the given width.
+     *
+     * <p>Larger widths make proof creation faster, but the memory needed for the pre-computed
+     * tables doubles with every increase in the width. The width used by the other constructors
+     * is 8.
+     *
+     * @param numThreads Number of threads to use, or 0 to let the library pick a sensible default.
+     * @param precompWidth The width of the pre-computed tables, between 2 and 16 inclusive.
+     * @throws IllegalArgumentException If either of the parameters is out of range.
+     */
+    public LibEthKZG(int numThreads, int precompWidth) {
+        ensureLibraryLoaded();
+        this.contextHandle = DASContextNewWithPrecompWidth(numThreads, precompWidth);
+    }
+
+    /**
+     * Constructs a LibEthKZG instance that uses the given trusted setup instead of the embedded one.
+     *
+     * <p>The trusted setup must be in the JSON format used by the consensus specs, containing the
+     * {@code g1_monomial}, {@code g1_lagrange} and {@code g2_monomial} points. All of the points are
+     * checked to be valid and in the correct subgroup.
+     *
+     * @param trustedSetupJson The trusted setup, as a JSON string.
+     * @param usePrecomp Whether to use pre-computation.
+     * @param numThreads Number of threads to use.
+     * @throws KZGTrustedSetupException If the trusted setup could not be parsed or contains invalid points.
+     */
+    public LibEthKZG(String trustedSetupJson, boolean usePrecomp, long numThreads) {
+        ensureLibraryLoaded();
+        this.contextHandle = DASContextNewWithTrustedSetup(trustedSetupJson, usePrecomp, numThreads);
+    }
+
+    /**
+     * Constructs a LibEthKZG instance that uses the given trusted setup instead of the embedded one.
+     *
+     * @param trustedSetupJson The trusted setup, as UTF-8 encoded JSON.
+     * @param usePrecomp Whether to use pre-computation.
+     * @param numThreads Number of threads to use.
+     * @throws KZGTrustedSetupException If the trusted setup could not be parsed or contains invalid points.
+     * @see #LibEthKZG(String, boolean, long)
+     */
+    public LibEthKZG(byte[] trustedSetupJson, boolean usePrecomp, long numThreads) {
+        this(new String(trustedSetupJson, StandardCharsets.UTF_8), usePrecomp, numThreads);
     }
 
     private static void ensureLibraryLoaded() {
@@ -72,18 +133,59 @@ public class LibEthKZG implements AutoCloseable{
      * This method should be called when the LibEthKZG instance is no longer needed.
      */
     public void destroy() {
-        if (contextPtr != 0) {
-            DASContextDestroy(contextPtr);
-            contextPtr = 0;
+        if (contextHandle != 0) {
+            DASContextDestroy(contextHandle);
+            contextHandle = 0;
         }
     }
 
     private void checkContextHasNotBeenFreed() {
-        if (contextPtr == 0) {
+        if (contextHandle == 0) {
             throw new IllegalStateException("KZG context context has been destroyed");
         }
     }
 
+    /**
+     * Returns the version of the native library that has been loaded.
+     *
+     * @return The version of the native library.
+     */
+    public static String version() {
+        ensureLibraryLoaded();
+        return libraryVersion();
+    }
+
+    /**
+     * Returns an identifier for the trusted setup that is embedded in the native library,
+     * which is used by the constructors that do not take a trusted setup.
+     *
+     * @return The identifier of the embedded trusted setup.
+     */
+    public static String embeddedTrustedSetup() {
+        ensureLibraryLoaded();
+        return embeddedTrustedSetupId();
+    }
+
+    /**
+     * Returns the number of threads used by this context.
+     *
+     * @return The number of threads in the native thread pool of this context.
+     */
+    public long numThreads() {
+        checkContextHasNotBeenFreed();
+        return numThreads(contextHandle);
+    }
+
+    /**
+     * Returns the width of the pre-computed tables used by this context.
+     *
+     * @return The width of the pre-computed tables, or 0 if pre-computation is not used.
+     */
+    public int precompWidth() {
+        checkContextHasNotBeenFreed();
+        return precompWidth(contextHandle);
+    }
+
     /**
      * Computes the KZG commitment for a given blob.
      *
@@ -92,7 +194,7 @@ public class LibEthKZG implements AutoCloseable{
      */
     public byte[] blobToKZGCommitment(byte[] blob) {
         checkContextHasNotBeenFreed();
-        return blobToKZGCommitment(contextPtr, blob);
+        return blobToKZGCommitment(contextHandle, blob);
     }
 
     /**
@@ -103,10 +205,52 @@ public class LibEthKZG implements AutoCloseable{
      */
     public CellsAndProofs computeCellsAndKZGProofs(byte[] blob) {
         checkContextHasNotBeenFreed();
-        CellsAndProofs cellsAndProofs = computeCellsAndKZGProofs(contextPtr, blob);
+        CellsAndProofs cellsAndProofs = computeCellsAndKZGProofs(contextHandle, blob);
         return cellsAndProofs;
     }
 
+    /**
+     * Computes the cells and KZG proofs for a given blob, returning them as flat arrays.
+     *
+     * This is equivalent to computeCellsAndKZGProofs, but avoids creating a separate array
+     * for every cell and proof, which is significantly cheaper.
+     *
+     * @param blob The input blob.
+     * @return FlatCellsAndProofs object containing the concatenated cells and proofs.
+     */
+    public FlatCellsAndProofs computeCellsAndKZGProofsFlat(byte[] blob) {
+        checkContextHasNotBeenFreed();
+        return computeCellsAndKZGProofsFlat(contextHandle, blob);
+    }
+
+    /**
+     * Computes the cells for a given blob, without computing their KZG proofs.
+     *
+     * This is much cheaper than computeCellsAndKZGProofs, and can be used when only the
+     * erasure-extended data is needed.
+     *
+     * @param blob The input blob.
+     * @return The cells of the extended blob.
+     */
+    public byte[][] computeCells(byte[] blob) {
+        checkContextHasNotBeenFreed();
+        return computeCells(contextHandle, blob);
+    }
+
+    /**
+     * Computes cells and KZG proofs for multiple blobs.
+     *
+     * The blobs are processed in parallel using the native thread pool, so this
+     * is faster than calling computeCellsAndKZGProofs for each blob.
+     *
+     * @param blobs The input blobs.
+     * @return An array containing the CellsAndProofs for each blob, in the same order as the blobs.
+     */
+    public CellsAndProofs[] computeCellsAndKZGProofsBatch(byte[][] blobs) {
+        checkContextHasNotBeenFreed();
+        return computeCellsAndKZGProofsBatch(contextHandle, blobs);
+    }
+
     /**
      * Verifies a batch of cell KZG proofs.
      *
@@ -119,19 +263,151 @@ public class LibEthKZG implements AutoCloseable{
     public boolean verifyCellKZGProofBatch(byte[][] commitmentsArr,  long[] cellIndices, byte[][] cellsArr,
             byte[][] proofsArr) {
                 checkContextHasNotBeenFreed();
-        return verifyCellKZGProofBatch(contextPtr, commitmentsArr, cellIndices, cellsArr, proofsArr);
+        return verifyCellKZGProofBatch(contextHandle, commitmentsArr, cellIndices, cellsArr, proofsArr);
+    }
+
+    /**
+     * Recovers all of the cells and computes their KZG proofs from a subset of the cells.
+     *
+     * This matches `recover_cells_and_kzg_proofs` in the consensus specs, which only takes
+     * the cell indices and cells. Proofs for the supplied cells are not accepted, since the
+     * proofs for all of the cells are computed together and so would not be cheaper to compute
+     * if some of them were already known.
+     *
+     * @param cellIndices Array of cell indices, one for each cell.
+     * @param cellsArr    Array of cells.
+     * @return CellsAndProofs object containing all of the cells and proofs.
+     */
+    public CellsAndProofs recoverCellsAndKZGProofs(long[] cellIndices, byte[][] cellsArr) {
+        checkContextHasNotBeenFreed();
+        return recoverCellsAndKZGProofs(contextHandle, cellIndices, cellsArr);
+    }
+
+    /**
+     * Recovers all of the cells and computes their KZG proofs from a subset of the cells,
+     * using flat arrays for both the input and the output.
+     *
+     * @param cellIndices Array of cell indices, one for each cell.
+     * @param cells       The concatenated cells, in the same order as the cell indices.
+     * @return FlatCellsAndProofs object containing all of the concatenated cells and proofs.
+     * @see #recoverCellsAndKZGProofs(long[], byte[][])
+     */
+    public FlatCellsAndProofs recoverCellsAndKZGProofsFlat(long[] cellIndices, byte[] cells) {
+        checkContextHasNotBeenFreed();
+        return recoverCellsAndKZGProofsFlat(contextHandle, cellIndices, cells);
+    }
+
+    /*
+     * The methods below are asynchronous variants of the methods above. The work is run on the
+     * thread pool of the native context, so the calling thread is not blocked while the cells and
+     * proofs are being computed.
+     *
+     * The returned future is completed on one of the native threads, so dependent stages that are
+     * not cheap should be run using one of the `*Async` methods of CompletableFuture. Failures
+     * complete the future exceptionally, with the exception that the synchronous method would have
+     * thrown.
+     */
+
+    /**
+     * Asynchronously computes the cells and KZG proofs for a given blob.
+     *
+     * @param blob The input blob.
+     * @return A future that is completed with the cells and proofs.
+     * @see #computeCellsAndKZGProofs(byte[])
+     */
+    public CompletableFuture<CellsAndProofs> computeCellsAndKZGProofsAsync(byte[] blob) {
+        checkContextHasNotBeenFreed();
+        CellsAndProofsFuture future = new CellsAndProofsFuture();
+        computeCellsAndKZGProofsAsync(contextHandle, blob, future);
+        return future;
     }
 
     /**
-     * Recovers cells and computes KZG proofs from given cell IDs and cells.
+     * Asynchronously recovers all of the cells and computes their KZG proofs from a subset of the cells.
      *
-     * @param cellIDs  Array of cell IDs.
-     * @param cellsArr Array of cells.
-     * @return CellsAndProofs object containing the recovered cells and proofs.
+     * @param cellIndices Array of cell indices, one for each cell.
+     * @param cellsArr    Array of cells.
+     * @return A future that is completed with all of the cells and proofs.
+     * @see #recoverCellsAndKZGProofs(long[], byte[][])
      */
-    public CellsAndProofs recoverCellsAndKZGProofs(long[] cellIDs, byte[][] cellsArr) {
+    public CompletableFuture<CellsAndProofs> recoverCellsAndKZGProofsAsync(long[] cellIndices, byte[][] cellsArr) {
         checkContextHasNotBeenFreed();
-        return recoverCellsAndKZGProofs(contextPtr, cellIDs, cellsArr);
+        CellsAndProofsFuture future = new CellsAndProofsFuture();
+        recoverCellsAndKZGProofsAsync(contextHandle, cellIndices, cellsArr, future);
+        return future;
+    }
+
+    /*
+     * The methods below are variants of the methods above which read their inputs from and write
+     * their outputs to direct ByteBuffers. This avoids copying the data between the Java heap and
+     * native memory on every call.
+     *
+     * Multiple items, such as the cells of an extended blob, are concatenated together in a single
+     * buffer. The whole capacity of each buffer is used, its position and limit are ignored.
+     */
+
+    /**
+     * Computes the KZG commitment for a given blob, using direct buffers.
+     *
+     * @param blob          A direct buffer containing the blob.
+     * @param commitmentOut A direct buffer of BYTES_PER_COMMITMENT bytes that the commitment will be written to.
+     */
+    public void blobToKZGCommitment(ByteBuffer blob, ByteBuffer commitmentOut) {
+        checkContextHasNotBeenFreed();
+        checkIsDirect(blob, commitmentOut);
+        blobToKZGCommitmentDirect(contextHandle, blob, commitmentOut);
+    }
+
+    /**
+     * Computes cells and KZG proofs for a given blob, using direct buffers.
+     *
+     * @param blob      A direct buffer containing the blob.
+     * @param cellsOut  A direct buffer of MAX_NUM_COLUMNS * BYTES_PER_CELL bytes that the cells will be written to.
+     * @param proofsOut A direct buffer of MAX_NUM_COLUMNS * BYTES_PER_PROOF bytes that the proofs will be written to.
+     */
+    public void computeCellsAndKZGProofs(ByteBuffer blob, ByteBuffer cellsOut, ByteBuffer proofsOut) {
+        checkContextHasNotBeenFreed();
+        checkIsDirect(blob, cellsOut, proofsOut);
+        computeCellsAndKZGProofsDirect(contextHandle, blob, cellsOut, proofsOut);
+    }
+
+    /**
+     * Verifies a batch of cell KZG proofs, using direct buffers.
+     *
+     * @param commitments A direct buffer containing the concatenated commitments.
+     * @param cellIndices Array of cell indices.
+     * @param cells       A direct buffer containing the concatenated cells.
+     * @param proofs      A direct buffer containing the concatenated proofs.
+     * @return true if the batch verification succeeds, false otherwise.
+     */
+    public boolean verifyCellKZGProofBatch(ByteBuffer commitments, long[] cellIndices, ByteBuffer cells,
+            ByteBuffer proofs) {
+        checkContextHasNotBeenFreed();
+        checkIsDirect(commitments, cells, proofs);
+        return verifyCellKZGProofBatchDirect(contextHandle, commitments, cellIndices, cells, proofs);
+    }
+
+    /**
+     * Recovers cells and computes KZG proofs from given cell indices and cells, using direct buffers.
+     *
+     * @param cellIndices Array of cell indices, one for each cell.
+     * @param cells       A direct buffer containing the concatenated cells.
+     * @param cellsOut    A direct buffer of MAX_NUM_COLUMNS * BYTES_PER_CELL bytes that the cells will be written to.
+     * @param proofsOut   A direct buffer of MAX_NUM_COLUMNS * BYTES_PER_PROOF bytes that the proofs will be written to.
+     */
+    public void recoverCellsAndKZGProofs(long[] cellIndices, ByteBuffer cells, ByteBuffer cellsOut,
+            ByteBuffer proofsOut) {
+        checkContextHasNotBeenFreed();
+        checkIsDirect(cells, cellsOut, proofsOut);
+        recoverCellsAndKZGProofsDirect(contextHandle, cellIndices, cells, cellsOut, proofsOut);
+    }
+
+    private static void checkIsDirect(ByteBuffer... buffers) {
+        for (ByteBuffer buffer : buffers) {
+            if (buffer == null || !buffer.isDirect()) {
+                throw new IllegalArgumentException("expected a direct ByteBuffer");
+            }
+        }
     }
 
     /*
@@ -141,16 +417,55 @@ public class LibEthKZG implements AutoCloseable{
 
     private static native long DASContextNew(boolean usePrecomp, long numThreads);
 
-    private static native void DASContextDestroy(long ctx_ptr);
+    private static native long DASContextNewWithPrecompWidth(int numThreads, int precompWidth);
+
+    private static native long DASContextNewWithTrustedSetup(
+            String trustedSetupJson, boolean usePrecomp, long numThreads);
 
-    private static native CellsAndProofs computeCellsAndKZGProofs(long context_ptr, byte[] blob);
+    private static native String libraryVersion();
 
-    private static native byte[] blobToKZGCommitment(long context_ptr, byte[] blob);
+    private static native String embeddedTrustedSetupId();
+
+    private static native long numThreads(long context_handle);
+
+    private static native int precompWidth(long context_handle);
+
+    private static native void DASContextDestroy(long context_handle);
+
+    private static native CellsAndProofs computeCellsAndKZGProofs(long context_handle, byte[] blob);
+
+    private static native FlatCellsAndProofs computeCellsAndKZGProofsFlat(long context_handle, byte[] blob);
+
+    private static native byte[][] computeCells(long context_handle, byte[] blob);
+
+    private static native CellsAndProofs[] computeCellsAndKZGProofsBatch(long context_handle, byte[][] blobs);
+
+    private static native byte[] blobToKZGCommitment(long context_handle, byte[] blob);
 
     private static native boolean verifyCellKZGProofBatch(
-            long context_ptr, byte[][] commitments, long[] cellIndices, byte[][] cells, byte[][] proofs);
+            long context_handle, byte[][] commitments, long[] cellIndices, byte[][] cells, byte[][] proofs);
+
+    private static native CellsAndProofs recoverCellsAndKZGProofs(long context_handle, long[] cellIndices, byte[][] cells);
 
-    private static native CellsAndProofs recoverCellsAndKZGProofs(long context_ptr, long[] cellIDs, byte[][] cells);
+    private static native FlatCellsAndProofs recoverCellsAndKZGProofsFlat(
+            long context_handle, long[] cellIndices, byte[] cells);
+
+    private static native void computeCellsAndKZGProofsAsync(
+            long context_handle, byte[] blob, CellsAndProofsFuture future);
+
+    private static native void recoverCellsAndKZGProofsAsync(
+            long context_handle, long[] cellIndices, byte[][] cells, CellsAndProofsFuture future);
+
+    private static native void blobToKZGCommitmentDirect(long context_handle, ByteBuffer blob, ByteBuffer commitmentOut);
+
+    private static native void computeCellsAndKZGProofsDirect(
+            long context_handle, ByteBuffer blob, ByteBuffer cellsOut, ByteBuffer proofsOut);
+
+    private static native boolean verifyCellKZGProofBatchDirect(
+            long context_handle, ByteBuffer commitments, long[] cellIndices, ByteBuffer cells, ByteBuffer proofs);
+
+    private static native void recoverCellsAndKZGProofsDirect(
+            long context_handle, long[] cellIndices, ByteBuffer cells, ByteBuffer cellsOut, ByteBuffer proofsOut);
 
     private static final String LIBRARY_NAME = "java_eth_kzg";
     private static final String PLATFORM_NATIVE_LIBRARY_NAME = System.mapLibraryName(LIBRARY_NAME);
@@ -166,8 +481,19 @@ public class LibEthKZG implements AutoCloseable{
         }
     }
 
+    private static boolean isAndroid() {
+        return System.getProperty("java.vm.vendor", "").toLowerCase().contains("android")
+                || System.getProperty("java.vm.name", "").equalsIgnoreCase("dalvik");
+    }
+
     /** Loads the appropriate native library based on your platform. */
     private static void loadNativeLibrary() {
+        // Android apps ship the library for their ABI in `jniLibs`, and cannot load the
+        // bundled Linux libraries since they are linked against glibc.
+        if (isAndroid()) {
+            System.loadLibrary(LIBRARY_NAME);
+            return;
+        }
 
         String osName = System.getProperty("os.name").toLowerCase();
         String osArch = getNormalizedArchitecture();
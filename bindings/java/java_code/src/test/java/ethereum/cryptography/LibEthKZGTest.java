@@ -4,6 +4,7 @@ import static org.junit.jupiter.api.Assertions.assertArrayEquals;
 import static org.junit.jupiter.api.Assertions.assertNull;
 import static org.junit.jupiter.api.Assertions.assertEquals;
 import static org.junit.jupiter.api.Assertions.assertFalse;
+import static org.junit.jupiter.api.Assertions.assertInstanceOf;
 import static org.junit.jupiter.api.Assertions.assertNotEquals;
 import static org.junit.jupiter.api.Assertions.assertNotNull;
 import static org.junit.jupiter.api.Assertions.assertThrows;
@@ -11,6 +12,8 @@ import static org.junit.jupiter.api.Assertions.assertTrue;
 
 import org.junit.jupiter.api.BeforeAll;
 import org.junit.jupiter.api.Test;
+import java.nio.ByteBuffer;
+import java.util.concurrent.ExecutionException;
 import java.util.stream.IntStream;
 import java.util.stream.LongStream;
 import java.util.stream.Stream;
@@ -106,4 +109,220 @@ public class LibEthKZGTest {
         }
     }
 
-}
\ No newline at end of file
+    @Test
+    void testDirectByteBufferMethodsMatchByteArrayMethods() {
+        byte[] blob = new byte[LibEthKZG.BYTES_PER_BLOB];
+        for (int i = 0; i < LibEthKZG.BYTES_PER_BLOB / LibEthKZG.BYTES_PER_FIELD_ELEMENT; i++) {
+            blob[i * LibEthKZG.BYTES_PER_FIELD_ELEMENT + LibEthKZG.BYTES_PER_FIELD_ELEMENT - 1] = (byte) i;
+        }
+        ByteBuffer blobBuffer = ByteBuffer.allocateDirect(blob.length).put(blob);
+
+        byte[] commitment = context.blobToKZGCommitment(blob);
+        ByteBuffer commitmentBuffer = ByteBuffer.allocateDirect(LibEthKZG.BYTES_PER_COMMITMENT);
+        context.blobToKZGCommitment(blobBuffer, commitmentBuffer);
+        assertEquals(ByteBuffer.wrap(commitment), commitmentBuffer.rewind());
+
+        CellsAndProofs cellsAndProofs = context.computeCellsAndKZGProofs(blob);
+        ByteBuffer cells = ByteBuffer.allocateDirect(LibEthKZG.MAX_NUM_COLUMNS * LibEthKZG.BYTES_PER_CELL);
+        ByteBuffer proofs = ByteBuffer.allocateDirect(LibEthKZG.MAX_NUM_COLUMNS * LibEthKZG.BYTES_PER_PROOF);
+        context.computeCellsAndKZGProofs(blobBuffer, cells, proofs);
+        assertEquals(ByteBuffer.wrap(cellsAndProofs.toBytes()),
+                ByteBuffer.allocate(cells.capacity() + proofs.capacity()).put(cells.rewind()).put(proofs.rewind()).flip());
+
+        ByteBuffer commitments = ByteBuffer.allocateDirect(LibEthKZG.MAX_NUM_COLUMNS * LibEthKZG.BYTES_PER_COMMITMENT);
+        long[] cellIndices = LongStream.range(0, LibEthKZG.MAX_NUM_COLUMNS).toArray();
+        for (int i = 0; i < LibEthKZG.MAX_NUM_COLUMNS; i++) {
+            commitments.put(commitment);
+        }
+        assertTrue(context.verifyCellKZGProofBatch(commitments, cellIndices, cells, proofs));
+
+        // Recover from every other cell
+        long[] halfCellIndices = LongStream.range(0, LibEthKZG.MAX_NUM_COLUMNS / 2).map(i -> 2 * i).toArray();
+        ByteBuffer halfCells = ByteBuffer.allocateDirect(halfCellIndices.length * LibEthKZG.BYTES_PER_CELL);
+        for (long cellIndex : halfCellIndices) {
+            halfCells.put(cellsAndProofs.getCells()[(int) cellIndex]);
+        }
+        ByteBuffer recoveredCells = ByteBuffer.allocateDirect(cells.capacity());
+        ByteBuffer recoveredProofs = ByteBuffer.allocateDirect(proofs.capacity());
+        context.recoverCellsAndKZGProofs(halfCellIndices, halfCells, recoveredCells, recoveredProofs);
+        assertEquals(cells.rewind(), recoveredCells);
+        assertEquals(proofs.rewind(), recoveredProofs);
+
+        // Heap buffers and incorrectly sized buffers are rejected
+        assertThrows(IllegalArgumentException.class,
+                () -> context.blobToKZGCommitment(ByteBuffer.wrap(blob), commitmentBuffer));
+        assertThrows(IllegalArgumentException.class,
+                () -> context.blobToKZGCommitment(blobBuffer, ByteBuffer.allocateDirect(1)));
+    }
+
+    @Test
+    void testContextWithPrecompWidth() {
+        byte[] blob = new byte[LibEthKZG.BYTES_PER_BLOB];
+        blob[LibEthKZG.BYTES_PER_FIELD_ELEMENT - 1] = 1;
+
+        try (LibEthKZG instance = new LibEthKZG(2, 4)) {
+            assertEquals(context.computeCellsAndKZGProofs(blob), instance.computeCellsAndKZGProofs(blob));
+        }
+
+        assertThrows(IllegalArgumentException.class, () -> new LibEthKZG(-1, 8));
+        assertThrows(IllegalArgumentException.class, () -> new LibEthKZG(1, 0));
+        assertThrows(IllegalArgumentException.class, () -> new LibEthKZG(1, 17));
+    }
+
+    @Test
+    void testExceptionsAreTypedByCategory() {
+        assertThrows(KZGSerializationException.class,
+                () -> context.blobToKZGCommitment(new byte[LibEthKZG.BYTES_PER_BLOB - 1]));
+
+        byte[] nonCanonicalBlob = new byte[LibEthKZG.BYTES_PER_BLOB];
+        java.util.Arrays.fill(nonCanonicalBlob, (byte) 0xff);
+        assertThrows(KZGSerializationException.class, () -> context.blobToKZGCommitment(nonCanonicalBlob));
+
+        CellsAndProofs cellsAndProofs = context.computeCellsAndKZGProofs(new byte[LibEthKZG.BYTES_PER_BLOB]);
+        byte[][] commitments = {context.blobToKZGCommitment(new byte[LibEthKZG.BYTES_PER_BLOB])};
+        long[] outOfRangeCellIndex = {LibEthKZG.MAX_NUM_COLUMNS};
+        assertThrows(KZGProofException.class,
+                () -> context.verifyCellKZGProofBatch(commitments, outOfRangeCellIndex,
+                        new byte[][] {cellsAndProofs.cells[0]}, new byte[][] {cellsAndProofs.proofs[0]}));
+
+        long[] tooFewCellIndices = {0};
+        assertThrows(KZGRecoveryException.class,
+                () -> context.recoverCellsAndKZGProofs(tooFewCellIndices, new byte[][] {cellsAndProofs.cells[0]}));
+    }
+
+    @Test
+    void testInvalidCellIndicesAndShapesAreRejected() {
+        CellsAndProofs cellsAndProofs = context.computeCellsAndKZGProofs(new byte[LibEthKZG.BYTES_PER_BLOB]);
+        byte[][] commitments = {context.blobToKZGCommitment(new byte[LibEthKZG.BYTES_PER_BLOB])};
+        byte[][] cells = {cellsAndProofs.cells[0]};
+        byte[][] proofs = {cellsAndProofs.proofs[0]};
+
+        KZGProofException negativeIndex = assertThrows(KZGProofException.class,
+                () -> context.verifyCellKZGProofBatch(commitments, new long[] {-1}, cells, proofs));
+        assertTrue(negativeIndex.getMessage().contains("cell index -1 at position 0 is negative"));
+
+        KZGRecoveryException outOfRangeIndex = assertThrows(KZGRecoveryException.class,
+                () -> context.recoverCellsAndKZGProofs(new long[] {LibEthKZG.MAX_NUM_COLUMNS}, cells));
+        assertTrue(outOfRangeIndex.getMessage().contains("is out of range"));
+
+        KZGProofException tooFewProofs = assertThrows(KZGProofException.class,
+                () -> context.verifyCellKZGProofBatch(commitments, new long[] {0}, cells, new byte[0][]));
+        assertTrue(tooFewProofs.getMessage().contains("proofs has 0 elements, but there are 1 cell indices"));
+
+        assertThrows(KZGRecoveryException.class,
+                () -> context.recoverCellsAndKZGProofs(new long[] {0, 1}, cells));
+    }
+
+    @Test
+    void testMalformedTrustedSetupThrows() {
+        assertThrows(KZGTrustedSetupException.class, () -> new LibEthKZG("not json", false, 1));
+        String setupWithInvalidPoint = "{\"g1_monomial\": [\"0x00\"], \"g1_lagrange\": [], \"g2_monomial\": []}";
+        assertThrows(KZGTrustedSetupException.class,
+                () -> new LibEthKZG(setupWithInvalidPoint.getBytes(java.nio.charset.StandardCharsets.UTF_8), false, 1));
+    }
+
+    @Test
+    void testAsyncMethodsMatchSyncMethods() throws Exception {
+        byte[] blob = new byte[LibEthKZG.BYTES_PER_BLOB];
+        blob[LibEthKZG.BYTES_PER_FIELD_ELEMENT - 1] = 3;
+
+        CellsAndProofs cellsAndProofs = context.computeCellsAndKZGProofs(blob);
+        assertEquals(cellsAndProofs, context.computeCellsAndKZGProofsAsync(blob).get());
+
+        int numCells = LibEthKZG.MAX_NUM_COLUMNS / 2;
+        long[] cellIndices = new long[numCells];
+        byte[][] cells = new byte[numCells][];
+        for (int i = 0; i < numCells; i++) {
+            cellIndices[i] = 2 * i;
+            cells[i] = cellsAndProofs.cells[2 * i];
+        }
+        assertEquals(cellsAndProofs, context.recoverCellsAndKZGProofsAsync(cellIndices, cells).get());
+
+        ExecutionException invalidBlob = assertThrows(ExecutionException.class,
+                () -> context.computeCellsAndKZGProofsAsync(new byte[1]).get());
+        assertInstanceOf(KZGSerializationException.class, invalidBlob.getCause());
+
+        ExecutionException tooFewCells = assertThrows(ExecutionException.class,
+                () -> context.recoverCellsAndKZGProofsAsync(new long[] {0}, new byte[][] {cells[0]}).get());
+        assertInstanceOf(KZGRecoveryException.class, tooFewCells.getCause());
+    }
+
+    @Test
+    void testComputeCellsMatchesComputeCellsAndKZGProofs() {
+        byte[] blob = new byte[LibEthKZG.BYTES_PER_BLOB];
+        blob[LibEthKZG.BYTES_PER_FIELD_ELEMENT - 1] = 7;
+
+        assertArrayEquals(context.computeCellsAndKZGProofs(blob).cells, context.computeCells(blob));
+        assertThrows(KZGSerializationException.class, () -> context.computeCells(new byte[1]));
+    }
+
+    @Test
+    void testFlatMethodsMatchArrayMethods() {
+        byte[] blob = new byte[LibEthKZG.BYTES_PER_BLOB];
+        blob[LibEthKZG.BYTES_PER_FIELD_ELEMENT - 1] = 9;
+
+        CellsAndProofs cellsAndProofs = context.computeCellsAndKZGProofs(blob);
+        FlatCellsAndProofs flatCellsAndProofs = context.computeCellsAndKZGProofsFlat(blob);
+        assertEquals(LibEthKZG.MAX_NUM_COLUMNS, flatCellsAndProofs.numCells());
+        assertEquals(cellsAndProofs, flatCellsAndProofs.toCellsAndProofs());
+
+        int numCells = LibEthKZG.MAX_NUM_COLUMNS / 2;
+        long[] cellIndices = LongStream.range(numCells, LibEthKZG.MAX_NUM_COLUMNS).toArray();
+        byte[] cells = java.util.Arrays.copyOfRange(
+                flatCellsAndProofs.cells, numCells * LibEthKZG.BYTES_PER_CELL, flatCellsAndProofs.cells.length);
+        assertEquals(flatCellsAndProofs, context.recoverCellsAndKZGProofsFlat(cellIndices, cells));
+
+        assertThrows(KZGSerializationException.class,
+                () -> context.recoverCellsAndKZGProofsFlat(cellIndices, new byte[LibEthKZG.BYTES_PER_CELL + 1]));
+    }
+
+    @Test
+    void testVersionAndConfiguration() {
+        assertFalse(LibEthKZG.version().isEmpty());
+        assertFalse(LibEthKZG.embeddedTrustedSetup().isEmpty());
+
+        assertEquals(1, context.numThreads());
+        assertEquals(8, context.precompWidth());
+        try (LibEthKZG instance = new LibEthKZG(2, 4)) {
+            assertEquals(2, instance.numThreads());
+            assertEquals(4, instance.precompWidth());
+        }
+        try (LibEthKZG instance = new LibEthKZG(false, 3)) {
+            assertEquals(3, instance.numThreads());
+            assertEquals(0, instance.precompWidth());
+        }
+    }
+
+    @Test
+    void testStaleContextHandleThrows() throws ReflectiveOperationException {
+        LibEthKZG instance = new LibEthKZG(false, 1);
+        java.lang.reflect.Field handleField = LibEthKZG.class.getDeclaredField("contextHandle");
+        handleField.setAccessible(true);
+        long handle = handleField.getLong(instance);
+        instance.close();
+
+        // Bypass the checks on the Java side, to check that the native code rejects the stale handle
+        handleField.setLong(instance, handle);
+        assertThrows(IllegalStateException.class,
+                () -> instance.blobToKZGCommitment(new byte[LibEthKZG.BYTES_PER_BLOB]));
+        assertThrows(IllegalStateException.class, instance::destroy);
+    }
+
+    @Test
+    void testComputeCellsAndKZGProofsBatch() {
+        byte[][] blobs = new byte[3][LibEthKZG.BYTES_PER_BLOB];
+        for (int i = 0; i < blobs.length; i++) {
+            blobs[i][LibEthKZG.BYTES_PER_FIELD_ELEMENT - 1] = (byte) i;
+        }
+
+        CellsAndProofs[] results = context.computeCellsAndKZGProofsBatch(blobs);
+        assertEquals(blobs.length, results.length);
+        for (int i = 0; i < blobs.length; i++) {
+            assertEquals(context.computeCellsAndKZGProofs(blobs[i]), results[i]);
+        }
+
+        assertEquals(0, context.computeCellsAndKZGProofsBatch(new byte[0][]).length);
+        assertThrows(IllegalArgumentException.class,
+                () -> context.computeCellsAndKZGProofsBatch(new byte[][] {new byte[1]}));
+    }
+}
@@ -27,6 +27,54 @@ extern "C" {
 JNIEXPORT jlong JNICALL Java_ethereum_cryptography_LibEthKZG_DASContextNew
   (JNIEnv *, jclass, jboolean, jlong);
 
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    DASContextNewWithPrecompWidth
+ * Signature: (II)J
+ */
+JNIEXPORT jlong JNICALL Java_ethereum_cryptography_LibEthKZG_DASContextNewWithPrecompWidth
+  (JNIEnv *, jclass, jint, jint);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    DASContextNewWithTrustedSetup
+ * Signature: (Ljava/lang/String;ZJ)J
+ */
+JNIEXPORT jlong JNICALL Java_ethereum_cryptography_LibEthKZG_DASContextNewWithTrustedSetup
+  (JNIEnv *, jclass, jstring, jboolean, jlong);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    libraryVersion
+ * Signature: ()Ljava/lang/String;
+ */
+JNIEXPORT jstring JNICALL Java_ethereum_cryptography_LibEthKZG_libraryVersion
+  (JNIEnv *, jclass);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    embeddedTrustedSetupId
+ * Signature: ()Ljava/lang/String;
+ */
+JNIEXPORT jstring JNICALL Java_ethereum_cryptography_LibEthKZG_embeddedTrustedSetupId
+  (JNIEnv *, jclass);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    numThreads
+ * Signature: (J)J
+ */
+JNIEXPORT jlong JNICALL Java_ethereum_cryptography_LibEthKZG_numThreads
+  (JNIEnv *, jclass, jlong);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    precompWidth
+ * Signature: (J)I
+ */
+JNIEXPORT jint JNICALL Java_ethereum_cryptography_LibEthKZG_precompWidth
+  (JNIEnv *, jclass, jlong);
+
 /*
  * Class:     ethereum_cryptography_LibEthKZG
  * Method:    DASContextDestroy
@@ -43,6 +91,30 @@ JNIEXPORT void JNICALL Java_ethereum_cryptography_LibEthKZG_DASContextDestroy
 JNIEXPORT jobject JNICALL Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofs
   (JNIEnv *, jclass, jlong, jbyteArray);
 
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    computeCellsAndKZGProofsFlat
+ * Signature: (J[B)Lethereum/cryptography/FlatCellsAndProofs;
+ */
+JNIEXPORT jobject JNICALL Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsFlat
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    computeCells
+ * Signature: (J[B)[[B
+ */
+JNIEXPORT jobjectArray JNICALL Java_ethereum_cryptography_LibEthKZG_computeCells
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    computeCellsAndKZGProofsBatch
+ * Signature: (J[[B)[Lethereum/cryptography/CellsAndProofs;
+ */
+JNIEXPORT jobjectArray JNICALL Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsBatch
+  (JNIEnv *, jclass, jlong, jobjectArray);
+
 /*
  * Class:     ethereum_cryptography_LibEthKZG
  * Method:    blobToKZGCommitment
@@ -67,6 +139,62 @@ JNIEXPORT jboolean JNICALL Java_ethereum_cryptography_LibEthKZG_verifyCellKZGPro
 JNIEXPORT jobject JNICALL Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofs
   (JNIEnv *, jclass, jlong, jlongArray, jobjectArray);
 
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    recoverCellsAndKZGProofsFlat
+ * Signature: (J[J[B)Lethereum/cryptography/FlatCellsAndProofs;
+ */
+JNIEXPORT jobject JNICALL Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsFlat
+  (JNIEnv *, jclass, jlong, jlongArray, jbyteArray);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    computeCellsAndKZGProofsAsync
+ * Signature: (J[BLethereum/cryptography/CellsAndProofsFuture;)V
+ */
+JNIEXPORT void JNICALL Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsAsync
+  (JNIEnv *, jclass, jlong, jbyteArray, jobject);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    recoverCellsAndKZGProofsAsync
+ * Signature: (J[J[[BLethereum/cryptography/CellsAndProofsFuture;)V
+ */
+JNIEXPORT void JNICALL Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsAsync
+  (JNIEnv *, jclass, jlong, jlongArray, jobjectArray, jobject);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    blobToKZGCommitmentDirect
+ * Signature: (JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)V
+ */
+JNIEXPORT void JNICALL Java_ethereum_cryptography_LibEthKZG_blobToKZGCommitmentDirect
+  (JNIEnv *, jclass, jlong, jobject, jobject);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    computeCellsAndKZGProofsDirect
+ * Signature: (JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)V
+ */
+JNIEXPORT void JNICALL Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsDirect
+  (JNIEnv *, jclass, jlong, jobject, jobject, jobject);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    verifyCellKZGProofBatchDirect
+ * Signature: (JLjava/nio/ByteBuffer;[JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)Z
+ */
+JNIEXPORT jboolean JNICALL Java_ethereum_cryptography_LibEthKZG_verifyCellKZGProofBatchDirect
+  (JNIEnv *, jclass, jlong, jobject, jlongArray, jobject, jobject);
+
+/*
+ * Class:     ethereum_cryptography_LibEthKZG
+ * Method:    recoverCellsAndKZGProofsDirect
+ * Signature: (J[JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)V
+ */
+JNIEXPORT void JNICALL Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsDirect
+  (JNIEnv *, jclass, jlong, jlongArray, jobject, jobject, jobject);
+
 #ifdef __cplusplus
 }
 #endif
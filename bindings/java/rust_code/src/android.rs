@@ -0,0 +1,70 @@
+use c_eth_kzg::CLogLevel;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+
+/// The tag that the library's messages are logged under in logcat.
+const TAG: &[u8] = b"eth_kzg\0";
+const FORMAT: &[u8] = b"%s\0";
+
+// The priorities from `android/log.h`.
+const ANDROID_LOG_VERBOSE: c_int = 2;
+const ANDROID_LOG_DEBUG: c_int = 3;
+const ANDROID_LOG_INFO: c_int = 4;
+const ANDROID_LOG_WARN: c_int = 5;
+const ANDROID_LOG_ERROR: c_int = 6;
+
+#[link(name = "log")]
+extern "C" {
+    fn __android_log_print(prio: c_int, tag: *const c_char, fmt: *const c_char, ...) -> c_int;
+}
+
+/// Forwards the library's tracing output to logcat.
+///
+/// Android discards anything written to stdout and stderr, so without this the
+/// library's log messages would be lost.
+pub(crate) fn init_logging() {
+    // Safety: `log_to_logcat` is safe to call from any thread and does not keep the message.
+    let result =
+        unsafe { c_eth_kzg::eth_kzg_set_log_callback(Some(log_to_logcat), CLogLevel::Info) };
+    if matches!(result.status, c_eth_kzg::CResultStatus::Err) {
+        // Another part of the app installed a global tracing subscriber, which now receives our messages.
+        log(
+            ANDROID_LOG_WARN,
+            "could not forward tracing output to logcat, a global tracing subscriber is already installed",
+        );
+        // Safety: The message was allocated by the library and is not used afterwards.
+        unsafe { c_eth_kzg::eth_kzg_free_error_message(result.error_msg) };
+    }
+}
+
+/// Logs the message of an exception that is about to be passed to Java.
+pub(crate) fn log_error(msg: &str) {
+    log(ANDROID_LOG_ERROR, msg);
+}
+
+extern "C" fn log_to_logcat(level: CLogLevel, message: *const c_char) {
+    let prio = match level {
+        CLogLevel::Error => ANDROID_LOG_ERROR,
+        CLogLevel::Warn => ANDROID_LOG_WARN,
+        CLogLevel::Info => ANDROID_LOG_INFO,
+        CLogLevel::Debug => ANDROID_LOG_DEBUG,
+        CLogLevel::Trace => ANDROID_LOG_VERBOSE,
+    };
+    // Safety: The message is a null-terminated string that is valid for the duration of the call.
+    unsafe { print(prio, message) };
+}
+
+fn log(prio: c_int, msg: &str) {
+    let msg =
+        CString::new(msg.replace('\0', "")).expect("null bytes were removed from the message");
+    // Safety: `msg` is a null-terminated string that outlives the call.
+    unsafe { print(prio, msg.as_ptr()) };
+}
+
+/// # Safety
+///
+/// `message` must point to a null-terminated string.
+unsafe fn print(prio: c_int, message: *const c_char) {
+    // The message is passed as an argument, so that a `%` in it is not treated as a format specifier.
+    __android_log_print(prio, TAG.as_ptr().cast(), FORMAT.as_ptr().cast(), message);
+}
@@ -0,0 +1,102 @@
+//! Checks of the arguments that are passed from Java, which do not need a JVM.
+
+use crate::errors::{Error, Operation};
+use c_eth_kzg::CellIndex;
+
+/// Splits a slice containing concatenated items of `N` bytes into references to each item.
+pub fn split_flat_slice<'a, const N: usize>(
+    slice: &'a [u8],
+    name: &'static str,
+) -> Result<Vec<&'a [u8; N]>, Error> {
+    if slice.len() % N != 0 {
+        return Err(Error::IncorrectSizeMultiple {
+            multiple_of: N,
+            got: slice.len(),
+            name,
+        });
+    }
+    Ok(slice
+        .chunks_exact(N)
+        .map(|chunk| chunk.try_into().expect("chunk has exactly N bytes"))
+        .collect())
+}
+
+/// Copies the concatenation of `items` into `output`, which must be exactly the right size.
+pub fn copy_to_output(
+    output: &mut [u8],
+    items: &[impl AsRef<[u8]>],
+    name: &'static str,
+) -> Result<(), Error> {
+    let expected: usize = items.iter().map(|item| item.as_ref().len()).sum();
+    if output.len() != expected {
+        return Err(Error::IncorrectSize {
+            expected,
+            got: output.len(),
+            name,
+        });
+    }
+    let mut offset = 0;
+    for item in items {
+        let item = item.as_ref();
+        output[offset..offset + item.len()].copy_from_slice(item);
+        offset += item.len();
+    }
+    Ok(())
+}
+
+/// Converts the elements of a Java `long[]` to cell indices, checking that every index is in range.
+///
+/// Without the check, a negative index would be converted to a huge `u64`.
+pub fn to_cell_indices(indices: &[i64], operation: Operation) -> Result<Vec<CellIndex>, Error> {
+    indices
+        .iter()
+        .enumerate()
+        .map(|(position, &index)| {
+            if index < 0 {
+                return Err(Error::NegativeCellIndex {
+                    index,
+                    position,
+                    operation,
+                });
+            }
+            CellIndex::try_from(index as u64).map_err(|_| Error::CellIndexOutOfRange {
+                index,
+                position,
+                operation,
+            })
+        })
+        .collect()
+}
+
+/// Checks that there is one item in `name` for each cell index.
+pub const fn check_matches_cell_indices(
+    name: &'static str,
+    len: usize,
+    cell_indices: &[CellIndex],
+    operation: Operation,
+) -> Result<(), Error> {
+    if len != cell_indices.len() {
+        return Err(Error::LengthMismatch {
+            name,
+            expected: cell_indices.len(),
+            got: len,
+            operation,
+        });
+    }
+    Ok(())
+}
+
+/// Convert a slice into a reference to an array
+///
+/// This is needed as the API for rust library does
+/// not accept slices.
+pub fn slice_to_array_ref<'a, const N: usize>(
+    slice: &'a [u8],
+    name: &'static str,
+) -> Result<&'a [u8; N], Error> {
+    slice.try_into().map_err(|_| Error::IncorrectSize {
+        expected: N,
+        got: slice.len(),
+        name,
+    })
+}
@@ -0,0 +1,139 @@
+use crate::errors::Error;
+use jni::objects::{GlobalRef, JClass, JMethodID, JObject, JValue};
+use jni::JNIEnv;
+use std::sync::OnceLock;
+
+pub(crate) const CELLS_AND_PROOFS: &str = "ethereum/cryptography/CellsAndProofs";
+pub(crate) const FLAT_CELLS_AND_PROOFS: &str = "ethereum/cryptography/FlatCellsAndProofs";
+pub(crate) const BYTE_ARRAY: &str = "[B";
+
+/// The signature of the constructor of `CellsAndProofs`.
+const CELLS_AND_PROOFS_CTOR: &str = "([[B[[B)V";
+/// The signature of the constructor of `FlatCellsAndProofs`.
+const FLAT_CELLS_AND_PROOFS_CTOR: &str = "([B[B)V";
+
+/// The classes and constructors that are used from native code, which are resolved when the library is loaded.
+///
+/// `FindClass` uses the class loader of the Java method that called into native code. A thread
+/// that was attached to the JVM from native code, such as a thread in the pool of a context,
+/// has no such method, so the JVM falls back to the system class loader. On a desktop JVM this
+/// loader usually sees the classes of the application, but on Android it only sees the
+/// classes of the framework, so looking up one of our classes from those threads fails.
+///
+/// Resolving them once also saves a class and method lookup for every object that is created.
+static CACHE: OnceLock<Cache> = OnceLock::new();
+
+struct Cache {
+    cells_and_proofs: GlobalRef,
+    cells_and_proofs_ctor: JMethodID,
+    flat_cells_and_proofs: GlobalRef,
+    flat_cells_and_proofs_ctor: JMethodID,
+    byte_array: GlobalRef,
+}
+
+/// Resolves the classes and constructors in the cache, using the class loader of the caller.
+///
+/// This is called from `JNI_OnLoad`, which runs with the class loader that loaded `LibEthKZG`.
+pub(crate) fn cache(env: &mut JNIEnv) -> Result<(), Error> {
+    let cells_and_proofs = env.find_class(CELLS_AND_PROOFS)?;
+    let cells_and_proofs_ctor =
+        env.get_method_id(&cells_and_proofs, "<init>", CELLS_AND_PROOFS_CTOR)?;
+    let flat_cells_and_proofs = env.find_class(FLAT_CELLS_AND_PROOFS)?;
+    let flat_cells_and_proofs_ctor =
+        env.get_method_id(&flat_cells_and_proofs, "<init>", FLAT_CELLS_AND_PROOFS_CTOR)?;
+    let byte_array = env.find_class(BYTE_ARRAY)?;
+
+    let _ = CACHE.set(Cache {
+        cells_and_proofs: env.new_global_ref(cells_and_proofs)?,
+        cells_and_proofs_ctor,
+        flat_cells_and_proofs: env.new_global_ref(flat_cells_and_proofs)?,
+        flat_cells_and_proofs_ctor,
+        byte_array: env.new_global_ref(byte_array)?,
+    });
+    Ok(())
+}
+
+/// Returns the class with the given name.
+///
+/// The class is taken from the cache when it was resolved in `JNI_OnLoad`, otherwise it is
+/// looked up with `FindClass`.
+pub(crate) fn find_class<'local>(
+    env: &mut JNIEnv<'local>,
+    name: &'static str,
+) -> Result<JClass<'local>, Error> {
+    let cached = CACHE.get().and_then(|cache| match name {
+        CELLS_AND_PROOFS => Some(&cache.cells_and_proofs),
+        FLAT_CELLS_AND_PROOFS => Some(&cache.flat_cells_and_proofs),
+        BYTE_ARRAY => Some(&cache.byte_array),
+        _ => None,
+    });
+    match cached {
+        Some(class) => Ok(JClass::from(env.new_local_ref(class)?)),
+        None => Ok(env.find_class(name)?),
+    }
+}
+
+/// Creates a `CellsAndProofs` object from the given arrays of cells and proofs.
+pub(crate) fn new_cells_and_proofs<'local>(
+    env: &mut JNIEnv<'local>,
+    cells: &JObject,
+    proofs: &JObject,
+) -> Result<JObject<'local>, Error> {
+    let ctor = CACHE
+        .get()
+        .map(|cache| (&cache.cells_and_proofs, cache.cells_and_proofs_ctor));
+    new_object(
+        env,
+        CELLS_AND_PROOFS,
+        CELLS_AND_PROOFS_CTOR,
+        ctor,
+        cells,
+        proofs,
+    )
+}
+
+/// Creates a `FlatCellsAndProofs` object from the given concatenated cells and proofs.
+pub(crate) fn new_flat_cells_and_proofs<'local>(
+    env: &mut JNIEnv<'local>,
+    cells: &JObject,
+    proofs: &JObject,
+) -> Result<JObject<'local>, Error> {
+    let ctor = CACHE.get().map(|cache| {
+        (
+            &cache.flat_cells_and_proofs,
+            cache.flat_cells_and_proofs_ctor,
+        )
+    });
+    new_object(
+        env,
+        FLAT_CELLS_AND_PROOFS,
+        FLAT_CELLS_AND_PROOFS_CTOR,
+        ctor,
+        cells,
+        proofs,
+    )
+}
+
+/// Calls a constructor that takes two arrays, using the cached constructor if there is one.
+fn new_object<'local>(
+    env: &mut JNIEnv<'local>,
+    class_name: &'static str,
+    ctor_sig: &'static str,
+    cached_ctor: Option<(&GlobalRef, JMethodID)>,
+    first: &JObject,
+    second: &JObject,
+) -> Result<JObject<'local>, Error> {
+    let args = [JValue::Object(first), JValue::Object(second)];
+    match cached_ctor {
+        // Safety: The constructor was looked up on this class with `ctor_sig`, which takes
+        // two arrays, matching `args`.
+        Some((class, ctor)) => Ok(unsafe {
+            env.new_object_unchecked(
+                <&JClass>::from(class.as_obj()),
+                ctor,
+                &args.map(|arg| arg.as_jni()),
+            )
+        }?),
+        None => Ok(env.new_object(class_name, ctor_sig, &args)?),
+    }
+}
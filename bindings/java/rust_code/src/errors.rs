@@ -1,24 +1,78 @@
 use c_eth_kzg::Error as KZGError;
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum Error {
-    Jni(jni::errors::Error),
+    #[error(transparent)]
+    Jni(#[from] jni::errors::Error),
+    #[error("{name} is not the correct size. expected: {expected}\ngot: {got}")]
     IncorrectSize {
         expected: usize,
         got: usize,
         name: &'static str,
     },
-    Cryptography(KZGError),
+    #[error("{name} has {got} bytes, which is not a multiple of {multiple_of}")]
+    IncorrectSizeMultiple {
+        multiple_of: usize,
+        got: usize,
+        name: &'static str,
+    },
+    #[error("{name} has an invalid value of {value}")]
+    InvalidParameter { name: &'static str, value: i64 },
+    #[error("context handle {handle} does not refer to a live context, it may have already been destroyed")]
+    InvalidContextHandle { handle: i64 },
+    #[error("cell index {index} at position {position} is negative")]
+    NegativeCellIndex {
+        index: i64,
+        position: usize,
+        operation: Operation,
+    },
+    #[error(
+        "cell index {index} at position {position} is out of range, it must be less than {}",
+        c_eth_kzg::CELLS_PER_EXT_BLOB
+    )]
+    CellIndexOutOfRange {
+        index: i64,
+        position: usize,
+        operation: Operation,
+    },
+    #[error("{name} has {got} elements, but there are {expected} cell indices")]
+    LengthMismatch {
+        name: &'static str,
+        expected: usize,
+        got: usize,
+        operation: Operation,
+    },
+    #[error("LibEthKZG.{name} is {got}, but the native library uses {expected}")]
+    ConstantMismatch {
+        name: &'static str,
+        expected: usize,
+        got: i64,
+    },
+    #[error("{source}")]
+    Cryptography {
+        source: KZGError,
+        operation: Operation,
+    },
+    #[error("the library panicked: {0}")]
+    Panic(String),
 }
 
-impl From<jni::errors::Error> for Error {
-    fn from(err: jni::errors::Error) -> Self {
-        Error::Jni(err)
+impl Error {
+    /// Returns a function that wraps an error of the library, which was returned by the
+    /// given operation.
+    pub(crate) const fn cryptography(operation: Operation) -> impl Fn(KZGError) -> Self {
+        move |source| Self::Cryptography { source, operation }
     }
 }
 
-impl From<KZGError> for Error {
-    fn from(err: KZGError) -> Self {
-        Error::Cryptography(err)
-    }
+/// The operation that an input was passed to.
+///
+/// Inputs that are rejected before they reach the library throw the same exception
+/// that the library would have caused for them in this operation.
+#[derive(Debug, Clone, Copy)]
+pub enum Operation {
+    Setup,
+    Proving,
+    Verification,
+    Recovery,
 }
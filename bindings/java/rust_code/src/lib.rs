@@ -1,41 +1,276 @@
-use c_eth_kzg::DASContext;
-use jni::objects::{JByteArray, JClass, JLongArray, JObject, JObjectArray, JValue};
-use jni::sys::{jboolean, jlong};
-use jni::JNIEnv;
+use c_eth_kzg::{verification_result_to_bool, CellIndex, ErrorKind};
+use jni::objects::{
+    AutoElements, JByteArray, JByteBuffer, JClass, JLongArray, JObject, JObjectArray, JString,
+    JValue, ReleaseMode,
+};
+use jni::sys::{jboolean, jbyte, jint, jlong, JNI_VERSION_1_6};
+use jni::{JNIEnv, JavaVM};
+use std::ffi::c_void;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
 
-mod errors;
-use errors::Error;
+#[cfg(all(feature = "android", target_os = "android"))]
+mod android;
+mod classes;
+// The checks of the arguments are public, so that they can be fuzzed without a JVM.
+pub mod arguments;
+pub mod errors;
+use arguments::{
+    check_matches_cell_indices, copy_to_output, slice_to_array_ref, split_flat_slice,
+    to_cell_indices,
+};
+use errors::{Error, Operation};
+mod natives;
+mod registry;
+
+/// The number of local references that we reserve when creating a new local frame.
+///
+/// This is enough for all of the references created while converting a single
+/// `CellsAndProofs` object.
+const LOCAL_FRAME_CAPACITY: i32 = 2 * c_eth_kzg::CELLS_PER_EXT_BLOB as i32 + 8;
+
+/// Called by the JVM when the library is loaded.
+///
+/// This registers the native methods of `LibEthKZG`, and resolves the classes that are
+/// created from native code while we still have the class loader that loaded `LibEthKZG`,
+/// see `classes::cache`. It also checks that the size constants of `LibEthKZG` match the ones
+/// used by the library, so that loading the library fails if they have drifted apart.
+///
+/// # Safety
+///
+/// `vm` must be a valid pointer to the JVM, which is always the case when it is called by the JVM.
+#[no_mangle]
+pub unsafe extern "system" fn JNI_OnLoad(
+    vm: *mut jni::sys::JavaVM,
+    _reserved: *mut c_void,
+) -> jint {
+    #[cfg(all(feature = "android", target_os = "android"))]
+    android::init_logging();
+
+    let Ok(vm) = JavaVM::from_raw(vm) else {
+        return JNI_VERSION_1_6;
+    };
+    let Ok(mut env) = vm.get_env() else {
+        return JNI_VERSION_1_6;
+    };
+    if catch_panic(|| natives::register(&mut env)).is_err() {
+        // The JVM falls back to looking up the exported symbol of each method.
+        let _ = env.exception_clear();
+    }
+    if catch_panic(|| classes::cache(&mut env)).is_err() {
+        // The classes are looked up on every call instead, which works on threads
+        // that were not attached from native code.
+        let _ = env.exception_clear();
+    }
+    // This runs last, as no other JNI calls can be made while its exception is pending.
+    match catch_panic(|| natives::check_constants(&mut env)) {
+        // The exception is pending when `JNI_OnLoad` returns, so loading the library fails with it.
+        Err(err @ Error::ConstantMismatch { .. }) => throw_on_error(&mut env, err, "JNI_OnLoad"),
+        Err(_) => {
+            let _ = env.exception_clear();
+        }
+        Ok(()) => {}
+    }
+
+    JNI_VERSION_1_6
+}
 
 #[no_mangle]
 pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_DASContextNew(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _class: JClass,
     use_precomp: jboolean,
     num_threads: jlong,
 ) -> jlong {
-    let use_precomp = use_precomp != 0;
+    let use_precomp = c_eth_kzg::recommended_precomp(use_precomp != 0);
     let num_threads = (num_threads as u64) as u32;
-    c_eth_kzg::eth_kzg_das_context_new(use_precomp, num_threads) as jlong
+    // Creating the context panics if its thread pool could not be started.
+    match catch_panic(|| {
+        let ctx = c_eth_kzg::DASContext::with_threads(num_threads as usize, use_precomp);
+        Ok(registry::insert(ctx))
+    }) {
+        Ok(ctx_handle) => ctx_handle,
+        Err(err) => {
+            throw_on_error(&mut env, err, "DASContextNew");
+            jlong::default()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_DASContextNewWithPrecompWidth(
+    mut env: JNIEnv,
+    _class: JClass,
+    num_threads: jint,
+    precomp_width: jint,
+) -> jlong {
+    match catch_panic(|| das_context_new_with_precomp_width(num_threads, precomp_width)) {
+        Ok(ctx_handle) => ctx_handle,
+        Err(err) => {
+            throw_on_error(&mut env, err, "DASContextNewWithPrecompWidth");
+            jlong::default()
+        }
+    }
+}
+
+fn das_context_new_with_precomp_width(
+    num_threads: jint,
+    precomp_width: jint,
+) -> Result<jlong, Error> {
+    let num_threads = usize::try_from(num_threads).map_err(|_| Error::InvalidParameter {
+        name: "num_threads",
+        value: num_threads.into(),
+    })?;
+    let precomp_width = usize::try_from(precomp_width)
+        .ok()
+        .filter(|width| {
+            (c_eth_kzg::MIN_PRECOMP_WIDTH..=c_eth_kzg::MAX_PRECOMP_WIDTH).contains(width)
+        })
+        .ok_or_else(|| Error::InvalidParameter {
+            name: "precomp_width",
+            value: precomp_width.into(),
+        })?;
+
+    let ctx = c_eth_kzg::DASContext::with_threads(
+        num_threads,
+        c_eth_kzg::UsePrecomp::Yes {
+            width: precomp_width,
+        },
+    );
+    Ok(registry::insert(ctx))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_DASContextNewWithTrustedSetup(
+    mut env: JNIEnv,
+    _class: JClass,
+    trusted_setup_json: JString,
+    use_precomp: jboolean,
+    num_threads: jlong,
+) -> jlong {
+    match catch_panic(|| {
+        das_context_new_with_trusted_setup(&mut env, trusted_setup_json, use_precomp, num_threads)
+    }) {
+        Ok(ctx_handle) => ctx_handle,
+        Err(err) => {
+            throw_on_error(&mut env, err, "DASContextNewWithTrustedSetup");
+            jlong::default()
+        }
+    }
+}
+
+fn das_context_new_with_trusted_setup(
+    env: &mut JNIEnv,
+    trusted_setup_json: JString,
+    use_precomp: jboolean,
+    num_threads: jlong,
+) -> Result<jlong, Error> {
+    let num_threads = usize::try_from(num_threads).map_err(|_| Error::InvalidParameter {
+        name: "num_threads",
+        value: num_threads,
+    })?;
+    let trusted_setup_json: String = env.get_string(&trusted_setup_json)?.into();
+    let trusted_setup =
+        c_eth_kzg::TrustedSetup::try_from_json(&trusted_setup_json).map_err(|err| {
+            Error::Cryptography {
+                source: err.into(),
+                operation: Operation::Setup,
+            }
+        })?;
+
+    let use_precomp = c_eth_kzg::recommended_precomp(use_precomp != 0);
+    let ctx = c_eth_kzg::DASContext::with_trusted_setup(&trusted_setup, num_threads, use_precomp);
+    Ok(registry::insert(ctx))
 }
 
 #[no_mangle]
 pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_DASContextDestroy(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _class: JClass,
-    ctx_ptr: jlong,
+    ctx_handle: jlong,
 ) {
-    c_eth_kzg::eth_kzg_das_context_free(ctx_ptr as *mut DASContext);
+    if let Err(err) = catch_panic(|| registry::remove(ctx_handle)) {
+        throw_on_error(&mut env, err, "DASContextDestroy");
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_libraryVersion<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+) -> JString<'local> {
+    match catch_panic(|| Ok(env.new_string(env!("CARGO_PKG_VERSION"))?)) {
+        Ok(version) => version,
+        Err(err) => {
+            throw_on_error(&mut env, err, "libraryVersion");
+            JString::default()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_embeddedTrustedSetupId<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+) -> JString<'local> {
+    match catch_panic(|| Ok(env.new_string(c_eth_kzg::EMBEDDED_TRUSTED_SETUP_ID)?)) {
+        Ok(trusted_setup_id) => trusted_setup_id,
+        Err(err) => {
+            throw_on_error(&mut env, err, "embeddedTrustedSetupId");
+            JString::default()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_numThreads(
+    mut env: JNIEnv,
+    _class: JClass,
+    ctx_handle: jlong,
+) -> jlong {
+    match catch_panic(|| Ok(registry::get(ctx_handle)?.num_threads() as jlong)) {
+        Ok(num_threads) => num_threads,
+        Err(err) => {
+            throw_on_error(&mut env, err, "numThreads");
+            jlong::default()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_precompWidth(
+    mut env: JNIEnv,
+    _class: JClass,
+    ctx_handle: jlong,
+) -> jint {
+    match catch_panic(|| {
+        Ok(
+            match registry::get(ctx_handle)?
+                .try_prover_ctx()
+                .map_or(c_eth_kzg::UsePrecomp::No, |prover_ctx| {
+                    prover_ctx.use_precomp()
+                }) {
+                c_eth_kzg::UsePrecomp::Yes { width } => width as jint,
+                c_eth_kzg::UsePrecomp::No => 0,
+            },
+        )
+    }) {
+        Ok(precomp_width) => precomp_width,
+        Err(err) => {
+            throw_on_error(&mut env, err, "precompWidth");
+            jint::default()
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofs<'local>(
     mut env: JNIEnv<'local>,
     _class: JClass,
-    ctx_ptr: jlong,
+    ctx_handle: jlong,
     blob: JByteArray<'local>,
 ) -> JObject<'local> {
-    let ctx = unsafe { &*(ctx_ptr as *const DASContext) };
-    match compute_cells_and_kzg_proofs(&mut env, ctx, blob) {
+    match catch_panic(|| compute_cells_and_kzg_proofs(&mut env, ctx_handle, blob)) {
         Ok(cells_and_proofs) => cells_and_proofs,
         Err(err) => {
             throw_on_error(&mut env, err, "computeCellsAndKZGProofs");
@@ -45,26 +280,144 @@ pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGPr
 }
 fn compute_cells_and_kzg_proofs<'local>(
     env: &mut JNIEnv<'local>,
-    ctx: &DASContext,
+    ctx_handle: jlong,
     blob: JByteArray<'local>,
 ) -> Result<JObject<'local>, Error> {
-    let blob = env.convert_byte_array(blob)?;
-    let blob = slice_to_array_ref(&blob, "blob")?;
+    let ctx = registry::get(ctx_handle)?;
+    let blob = byte_array_elements(env, &blob)?;
+    let blob = slice_to_array_ref(as_bytes(&blob), "blob")?;
+
+    let (cells, proofs) = ctx
+        .compute_cells_and_kzg_proofs(blob)
+        .map_err(Error::cryptography(Operation::Proving))?;
+    let cells = cells.map(|cell| *cell);
+    cells_and_proofs_to_jobject(env, &cells, &proofs)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsFlat<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    blob: JByteArray<'local>,
+) -> JObject<'local> {
+    match catch_panic(|| compute_cells_and_kzg_proofs_flat(&mut env, ctx_handle, blob)) {
+        Ok(cells_and_proofs) => cells_and_proofs,
+        Err(err) => {
+            throw_on_error(&mut env, err, "computeCellsAndKZGProofsFlat");
+            JObject::default()
+        }
+    }
+}
+fn compute_cells_and_kzg_proofs_flat<'local>(
+    env: &mut JNIEnv<'local>,
+    ctx_handle: jlong,
+    blob: JByteArray<'local>,
+) -> Result<JObject<'local>, Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let blob = byte_array_elements(env, &blob)?;
+    let blob = slice_to_array_ref(as_bytes(&blob), "blob")?;
 
-    let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob)?;
+    let (cells, proofs) = ctx
+        .compute_cells_and_kzg_proofs(blob)
+        .map_err(Error::cryptography(Operation::Proving))?;
     let cells = cells.map(|cell| *cell);
-    cells_and_proofs_to_jobject(env, &cells, &proofs).map_err(Error::from)
+    flat_cells_and_proofs_to_jobject(env, &cells, &proofs)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_computeCells<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    blob: JByteArray<'local>,
+) -> JObjectArray<'local> {
+    match catch_panic(|| compute_cells(&mut env, ctx_handle, blob)) {
+        Ok(cells) => cells,
+        Err(err) => {
+            throw_on_error(&mut env, err, "computeCells");
+            JObjectArray::default()
+        }
+    }
+}
+fn compute_cells<'local>(
+    env: &mut JNIEnv<'local>,
+    ctx_handle: jlong,
+    blob: JByteArray<'local>,
+) -> Result<JObjectArray<'local>, Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let blob = byte_array_elements(env, &blob)?;
+    let blob = slice_to_array_ref(as_bytes(&blob), "blob")?;
+
+    let cells = ctx
+        .compute_cells(blob)
+        .map_err(Error::cryptography(Operation::Proving))?;
+    let cells = cells.map(|cell| *cell);
+    byte_arrays_to_jobject_array(env, &cells)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsBatch<
+    'local,
+>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    blobs: JObjectArray<'local>,
+) -> JObjectArray<'local> {
+    match catch_panic(|| compute_cells_and_kzg_proofs_batch(&mut env, ctx_handle, blobs)) {
+        Ok(cells_and_proofs) => cells_and_proofs,
+        Err(err) => {
+            throw_on_error(&mut env, err, "computeCellsAndKZGProofsBatch");
+            JObjectArray::default()
+        }
+    }
+}
+fn compute_cells_and_kzg_proofs_batch<'local>(
+    env: &mut JNIEnv<'local>,
+    ctx_handle: jlong,
+    blobs: JObjectArray<'local>,
+) -> Result<JObjectArray<'local>, Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let blobs = jobject_array_to_byte_arrays(env, &blobs)?;
+    let blobs = byte_arrays_elements(env, &blobs)?;
+    let blobs: Vec<_> = blobs
+        .iter()
+        .map(|blob| slice_to_array_ref(as_bytes(blob), "blob"))
+        .collect::<Result<_, _>>()?;
+
+    let results = ctx
+        .compute_cells_and_kzg_proofs_batch(blobs)
+        .map_err(Error::cryptography(Operation::Proving))?;
+
+    let cells_and_proofs_class = classes::find_class(env, classes::CELLS_AND_PROOFS)?;
+    let results_array = env.new_object_array(
+        results.len() as i32,
+        cells_and_proofs_class,
+        JObject::null(),
+    )?;
+    for (i, (cells, proofs)) in results.into_iter().enumerate() {
+        let cells = cells.map(|cell| *cell);
+        // Each CellsAndProofs object creates a local reference per cell and proof,
+        // so we free them after each blob, to avoid exhausting the local reference table.
+        env.with_local_frame(LOCAL_FRAME_CAPACITY, |env| -> Result<(), Error> {
+            let cells_and_proofs = cells_and_proofs_to_jobject(env, &cells, &proofs)?;
+            env.set_object_array_element(&results_array, i as i32, cells_and_proofs)?;
+            Ok(())
+        })?;
+    }
+
+    Ok(results_array)
 }
 
 #[no_mangle]
 pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_blobToKZGCommitment<'local>(
     mut env: JNIEnv<'local>,
     _class: JClass,
-    ctx_ptr: jlong,
+    ctx_handle: jlong,
     blob: JByteArray<'local>,
 ) -> JByteArray<'local> {
-    let ctx = unsafe { &*(ctx_ptr as *const DASContext) };
-    match blob_to_kzg_commitment(&mut env, ctx, blob) {
+    match catch_panic(|| blob_to_kzg_commitment(&mut env, ctx_handle, blob)) {
         Ok(commitment) => commitment,
         Err(err) => {
             throw_on_error(&mut env, err, "blobToKZGCommitment");
@@ -74,13 +427,16 @@ pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_blobToKZGCommitment<
 }
 fn blob_to_kzg_commitment<'local>(
     env: &mut JNIEnv<'local>,
-    ctx: &DASContext,
+    ctx_handle: jlong,
     blob: JByteArray<'local>,
 ) -> Result<JByteArray<'local>, Error> {
-    let blob = env.convert_byte_array(blob)?;
-    let blob = slice_to_array_ref(&blob, "blob")?;
+    let ctx = registry::get(ctx_handle)?;
+    let blob = byte_array_elements(env, &blob)?;
+    let blob = slice_to_array_ref(as_bytes(&blob), "blob")?;
 
-    let commitment = ctx.blob_to_kzg_commitment(blob)?;
+    let commitment = ctx
+        .blob_to_kzg_commitment(blob)
+        .map_err(Error::cryptography(Operation::Proving))?;
     env.byte_array_from_slice(&commitment).map_err(Error::from)
 }
 
@@ -88,15 +444,22 @@ fn blob_to_kzg_commitment<'local>(
 pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_verifyCellKZGProofBatch<'local>(
     mut env: JNIEnv<'local>,
     _class: JClass,
-    ctx_ptr: jlong,
+    ctx_handle: jlong,
     commitment: JObjectArray<'local>,
     cell_indices: JLongArray,
     cells: JObjectArray<'local>,
     proofs: JObjectArray<'local>,
 ) -> jboolean {
-    let ctx = unsafe { &*(ctx_ptr as *const DASContext) };
-
-    match verify_cell_kzg_proof_batch(&mut env, ctx, commitment, cell_indices, cells, proofs) {
+    match catch_panic(|| {
+        verify_cell_kzg_proof_batch(
+            &mut env,
+            ctx_handle,
+            commitment,
+            cell_indices,
+            cells,
+            proofs,
+        )
+    }) {
         Ok(result) => result,
         Err(err) => {
             throw_on_error(&mut env, err, "verifyCellKZGProofBatch");
@@ -106,48 +469,64 @@ pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_verifyCellKZGProofBa
 }
 fn verify_cell_kzg_proof_batch<'local>(
     env: &mut JNIEnv,
-    ctx: &DASContext,
+    ctx_handle: jlong,
     commitment: JObjectArray<'local>,
     cell_indices: JLongArray,
     cells: JObjectArray<'local>,
     proofs: JObjectArray<'local>,
 ) -> Result<jboolean, Error> {
-    let commitment = jobject_array_to_2d_byte_array(env, commitment)?;
-    let cell_indices = jlongarray_to_vec_u64(env, cell_indices)?;
-    let cells = jobject_array_to_2d_byte_array(env, cells)?;
-    let proofs = jobject_array_to_2d_byte_array(env, proofs)?;
+    let ctx = registry::get(ctx_handle)?;
+    let commitment = jobject_array_to_byte_arrays(env, &commitment)?;
+    let cell_indices = jlongarray_to_cell_indices(env, cell_indices, Operation::Verification)?;
+    let cells = jobject_array_to_byte_arrays(env, &cells)?;
+    let proofs = jobject_array_to_byte_arrays(env, &proofs)?;
+    check_matches_cell_indices(
+        "commitments",
+        commitment.len(),
+        &cell_indices,
+        Operation::Verification,
+    )?;
+    check_matches_cell_indices("cells", cells.len(), &cell_indices, Operation::Verification)?;
+    check_matches_cell_indices(
+        "proofs",
+        proofs.len(),
+        &cell_indices,
+        Operation::Verification,
+    )?;
+
+    let commitment = byte_arrays_elements(env, &commitment)?;
+    let cells = byte_arrays_elements(env, &cells)?;
+    let proofs = byte_arrays_elements(env, &proofs)?;
 
     let cells: Vec<_> = cells
         .iter()
-        .map(|cell| slice_to_array_ref(cell, "cell"))
+        .map(|cell| slice_to_array_ref(as_bytes(cell), "cell"))
         .collect::<Result<_, _>>()?;
     let commitments: Vec<_> = commitment
         .iter()
-        .map(|commitment| slice_to_array_ref(commitment, "commitment"))
+        .map(|commitment| slice_to_array_ref(as_bytes(commitment), "commitment"))
         .collect::<Result<_, _>>()?;
     let proofs: Vec<_> = proofs
         .iter()
-        .map(|proof| slice_to_array_ref(proof, "proof"))
+        .map(|proof| slice_to_array_ref(as_bytes(proof), "proof"))
         .collect::<Result<_, _>>()?;
 
-    match ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs) {
-        Ok(_) => Ok(jboolean::from(true)),
-        Err(x) if x.invalid_proof() => Ok(jboolean::from(false)),
-        Err(err) => Err(Error::Cryptography(err)),
-    }
+    let verification_result =
+        ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs);
+    let verified = verification_result_to_bool(verification_result)
+        .map_err(Error::cryptography(Operation::Verification))?;
+    Ok(jboolean::from(verified))
 }
 
 #[no_mangle]
 pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofs<'local>(
     mut env: JNIEnv<'local>,
     _class: JClass,
-    ctx_ptr: jlong,
-    cell_ids: JLongArray,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
     cells: JObjectArray<'local>,
 ) -> JObject<'local> {
-    let ctx = unsafe { &*(ctx_ptr as *const DASContext) };
-
-    match recover_cells_and_kzg_proofs(&mut env, ctx, cell_ids, cells) {
+    match catch_panic(|| recover_cells_and_kzg_proofs(&mut env, ctx_handle, cell_indices, cells)) {
         Ok(cells_and_proofs) => cells_and_proofs,
         Err(err) => {
             throw_on_error(&mut env, err, "recoverCellsAndKZGProofs");
@@ -157,35 +536,523 @@ pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGPr
 }
 fn recover_cells_and_kzg_proofs<'local>(
     env: &mut JNIEnv<'local>,
-    ctx: &DASContext,
-    cell_ids: JLongArray,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
     cells: JObjectArray<'local>,
 ) -> Result<JObject<'local>, Error> {
-    let cell_ids = jlongarray_to_vec_u64(env, cell_ids)?;
-    let cells = jobject_array_to_2d_byte_array(env, cells)?;
+    let ctx = registry::get(ctx_handle)?;
+    let cell_indices = jlongarray_to_cell_indices(env, cell_indices, Operation::Recovery)?;
+    let cells = jobject_array_to_byte_arrays(env, &cells)?;
+    check_matches_cell_indices("cells", cells.len(), &cell_indices, Operation::Recovery)?;
+    let cells = byte_arrays_elements(env, &cells)?;
     let cells: Vec<_> = cells
         .iter()
-        .map(|cell| slice_to_array_ref(cell, "cell"))
+        .map(|cell| slice_to_array_ref(as_bytes(cell), "cell"))
         .collect::<Result<_, _>>()?;
 
-    let (recovered_cells, recovered_proofs) = ctx.recover_cells_and_kzg_proofs(cell_ids, cells)?;
+    let (recovered_cells, recovered_proofs) = ctx
+        .recover_cells_and_kzg_proofs(cell_indices, cells)
+        .map_err(Error::cryptography(Operation::Recovery))?;
     let recovered_cells = recovered_cells.map(|cell| *cell);
-    cells_and_proofs_to_jobject(env, &recovered_cells, &recovered_proofs).map_err(Error::from)
+    cells_and_proofs_to_jobject(env, &recovered_cells, &recovered_proofs)
 }
 
-/// Converts a JLongArray to a Vec<u64>
-fn jlongarray_to_vec_u64(env: &JNIEnv, array: JLongArray) -> Result<Vec<u64>, Error> {
-    // Step 1: Get the length of the JLongArray
-    let array_length = env.get_array_length(&array)?;
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsFlat<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
+    cells: JByteArray<'local>,
+) -> JObject<'local> {
+    match catch_panic(|| {
+        recover_cells_and_kzg_proofs_flat(&mut env, ctx_handle, cell_indices, cells)
+    }) {
+        Ok(cells_and_proofs) => cells_and_proofs,
+        Err(err) => {
+            throw_on_error(&mut env, err, "recoverCellsAndKZGProofsFlat");
+            JObject::default()
+        }
+    }
+}
+fn recover_cells_and_kzg_proofs_flat<'local>(
+    env: &mut JNIEnv<'local>,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
+    cells: JByteArray<'local>,
+) -> Result<JObject<'local>, Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let cell_indices = jlongarray_to_cell_indices(env, cell_indices, Operation::Recovery)?;
+    let cells = byte_array_elements(env, &cells)?;
+    let cells = split_flat_slice::<{ c_eth_kzg::BYTES_PER_CELL }>(as_bytes(&cells), "cells")?;
+    check_matches_cell_indices("cells", cells.len(), &cell_indices, Operation::Recovery)?;
 
-    // Step 2: Create a buffer to hold the jlong elements (these are i64s)
-    let mut buffer: Vec<i64> = vec![0; array_length as usize];
+    let (recovered_cells, recovered_proofs) = ctx
+        .recover_cells_and_kzg_proofs(cell_indices, cells)
+        .map_err(Error::cryptography(Operation::Recovery))?;
+    let recovered_cells = recovered_cells.map(|cell| *cell);
+    flat_cells_and_proofs_to_jobject(env, &recovered_cells, &recovered_proofs)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_blobToKZGCommitmentDirect<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    blob: JByteBuffer<'local>,
+    commitment_out: JByteBuffer<'local>,
+) {
+    if let Err(err) =
+        catch_panic(|| blob_to_kzg_commitment_direct(&env, ctx_handle, &blob, &commitment_out))
+    {
+        throw_on_error(&mut env, err, "blobToKZGCommitmentDirect");
+    }
+}
+fn blob_to_kzg_commitment_direct(
+    env: &JNIEnv,
+    ctx_handle: jlong,
+    blob: &JByteBuffer,
+    commitment_out: &JByteBuffer,
+) -> Result<(), Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let commitment = {
+        let blob = direct_buffer_as_slice(env, blob)?;
+        let blob = slice_to_array_ref(blob, "blob")?;
+        ctx.blob_to_kzg_commitment(blob)
+            .map_err(Error::cryptography(Operation::Proving))?
+    };
+
+    let commitment_out = direct_buffer_as_mut_slice(env, commitment_out)?;
+    copy_to_output(commitment_out, &[commitment], "commitment_out")
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsDirect<
+    'local,
+>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    blob: JByteBuffer<'local>,
+    cells_out: JByteBuffer<'local>,
+    proofs_out: JByteBuffer<'local>,
+) {
+    if let Err(err) = catch_panic(|| {
+        compute_cells_and_kzg_proofs_direct(&env, ctx_handle, &blob, &cells_out, &proofs_out)
+    }) {
+        throw_on_error(&mut env, err, "computeCellsAndKZGProofsDirect");
+    }
+}
+fn compute_cells_and_kzg_proofs_direct(
+    env: &JNIEnv,
+    ctx_handle: jlong,
+    blob: &JByteBuffer,
+    cells_out: &JByteBuffer,
+    proofs_out: &JByteBuffer,
+) -> Result<(), Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let (cells, proofs) = {
+        let blob = direct_buffer_as_slice(env, blob)?;
+        let blob = slice_to_array_ref(blob, "blob")?;
+        ctx.compute_cells_and_kzg_proofs(blob)
+            .map_err(Error::cryptography(Operation::Proving))?
+    };
+    let cells = cells.map(|cell| *cell);
+
+    copy_to_output(
+        direct_buffer_as_mut_slice(env, cells_out)?,
+        &cells,
+        "cells_out",
+    )?;
+    copy_to_output(
+        direct_buffer_as_mut_slice(env, proofs_out)?,
+        &proofs,
+        "proofs_out",
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_verifyCellKZGProofBatchDirect<
+    'local,
+>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    commitments: JByteBuffer<'local>,
+    cell_indices: JLongArray,
+    cells: JByteBuffer<'local>,
+    proofs: JByteBuffer<'local>,
+) -> jboolean {
+    match catch_panic(|| {
+        verify_cell_kzg_proof_batch_direct(
+            &env,
+            ctx_handle,
+            &commitments,
+            cell_indices,
+            &cells,
+            &proofs,
+        )
+    }) {
+        Ok(result) => result,
+        Err(err) => {
+            throw_on_error(&mut env, err, "verifyCellKZGProofBatchDirect");
+            jboolean::default()
+        }
+    }
+}
+fn verify_cell_kzg_proof_batch_direct(
+    env: &JNIEnv,
+    ctx_handle: jlong,
+    commitments: &JByteBuffer,
+    cell_indices: JLongArray,
+    cells: &JByteBuffer,
+    proofs: &JByteBuffer,
+) -> Result<jboolean, Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let cell_indices = jlongarray_to_cell_indices(env, cell_indices, Operation::Verification)?;
+    let commitments = split_flat_slice(direct_buffer_as_slice(env, commitments)?, "commitments")?;
+    let cells = split_flat_slice(direct_buffer_as_slice(env, cells)?, "cells")?;
+    let proofs = split_flat_slice(direct_buffer_as_slice(env, proofs)?, "proofs")?;
+    check_matches_cell_indices(
+        "commitments",
+        commitments.len(),
+        &cell_indices,
+        Operation::Verification,
+    )?;
+    check_matches_cell_indices("cells", cells.len(), &cell_indices, Operation::Verification)?;
+    check_matches_cell_indices(
+        "proofs",
+        proofs.len(),
+        &cell_indices,
+        Operation::Verification,
+    )?;
+
+    let verification_result =
+        ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs);
+    let verified = verification_result_to_bool(verification_result)
+        .map_err(Error::cryptography(Operation::Verification))?;
+    Ok(jboolean::from(verified))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsDirect<
+    'local,
+>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
+    cells: JByteBuffer<'local>,
+    cells_out: JByteBuffer<'local>,
+    proofs_out: JByteBuffer<'local>,
+) {
+    if let Err(err) = catch_panic(|| {
+        recover_cells_and_kzg_proofs_direct(
+            &env,
+            ctx_handle,
+            cell_indices,
+            &cells,
+            &cells_out,
+            &proofs_out,
+        )
+    }) {
+        throw_on_error(&mut env, err, "recoverCellsAndKZGProofsDirect");
+    }
+}
+fn recover_cells_and_kzg_proofs_direct(
+    env: &JNIEnv,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
+    cells: &JByteBuffer,
+    cells_out: &JByteBuffer,
+    proofs_out: &JByteBuffer,
+) -> Result<(), Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let cell_indices = jlongarray_to_cell_indices(env, cell_indices, Operation::Recovery)?;
+    let (recovered_cells, recovered_proofs) = {
+        let cells = split_flat_slice(direct_buffer_as_slice(env, cells)?, "cells")?;
+        check_matches_cell_indices("cells", cells.len(), &cell_indices, Operation::Recovery)?;
+        ctx.recover_cells_and_kzg_proofs(cell_indices, cells)
+            .map_err(Error::cryptography(Operation::Recovery))?
+    };
+    let recovered_cells = recovered_cells.map(|cell| *cell);
 
-    // Step 3: Get the elements from the JLongArray
+    copy_to_output(
+        direct_buffer_as_mut_slice(env, cells_out)?,
+        &recovered_cells,
+        "cells_out",
+    )?;
+    copy_to_output(
+        direct_buffer_as_mut_slice(env, proofs_out)?,
+        &recovered_proofs,
+        "proofs_out",
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsAsync<
+    'local,
+>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    blob: JByteArray<'local>,
+    future: JObject<'local>,
+) {
+    if let Err(err) =
+        catch_panic(|| compute_cells_and_kzg_proofs_async(&mut env, ctx_handle, blob, &future))
+    {
+        complete_future_exceptionally(&mut env, &future, err, "computeCellsAndKZGProofsAsync");
+    }
+}
+fn compute_cells_and_kzg_proofs_async<'local>(
+    env: &mut JNIEnv<'local>,
+    ctx_handle: jlong,
+    blob: JByteArray<'local>,
+    future: &JObject<'local>,
+) -> Result<(), Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let blob = env.convert_byte_array(blob)?;
+
+    spawn_cells_and_proofs_task(
+        env,
+        ctx,
+        future,
+        "computeCellsAndKZGProofsAsync",
+        move |ctx| {
+            let blob = slice_to_array_ref(&blob, "blob")?;
+            let (cells, proofs) = ctx
+                .compute_cells_and_kzg_proofs(blob)
+                .map_err(Error::cryptography(Operation::Proving))?;
+            Ok((cells.iter().map(|cell| **cell).collect(), proofs.to_vec()))
+        },
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsAsync<
+    'local,
+>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
+    cells: JObjectArray<'local>,
+    future: JObject<'local>,
+) {
+    if let Err(err) = catch_panic(|| {
+        recover_cells_and_kzg_proofs_async(&mut env, ctx_handle, cell_indices, cells, &future)
+    }) {
+        complete_future_exceptionally(&mut env, &future, err, "recoverCellsAndKZGProofsAsync");
+    }
+}
+fn recover_cells_and_kzg_proofs_async<'local>(
+    env: &mut JNIEnv<'local>,
+    ctx_handle: jlong,
+    cell_indices: JLongArray,
+    cells: JObjectArray<'local>,
+    future: &JObject<'local>,
+) -> Result<(), Error> {
+    let ctx = registry::get(ctx_handle)?;
+    let cell_indices = jlongarray_to_cell_indices(env, cell_indices, Operation::Recovery)?;
+    let cells = jobject_array_to_2d_byte_array(env, cells)?;
+    check_matches_cell_indices("cells", cells.len(), &cell_indices, Operation::Recovery)?;
+
+    spawn_cells_and_proofs_task(
+        env,
+        ctx,
+        future,
+        "recoverCellsAndKZGProofsAsync",
+        move |ctx| {
+            let cells: Vec<_> = cells
+                .iter()
+                .map(|cell| slice_to_array_ref(cell, "cell"))
+                .collect::<Result<_, _>>()?;
+            let (recovered_cells, recovered_proofs) = ctx
+                .recover_cells_and_kzg_proofs(cell_indices, cells)
+                .map_err(Error::cryptography(Operation::Recovery))?;
+            Ok((
+                recovered_cells.iter().map(|cell| **cell).collect(),
+                recovered_proofs.to_vec(),
+            ))
+        },
+    )
+}
+
+/// The cells and proofs computed by an asynchronous task.
+type CellsAndProofsTaskResult = Result<
+    (
+        Vec<[u8; c_eth_kzg::BYTES_PER_CELL]>,
+        Vec<[u8; c_eth_kzg::BYTES_PER_COMMITMENT]>,
+    ),
+    Error,
+>;
+
+/// Runs `task` on the thread pool of the context and uses its result to complete
+/// the `CellsAndProofsFuture`, without blocking the calling thread.
+fn spawn_cells_and_proofs_task<F>(
+    env: &mut JNIEnv,
+    ctx: Arc<c_eth_kzg::DASContext>,
+    future: &JObject,
+    func_name: &'static str,
+    task: F,
+) -> Result<(), Error>
+where
+    F: FnOnce(&c_eth_kzg::DASContext) -> CellsAndProofsTaskResult + Send + 'static,
+{
+    let vm = env.get_java_vm()?;
+    let future = env.new_global_ref(future)?;
+
+    let task_ctx = Arc::clone(&ctx);
+    ctx.spawn(move || {
+        // A panic in a task of the thread pool aborts the process, so it is reported
+        // through the future instead.
+        let result = catch_panic(|| task(&task_ctx));
+
+        // The threads in the pool stay attached to the JVM, so that the cost
+        // of attaching is only paid once per thread.
+        let Ok(mut env) = vm.attach_current_thread_as_daemon() else {
+            // There is no way to reach the future without a JNIEnv.
+            return;
+        };
+        // Local references are only freed when a native method returns, which never
+        // happens on this thread, so we create them in a frame that we pop ourselves.
+        let _ = env.with_local_frame(LOCAL_FRAME_CAPACITY, |env| -> Result<(), Error> {
+            let completed = result.and_then(|(cells, proofs)| {
+                let cells = byte_arrays_to_jobject_array(env, &cells)?;
+                let proofs = byte_arrays_to_jobject_array(env, &proofs)?;
+                env.call_method(
+                    future.as_obj(),
+                    "completeWithCellsAndProofs",
+                    "([[B[[B)V",
+                    &[JValue::Object(&cells), JValue::Object(&proofs)],
+                )?;
+                Ok(())
+            });
+            if let Err(err) = completed {
+                complete_future_exceptionally(env, future.as_obj(), err, func_name);
+            }
+            Ok(())
+        });
+    });
+
+    Ok(())
+}
+
+/// Completes a `CellsAndProofsFuture` with the exception that would have been thrown for `err`.
+fn complete_future_exceptionally(
+    env: &mut JNIEnv,
+    future: &JObject,
+    err: Error,
+    func_name: &'static str,
+) {
+    // We cannot call back into Java while an exception is pending. The error
+    // that it caused is reported through the future instead.
+    let _ = env.exception_clear();
+
+    let exception_class = exception_class(&err);
+    let msg = error_message(&err, func_name);
+    #[cfg(all(feature = "android", target_os = "android"))]
+    android::log_error(&msg);
+    let completed = env.new_string(exception_class).and_then(|exception_class| {
+        let msg = env.new_string(msg)?;
+        env.call_method(
+            future,
+            "completeWithError",
+            "(Ljava/lang/String;Ljava/lang/String;)V",
+            &[JValue::Object(&exception_class), JValue::Object(&msg)],
+        )
+    });
+    if completed.is_err() {
+        let _ = env.exception_clear();
+    }
+}
+
+/// Returns the contents of a direct ByteBuffer as a slice, without copying it.
+///
+/// Note: The position and limit of the buffer are ignored, the slice always
+/// covers the whole capacity of the buffer.
+fn direct_buffer_as_slice<'a>(env: &JNIEnv, buffer: &'a JByteBuffer) -> Result<&'a [u8], Error> {
+    let ptr = env.get_direct_buffer_address(buffer)?;
+    let len = env.get_direct_buffer_capacity(buffer)?;
+    // Safety: The JVM guarantees that a direct buffer's memory is valid for `len` bytes
+    // and will not be freed while we hold a reference to the buffer.
+    Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+}
+
+/// Mutable version of `direct_buffer_as_slice`.
+///
+/// Note: Callers must ensure that no slice returned by `direct_buffer_as_slice` is alive
+/// when this is called, since Java may pass the same buffer for inputs and outputs.
+#[allow(clippy::mut_from_ref)]
+fn direct_buffer_as_mut_slice<'a>(
+    env: &JNIEnv,
+    buffer: &'a JByteBuffer,
+) -> Result<&'a mut [u8], Error> {
+    let ptr = env.get_direct_buffer_address(buffer)?;
+    let len = env.get_direct_buffer_capacity(buffer)?;
+    // Safety: See `direct_buffer_as_slice`
+    Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len) })
+}
+
+/// Converts a JLongArray of cell indices to a Vec<CellIndex>, checking that every index is in range.
+fn jlongarray_to_cell_indices(
+    env: &JNIEnv,
+    array: JLongArray,
+    operation: Operation,
+) -> Result<Vec<CellIndex>, Error> {
+    let array_length = env.get_array_length(&array)?;
+    let mut buffer: Vec<i64> = vec![0; array_length as usize];
     env.get_long_array_region(array, 0, &mut buffer)?;
+    to_cell_indices(&buffer, operation)
+}
 
-    // Step 4: Convert the Vec<i64> to Vec<u64>
-    Ok(buffer.into_iter().map(|x| x as u64).collect())
+/// Returns the byte arrays in a JObjectArray, without copying their contents.
+fn jobject_array_to_byte_arrays<'local>(
+    env: &mut JNIEnv<'local>,
+    array: &JObjectArray,
+) -> Result<Vec<JByteArray<'local>>, Error> {
+    let len = env.get_array_length(array)?;
+    (0..len)
+        .map(|i| Ok(JByteArray::from(env.get_object_array_element(array, i)?)))
+        .collect()
+}
+
+/// Returns the elements of a Java byte array.
+///
+/// This uses `GetByteArrayElements`, which gives direct access to the array when the JVM
+/// supports pinning it, and otherwise copies it once. The elements are released without
+/// being copied back, since they are only read.
+///
+/// `GetPrimitiveArrayCritical` is not used, because the elements are read while the
+/// computation runs on the thread pool of the context. A critical region must not wait
+/// on other threads, and the threads in the pool call into Java to complete the futures
+/// of asynchronous tasks, which could block them on the garbage collector until the
+/// critical region ends.
+fn byte_array_elements<'local, 'array>(
+    env: &mut JNIEnv<'local>,
+    array: &'array JByteArray<'local>,
+) -> Result<AutoElements<'local, 'local, 'array, jbyte>, Error> {
+    // Safety: The elements are only read, and the array is not accessed through JNI while
+    // they are borrowed.
+    Ok(unsafe { env.get_array_elements(array, ReleaseMode::NoCopyBack) }?)
+}
+
+/// Returns the elements of each of the byte arrays, see `byte_array_elements`.
+fn byte_arrays_elements<'local, 'array>(
+    env: &mut JNIEnv<'local>,
+    arrays: &'array [JByteArray<'local>],
+) -> Result<Vec<AutoElements<'local, 'local, 'array, jbyte>>, Error> {
+    arrays
+        .iter()
+        .map(|array| byte_array_elements(env, array))
+        .collect()
+}
+
+/// Returns the elements of a Java byte array as bytes.
+fn as_bytes<'a>(elements: &'a AutoElements<jbyte>) -> &'a [u8] {
+    // Safety: `jbyte` and `u8` have the same size and alignment.
+    unsafe { std::slice::from_raw_parts(elements.as_ptr().cast::<u8>(), elements.len()) }
 }
 
 /// Converts a JObjectArray to a Vec<Vec<u8>>
@@ -225,76 +1092,126 @@ fn cells_and_proofs_to_jobject<'local>(
     cells: &[impl AsRef<[u8]>],
     proofs: &[impl AsRef<[u8]>],
 ) -> Result<JObject<'local>, Error> {
-    // Create a new instance of the CellsAndProofs class in Java
-    let cells_and_proofs_class = env.find_class("ethereum/cryptography/CellsAndProofs")?;
+    let cells_array = byte_arrays_to_jobject_array(env, cells)?;
+    let proofs_array = byte_arrays_to_jobject_array(env, proofs)?;
 
-    let cell_byte_array_class = env.find_class("[B")?;
-    let proof_byte_array_class = env.find_class("[B")?;
+    // Create the CellsAndProofs object
+    classes::new_cells_and_proofs(env, &cells_array, &proofs_array)
+}
 
-    // Create 2D array for cells
-    let cells_array = env.new_object_array(
-        cells.len() as i32,
-        cell_byte_array_class,
-        env.new_byte_array(0)?,
-    )?;
+/// Creates a FlatCellsAndProofs object in Java, with the cells and the proofs
+/// each concatenated into a single byte array.
+///
+/// This only needs two byte arrays to be created, instead of one for every cell and proof.
+fn flat_cells_and_proofs_to_jobject<'local>(
+    env: &mut JNIEnv<'local>,
+    cells: &[impl AsRef<[u8]>],
+    proofs: &[impl AsRef<[u8]>],
+) -> Result<JObject<'local>, Error> {
+    let flat_cells: Vec<u8> = cells
+        .iter()
+        .flat_map(|cell| cell.as_ref())
+        .copied()
+        .collect();
+    let flat_proofs: Vec<u8> = proofs
+        .iter()
+        .flat_map(|proof| proof.as_ref())
+        .copied()
+        .collect();
 
-    for (i, cell) in cells.iter().enumerate() {
-        let cell_array = env.byte_array_from_slice(cell.as_ref())?;
-        env.set_object_array_element(&cells_array, i as i32, cell_array)?;
-    }
+    let cells_array = env.byte_array_from_slice(&flat_cells)?;
+    let proofs_array = env.byte_array_from_slice(&flat_proofs)?;
+
+    classes::new_flat_cells_and_proofs(env, &cells_array, &proofs_array)
+}
+
+/// Converts a slice of byte arrays into a 2D byte array in Java
+fn byte_arrays_to_jobject_array<'local>(
+    env: &mut JNIEnv<'local>,
+    byte_arrays: &[impl AsRef<[u8]>],
+) -> Result<JObjectArray<'local>, Error> {
+    let byte_array_class = classes::find_class(env, classes::BYTE_ARRAY)?;
 
-    // Create 2D array for proofs
-    let proofs_array = env.new_object_array(
-        proofs.len() as i32,
-        proof_byte_array_class,
+    let jobject_array = env.new_object_array(
+        byte_arrays.len() as i32,
+        byte_array_class,
         env.new_byte_array(0)?,
     )?;
 
-    for (i, proof) in proofs.iter().enumerate() {
-        let proof_array = env.byte_array_from_slice(proof.as_ref())?;
-        env.set_object_array_element(&proofs_array, i as i32, proof_array)?;
+    for (i, bytes) in byte_arrays.iter().enumerate() {
+        let byte_array = env.byte_array_from_slice(bytes.as_ref())?;
+        env.set_object_array_element(&jobject_array, i as i32, byte_array)?;
     }
 
-    // Create the CellsAndProofs object
-    let cells_and_proofs_obj = env.new_object(
-        cells_and_proofs_class,
-        "([[B[[B)V",
-        &[JValue::Object(&cells_array), JValue::Object(&proofs_array)],
-    )?;
-
-    Ok(cells_and_proofs_obj)
+    Ok(jobject_array)
 }
 
 /// Throws an exception in Java
 fn throw_on_error(env: &mut JNIEnv, err: Error, func_name: &'static str) {
-    let reason = match err {
-        Error::Jni(err) => format!("{:?}", err),
-        Error::IncorrectSize {
-            expected,
-            got,
-            name,
-        } => format!("{name} is not the correct size. expected: {expected}\ngot: {got}"),
-        Error::Cryptography(err) => format!("{:?}", err),
-    };
-    let msg = format!(
-        "function {} has thrown an exception, with reason: {}",
-        func_name, reason
-    );
-    env.throw_new("java/lang/IllegalArgumentException", msg)
-        .expect("Failed to throw exception");
+    let msg = error_message(&err, func_name);
+    #[cfg(all(feature = "android", target_os = "android"))]
+    android::log_error(&msg);
+    // If the exception cannot be thrown, another exception, such as an `OutOfMemoryError`,
+    // is already pending and is thrown instead.
+    let _ = env.throw_new(exception_class(&err), msg);
 }
 
-/// Convert a slice into a reference to an array
+/// Runs the body of a native method, converting a panic into an error.
 ///
-/// This is needed as the API for rust library does
-/// not accept slices.
-fn slice_to_array_ref<'a, const N: usize>(
-    slice: &'a [u8],
-    name: &'static str,
-) -> Result<&'a [u8; N], Error> {
-    slice.try_into().map_err(|_| Error::IncorrectSize {
-        expected: N,
-        got: slice.len(),
-        name,
+/// A panic that unwinds into the JVM is undefined behavior, and recent versions of Rust abort
+/// the process when it happens, which takes down the JVM with it. Every native method therefore
+/// runs its body through this, so that a panic is thrown as an exception instead.
+fn catch_panic<T>(body: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    std::panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        let reason = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown error");
+        Err(Error::Panic(reason.to_owned()))
     })
 }
+
+/// Returns the message of the exception that is thrown for the given error.
+fn error_message(err: &Error, func_name: &'static str) -> String {
+    format!(
+        "function {} has thrown an exception, with reason: {}",
+        func_name, err
+    )
+}
+
+/// Returns the Java class of the exception that should be thrown for the given error.
+///
+/// The exception classes in `ethereum.cryptography` all extend `IllegalArgumentException`.
+const fn exception_class(err: &Error) -> &'static str {
+    match err {
+        Error::InvalidContextHandle { .. } | Error::ConstantMismatch { .. } | Error::Panic(_) => {
+            "java/lang/IllegalStateException"
+        }
+        Error::IncorrectSize { .. } | Error::IncorrectSizeMultiple { .. } => {
+            "ethereum/cryptography/KZGSerializationException"
+        }
+        Error::Cryptography { source, operation } => match source.kind() {
+            ErrorKind::InvalidProof => "ethereum/cryptography/KZGProofException",
+            ErrorKind::BadEncoding => "ethereum/cryptography/KZGSerializationException",
+            ErrorKind::BadTrustedSetup => "ethereum/cryptography/KZGTrustedSetupException",
+            ErrorKind::Internal => "java/lang/IllegalStateException",
+            ErrorKind::BadLength | ErrorKind::BadArgument => operation_exception_class(*operation),
+        },
+        Error::NegativeCellIndex { operation, .. }
+        | Error::CellIndexOutOfRange { operation, .. }
+        | Error::LengthMismatch { operation, .. } => operation_exception_class(*operation),
+        Error::Jni(_) | Error::InvalidParameter { .. } => "java/lang/IllegalArgumentException",
+    }
+}
+
+/// Returns the Java class of the exception that should be thrown when an operation
+/// is given an argument that has the wrong length or an invalid value.
+const fn operation_exception_class(operation: Operation) -> &'static str {
+    match operation {
+        Operation::Setup => "ethereum/cryptography/KZGTrustedSetupException",
+        Operation::Proving => "ethereum/cryptography/KZGSerializationException",
+        Operation::Verification => "ethereum/cryptography/KZGProofException",
+        Operation::Recovery => "ethereum/cryptography/KZGRecoveryException",
+    }
+}
@@ -0,0 +1,170 @@
+use crate::errors::Error;
+use jni::{JNIEnv, NativeMethod};
+
+/// The class that declares the native methods.
+const LIB_ETH_KZG: &str = "ethereum/cryptography/LibEthKZG";
+
+/// Creates the `NativeMethod` for the native method of `LibEthKZG` with the given name and signature.
+macro_rules! native {
+    ($name:literal, $sig:literal, $fn:path) => {
+        NativeMethod {
+            name: $name.into(),
+            sig: $sig.into(),
+            fn_ptr: $fn as *mut std::ffi::c_void,
+        }
+    };
+}
+
+/// Binds the native methods of `LibEthKZG` to their implementations.
+///
+/// The JVM would otherwise resolve each method by searching the loaded libraries for its
+/// mangled symbol the first time it is called. The symbols are still exported, so that
+/// the methods keep working if registration fails.
+pub(crate) fn register(env: &mut JNIEnv) -> Result<(), Error> {
+    let methods = [
+        native!(
+            "DASContextNew",
+            "(ZJ)J",
+            crate::Java_ethereum_cryptography_LibEthKZG_DASContextNew
+        ),
+        native!(
+            "DASContextNewWithPrecompWidth",
+            "(II)J",
+            crate::Java_ethereum_cryptography_LibEthKZG_DASContextNewWithPrecompWidth
+        ),
+        native!(
+            "DASContextNewWithTrustedSetup",
+            "(Ljava/lang/String;ZJ)J",
+            crate::Java_ethereum_cryptography_LibEthKZG_DASContextNewWithTrustedSetup
+        ),
+        native!(
+            "DASContextDestroy",
+            "(J)V",
+            crate::Java_ethereum_cryptography_LibEthKZG_DASContextDestroy
+        ),
+        native!(
+            "libraryVersion",
+            "()Ljava/lang/String;",
+            crate::Java_ethereum_cryptography_LibEthKZG_libraryVersion
+        ),
+        native!(
+            "embeddedTrustedSetupId",
+            "()Ljava/lang/String;",
+            crate::Java_ethereum_cryptography_LibEthKZG_embeddedTrustedSetupId
+        ),
+        native!(
+            "numThreads",
+            "(J)J",
+            crate::Java_ethereum_cryptography_LibEthKZG_numThreads
+        ),
+        native!(
+            "precompWidth",
+            "(J)I",
+            crate::Java_ethereum_cryptography_LibEthKZG_precompWidth
+        ),
+        native!(
+            "computeCellsAndKZGProofs",
+            "(J[B)Lethereum/cryptography/CellsAndProofs;",
+            crate::Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofs
+        ),
+        native!(
+            "computeCellsAndKZGProofsFlat",
+            "(J[B)Lethereum/cryptography/FlatCellsAndProofs;",
+            crate::Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsFlat
+        ),
+        native!(
+            "computeCells",
+            "(J[B)[[B",
+            crate::Java_ethereum_cryptography_LibEthKZG_computeCells
+        ),
+        native!(
+            "computeCellsAndKZGProofsBatch",
+            "(J[[B)[Lethereum/cryptography/CellsAndProofs;",
+            crate::Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsBatch
+        ),
+        native!(
+            "blobToKZGCommitment",
+            "(J[B)[B",
+            crate::Java_ethereum_cryptography_LibEthKZG_blobToKZGCommitment
+        ),
+        native!(
+            "verifyCellKZGProofBatch",
+            "(J[[B[J[[B[[B)Z",
+            crate::Java_ethereum_cryptography_LibEthKZG_verifyCellKZGProofBatch
+        ),
+        native!(
+            "recoverCellsAndKZGProofs",
+            "(J[J[[B)Lethereum/cryptography/CellsAndProofs;",
+            crate::Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofs
+        ),
+        native!(
+            "recoverCellsAndKZGProofsFlat",
+            "(J[J[B)Lethereum/cryptography/FlatCellsAndProofs;",
+            crate::Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsFlat
+        ),
+        native!(
+            "blobToKZGCommitmentDirect",
+            "(JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)V",
+            crate::Java_ethereum_cryptography_LibEthKZG_blobToKZGCommitmentDirect
+        ),
+        native!(
+            "computeCellsAndKZGProofsDirect",
+            "(JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)V",
+            crate::Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsDirect
+        ),
+        native!(
+            "verifyCellKZGProofBatchDirect",
+            "(JLjava/nio/ByteBuffer;[JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)Z",
+            crate::Java_ethereum_cryptography_LibEthKZG_verifyCellKZGProofBatchDirect
+        ),
+        native!(
+            "recoverCellsAndKZGProofsDirect",
+            "(J[JLjava/nio/ByteBuffer;Ljava/nio/ByteBuffer;Ljava/nio/ByteBuffer;)V",
+            crate::Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsDirect
+        ),
+        native!(
+            "computeCellsAndKZGProofsAsync",
+            "(J[BLethereum/cryptography/CellsAndProofsFuture;)V",
+            crate::Java_ethereum_cryptography_LibEthKZG_computeCellsAndKZGProofsAsync
+        ),
+        native!(
+            "recoverCellsAndKZGProofsAsync",
+            "(J[J[[BLethereum/cryptography/CellsAndProofsFuture;)V",
+            crate::Java_ethereum_cryptography_LibEthKZG_recoverCellsAndKZGProofsAsync
+        ),
+    ];
+    env.register_native_methods(LIB_ETH_KZG, &methods)?;
+    Ok(())
+}
+
+/// The size constants that `LibEthKZG` declares, and the values that the library uses for them.
+const CONSTANTS: [(&str, usize); 6] = [
+    ("BYTES_PER_COMMITMENT", c_eth_kzg::BYTES_PER_COMMITMENT),
+    ("BYTES_PER_PROOF", c_eth_kzg::BYTES_PER_PROOF),
+    (
+        "BYTES_PER_FIELD_ELEMENT",
+        c_eth_kzg::BYTES_PER_FIELD_ELEMENT,
+    ),
+    ("BYTES_PER_BLOB", c_eth_kzg::BYTES_PER_BLOB),
+    ("MAX_NUM_COLUMNS", c_eth_kzg::CELLS_PER_EXT_BLOB),
+    ("BYTES_PER_CELL", c_eth_kzg::BYTES_PER_CELL),
+];
+
+/// Checks that the size constants of `LibEthKZG` have the values that the library uses.
+///
+/// The constants are declared in Java so that they are compile time constants there, which
+/// means that they could drift from the library without this check.
+pub(crate) fn check_constants(env: &mut JNIEnv) -> Result<(), Error> {
+    let class = env.find_class(LIB_ETH_KZG)?;
+    for (name, expected) in CONSTANTS {
+        let got = env.get_static_field(&class, name, "I")?.i()?;
+        if usize::try_from(got) != Ok(expected) {
+            return Err(Error::ConstantMismatch {
+                name,
+                expected,
+                got: got.into(),
+            });
+        }
+    }
+    Ok(())
+}
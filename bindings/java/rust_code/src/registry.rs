@@ -0,0 +1,144 @@
+use crate::errors::Error;
+use c_eth_kzg::DASContext;
+use jni::sys::jlong;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// The contexts that have been handed out to Java.
+///
+/// Java only ever sees a handle to a context, instead of a raw pointer. A handle
+/// contains the index of the context in the registry and the generation of that
+/// slot, which is incremented every time a context is removed. This means that
+/// using a handle after the context has been destroyed, or destroying a context twice,
+/// returns an error instead of causing undefined behavior.
+static REGISTRY: Mutex<ContextRegistry> = Mutex::new(ContextRegistry::new());
+
+/// Adds a context to the registry and returns the handle that refers to it.
+pub(crate) fn insert(context: DASContext) -> jlong {
+    lock_registry().insert(Arc::new(context))
+}
+
+/// Returns the context that the handle refers to.
+///
+/// Note: The context is reference counted, so a context that is destroyed while
+/// it is being used is only freed once the call using it has finished.
+pub(crate) fn get(handle: jlong) -> Result<Arc<DASContext>, Error> {
+    lock_registry().get(handle)
+}
+
+/// Removes the context that the handle refers to from the registry.
+pub(crate) fn remove(handle: jlong) -> Result<(), Error> {
+    let ctx = lock_registry().remove(handle)?;
+    // Destroying the context can take a while, so it is dropped after the registry has been
+    // unlocked, instead of blocking the other threads that use the registry
+    drop(ctx);
+    Ok(())
+}
+
+fn lock_registry() -> std::sync::MutexGuard<'static, ContextRegistry> {
+    // The registry is always left in a consistent state, so we can ignore poisoning
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<Arc<T>>,
+}
+
+struct Registry<T> {
+    slots: Vec<Slot<T>>,
+    free_slots: Vec<u32>,
+}
+
+type ContextRegistry = Registry<DASContext>;
+
+impl<T> Registry<T> {
+    const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, value: Arc<T>) -> jlong {
+        let index = match self.free_slots.pop() {
+            Some(index) => index,
+            None => {
+                // Generations start at 1, so that a handle is never zero
+                self.slots.push(Slot {
+                    generation: 1,
+                    value: None,
+                });
+                (self.slots.len() - 1) as u32
+            }
+        };
+
+        let slot = &mut self.slots[index as usize];
+        slot.value = Some(value);
+        encode_handle(index, slot.generation)
+    }
+
+    fn get(&self, handle: jlong) -> Result<Arc<T>, Error> {
+        let (index, generation) = decode_handle(handle);
+        self.slots
+            .get(index as usize)
+            .filter(|slot| slot.generation == generation)
+            .and_then(|slot| slot.value.clone())
+            .ok_or(Error::InvalidContextHandle { handle })
+    }
+
+    /// Removes the value from the registry and returns it, so that the caller can drop it
+    /// after releasing the lock.
+    fn remove(&mut self, handle: jlong) -> Result<Arc<T>, Error> {
+        let (index, generation) = decode_handle(handle);
+        let slot = self
+            .slots
+            .get_mut(index as usize)
+            .filter(|slot| slot.generation == generation)
+            .ok_or(Error::InvalidContextHandle { handle })?;
+        let value = slot
+            .value
+            .take()
+            .ok_or(Error::InvalidContextHandle { handle })?;
+
+        slot.generation = slot.generation.wrapping_add(1).max(1);
+        self.free_slots.push(index);
+        Ok(value)
+    }
+}
+
+const fn encode_handle(index: u32, generation: u32) -> jlong {
+    (((generation as u64) << 32) | index as u64) as jlong
+}
+
+const fn decode_handle(handle: jlong) -> (u32, u32) {
+    let handle = handle as u64;
+    (handle as u32, (handle >> 32) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+    use std::sync::Arc;
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        let mut registry = Registry::new();
+
+        let handle = registry.insert(Arc::new(1));
+        assert_ne!(handle, 0);
+        assert_eq!(*registry.get(handle).unwrap(), 1);
+
+        assert_eq!(*registry.remove(handle).unwrap(), 1);
+        assert!(registry.get(handle).is_err());
+        assert!(registry.remove(handle).is_err());
+
+        // The slot is reused, but the old handle still does not refer to the new value
+        let new_handle = registry.insert(Arc::new(2));
+        assert_ne!(handle, new_handle);
+        assert!(registry.get(handle).is_err());
+        assert_eq!(*registry.get(new_handle).unwrap(), 2);
+
+        assert!(registry.get(0).is_err());
+        assert!(registry.get(-1).is_err());
+    }
+}
@@ -63,11 +63,20 @@ template toPtrPtr(cells: openArray[untyped]): ptr pointer =
   # Return the pointer to the seq of pointers
   cast[ptr pointer](ptrSeq.safeGetPtr)
 
+type
+  KZGError* = object
+    ## The reason that a call into the library failed.
+    code*: CResultCode
+    msg*: string
+
+proc `$`*(e: KZGError): string =
+  $e.code & ": " & e.msg
+
 template verify_result(res: CResult, ret: untyped): untyped =
   if res.xstatus != CResultStatus.Ok:
-    let errorMsg = $res
+    let error = KZGError(code: res.xcode, msg: $cast[cstring](res.xerror_msg))
     eth_kzg_free_error_message(res.xerror_msg)
-    return err(errorMsg)
+    return err(error)
   ok(ret)
 
 
@@ -84,13 +93,17 @@ proc `=destroy`(x: typeof KZGCtx()[]) =
   if x.ctx_ptr != nil:
     eth_kzg_das_context_free(x.ctx_ptr)
 
-proc newKZGCtx*(use_precomp: bool = true, num_threads: uint32 = 1): KZGCtx =
+proc newKZGCtx*(use_precomp: bool = true, num_threads: uint32 = 1): Result[KZGCtx, KZGError] =
+  var code: CResultCode
   var kzgCtx = KZGCtx()
-  kzgCtx.ctx_ptr = eth_kzg_das_context_new(use_precomp, num_threads)
-  return kzgCtx
+  kzgCtx.ctx_ptr = eth_kzg_das_context_new(use_precomp, num_threads, code.addr)
+  if kzgCtx.ctx_ptr == nil:
+    let reason = $cast[cstring](eth_kzg_error_to_string(code))
+    return err(KZGError(code: code, msg: "could not create the context: " & reason))
+  return ok(kzgCtx)
 
 
-proc blobToKZGCommitment*(ctx: KZGCtx, blob : Blob): Result[KZGCommitment, string] {.gcsafe.} =
+proc blobToKZGCommitment*(ctx: KZGCtx, blob : Blob): Result[KZGCommitment, KZGError] {.gcsafe.} =
   var ret: KZGCommitment
 
   let res = eth_kzg_blob_to_kzg_commitment(
@@ -102,7 +115,7 @@ proc blobToKZGCommitment*(ctx: KZGCtx, blob : Blob): Result[KZGCommitment, strin
   )
   verify_result(res, ret)
 
-proc computeCellsAndProofs*(ctx: KZGCtx, blob : Blob): Result[CellsAndProofs, string] {.gcsafe.} =
+proc computeCellsAndProofs*(ctx: KZGCtx, blob : Blob): Result[CellsAndProofs, KZGError] {.gcsafe.} =
   var ret: CellsAndProofs
 
   let outCellsPtr = toPtrPtr(ret.cells)
@@ -113,7 +126,10 @@ proc computeCellsAndProofs*(ctx: KZGCtx, blob : Blob): Result[CellsAndProofs, st
 
     blob.bytes.getPtr,
 
+    uint64(len(ret.cells)),
     outCellsPtr,
+
+    uint64(len(ret.proofs)),
     outProofsPtr
   )
   verify_result(res, ret)
@@ -121,7 +137,7 @@ proc computeCellsAndProofs*(ctx: KZGCtx, blob : Blob): Result[CellsAndProofs, st
 proc verifyCellKZGProofBatch*(ctx: KZGCtx, commitments: openArray[Bytes48],
                    cellIndices: openArray[uint64],
                    cells: openArray[Cell],
-                   proofs: openArray[Bytes48]): Result[bool, string] {.gcsafe.} =
+                   proofs: openArray[Bytes48]): Result[bool, KZGError] {.gcsafe.} =
   var valid: bool
 
   let cellsPtr = toPtrPtr(cells)
@@ -149,7 +165,7 @@ proc verifyCellKZGProofBatch*(ctx: KZGCtx, commitments: openArray[Bytes48],
 
 proc recoverCellsAndProofs*(ctx: KZGCtx,
                    cellIds: openArray[uint64],
-                   cells: openArray[Cell]): Result[CellsAndProofs, string] {.gcsafe.} =
+                   cells: openArray[Cell]): Result[CellsAndProofs, KZGError] {.gcsafe.} =
 
   var ret: CellsAndProofs
 
@@ -166,7 +182,10 @@ proc recoverCellsAndProofs*(ctx: KZGCtx,
     uint64(len(cellIds)),
     cellIds.safeGetPtr,
 
+    uint64(len(ret.cells)),
     outCellsPtr,
+
+    uint64(len(ret.proofs)),
     outProofsPtr,
   )
 
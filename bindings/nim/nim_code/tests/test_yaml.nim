@@ -78,7 +78,7 @@ suite "yaml tests":
   # and we only want to run this once
   #
   # This should also remove order dependency between tests; ie if we ran setup in a test
-  ctx = newKZGCtx()
+  ctx = newKZGCtx().expect("the context is created")
 
   runTests(BLOB_TO_KZG_COMMITMENT_TESTS):
     let
@@ -116,4 +116,19 @@ suite "yaml tests":
       cells = Cell.fromHexList(n["input"]["cells"])
       proofs = KZGProof.fromHexList(n["input"]["proofs"])
       res = ctx.verifyCellKZGProofBatch(commitments, cellIndices, cells, proofs)
-    checkBool(res)
\ No newline at end of file
+    checkBool(res)
+
+suite "errors":
+  let ctx = newKZGCtx().expect("the context is created")
+
+  test "errors have the result code of the failure":
+    var cells: seq[Cell]
+    cells.setLen(MAX_NUM_COLUMNS div 2 - 1)
+    var cellIndices: seq[uint64]
+    for i in 0..<cells.len:
+      cellIndices.add(uint64(i))
+
+    let res = ctx.recoverCellsAndProofs(cellIndices, cells)
+    check res.isErr
+    check res.error.code == CResultCode.BadLength
+    check res.error.msg.len > 0
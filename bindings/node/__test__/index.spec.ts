@@ -1,5 +1,8 @@
 import {
+  BYTES_PER_BLOB,
+  BYTES_PER_CELL,
   DasContextJs,
+  MAX_NUM_COLUMNS,
 } from "../index.js";
 
 import { readFileSync } from "fs";
@@ -179,3 +182,80 @@ describe("Spec tests", () => {
     });
   });
 });
+
+describe("Byte arrays", () => {
+  const ctx = new DasContextJs();
+
+  it("should accept Buffers and views into a larger buffer", () => {
+    const tests = globSync(BLOB_TO_KZG_COMMITMENT_TESTS);
+    const test: BlobToKzgCommitmentTest = yaml.load(
+      readFileSync(tests.find((testFile) => testFile.includes("_valid_")) as string, "ascii"),
+    );
+    const blob = bytesFromHex(test.input.blob);
+    const expected = ctx.blobToKzgCommitment(blob);
+
+    assertBytesEqual(ctx.blobToKzgCommitment(Buffer.from(blob)), expected);
+
+    const padded = new Uint8Array(blob.length + 7);
+    padded.set(blob, 7);
+    assertBytesEqual(ctx.blobToKzgCommitment(padded.subarray(7)), expected);
+  });
+
+  it("should only accept byte arrays", () => {
+    // @ts-expect-error The type definitions only allow byte arrays
+    expect(() => ctx.blobToKzgCommitment("0x00")).toThrow();
+    // @ts-expect-error The type definitions only allow bigint cell indices
+    expect(() => ctx.recoverCellsAndKzgProofs([0], [])).toThrow();
+  });
+
+  it("should return cells that are not shared with each other", () => {
+    const blob = new Uint8Array(BYTES_PER_BLOB);
+    const cells = ctx.computeCells(blob);
+    expect(cells.length).toBe(MAX_NUM_COLUMNS);
+
+    cells[0][0] = 1;
+    expect(cells[1][0]).toBe(0);
+    expect(cells[0].length).toBe(BYTES_PER_CELL);
+  });
+});
+
+describe("Async methods", () => {
+  const ctx = new DasContextJs();
+
+  it("should match the results of the synchronous methods", async () => {
+    const tests = globSync(COMPUTE_CELLS_AND_KZG_PROOFS_TESTS);
+    const test: ComputeCellsAndKzgProofsTest = yaml.load(
+      readFileSync(tests.find((testFile) => testFile.includes("_valid_")) as string, "ascii"),
+    );
+    const blob = bytesFromHex(test.input.blob);
+
+    const commitment = await ctx.asyncBlobToKzgCommitment(blob);
+    assertBytesEqual(commitment, ctx.blobToKzgCommitment(blob));
+
+    const { cells, proofs } = await ctx.asyncComputeCellsAndKzgProofs(blob);
+    const expected = ctx.computeCellsAndKzgProofs(blob);
+    for (let i = 0; i < cells.length; i++) {
+      assertBytesEqual(cells[i], expected.cells[i]);
+      assertBytesEqual(proofs[i], expected.proofs[i]);
+    }
+
+    const cellIndices = [0n, 1n];
+    const valid = await ctx.asyncVerifyCellKzgProofBatch(
+      [commitment, commitment],
+      cellIndices,
+      cells.slice(0, 2),
+      proofs.slice(0, 2),
+    );
+    expect(valid).toBe(true);
+
+    const halfIndices = Array.from({ length: cells.length / 2 }, (_, i) => BigInt(i));
+    const recovered = await ctx.asyncRecoverCellsAndKzgProofs(halfIndices, cells.slice(0, cells.length / 2));
+    for (let i = 0; i < cells.length; i++) {
+      assertBytesEqual(recovered.cells[i], cells[i]);
+    }
+  });
+
+  it("should reject the promise when an input is invalid", async () => {
+    await expect(ctx.asyncBlobToKzgCommitment(new Uint8Array(1))).rejects.toThrow();
+  });
+});
@@ -3,32 +3,63 @@
 
 /* auto-generated by NAPI-RS */
 
+/** The number of bytes in a KZG commitment. */
 export const BYTES_PER_COMMITMENT: number
+/** The number of bytes in a KZG proof. */
 export const BYTES_PER_PROOF: number
+/** The number of bytes needed to represent a field element. */
 export const BYTES_PER_FIELD_ELEMENT: number
+/** The number of bytes in a blob. */
 export const BYTES_PER_BLOB: number
+/** The number of cells in an extended blob, which is also the number of columns. */
 export const MAX_NUM_COLUMNS: number
+/** The number of bytes in a cell. */
 export const BYTES_PER_CELL: number
+/** The options used to create a context with `DasContextJs.create`. */
 export interface DasContextOptions {
+  /** Whether to use precomputed tables, which speed up proof creation at the cost of memory. */
   usePrecomp: boolean
+  /** The number of threads to use. Set to `0` to let the library pick a sensible default. */
   numThreads: number
 }
+/** All of the cells in an extended blob, along with a KZG proof for each of them. */
 export class CellsAndProofs {
   cells: Array<Uint8Array>
   proofs: Array<Uint8Array>
 }
 export type DASContextJs = DasContextJs
+/**
+ * A context that is used to create and verify the KZG proofs of cells.
+ *
+ * The context is expensive to create, so it should be created once and reused.
+ */
 export class DasContextJs {
+  /** Creates a context that uses precomputed tables and a single thread. */
   constructor()
+  /** Creates a context using the given options. */
   static create(options: DasContextOptions): DasContextJs
+  /** Computes the KZG commitment for the blob. */
   blobToKzgCommitment(blob: Uint8Array): Uint8Array
+  /** Computes the KZG commitment for the blob on the libuv thread pool. */
   asyncBlobToKzgCommitment(blob: Uint8Array): Promise<Uint8Array>
+  /** Computes the cells and the KZG proofs for the blob. */
   computeCellsAndKzgProofs(blob: Uint8Array): CellsAndProofs
+  /** Computes the cells and the KZG proofs for the blob on the libuv thread pool. */
   asyncComputeCellsAndKzgProofs(blob: Uint8Array): Promise<CellsAndProofs>
+  /** Computes the cells for the blob. */
   computeCells(blob: Uint8Array): Array<Uint8Array>
+  /** Computes the cells for the blob on the libuv thread pool. */
   asyncComputeCells(blob: Uint8Array): Promise<Array<Uint8Array>>
+  /** Recovers all of the cells and computes their KZG proofs, given at least half of the cells. */
   recoverCellsAndKzgProofs(cellIndices: Array<bigint>, cells: Array<Uint8Array>): CellsAndProofs
+  /** Recovers the cells and computes the KZG proofs on the libuv thread pool. */
   asyncRecoverCellsAndKzgProofs(cellIndices: Array<bigint>, cells: Array<Uint8Array>): Promise<CellsAndProofs>
+  /**
+   * Verifies a batch of cell proofs.
+   *
+   * Returns false if any of the proofs is invalid, and throws if an input is malformed.
+   */
   verifyCellKzgProofBatch(commitments: Array<Uint8Array>, cellIndices: Array<bigint>, cells: Array<Uint8Array>, proofs: Array<Uint8Array>): boolean
+  /** Verifies the batch of cell proofs on the libuv thread pool. */
   asyncVerifyCellKzgProofBatch(commitments: Array<Uint8Array>, cellIndices: Array<bigint>, cells: Array<Uint8Array>, proofs: Array<Uint8Array>): Promise<boolean>
 }
@@ -1,35 +1,61 @@
 use std::sync::Arc;
 
 use napi::{
-  bindgen_prelude::{BigInt, Error, Uint8Array},
+  bindgen_prelude::{AsyncTask, BigInt, Error, Uint8Array},
   Result,
 };
 use napi_derive::napi;
 
 use rust_eth_kzg::{
   constants::{self, RECOMMENDED_PRECOMP_WIDTH},
-  DASContext, ThreadCount, TrustedSetup, UsePrecomp,
+  verification_result_to_bool, Cell, CellIndex, CellsAndKZGProofs, DASContext, RecoveryError,
+  ThreadCount, TrustedSetup, UsePrecomp, VerifierError,
 };
 
+mod tasks;
+use tasks::{
+  BlobToKzgCommitmentTask, ComputeCellsAndKzgProofsTask, ComputeCellsTask,
+  RecoverCellsAndKzgProofsTask, VerifyCellKzgProofBatchTask,
+};
+
+/// The number of bytes in a KZG commitment.
 #[napi]
 pub const BYTES_PER_COMMITMENT: u32 = constants::BYTES_PER_COMMITMENT as u32;
+/// The number of bytes in a KZG proof.
 #[napi]
-pub const BYTES_PER_PROOF: u32 = constants::BYTES_PER_COMMITMENT as u32;
+pub const BYTES_PER_PROOF: u32 = constants::BYTES_PER_PROOF as u32;
+/// The number of bytes needed to represent a field element.
 #[napi]
 pub const BYTES_PER_FIELD_ELEMENT: u32 = constants::BYTES_PER_FIELD_ELEMENT as u32;
+/// The number of bytes in a blob.
 #[napi]
 pub const BYTES_PER_BLOB: u32 = constants::BYTES_PER_BLOB as u32;
+/// The number of cells in an extended blob, which is also the number of columns.
 #[napi]
 pub const MAX_NUM_COLUMNS: u32 = constants::CELLS_PER_EXT_BLOB as u32;
+/// The number of bytes in a cell.
 #[napi]
 pub const BYTES_PER_CELL: u32 = constants::BYTES_PER_CELL as u32;
 
+/// All of the cells in an extended blob, along with a KZG proof for each of them.
 #[napi]
 pub struct CellsAndProofs {
   pub cells: Vec<Uint8Array>,
   pub proofs: Vec<Uint8Array>,
 }
 
+impl From<CellsAndKZGProofs> for CellsAndProofs {
+  fn from((cells, proofs): CellsAndKZGProofs) -> Self {
+    CellsAndProofs {
+      cells: cells.into_iter().map(cell_to_uint8array).collect(),
+      proofs: proofs.into_iter().map(Uint8Array::from).collect(),
+    }
+  }
+}
+
+/// A context that is used to create and verify the KZG proofs of cells.
+///
+/// The context is expensive to create, so it should be created once and reused.
 #[napi]
 pub struct DASContextJs {
   inner: Arc<DASContext>,
@@ -41,9 +67,12 @@ impl Default for DASContextJs {
   }
 }
 
+/// The options used to create a context with `DasContextJs.create`.
 #[napi(object)]
 pub struct DASContextOptions {
+  /// Whether to use precomputed tables, which speed up proof creation at the cost of memory.
   pub use_precomp: bool,
+  /// The number of threads to use. Set to `0` to let the library pick a sensible default.
   pub num_threads: u32,
 }
 
@@ -58,11 +87,13 @@ impl Default for DASContextOptions {
 
 #[napi]
 impl DASContextJs {
+  /// Creates a context that uses precomputed tables and a single thread.
   #[napi(constructor)]
   pub fn new() -> Self {
     Self::create(DASContextOptions::default())
   }
 
+  /// Creates a context using the given options.
   #[napi(factory)]
   pub fn create(options: DASContextOptions) -> Self {
     let use_precomp = options.use_precomp;
@@ -85,125 +116,85 @@ impl DASContextJs {
     }
   }
 
+  /// Computes the KZG commitment for the blob.
   #[napi]
   pub fn blob_to_kzg_commitment(&self, blob: Uint8Array) -> Result<Uint8Array> {
-    let blob = blob.as_ref();
-    let ctx = &self.inner;
-    let blob = slice_to_array_ref(blob, "blob")?;
-
-    let commitment = ctx.blob_to_kzg_commitment(blob).map_err(|err| {
-      Error::from_reason(format!(
-        "failed to compute blob_to_kzg_commitment: {:?}",
-        err
-      ))
-    })?;
-    Ok(Uint8Array::from(&commitment))
+    blob_to_kzg_commitment(&self.inner, &blob)
   }
 
+  /// Computes the KZG commitment for the blob on the libuv thread pool.
   #[napi]
-  pub async fn async_blob_to_kzg_commitment(&self, blob: Uint8Array) -> Result<Uint8Array> {
-    self.blob_to_kzg_commitment(blob)
+  pub fn async_blob_to_kzg_commitment(
+    &self,
+    blob: Uint8Array,
+  ) -> AsyncTask<BlobToKzgCommitmentTask> {
+    AsyncTask::new(BlobToKzgCommitmentTask {
+      ctx: self.inner.clone(),
+      blob,
+    })
   }
 
+  /// Computes the cells and the KZG proofs for the blob.
   #[napi]
   pub fn compute_cells_and_kzg_proofs(&self, blob: Uint8Array) -> Result<CellsAndProofs> {
-    let blob = blob.as_ref();
-    let ctx = &self.inner;
-
-    let blob = slice_to_array_ref(blob, "blob")?;
-
-    let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(blob).map_err(|err| {
-      Error::from_reason(format!(
-        "failed to compute compute_cells_and_kzg_proofs: {:?}",
-        err
-      ))
-    })?;
-
-    let cells_uint8array = cells
-      .into_iter()
-      .map(|cell| Uint8Array::from(cell.to_vec()))
-      .collect::<Vec<Uint8Array>>();
-    let proofs_uint8array = proofs
-      .into_iter()
-      .map(Uint8Array::from)
-      .collect::<Vec<Uint8Array>>();
-
-    Ok(CellsAndProofs {
-      cells: cells_uint8array,
-      proofs: proofs_uint8array,
-    })
+    compute_cells_and_kzg_proofs(&self.inner, &blob)
   }
 
+  /// Computes the cells and the KZG proofs for the blob on the libuv thread pool.
   #[napi]
-  pub async fn async_compute_cells_and_kzg_proofs(
+  pub fn async_compute_cells_and_kzg_proofs(
     &self,
     blob: Uint8Array,
-  ) -> Result<CellsAndProofs> {
-    self.compute_cells_and_kzg_proofs(blob)
+  ) -> AsyncTask<ComputeCellsAndKzgProofsTask> {
+    AsyncTask::new(ComputeCellsAndKzgProofsTask {
+      ctx: self.inner.clone(),
+      blob,
+    })
   }
 
+  /// Computes the cells for the blob.
   #[napi]
   pub fn compute_cells(&self, blob: Uint8Array) -> Result<Vec<Uint8Array>> {
-    self
-      .compute_cells_and_kzg_proofs(blob)
-      .map(|cells_and_proofs| cells_and_proofs.cells)
+    compute_cells(&self.inner, &blob)
   }
 
+  /// Computes the cells for the blob on the libuv thread pool.
   #[napi]
-  pub async fn async_compute_cells(&self, blob: Uint8Array) -> Result<Vec<Uint8Array>> {
-    self.compute_cells(blob)
+  pub fn async_compute_cells(&self, blob: Uint8Array) -> AsyncTask<ComputeCellsTask> {
+    AsyncTask::new(ComputeCellsTask {
+      ctx: self.inner.clone(),
+      blob,
+    })
   }
 
-  #[allow(deprecated)]
+  /// Recovers all of the cells and computes their KZG proofs, given at least half of the cells.
   #[napi]
   pub fn recover_cells_and_kzg_proofs(
     &self,
     cell_indices: Vec<BigInt>,
     cells: Vec<Uint8Array>,
   ) -> Result<CellsAndProofs> {
-    let cell_indices: Vec<_> = cell_indices.into_iter().map(bigint_to_u64).collect();
-    let cells: Vec<_> = cells.iter().map(|cell| cell.as_ref()).collect();
-
-    let ctx = &self.inner;
-
-    let cells: Vec<_> = cells
-      .iter()
-      .map(|cell| slice_to_array_ref(cell, "cell"))
-      .collect::<Result<_, _>>()?;
-
-    let (cells, proofs) = ctx
-      .recover_cells_and_kzg_proofs(cell_indices, cells)
-      .map_err(|err| {
-        Error::from_reason(format!(
-          "failed to compute recover_cells_and_kzg_proofs: {:?}",
-          err
-        ))
-      })?;
-
-    let cells_uint8array = cells
-      .into_iter()
-      .map(|cell| Uint8Array::from(cell.to_vec()))
-      .collect::<Vec<Uint8Array>>();
-    let proofs_uint8array = proofs
-      .into_iter()
-      .map(Uint8Array::from)
-      .collect::<Vec<Uint8Array>>();
-
-    Ok(CellsAndProofs {
-      cells: cells_uint8array,
-      proofs: proofs_uint8array,
-    })
+    let cell_indices = cell_indices.into_iter().map(bigint_to_u64).collect();
+    recover_cells_and_kzg_proofs(&self.inner, cell_indices, &cells)
   }
 
+  /// Recovers the cells and computes the KZG proofs on the libuv thread pool.
   #[napi]
-  pub async fn async_recover_cells_and_kzg_proofs(
+  pub fn async_recover_cells_and_kzg_proofs(
     &self,
     cell_indices: Vec<BigInt>,
     cells: Vec<Uint8Array>,
-  ) -> Result<CellsAndProofs> {
-    self.recover_cells_and_kzg_proofs(cell_indices, cells)
+  ) -> AsyncTask<RecoverCellsAndKzgProofsTask> {
+    AsyncTask::new(RecoverCellsAndKzgProofsTask {
+      ctx: self.inner.clone(),
+      cell_indices: cell_indices.into_iter().map(bigint_to_u64).collect(),
+      cells,
+    })
   }
 
+  /// Verifies a batch of cell proofs.
+  ///
+  /// Returns false if any of the proofs is invalid, and throws if an input is malformed.
   #[napi]
   pub fn verify_cell_kzg_proof_batch(
     &self,
@@ -212,46 +203,129 @@ impl DASContextJs {
     cells: Vec<Uint8Array>,
     proofs: Vec<Uint8Array>,
   ) -> Result<bool> {
-    let cell_indices: Vec<_> = cell_indices.into_iter().map(bigint_to_u64).collect();
-
-    let commitments: Vec<_> = commitments
-      .iter()
-      .map(|commitment| slice_to_array_ref(commitment, "commitment"))
-      .collect::<Result<_, _>>()?;
-    let cells: Vec<_> = cells
-      .iter()
-      .map(|cell| slice_to_array_ref(cell, "cell"))
-      .collect::<Result<_, _>>()?;
-    let proofs: Vec<_> = proofs
-      .iter()
-      .map(|proof| slice_to_array_ref(proof, "proof"))
-      .collect::<Result<_, _>>()?;
-
-    let ctx = &self.inner;
-
-    let valid = ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs);
-    match valid {
-      Ok(_) => Ok(true),
-      Err(x) if x.invalid_proof() => Ok(false),
-      Err(err) => Err(Error::from_reason(format!(
-        "failed to compute verify_cell_kzg_proof_batch: {:?}",
-        err
-      ))),
-    }
+    let cell_indices = cell_indices.into_iter().map(bigint_to_u64).collect();
+    verify_cell_kzg_proof_batch(&self.inner, &commitments, cell_indices, &cells, &proofs)
   }
 
+  /// Verifies the batch of cell proofs on the libuv thread pool.
   #[napi]
-  pub async fn async_verify_cell_kzg_proof_batch(
+  pub fn async_verify_cell_kzg_proof_batch(
     &self,
     commitments: Vec<Uint8Array>,
     cell_indices: Vec<BigInt>,
     cells: Vec<Uint8Array>,
     proofs: Vec<Uint8Array>,
-  ) -> Result<bool> {
-    self.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs)
+  ) -> AsyncTask<VerifyCellKzgProofBatchTask> {
+    AsyncTask::new(VerifyCellKzgProofBatchTask {
+      ctx: self.inner.clone(),
+      commitments,
+      cell_indices: cell_indices.into_iter().map(bigint_to_u64).collect(),
+      cells,
+      proofs,
+    })
   }
 }
 
+pub(crate) fn blob_to_kzg_commitment(ctx: &DASContext, blob: &[u8]) -> Result<Uint8Array> {
+  let blob = slice_to_array_ref(blob, "blob")?;
+
+  let commitment = ctx
+    .blob_to_kzg_commitment(blob)
+    .map_err(|err| library_error("blob_to_kzg_commitment", err))?;
+  Ok(Uint8Array::from(&commitment))
+}
+
+pub(crate) fn compute_cells_and_kzg_proofs(
+  ctx: &DASContext,
+  blob: &[u8],
+) -> Result<CellsAndProofs> {
+  let blob = slice_to_array_ref(blob, "blob")?;
+
+  let cells_and_proofs = ctx
+    .compute_cells_and_kzg_proofs(blob)
+    .map_err(|err| library_error("compute_cells_and_kzg_proofs", err))?;
+
+  Ok(CellsAndProofs::from(cells_and_proofs))
+}
+
+pub(crate) fn compute_cells(ctx: &DASContext, blob: &[u8]) -> Result<Vec<Uint8Array>> {
+  let blob = slice_to_array_ref(blob, "blob")?;
+
+  let cells = ctx
+    .compute_cells(blob)
+    .map_err(|err| library_error("compute_cells", err))?;
+
+  Ok(cells.into_iter().map(cell_to_uint8array).collect())
+}
+
+pub(crate) fn recover_cells_and_kzg_proofs(
+  ctx: &DASContext,
+  cell_indices: Vec<u64>,
+  cells: &[Uint8Array],
+) -> Result<CellsAndProofs> {
+  let cells: Vec<_> = cells
+    .iter()
+    .map(|cell| slice_to_array_ref(cell, "cell"))
+    .collect::<Result<_, _>>()?;
+  let cell_indices = CellIndex::try_from_u64s(&cell_indices).map_err(|err| {
+    library_error(
+      "recover_cells_and_kzg_proofs",
+      RecoveryError::from(err).into(),
+    )
+  })?;
+
+  let cells_and_proofs = ctx
+    .recover_cells_and_kzg_proofs(cell_indices, cells)
+    .map_err(|err| library_error("recover_cells_and_kzg_proofs", err))?;
+
+  Ok(CellsAndProofs::from(cells_and_proofs))
+}
+
+pub(crate) fn verify_cell_kzg_proof_batch(
+  ctx: &DASContext,
+  commitments: &[Uint8Array],
+  cell_indices: Vec<u64>,
+  cells: &[Uint8Array],
+  proofs: &[Uint8Array],
+) -> Result<bool> {
+  let commitments: Vec<_> = commitments
+    .iter()
+    .map(|commitment| slice_to_array_ref(commitment, "commitment"))
+    .collect::<Result<_, _>>()?;
+  let cells: Vec<_> = cells
+    .iter()
+    .map(|cell| slice_to_array_ref(cell, "cell"))
+    .collect::<Result<_, _>>()?;
+  let proofs: Vec<_> = proofs
+    .iter()
+    .map(|proof| slice_to_array_ref(proof, "proof"))
+    .collect::<Result<_, _>>()?;
+  let cell_indices = CellIndex::try_from_u64s(&cell_indices).map_err(|err| {
+    library_error(
+      "verify_cell_kzg_proof_batch",
+      VerifierError::from(err).into(),
+    )
+  })?;
+
+  let valid = ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs);
+  verification_result_to_bool(valid)
+    .map_err(|err| library_error("verify_cell_kzg_proof_batch", err))
+}
+
+/// Converts a cell into a `Uint8Array` without copying it.
+///
+/// The allocation of the cell is handed to javascript as an external array buffer,
+/// which is freed once the array has been garbage collected.
+fn cell_to_uint8array(cell: Cell) -> Uint8Array {
+  let cell: Box<[u8]> = cell;
+  Uint8Array::new(cell.into_vec())
+}
+
+/// Converts an error from the underlying library, tagged with its `ErrorKind`, into a javascript error
+fn library_error(func_name: &str, err: rust_eth_kzg::Error) -> Error {
+  Error::from_reason(format!("failed to compute {func_name} ({}): {err}", err.kind()))
+}
+
 // We use bigint because u64 cannot be used as an argument, see : https://napi.rs/docs/concepts/values.en#bigint
 fn bigint_to_u64(value: BigInt) -> u64 {
   let (signed, value_u128, _) = value.get_u128();
@@ -0,0 +1,118 @@
+//! Tasks that run the expensive methods of the context on the libuv thread pool.
+//!
+//! Each `async` method on the context returns one of these tasks, which napi turns into a
+//! `Promise`. The inputs are moved into the task, so that they can be read from the worker
+//! thread, and the result is converted into a javascript value back on the main thread.
+
+use std::sync::Arc;
+
+use napi::{bindgen_prelude::Uint8Array, Env, Result, Task};
+use napi_derive::napi;
+use rust_eth_kzg::DASContext;
+
+use crate::CellsAndProofs;
+
+pub struct BlobToKzgCommitmentTask {
+  pub(crate) ctx: Arc<DASContext>,
+  pub(crate) blob: Uint8Array,
+}
+
+#[napi]
+impl Task for BlobToKzgCommitmentTask {
+  type Output = Uint8Array;
+  type JsValue = Uint8Array;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    crate::blob_to_kzg_commitment(&self.ctx, &self.blob)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct ComputeCellsAndKzgProofsTask {
+  pub(crate) ctx: Arc<DASContext>,
+  pub(crate) blob: Uint8Array,
+}
+
+#[napi]
+impl Task for ComputeCellsAndKzgProofsTask {
+  type Output = CellsAndProofs;
+  type JsValue = CellsAndProofs;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    crate::compute_cells_and_kzg_proofs(&self.ctx, &self.blob)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct ComputeCellsTask {
+  pub(crate) ctx: Arc<DASContext>,
+  pub(crate) blob: Uint8Array,
+}
+
+#[napi]
+impl Task for ComputeCellsTask {
+  type Output = Vec<Uint8Array>;
+  type JsValue = Vec<Uint8Array>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    crate::compute_cells(&self.ctx, &self.blob)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct RecoverCellsAndKzgProofsTask {
+  pub(crate) ctx: Arc<DASContext>,
+  pub(crate) cell_indices: Vec<u64>,
+  pub(crate) cells: Vec<Uint8Array>,
+}
+
+#[napi]
+impl Task for RecoverCellsAndKzgProofsTask {
+  type Output = CellsAndProofs;
+  type JsValue = CellsAndProofs;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    crate::recover_cells_and_kzg_proofs(&self.ctx, self.cell_indices.clone(), &self.cells)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct VerifyCellKzgProofBatchTask {
+  pub(crate) ctx: Arc<DASContext>,
+  pub(crate) commitments: Vec<Uint8Array>,
+  pub(crate) cell_indices: Vec<u64>,
+  pub(crate) cells: Vec<Uint8Array>,
+  pub(crate) proofs: Vec<Uint8Array>,
+}
+
+#[napi]
+impl Task for VerifyCellKzgProofBatchTask {
+  type Output = bool;
+  type JsValue = bool;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    crate::verify_cell_kzg_proof_batch(
+      &self.ctx,
+      &self.commitments,
+      self.cell_indices.clone(),
+      &self.cells,
+      &self.proofs,
+    )
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
@@ -0,0 +1,216 @@
+use std::borrow::Borrow;
+
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+
+use rust_eth_kzg::{
+    constants::{self, RECOMMENDED_PRECOMP_WIDTH},
+    verification_result_to_bool, CellIndex, DASContext as Context, RecoveryError, ThreadCount,
+    TrustedSetup, UsePrecomp, VerifierError,
+};
+
+create_exception!(
+    eth_kzg,
+    KZGError,
+    PyException,
+    "Raised when the library fails to create or verify a proof, for example because a commitment is not a valid point."
+);
+
+/// All of the cells in an extended blob, along with a KZG proof for each of them.
+type CellsAndProofs<'py> = (Vec<Bound<'py, PyBytes>>, Vec<Bound<'py, PyBytes>>);
+
+/// A context that is used to create and verify the KZG proofs of cells.
+///
+/// The context is expensive to create, so it should be created once and reused.
+#[pyclass(frozen, module = "eth_kzg")]
+pub struct DASContext {
+    inner: Context,
+}
+
+#[pymethods]
+impl DASContext {
+    /// Creates a context that uses the embedded trusted setup.
+    ///
+    /// `use_precomp` uses precomputed tables, which speed up proof creation at the cost of memory.
+    /// `num_threads` is the number of threads to use, where `0` lets the library pick a sensible default.
+    #[new]
+    #[pyo3(signature = (use_precomp = true, num_threads = 1))]
+    fn new(use_precomp: bool, num_threads: usize) -> Self {
+        let precomp = if use_precomp {
+            UsePrecomp::Yes {
+                width: RECOMMENDED_PRECOMP_WIDTH,
+            }
+        } else {
+            UsePrecomp::No
+        };
+
+        Self {
+            inner: Context::with_threads(
+                &TrustedSetup::default(),
+                ThreadCount::Multi(num_threads),
+                precomp,
+            ),
+        }
+    }
+
+    /// Computes the KZG commitment for the blob.
+    fn blob_to_kzg_commitment<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let blob = slice_to_array_ref(blob, "blob")?;
+
+        let commitment = py
+            .allow_threads(|| self.inner.blob_to_kzg_commitment(blob))
+            .map_err(|err| library_error("blob_to_kzg_commitment", err))?;
+        Ok(PyBytes::new(py, &commitment))
+    }
+
+    /// Computes the cells and the KZG proofs for the blob.
+    ///
+    /// Returns a tuple of the cells and the proofs.
+    fn compute_cells_and_kzg_proofs<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+    ) -> PyResult<CellsAndProofs<'py>> {
+        let blob = slice_to_array_ref(blob, "blob")?;
+
+        let (cells, proofs) = py
+            .allow_threads(|| self.inner.compute_cells_and_kzg_proofs(blob))
+            .map_err(|err| library_error("compute_cells_and_kzg_proofs", err))?;
+        Ok((to_py_bytes(py, &cells), to_py_bytes(py, &proofs)))
+    }
+
+    /// Computes the cells for the blob.
+    fn compute_cells<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+    ) -> PyResult<Vec<Bound<'py, PyBytes>>> {
+        let blob = slice_to_array_ref(blob, "blob")?;
+
+        let cells = py
+            .allow_threads(|| self.inner.compute_cells(blob))
+            .map_err(|err| library_error("compute_cells", err))?;
+        Ok(to_py_bytes(py, &cells))
+    }
+
+    /// Recovers all of the cells and computes their KZG proofs, given at least half of the cells.
+    ///
+    /// Returns a tuple of the cells and the proofs.
+    fn recover_cells_and_kzg_proofs<'py>(
+        &self,
+        py: Python<'py>,
+        cell_indices: Vec<u64>,
+        cells: Vec<Bound<'py, PyBytes>>,
+    ) -> PyResult<CellsAndProofs<'py>> {
+        let cells: Vec<_> = cells
+            .iter()
+            .map(|cell| slice_to_array_ref(cell.as_bytes(), "cell"))
+            .collect::<Result<_, _>>()?;
+        let cell_indices = CellIndex::try_from_u64s(&cell_indices).map_err(|err| {
+            library_error(
+                "recover_cells_and_kzg_proofs",
+                RecoveryError::from(err).into(),
+            )
+        })?;
+
+        let (cells, proofs) = py
+            .allow_threads(|| self.inner.recover_cells_and_kzg_proofs(cell_indices, cells))
+            .map_err(|err| library_error("recover_cells_and_kzg_proofs", err))?;
+        Ok((to_py_bytes(py, &cells), to_py_bytes(py, &proofs)))
+    }
+
+    /// Verifies a batch of cell proofs.
+    ///
+    /// Returns False if any of the proofs is invalid, and raises if an input is malformed.
+    fn verify_cell_kzg_proof_batch<'py>(
+        &self,
+        py: Python<'py>,
+        commitments: Vec<Bound<'py, PyBytes>>,
+        cell_indices: Vec<u64>,
+        cells: Vec<Bound<'py, PyBytes>>,
+        proofs: Vec<Bound<'py, PyBytes>>,
+    ) -> PyResult<bool> {
+        let commitments: Vec<_> = commitments
+            .iter()
+            .map(|commitment| slice_to_array_ref(commitment.as_bytes(), "commitment"))
+            .collect::<Result<_, _>>()?;
+        let cells: Vec<_> = cells
+            .iter()
+            .map(|cell| slice_to_array_ref(cell.as_bytes(), "cell"))
+            .collect::<Result<_, _>>()?;
+        let proofs: Vec<_> = proofs
+            .iter()
+            .map(|proof| slice_to_array_ref(proof.as_bytes(), "proof"))
+            .collect::<Result<_, _>>()?;
+        let cell_indices = CellIndex::try_from_u64s(&cell_indices).map_err(|err| {
+            library_error(
+                "verify_cell_kzg_proof_batch",
+                VerifierError::from(err).into(),
+            )
+        })?;
+
+        let valid = py.allow_threads(|| {
+            self.inner
+                .verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs)
+        });
+        verification_result_to_bool(valid)
+            .map_err(|err| library_error("verify_cell_kzg_proof_batch", err))
+    }
+}
+
+#[pymodule]
+fn eth_kzg(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<DASContext>()?;
+    m.add("KZGError", m.py().get_type::<KZGError>())?;
+
+    m.add("BYTES_PER_COMMITMENT", constants::BYTES_PER_COMMITMENT)?;
+    m.add("BYTES_PER_PROOF", constants::BYTES_PER_PROOF)?;
+    m.add(
+        "BYTES_PER_FIELD_ELEMENT",
+        constants::BYTES_PER_FIELD_ELEMENT,
+    )?;
+    m.add("BYTES_PER_BLOB", constants::BYTES_PER_BLOB)?;
+    m.add("BYTES_PER_CELL", constants::BYTES_PER_CELL)?;
+    m.add("CELLS_PER_EXT_BLOB", constants::CELLS_PER_EXT_BLOB)?;
+    Ok(())
+}
+
+/// Copies each of the byte arrays into a python `bytes` object.
+fn to_py_bytes<'py, const N: usize>(
+    py: Python<'py>,
+    arrays: &[impl Borrow<[u8; N]>],
+) -> Vec<Bound<'py, PyBytes>> {
+    arrays
+        .iter()
+        .map(|array| PyBytes::new(py, array.borrow()))
+        .collect()
+}
+
+/// Converts an error from the underlying library, tagged with its `ErrorKind`, into a `KZGError`
+fn library_error(func_name: &str, err: rust_eth_kzg::Error) -> PyErr {
+    KZGError::new_err(format!("failed to compute {func_name} ({}): {err}", err.kind()))
+}
+
+/// Convert a slice into a reference to an array
+///
+/// This is needed as the API for rust library does
+/// not accept slices.
+fn slice_to_array_ref<'a, const N: usize>(
+    slice: &'a [u8],
+    name: &'static str,
+) -> PyResult<&'a [u8; N]> {
+    slice.try_into().map_err(|_| {
+        PyValueError::new_err(format!(
+            "{name} must have size {N}, found size {}",
+            slice.len()
+        ))
+    })
+}
@@ -0,0 +1,115 @@
+import glob
+import os
+
+import pytest
+import yaml
+
+from eth_kzg import BYTES_PER_BLOB, BYTES_PER_CELL, CELLS_PER_EXT_BLOB, DASContext, KZGError
+
+TEST_VECTORS = os.path.join(os.path.dirname(__file__), "..", "..", "..", "test_vectors")
+
+ctx = DASContext()
+
+
+def load_tests(name):
+    """Returns the test cases for the given function, as a list of (input, output) pairs."""
+    test_files = glob.glob(os.path.join(TEST_VECTORS, name, "*", "*", "data.yaml"))
+    assert len(test_files) > 0
+
+    tests = []
+    for test_file in test_files:
+        with open(test_file) as f:
+            test = yaml.safe_load(f)
+        tests.append((test["input"], test["output"]))
+    return tests
+
+
+def bytes_from_hex(hex_string):
+    if hex_string.startswith("0x"):
+        hex_string = hex_string[2:]
+    return bytes.fromhex(hex_string)
+
+
+def raises_for_malformed_input(func, *args):
+    """Calls `func`, returning None if it raised because an input was malformed."""
+    try:
+        return func(*args)
+    except (KZGError, ValueError):
+        return None
+
+
+def test_blob_to_kzg_commitment():
+    for test_input, output in load_tests("blob_to_kzg_commitment"):
+        blob = bytes_from_hex(test_input["blob"])
+
+        commitment = raises_for_malformed_input(ctx.blob_to_kzg_commitment, blob)
+        if output is None:
+            assert commitment is None
+        else:
+            assert commitment == bytes_from_hex(output)
+
+
+def test_compute_cells_and_kzg_proofs():
+    for test_input, output in load_tests("compute_cells_and_kzg_proofs"):
+        blob = bytes_from_hex(test_input["blob"])
+
+        result = raises_for_malformed_input(ctx.compute_cells_and_kzg_proofs, blob)
+        if output is None:
+            assert result is None
+            continue
+
+        cells, proofs = result
+        assert cells == [bytes_from_hex(cell) for cell in output[0]]
+        assert proofs == [bytes_from_hex(proof) for proof in output[1]]
+        assert ctx.compute_cells(blob) == cells
+
+
+def test_recover_cells_and_kzg_proofs():
+    for test_input, output in load_tests("recover_cells_and_kzg_proofs"):
+        cells = [bytes_from_hex(cell) for cell in test_input["cells"]]
+
+        result = raises_for_malformed_input(
+            ctx.recover_cells_and_kzg_proofs, test_input["cell_indices"], cells
+        )
+        if output is None:
+            assert result is None
+            continue
+
+        cells, proofs = result
+        assert cells == [bytes_from_hex(cell) for cell in output[0]]
+        assert proofs == [bytes_from_hex(proof) for proof in output[1]]
+
+
+def test_verify_cell_kzg_proof_batch():
+    for test_input, output in load_tests("verify_cell_kzg_proof_batch"):
+        commitments = [bytes_from_hex(commitment) for commitment in test_input["commitments"]]
+        cells = [bytes_from_hex(cell) for cell in test_input["cells"]]
+        proofs = [bytes_from_hex(proof) for proof in test_input["proofs"]]
+
+        valid = raises_for_malformed_input(
+            ctx.verify_cell_kzg_proof_batch,
+            commitments,
+            test_input["cell_indices"],
+            cells,
+            proofs,
+        )
+        assert valid == output
+
+
+def test_wrong_input_size_raises_value_error():
+    with pytest.raises(ValueError, match="blob must have size"):
+        ctx.blob_to_kzg_commitment(bytes(BYTES_PER_BLOB - 1))
+
+    with pytest.raises(ValueError, match="cell must have size"):
+        ctx.recover_cells_and_kzg_proofs([0], [bytes(BYTES_PER_CELL + 1)])
+
+
+def test_library_error_raises_kzg_error():
+    cells = [bytes(BYTES_PER_CELL)] * (CELLS_PER_EXT_BLOB // 2 - 1)
+    with pytest.raises(KZGError, match="recover_cells_and_kzg_proofs"):
+        ctx.recover_cells_and_kzg_proofs(list(range(len(cells))), cells)
+
+
+def test_kzg_error_is_an_exception():
+    assert issubclass(KZGError, Exception)
+    assert not issubclass(KZGError, ValueError)
@@ -0,0 +1,221 @@
+// The scaffolding that UniFFI generates from the interface definition trips these lints.
+#![allow(clippy::empty_line_after_doc_comments, clippy::missing_const_for_fn)]
+
+use rust_eth_kzg::{
+    constants::RECOMMENDED_PRECOMP_WIDTH, verification_result_to_bool, CellIndex,
+    CellsAndKZGProofs, DASContext, RecoveryError, ThreadCount, TrustedSetup, UsePrecomp,
+    VerifierError,
+};
+
+uniffi::include_scaffolding!("eth_kzg");
+
+/// The reason that a call into the library failed.
+///
+/// The error is flat in the generated bindings, so the message is the only detail
+/// that reaches the caller.
+#[derive(Debug, thiserror::Error)]
+pub enum KzgError {
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("{0}")]
+    Serialization(String),
+    #[error("{0}")]
+    Verifier(String),
+    #[error("{0}")]
+    Recovery(String),
+    #[error("{0}")]
+    Prover(String),
+    #[error("{0}")]
+    TrustedSetup(String),
+}
+
+impl From<rust_eth_kzg::Error> for KzgError {
+    fn from(err: rust_eth_kzg::Error) -> Self {
+        let message = err.to_string();
+        match err {
+            rust_eth_kzg::Error::Serialization(_) => Self::Serialization(message),
+            rust_eth_kzg::Error::Verifier(_) => Self::Verifier(message),
+            rust_eth_kzg::Error::Recovery(_) => Self::Recovery(message),
+            rust_eth_kzg::Error::Prover(_) => Self::Prover(message),
+            rust_eth_kzg::Error::TrustedSetup(_) => Self::TrustedSetup(message),
+        }
+    }
+}
+
+/// All of the cells in an extended blob, along with a KZG proof for each of them.
+#[derive(Debug)]
+pub struct CellsAndProofs {
+    pub cells: Vec<Vec<u8>>,
+    pub proofs: Vec<Vec<u8>>,
+}
+
+impl From<CellsAndKZGProofs> for CellsAndProofs {
+    fn from((cells, proofs): CellsAndKZGProofs) -> Self {
+        Self {
+            cells: cells.iter().map(|cell| cell.to_vec()).collect(),
+            proofs: proofs.iter().map(|proof| proof.to_vec()).collect(),
+        }
+    }
+}
+
+/// A context that is used to create and verify the KZG proofs of cells.
+pub struct DasContext {
+    inner: DASContext,
+}
+
+impl DasContext {
+    pub fn new(use_precomp: bool, num_threads: u32) -> Self {
+        let precomp = if use_precomp {
+            UsePrecomp::Yes {
+                width: RECOMMENDED_PRECOMP_WIDTH,
+            }
+        } else {
+            UsePrecomp::No
+        };
+
+        Self {
+            inner: DASContext::with_threads(
+                &TrustedSetup::default(),
+                ThreadCount::Multi(num_threads as usize),
+                precomp,
+            ),
+        }
+    }
+
+    pub fn blob_to_kzg_commitment(&self, blob: Vec<u8>) -> Result<Vec<u8>, KzgError> {
+        let blob = slice_to_array_ref(&blob, "blob")?;
+
+        let commitment = self.inner.blob_to_kzg_commitment(blob)?;
+        Ok(commitment.to_vec())
+    }
+
+    pub fn compute_cells_and_kzg_proofs(&self, blob: Vec<u8>) -> Result<CellsAndProofs, KzgError> {
+        let blob = slice_to_array_ref(&blob, "blob")?;
+
+        let cells_and_proofs = self.inner.compute_cells_and_kzg_proofs(blob)?;
+        Ok(CellsAndProofs::from(cells_and_proofs))
+    }
+
+    pub fn compute_cells(&self, blob: Vec<u8>) -> Result<Vec<Vec<u8>>, KzgError> {
+        let blob = slice_to_array_ref(&blob, "blob")?;
+
+        let cells = self.inner.compute_cells(blob)?;
+        Ok(cells.iter().map(|cell| cell.to_vec()).collect())
+    }
+
+    pub fn recover_cells_and_kzg_proofs(
+        &self,
+        cell_indices: Vec<u64>,
+        cells: Vec<Vec<u8>>,
+    ) -> Result<CellsAndProofs, KzgError> {
+        let cells: Vec<_> = cells
+            .iter()
+            .map(|cell| slice_to_array_ref(cell, "cell"))
+            .collect::<Result<_, _>>()?;
+        let cell_indices = CellIndex::try_from_u64s(&cell_indices)
+            .map_err(|err| rust_eth_kzg::Error::from(RecoveryError::from(err)))?;
+
+        let cells_and_proofs = self
+            .inner
+            .recover_cells_and_kzg_proofs(cell_indices, cells)?;
+        Ok(CellsAndProofs::from(cells_and_proofs))
+    }
+
+    pub fn verify_cell_kzg_proof_batch(
+        &self,
+        commitments: Vec<Vec<u8>>,
+        cell_indices: Vec<u64>,
+        cells: Vec<Vec<u8>>,
+        proofs: Vec<Vec<u8>>,
+    ) -> Result<bool, KzgError> {
+        let commitments: Vec<_> = commitments
+            .iter()
+            .map(|commitment| slice_to_array_ref(commitment, "commitment"))
+            .collect::<Result<_, _>>()?;
+        let cells: Vec<_> = cells
+            .iter()
+            .map(|cell| slice_to_array_ref(cell, "cell"))
+            .collect::<Result<_, _>>()?;
+        let proofs: Vec<_> = proofs
+            .iter()
+            .map(|proof| slice_to_array_ref(proof, "proof"))
+            .collect::<Result<_, _>>()?;
+        let cell_indices = CellIndex::try_from_u64s(&cell_indices)
+            .map_err(|err| rust_eth_kzg::Error::from(VerifierError::from(err)))?;
+
+        let valid =
+            self.inner
+                .verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs);
+        Ok(verification_result_to_bool(valid)?)
+    }
+}
+
+/// Convert a slice into a reference to an array
+///
+/// This is needed as the API for rust library does
+/// not accept slices.
+fn slice_to_array_ref<'a, const N: usize>(
+    slice: &'a [u8],
+    name: &'static str,
+) -> Result<&'a [u8; N], KzgError> {
+    slice.try_into().map_err(|_| {
+        KzgError::InvalidInput(format!(
+            "{name} must have size {N}, found size {}",
+            slice.len()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_eth_kzg::constants::{BYTES_PER_BLOB, BYTES_PER_CELL, CELLS_PER_EXT_BLOB};
+
+    #[test]
+    fn compute_verify_and_recover_round_trip() {
+        let ctx = DasContext::new(false, 1);
+        let blob = vec![0u8; BYTES_PER_BLOB];
+
+        let commitment = ctx.blob_to_kzg_commitment(blob.clone()).unwrap();
+        let CellsAndProofs { cells, proofs } = ctx.compute_cells_and_kzg_proofs(blob).unwrap();
+
+        let cell_indices: Vec<u64> = (0..CELLS_PER_EXT_BLOB as u64).collect();
+        let valid = ctx
+            .verify_cell_kzg_proof_batch(
+                vec![commitment; CELLS_PER_EXT_BLOB],
+                cell_indices.clone(),
+                cells.clone(),
+                proofs.clone(),
+            )
+            .unwrap();
+        assert!(valid);
+
+        let half = CELLS_PER_EXT_BLOB / 2;
+        let recovered = ctx
+            .recover_cells_and_kzg_proofs(cell_indices[half..].to_vec(), cells[half..].to_vec())
+            .unwrap();
+        assert_eq!(recovered.cells, cells);
+        assert_eq!(recovered.proofs, proofs);
+    }
+
+    #[test]
+    fn errors_are_mapped_to_their_kind() {
+        let ctx = DasContext::new(false, 1);
+
+        let err = ctx.blob_to_kzg_commitment(vec![0u8; 1]).unwrap_err();
+        assert!(matches!(err, KzgError::InvalidInput(_)));
+
+        let err = ctx
+            .recover_cells_and_kzg_proofs(vec![0], vec![vec![0u8; BYTES_PER_CELL]])
+            .unwrap_err();
+        assert!(matches!(err, KzgError::Recovery(_)));
+
+        let err = ctx
+            .recover_cells_and_kzg_proofs(
+                vec![CELLS_PER_EXT_BLOB as u64],
+                vec![vec![0u8; BYTES_PER_CELL]],
+            )
+            .unwrap_err();
+        assert!(matches!(err, KzgError::Recovery(_)));
+    }
+}
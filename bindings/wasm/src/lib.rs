@@ -0,0 +1,216 @@
+#[cfg(all(
+    feature = "threads",
+    target_arch = "wasm32",
+    not(target_feature = "atomics")
+))]
+compile_error!(
+    "the `threads` feature requires building with RUSTFLAGS=\"-C target-feature=+atomics,+bulk-memory\""
+);
+
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+use rust_eth_kzg::{verification_result_to_bool, CellIndex, VerifierContext, VerifierError};
+
+/// Starts the Web Workers that the parallel parts of the library run on.
+///
+/// This must be awaited once, before any method is called. Javascript sees this as `initThreadPool`.
+#[cfg(all(feature = "threads", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+/// A context that is used to verify, and when the `prover` feature is enabled create,
+/// the KZG proofs of cells.
+///
+/// The context is expensive to create, so it should be created once and reused.
+#[wasm_bindgen]
+pub struct DASContext {
+    #[cfg(feature = "prover")]
+    inner: rust_eth_kzg::DASContext,
+    #[cfg(not(feature = "prover"))]
+    inner: VerifierContext,
+}
+
+#[wasm_bindgen]
+impl DASContext {
+    /// Creates a context that uses the embedded trusted setup.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            inner: Default::default(),
+        }
+    }
+
+    /// Verifies a batch of cell proofs.
+    ///
+    /// Returns false if any of the proofs is invalid, and throws if an input is malformed.
+    #[wasm_bindgen(js_name = verifyCellKzgProofBatch)]
+    pub fn verify_cell_kzg_proof_batch(
+        &self,
+        commitments: Vec<Uint8Array>,
+        cell_indices: Vec<u64>,
+        cells: Vec<Uint8Array>,
+        proofs: Vec<Uint8Array>,
+    ) -> Result<bool, JsError> {
+        let commitments = to_vecs(&commitments);
+        let cells = to_vecs(&cells);
+        let proofs = to_vecs(&proofs);
+
+        let commitments: Vec<_> = commitments
+            .iter()
+            .map(|commitment| slice_to_array_ref(commitment, "commitment"))
+            .collect::<Result<_, _>>()?;
+        let cells: Vec<_> = cells
+            .iter()
+            .map(|cell| slice_to_array_ref(cell, "cell"))
+            .collect::<Result<_, _>>()?;
+        let proofs: Vec<_> = proofs
+            .iter()
+            .map(|proof| slice_to_array_ref(proof, "proof"))
+            .collect::<Result<_, _>>()?;
+        let cell_indices = CellIndex::try_from_u64s(&cell_indices).map_err(|err| {
+            library_error(
+                "verify_cell_kzg_proof_batch",
+                VerifierError::from(err).into(),
+            )
+        })?;
+
+        let valid =
+            self.verifier()
+                .verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs);
+        verification_result_to_bool(valid)
+            .map_err(|err| library_error("verify_cell_kzg_proof_batch", err))
+    }
+
+    const fn verifier(&self) -> &VerifierContext {
+        #[cfg(feature = "prover")]
+        {
+            self.inner.verifier_ctx()
+        }
+        #[cfg(not(feature = "prover"))]
+        {
+            &self.inner
+        }
+    }
+}
+
+#[cfg(feature = "prover")]
+#[wasm_bindgen]
+impl DASContext {
+    /// Computes the KZG commitment for the blob.
+    #[wasm_bindgen(js_name = blobToKzgCommitment)]
+    pub fn blob_to_kzg_commitment(&self, blob: &[u8]) -> Result<Uint8Array, JsError> {
+        let blob = slice_to_array_ref(blob, "blob")?;
+
+        let commitment = self
+            .inner
+            .blob_to_kzg_commitment(blob)
+            .map_err(|err| library_error("blob_to_kzg_commitment", err))?;
+        Ok(Uint8Array::from(commitment.as_slice()))
+    }
+
+    /// Computes the cells and the KZG proofs for the blob.
+    #[wasm_bindgen(js_name = computeCellsAndKzgProofs)]
+    pub fn compute_cells_and_kzg_proofs(&self, blob: &[u8]) -> Result<CellsAndProofs, JsError> {
+        let blob = slice_to_array_ref(blob, "blob")?;
+
+        let cells_and_proofs = self
+            .inner
+            .compute_cells_and_kzg_proofs(blob)
+            .map_err(|err| library_error("compute_cells_and_kzg_proofs", err))?;
+        Ok(CellsAndProofs::from(cells_and_proofs))
+    }
+
+    /// Recovers all of the cells and computes their KZG proofs, given at least half of the cells.
+    #[wasm_bindgen(js_name = recoverCellsAndKzgProofs)]
+    pub fn recover_cells_and_kzg_proofs(
+        &self,
+        cell_indices: Vec<u64>,
+        cells: Vec<Uint8Array>,
+    ) -> Result<CellsAndProofs, JsError> {
+        let cells = to_vecs(&cells);
+        let cells: Vec<_> = cells
+            .iter()
+            .map(|cell| slice_to_array_ref(cell, "cell"))
+            .collect::<Result<_, _>>()?;
+        let cell_indices = CellIndex::try_from_u64s(&cell_indices).map_err(|err| {
+            library_error(
+                "recover_cells_and_kzg_proofs",
+                rust_eth_kzg::RecoveryError::from(err).into(),
+            )
+        })?;
+
+        let cells_and_proofs = self
+            .inner
+            .recover_cells_and_kzg_proofs(cell_indices, cells)
+            .map_err(|err| library_error("recover_cells_and_kzg_proofs", err))?;
+        Ok(CellsAndProofs::from(cells_and_proofs))
+    }
+}
+
+/// All of the cells in an extended blob, along with a KZG proof for each of them.
+#[cfg(feature = "prover")]
+#[wasm_bindgen]
+pub struct CellsAndProofs {
+    cells: Vec<Uint8Array>,
+    proofs: Vec<Uint8Array>,
+}
+
+#[cfg(feature = "prover")]
+#[wasm_bindgen]
+impl CellsAndProofs {
+    #[wasm_bindgen(getter)]
+    pub fn cells(&self) -> Vec<Uint8Array> {
+        self.cells.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn proofs(&self) -> Vec<Uint8Array> {
+        self.proofs.clone()
+    }
+}
+
+#[cfg(feature = "prover")]
+impl From<rust_eth_kzg::CellsAndKZGProofs> for CellsAndProofs {
+    fn from((cells, proofs): rust_eth_kzg::CellsAndKZGProofs) -> Self {
+        Self {
+            cells: cells
+                .iter()
+                .map(|cell| Uint8Array::from(cell.as_slice()))
+                .collect(),
+            proofs: proofs
+                .iter()
+                .map(|proof| Uint8Array::from(proof.as_slice()))
+                .collect(),
+        }
+    }
+}
+
+/// Copies the byte arrays from javascript into the memory of the module.
+fn to_vecs(arrays: &[Uint8Array]) -> Vec<Vec<u8>> {
+    arrays.iter().map(Uint8Array::to_vec).collect()
+}
+
+/// Converts an error from the underlying library, tagged with its `ErrorKind`, into a javascript error
+fn library_error(func_name: &str, err: rust_eth_kzg::Error) -> JsError {
+    JsError::new(&format!(
+        "failed to compute {func_name} ({}): {err}",
+        err.kind()
+    ))
+}
+
+/// Convert a slice into a reference to an array
+///
+/// This is needed as the API for rust library does
+/// not accept slices.
+fn slice_to_array_ref<'a, const N: usize>(
+    slice: &'a [u8],
+    name: &'static str,
+) -> Result<&'a [u8; N], JsError> {
+    slice.try_into().map_err(|_| {
+        JsError::new(&format!(
+            "{name} must have size {N}, found size {}",
+            slice.len()
+        ))
+    })
+}
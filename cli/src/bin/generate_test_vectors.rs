@@ -0,0 +1,471 @@
+//! Generates test vectors in the format of the consensus spec tests, using this library to compute
+//! the expected outputs.
+//!
+//! The vectors are written to `<OUT_DIR>/<function>/kzg-mainnet/<function>_case_<case>_<hash>/data.yaml`,
+//! where `<hash>` is taken from the input so that every case has a unique name. Each case states
+//! whether its input is valid, and the generator fails if the library disagrees, so a vector is
+//! never written with an output that contradicts its name.
+
+use clap::Parser;
+use eth_das_kzg_cli::{
+    input::{to_hex, BlobInput, CellsAndProofsHex, RecoverInput, VerifyInput},
+    Result,
+};
+use rust_eth_kzg::{
+    constants::{BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB},
+    CellsAndKZGProofs, DASContext, ThreadCount, TrustedSetup, UsePrecomp,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{fs, path::PathBuf, process::ExitCode};
+
+/// The modulus of the scalar field, which is the smallest value that is not a valid field element.
+const BLS_MODULUS: &str = "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+/// The largest valid field element.
+const BLS_MODULUS_MINUS_ONE: &str =
+    "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000";
+/// The compressed generator of G1.
+const G1_GENERATOR: &str = "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+/// A point that is on the curve, but not in the subgroup of G1.
+const POINT_NOT_IN_SUBGROUP: &str = "0x8123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+/// A point that is not on the curve.
+const POINT_NOT_ON_CURVE: &str = "0x8123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcde0";
+
+const FIELD_ELEMENTS_PER_BLOB: usize = BYTES_PER_BLOB / BYTES_PER_FIELD_ELEMENT;
+
+#[derive(Parser)]
+#[command(name = "generate-test-vectors", version, about)]
+struct Cli {
+    /// The directory that the test vectors are written to.
+    out_dir: PathBuf,
+}
+
+/// What the function is expected to return for the input of a case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    /// The function returns an output, which is `true` for a verification function.
+    Valid,
+    /// A proof does not verify, so the verification function returns `false`.
+    Incorrect,
+    /// The input is rejected, so the output is `null`.
+    Invalid,
+}
+
+#[derive(Serialize)]
+struct TestVector<'a, I, O> {
+    input: &'a I,
+    output: Option<O>,
+}
+
+struct Generator {
+    ctx: DASContext,
+    out_dir: PathBuf,
+    num_vectors: usize,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut generator = Generator {
+        ctx: DASContext::with_threads(
+            &TrustedSetup::default(),
+            ThreadCount::SensibleDefault,
+            UsePrecomp::No,
+        ),
+        out_dir: cli.out_dir,
+        num_vectors: 0,
+    };
+    match generator.generate() {
+        Ok(()) => {
+            println!(
+                "wrote {} test vectors to {}",
+                generator.num_vectors,
+                generator.out_dir.display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl Generator {
+    fn generate(&mut self) -> Result<()> {
+        self.blob_cases()?;
+        self.verify_cell_kzg_proof_batch_cases()?;
+        self.recover_cells_and_kzg_proofs_cases()
+    }
+
+    /// The cases of the functions that take a blob.
+    fn blob_cases(&mut self) -> Result<()> {
+        let mut one_at_the_end = vec![0u8; BYTES_PER_BLOB];
+        one_at_the_end[BYTES_PER_BLOB - 1] = 1;
+        let valid_blobs = [
+            to_hex(vec![0u8; BYTES_PER_BLOB]),
+            random_blob(0),
+            random_blob(1),
+            random_blob(2),
+            to_hex(one_at_the_end),
+            repeat_field_element(BLS_MODULUS_MINUS_ONE),
+        ];
+        let invalid_blobs = [
+            to_hex(vec![0xff; BYTES_PER_BLOB]),
+            repeat_field_element(BLS_MODULUS),
+            with_field_element(&random_blob(3), 0, BLS_MODULUS),
+            with_field_element(&random_blob(4), FIELD_ELEMENTS_PER_BLOB - 1, BLS_MODULUS),
+        ];
+
+        let blobs = valid_blobs
+            .into_iter()
+            .map(|blob| (Expect::Valid, blob))
+            .chain(
+                invalid_blobs
+                    .into_iter()
+                    .map(|blob| (Expect::Invalid, blob)),
+            );
+        for (expect, blob) in blobs {
+            let input = BlobInput { blob };
+            // The test vectors of `blob_to_kzg_commitment` name the valid case differently
+            let (case, commitment_case) = if expect == Expect::Valid {
+                ("valid", "valid_blob")
+            } else {
+                ("invalid_blob", "invalid_blob")
+            };
+
+            let output = eth_das_kzg_cli::blob_to_kzg_commitment(&self.ctx, &input);
+            let output = check(expect, output.map(to_hex))?;
+            self.write("blob_to_kzg_commitment", commitment_case, &input, output)?;
+
+            let output = eth_das_kzg_cli::compute_cells(&self.ctx, &input);
+            let output = check(
+                expect,
+                output.map(|cells| cells.iter().map(|cell| to_hex(cell.as_slice())).collect()),
+            )?;
+            self.write::<_, Vec<String>>("compute_cells", case, &input, output)?;
+
+            let output = eth_das_kzg_cli::compute_cells_and_kzg_proofs(&self.ctx, &input);
+            let output = check(
+                expect,
+                output.map(|cells_and_proofs| cells_and_proofs_to_hex(&cells_and_proofs)),
+            )?;
+            self.write("compute_cells_and_kzg_proofs", case, &input, output)?;
+        }
+        Ok(())
+    }
+
+    fn verify_cell_kzg_proof_batch_cases(&mut self) -> Result<()> {
+        let (commitment, (cells, proofs)) = self.blob_with_commitment(5)?;
+        let (other_commitment, (other_cells, other_proofs)) = self.blob_with_commitment(6)?;
+
+        let batch = |indices: &[usize]| VerifyInput {
+            commitments: vec![commitment.clone(); indices.len()],
+            cell_indices: indices.iter().map(|&i| i as u64).collect(),
+            cells: indices.iter().map(|&i| cells[i].clone()).collect(),
+            proofs: indices.iter().map(|&i| proofs[i].clone()).collect(),
+        };
+        let modified = |indices: &[usize], modify: &dyn Fn(&mut VerifyInput)| {
+            let mut input = batch(indices);
+            modify(&mut input);
+            input
+        };
+
+        let all_cells: Vec<usize> = (0..CELLS_PER_EXT_BLOB).collect();
+        let multiple_blobs = {
+            let mut input = batch(&[0, 7, 64]);
+            input.commitments.push(other_commitment.clone());
+            input.cell_indices.push(3);
+            input.cells.push(other_cells[3].clone());
+            input.proofs.push(other_proofs[3].clone());
+            input
+        };
+
+        let cases = [
+            ("valid", Expect::Valid, batch(&all_cells)),
+            ("valid_zero_cells", Expect::Valid, batch(&[])),
+            ("valid_multiple_blobs", Expect::Valid, multiple_blobs),
+            (
+                "valid_same_cell_multiple_times",
+                Expect::Valid,
+                batch(&[5, 5, 5]),
+            ),
+            (
+                "incorrect_cell",
+                Expect::Incorrect,
+                modified(&[0, 1], &|input| {
+                    input.cells[1] = with_field_element(&input.cells[1], 0, "0x01")
+                }),
+            ),
+            (
+                "incorrect_commitment",
+                Expect::Incorrect,
+                modified(&[0, 1], &|input| {
+                    input.commitments[1] = other_commitment.clone()
+                }),
+            ),
+            (
+                "incorrect_proof",
+                Expect::Incorrect,
+                modified(&[0, 1], &|input| input.proofs[1] = other_proofs[1].clone()),
+            ),
+            (
+                "invalid_cell",
+                Expect::Invalid,
+                modified(&[0, 1], &|input| {
+                    input.cells[1] = with_field_element(&input.cells[1], 0, BLS_MODULUS)
+                }),
+            ),
+            (
+                "invalid_cell_index",
+                Expect::Invalid,
+                modified(&[0, 1], &|input| {
+                    input.cell_indices[1] = CELLS_PER_EXT_BLOB as u64
+                }),
+            ),
+            (
+                "invalid_missing_commitment",
+                Expect::Invalid,
+                modified(&[0, 1], &|input| {
+                    input.commitments.pop();
+                }),
+            ),
+            (
+                "invalid_missing_cell_index",
+                Expect::Invalid,
+                modified(&[0, 1], &|input| {
+                    input.cell_indices.pop();
+                }),
+            ),
+            (
+                "invalid_missing_cell",
+                Expect::Invalid,
+                modified(&[0, 1], &|input| {
+                    input.cells.pop();
+                }),
+            ),
+            (
+                "invalid_missing_proof",
+                Expect::Invalid,
+                modified(&[0, 1], &|input| {
+                    input.proofs.pop();
+                }),
+            ),
+        ];
+        for (case, expect, input) in cases {
+            self.verify_case(case, expect, &input)?;
+        }
+
+        // The same invalid encodings are used for commitments and proofs
+        let too_short = G1_GENERATOR[..G1_GENERATOR.len() - 2].to_owned();
+        let too_long = format!("{G1_GENERATOR}00");
+        let invalid_points = [
+            POINT_NOT_ON_CURVE.to_owned(),
+            POINT_NOT_IN_SUBGROUP.to_owned(),
+            too_short,
+            too_long,
+        ];
+        for point in invalid_points {
+            let input = modified(&[0], &|input| input.commitments[0] = point.clone());
+            self.verify_case("invalid_commitment", Expect::Invalid, &input)?;
+            let input = modified(&[0], &|input| input.proofs[0] = point.clone());
+            self.verify_case("invalid_proof", Expect::Invalid, &input)?;
+        }
+        Ok(())
+    }
+
+    fn verify_case(&mut self, case: &str, expect: Expect, input: &VerifyInput) -> Result<()> {
+        let output = eth_das_kzg_cli::verify_cell_kzg_proof_batch(&self.ctx, input);
+        let actual = match &output {
+            Ok(true) => Expect::Valid,
+            Ok(false) => Expect::Incorrect,
+            Err(_) => Expect::Invalid,
+        };
+        if actual != expect {
+            return Err(format!(
+                "verify_cell_kzg_proof_batch {case}: expected {expect:?}, got {actual:?}"
+            )
+            .into());
+        }
+        self.write("verify_cell_kzg_proof_batch", case, input, output.ok())
+    }
+
+    fn recover_cells_and_kzg_proofs_cases(&mut self) -> Result<()> {
+        let (_, (cells, _)) = self.blob_with_commitment(7)?;
+        let half = CELLS_PER_EXT_BLOB / 2;
+
+        let subset = |indices: &[usize]| RecoverInput {
+            cell_indices: indices.iter().map(|&i| i as u64).collect(),
+            cells: indices.iter().map(|&i| cells[i].clone()).collect(),
+        };
+        let first_half: Vec<usize> = (0..half).collect();
+        let second_half: Vec<usize> = (half..CELLS_PER_EXT_BLOB).collect();
+        let every_other: Vec<usize> = (0..CELLS_PER_EXT_BLOB).step_by(2).collect();
+        let all_cells: Vec<usize> = (0..CELLS_PER_EXT_BLOB).collect();
+
+        let mut invalid_cell = subset(&first_half);
+        invalid_cell.cells[0] = with_field_element(&invalid_cell.cells[0], 0, BLS_MODULUS);
+        let mut invalid_cell_index = subset(&first_half);
+        invalid_cell_index.cell_indices[0] = CELLS_PER_EXT_BLOB as u64;
+        let mut duplicate_cell_index = subset(&first_half);
+        duplicate_cell_index.cell_indices[1] = duplicate_cell_index.cell_indices[0];
+        let mut more_cells_than_cell_indices = subset(&first_half);
+        more_cells_than_cell_indices.cell_indices.pop();
+        let mut more_cell_indices_than_cells = subset(&first_half);
+        more_cell_indices_than_cells.cells.pop();
+        let mut more_cells_than_cells_per_ext_blob = subset(&all_cells);
+        more_cells_than_cells_per_ext_blob.cell_indices.push(0);
+        more_cells_than_cells_per_ext_blob
+            .cells
+            .push(cells[0].clone());
+
+        let cases = [
+            ("valid_no_missing", Expect::Valid, subset(&all_cells)),
+            (
+                "valid_half_missing_first_half",
+                Expect::Valid,
+                subset(&second_half),
+            ),
+            (
+                "valid_half_missing_second_half",
+                Expect::Valid,
+                subset(&first_half),
+            ),
+            (
+                "valid_half_missing_every_other_cell",
+                Expect::Valid,
+                subset(&every_other),
+            ),
+            (
+                "invalid_all_cells_are_missing",
+                Expect::Invalid,
+                subset(&[]),
+            ),
+            (
+                "invalid_more_than_half_missing",
+                Expect::Invalid,
+                subset(&first_half[1..]),
+            ),
+            ("invalid_cell", Expect::Invalid, invalid_cell),
+            ("invalid_cell_index", Expect::Invalid, invalid_cell_index),
+            (
+                "invalid_duplicate_cell_index",
+                Expect::Invalid,
+                duplicate_cell_index,
+            ),
+            (
+                "invalid_more_cells_than_cell_indices",
+                Expect::Invalid,
+                more_cells_than_cell_indices,
+            ),
+            (
+                "invalid_more_cell_indices_than_cells",
+                Expect::Invalid,
+                more_cell_indices_than_cells,
+            ),
+            (
+                "invalid_more_cells_than_cells_per_ext_blob",
+                Expect::Invalid,
+                more_cells_than_cells_per_ext_blob,
+            ),
+        ];
+        for (case, expect, input) in cases {
+            let output = eth_das_kzg_cli::recover_cells_and_kzg_proofs(&self.ctx, &input);
+            let output = check(
+                expect,
+                output.map(|cells_and_proofs| cells_and_proofs_to_hex(&cells_and_proofs)),
+            )?;
+            self.write("recover_cells_and_kzg_proofs", case, &input, output)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the commitment, cells and proofs of a random blob, as hex strings.
+    fn blob_with_commitment(&self, seed: u64) -> Result<(String, CellsAndProofsHex)> {
+        let input = BlobInput {
+            blob: random_blob(seed),
+        };
+        let commitment = eth_das_kzg_cli::blob_to_kzg_commitment(&self.ctx, &input)?;
+        let cells_and_proofs = eth_das_kzg_cli::compute_cells_and_kzg_proofs(&self.ctx, &input)?;
+        Ok((
+            to_hex(commitment),
+            cells_and_proofs_to_hex(&cells_and_proofs),
+        ))
+    }
+
+    /// Writes a test vector, where an output of `None` is written as `null`.
+    fn write<I: Serialize, O: Serialize>(
+        &mut self,
+        function: &str,
+        case: &str,
+        input: &I,
+        output: Option<O>,
+    ) -> Result<()> {
+        let hash = Sha256::digest(serde_yaml::to_string(input)?);
+        let name = format!("{function}_case_{case}_{}", hex::encode(&hash[..8]));
+        let dir = self.out_dir.join(function).join("kzg-mainnet").join(name);
+
+        fs::create_dir_all(&dir)?;
+        let test_vector = TestVector { input, output };
+        fs::write(
+            dir.join("data.yaml"),
+            eth_das_kzg_cli::to_yaml(&test_vector)?,
+        )?;
+        self.num_vectors += 1;
+        Ok(())
+    }
+}
+
+/// Returns the output of a function, after checking that the library agrees with the case about
+/// whether the input is valid.
+fn check<T>(expect: Expect, output: Result<T>) -> Result<Option<T>> {
+    match (expect, output) {
+        (Expect::Valid, Ok(output)) => Ok(Some(output)),
+        (Expect::Invalid, Err(_)) => Ok(None),
+        (Expect::Valid, Err(err)) => Err(format!("expected a valid input, got: {err}").into()),
+        (_, _) => Err(format!("expected {expect:?}, but the input was accepted").into()),
+    }
+}
+
+fn cells_and_proofs_to_hex((cells, proofs): &CellsAndKZGProofs) -> CellsAndProofsHex {
+    (
+        cells.iter().map(|cell| to_hex(cell.as_slice())).collect(),
+        proofs.iter().map(to_hex).collect(),
+    )
+}
+
+/// Returns a blob of field elements that are derived from `seed`.
+fn random_blob(seed: u64) -> String {
+    let blob: Vec<u8> = (0..FIELD_ELEMENTS_PER_BLOB as u64)
+        .flat_map(|i| {
+            let mut field_element: [u8; BYTES_PER_FIELD_ELEMENT] =
+                Sha256::digest([seed.to_le_bytes(), i.to_le_bytes()].concat()).into();
+            // Clear the top bits, so that the field element is smaller than the modulus
+            field_element[0] &= 0x3f;
+            field_element
+        })
+        .collect();
+    to_hex(blob)
+}
+
+/// Returns a blob where every field element is `field_element`.
+fn repeat_field_element(field_element: &str) -> String {
+    format!(
+        "0x{}",
+        field_element
+            .trim_start_matches("0x")
+            .repeat(FIELD_ELEMENTS_PER_BLOB)
+    )
+}
+
+/// Replaces the field element at `index` of a blob or cell, given as a hex string.
+///
+/// `field_element` is padded with zeros on the left, so that it is encoded in big-endian.
+fn with_field_element(hex: &str, index: usize, field_element: &str) -> String {
+    let hex_len = 2 * BYTES_PER_FIELD_ELEMENT;
+    let field_element = format!("{:0>hex_len$}", field_element.trim_start_matches("0x"));
+    let start = 2 + index * hex_len;
+
+    let mut hex = hex.to_owned();
+    hex.replace_range(start..start + hex_len, &field_element);
+    hex
+}
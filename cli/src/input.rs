@@ -0,0 +1,134 @@
+//! Reading the inputs of the commands, which are either plain YAML documents or the test vectors
+//! from the consensus specs.
+
+use crate::Result;
+use rust_eth_kzg::constants::BYTES_PER_BLOB;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// The input of a command, along with the expected output if it was read from a test vector.
+pub struct Document<I, O> {
+    pub input: I,
+    /// The `output` of the test vector, where `Some(None)` means that an error is expected.
+    ///
+    /// This is `None` if the input was not a test vector.
+    pub output: Option<Option<O>>,
+}
+
+/// The cells and proofs in the output of a test vector, as hex strings.
+pub type CellsAndProofsHex = (Vec<String>, Vec<String>);
+
+/// The input of the functions that take a blob.
+#[derive(Serialize, Deserialize)]
+pub struct BlobInput {
+    pub blob: String,
+}
+
+/// The input of `verify_cell_kzg_proof_batch`.
+#[derive(Serialize, Deserialize)]
+pub struct VerifyInput {
+    pub commitments: Vec<String>,
+    pub cell_indices: Vec<u64>,
+    pub cells: Vec<String>,
+    pub proofs: Vec<String>,
+}
+
+/// The input of `recover_cells_and_kzg_proofs`.
+#[derive(Serialize, Deserialize)]
+pub struct RecoverInput {
+    pub cell_indices: Vec<u64>,
+    pub cells: Vec<String>,
+}
+
+/// Reads a YAML document, which is a test vector if it has an `input` field.
+pub fn read_yaml<I: DeserializeOwned, O: DeserializeOwned>(bytes: &[u8]) -> Result<Document<I, O>> {
+    let mut document: Value = serde_yaml::from_slice(bytes)?;
+
+    // The fields are read separately, so that an error names the field that could not be read
+    let Some(input) = document.get_mut("input").map(std::mem::take) else {
+        return Ok(Document {
+            input: serde_yaml::from_value(document)?,
+            output: None,
+        });
+    };
+    let output = document
+        .get_mut("output")
+        .map(std::mem::take)
+        .unwrap_or_default();
+
+    Ok(Document {
+        input: serde_yaml::from_value(input)?,
+        output: Some(serde_yaml::from_value(output)?),
+    })
+}
+
+/// Reads a blob that is stored as raw bytes, as a hex string, or in a test vector.
+///
+/// The blob is returned as a hex string, which is decoded by `parse_blob`. This is so that a blob
+/// with an invalid length in a test vector is reported as an error of the command.
+pub fn read_blob(bytes: &[u8]) -> Result<Document<BlobInput, CellsAndProofsHex>> {
+    if bytes.len() == BYTES_PER_BLOB {
+        return Ok(Document {
+            input: BlobInput {
+                blob: to_hex(bytes),
+            },
+            output: None,
+        });
+    }
+
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| format!("expected a blob of {BYTES_PER_BLOB} bytes, or a hex string"))?
+        .trim();
+    if text.starts_with("0x") && !text.contains(char::is_whitespace) {
+        return Ok(Document {
+            input: BlobInput {
+                blob: text.to_owned(),
+            },
+            output: None,
+        });
+    }
+
+    read_yaml(text.as_bytes())
+}
+
+/// Decodes a blob from a hex string.
+pub(crate) fn parse_blob(blob: &str) -> Result<Box<[u8; BYTES_PER_BLOB]>> {
+    parse_hex("blob", blob)
+}
+
+/// Decodes a list of hex strings, which must each decode to `N` bytes.
+///
+/// `kind` names the items in the list, for the error messages.
+pub(crate) fn parse_all<const N: usize>(kind: &str, items: &[String]) -> Result<Vec<Box<[u8; N]>>> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| parse_hex(&format!("{kind} {i}"), item))
+        .collect()
+}
+
+/// Decodes a hex string, which may be prefixed with `0x`, into `N` bytes.
+fn parse_hex<const N: usize>(name: &str, hex: &str) -> Result<Box<[u8; N]>> {
+    let bytes = hex::decode(hex.strip_prefix("0x").unwrap_or(hex))
+        .map_err(|err| format!("{name} is not a valid hex string: {err}"))?;
+    bytes
+        .into_boxed_slice()
+        .try_into()
+        .map_err(|bytes: Box<[u8]>| {
+            format!("{name} has {} bytes, expected {N}", bytes.len()).into()
+        })
+}
+
+/// Encodes bytes as a `0x` prefixed hex string, which is the encoding used in the test vectors.
+pub fn to_hex(bytes: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Returns true if both lists contain the same hex strings, ignoring their case.
+pub fn hex_eq(actual: &[String], expected: &[String]) -> bool {
+    actual.len() == expected.len()
+        && actual
+            .iter()
+            .zip(expected)
+            .all(|(actual, expected)| actual.eq_ignore_ascii_case(expected))
+}
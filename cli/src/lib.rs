@@ -0,0 +1,105 @@
+//! The functions behind the `eth-das-kzg` and `generate-test-vectors` binaries.
+//!
+//! The functions take their inputs as the hex strings that appear in the test vectors of the
+//! consensus specs, so an input with an invalid length is reported as an error of the function,
+//! just like an input that the library rejects.
+
+pub mod input;
+
+use input::{BlobInput, RecoverInput, VerifyInput};
+use rust_eth_kzg::{
+    constants::{BYTES_PER_CELL, BYTES_PER_COMMITMENT, CELLS_PER_EXT_BLOB},
+    verification_result_to_bool, Cell, CellIndex, CellsAndKZGProofs, DASContext, Error,
+    KZGCommitment, RecoveryError, VerifierError,
+};
+use serde::Serialize;
+use serde_yaml::Value;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+pub fn blob_to_kzg_commitment(ctx: &DASContext, input: &BlobInput) -> Result<KZGCommitment> {
+    let blob = input::parse_blob(&input.blob)?;
+    Ok(ctx.blob_to_kzg_commitment(&blob)?)
+}
+
+pub fn compute_cells(ctx: &DASContext, input: &BlobInput) -> Result<[Cell; CELLS_PER_EXT_BLOB]> {
+    let blob = input::parse_blob(&input.blob)?;
+    Ok(ctx.compute_cells(&blob)?)
+}
+
+pub fn compute_cells_and_kzg_proofs(
+    ctx: &DASContext,
+    input: &BlobInput,
+) -> Result<CellsAndKZGProofs> {
+    let blob = input::parse_blob(&input.blob)?;
+    Ok(ctx.compute_cells_and_kzg_proofs(&blob)?)
+}
+
+/// Returns false if a proof is invalid, and an error if the input is malformed.
+pub fn verify_cell_kzg_proof_batch(ctx: &DASContext, input: &VerifyInput) -> Result<bool> {
+    let commitments = input::parse_all::<BYTES_PER_COMMITMENT>("commitment", &input.commitments)?;
+    let cells = input::parse_all::<BYTES_PER_CELL>("cell", &input.cells)?;
+    let proofs = input::parse_all::<BYTES_PER_COMMITMENT>("proof", &input.proofs)?;
+    let cell_indices = CellIndex::try_from_u64s(&input.cell_indices)
+        .map_err(|err| Error::from(VerifierError::from(err)))?;
+
+    let result = ctx.verify_cell_kzg_proof_batch(
+        commitments.iter().map(|commitment| &**commitment).collect(),
+        cell_indices,
+        cells.iter().map(|cell| &**cell).collect(),
+        proofs.iter().map(|proof| &**proof).collect(),
+    );
+    Ok(verification_result_to_bool(result)?)
+}
+
+pub fn recover_cells_and_kzg_proofs(
+    ctx: &DASContext,
+    input: &RecoverInput,
+) -> Result<CellsAndKZGProofs> {
+    let cells = input::parse_all::<BYTES_PER_CELL>("cell", &input.cells)?;
+    let cell_indices = CellIndex::try_from_u64s(&input.cell_indices)
+        .map_err(|err| Error::from(RecoveryError::from(err)))?;
+    Ok(ctx.recover_cells_and_kzg_proofs(cell_indices, cells.iter().map(|cell| &**cell).collect())?)
+}
+
+/// Encodes a struct as YAML in the style of the test vectors, where each field is on its own line
+/// and every string is quoted.
+///
+/// `serde_yaml` does not quote hex strings, which YAML 1.1 parsers such as PyYAML read as integers.
+pub fn to_yaml<T: Serialize>(value: &T) -> Result<String> {
+    let Value::Mapping(fields) = serde_yaml::to_value(value)? else {
+        return Err("only a struct can be encoded as a YAML document".into());
+    };
+    Ok(fields
+        .iter()
+        .map(|(key, value)| format!("{}: {}\n", to_yaml_key(key), to_flow_yaml(value)))
+        .collect())
+}
+
+/// Encodes a value as YAML on a single line.
+fn to_flow_yaml(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_owned(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::String(value) => format!("'{}'", value.replace('\'', "''")),
+        Value::Sequence(items) => {
+            let items: Vec<_> = items.iter().map(to_flow_yaml).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Mapping(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(key, value)| format!("{}: {}", to_yaml_key(key), to_flow_yaml(value)))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+        Value::Tagged(tagged) => to_flow_yaml(&tagged.value),
+    }
+}
+
+/// Encodes the key of a mapping, which is the name of a field, so it is not quoted.
+fn to_yaml_key(key: &Value) -> String {
+    key.as_str()
+        .map_or_else(|| to_flow_yaml(key), str::to_owned)
+}
@@ -0,0 +1,191 @@
+//! `eth-das-kzg` is a command line tool for computing, verifying and recovering the cells of a blob.
+//!
+//! It is meant for debugging interop failures between clients, so every command accepts the
+//! YAML test vectors from the consensus specs, and prints its result in the same hex encoding.
+//! When the input is a test vector, the result is also compared with the expected output.
+
+use clap::{Parser, Subcommand};
+use eth_das_kzg_cli::{
+    input::{self, CellsAndProofsHex, Document, RecoverInput, VerifyInput},
+    Result,
+};
+use rust_eth_kzg::{CellsAndKZGProofs, DASContext, ThreadCount, TrustedSetup, UsePrecomp};
+use serde::Serialize;
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+/// The exit code when a proof is invalid, or the result does not match the expected output.
+const EXIT_MISMATCH: u8 = 1;
+/// The exit code when the input could not be read.
+///
+/// This is the same exit code that clap uses for invalid arguments.
+const EXIT_ERROR: u8 = 2;
+
+#[derive(Parser)]
+#[command(name = "eth-das-kzg", version, about)]
+struct Cli {
+    /// Path to a trusted setup in the JSON format used by the consensus specs.
+    ///
+    /// The trusted setup that is embedded in the library is used by default.
+    #[arg(long, global = true)]
+    trusted_setup: Option<PathBuf>,
+
+    /// The number of threads to use, where `0` uses the number of cores.
+    #[arg(long, global = true, default_value_t = 0)]
+    threads: usize,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Computes the cells of a blob and their KZG proofs.
+    Compute {
+        /// A file containing the blob as raw bytes, as a hex string, or a
+        /// `compute_cells_and_kzg_proofs` test vector.
+        file: PathBuf,
+    },
+    /// Verifies a batch of cells against their commitments and KZG proofs.
+    Verify {
+        /// A YAML file containing the `commitments`, `cell_indices`, `cells` and `proofs`,
+        /// or a `verify_cell_kzg_proof_batch` test vector.
+        file: PathBuf,
+    },
+    /// Recovers all of the cells of a blob and their KZG proofs, given at least half of the cells.
+    Recover {
+        /// A YAML file containing the `cell_indices` and `cells`, or a
+        /// `recover_cells_and_kzg_proofs` test vector.
+        file: PathBuf,
+    },
+}
+
+/// The cells and proofs that are printed by `compute` and `recover`.
+#[derive(Serialize)]
+struct CellsAndProofsOutput {
+    cells: Vec<String>,
+    proofs: Vec<String>,
+}
+
+impl From<&CellsAndKZGProofs> for CellsAndProofsOutput {
+    fn from((cells, proofs): &CellsAndKZGProofs) -> Self {
+        Self {
+            cells: cells
+                .iter()
+                .map(|cell| input::to_hex(cell.as_slice()))
+                .collect(),
+            proofs: proofs.iter().map(input::to_hex).collect(),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<ExitCode> {
+    let trusted_setup = match &cli.trusted_setup {
+        Some(path) => TrustedSetup::try_from_json(&fs::read_to_string(path)?)?,
+        None => TrustedSetup::default(),
+    };
+    let ctx = DASContext::with_threads(
+        &trusted_setup,
+        ThreadCount::Multi(cli.threads),
+        UsePrecomp::No,
+    );
+
+    match cli.command {
+        Command::Compute { file } => {
+            let document = input::read_blob(&fs::read(file)?)?;
+            let result = eth_das_kzg_cli::compute_cells_and_kzg_proofs(&ctx, &document.input);
+            report_cells_and_proofs(result, document.output)
+        }
+        Command::Verify { file } => {
+            let document: Document<VerifyInput, bool> = input::read_yaml(&fs::read(file)?)?;
+            let result = eth_das_kzg_cli::verify_cell_kzg_proof_batch(&ctx, &document.input);
+            if let Ok(valid) = result {
+                println!("{}", if valid { "valid" } else { "invalid" });
+            }
+            let code = match document.output {
+                Some(expected) => compare(&result, expected, |valid, expected| valid == expected),
+                None if matches!(result, Ok(false)) => ExitCode::from(EXIT_MISMATCH),
+                None => exit_code(&result),
+            };
+            Ok(code)
+        }
+        Command::Recover { file } => {
+            let document: Document<RecoverInput, CellsAndProofsHex> =
+                input::read_yaml(&fs::read(file)?)?;
+            let result = eth_das_kzg_cli::recover_cells_and_kzg_proofs(&ctx, &document.input);
+            report_cells_and_proofs(result, document.output)
+        }
+    }
+}
+
+/// Prints the cells and proofs as YAML, and compares them with the expected output if there is one.
+fn report_cells_and_proofs(
+    result: Result<CellsAndKZGProofs>,
+    expected: Option<Option<CellsAndProofsHex>>,
+) -> Result<ExitCode> {
+    let result = result.map(|cells_and_proofs| CellsAndProofsOutput::from(&cells_and_proofs));
+    if let Ok(output) = &result {
+        print!("{}", eth_das_kzg_cli::to_yaml(output)?);
+    }
+
+    Ok(match expected {
+        Some(expected) => compare(&result, expected, |output, (cells, proofs)| {
+            input::hex_eq(&output.cells, cells) && input::hex_eq(&output.proofs, proofs)
+        }),
+        None => exit_code(&result),
+    })
+}
+
+/// Compares a result with the output of a test vector, where `None` means that an error was expected.
+fn compare<T, E>(
+    result: &Result<T>,
+    expected: Option<E>,
+    eq: impl FnOnce(&T, &E) -> bool,
+) -> ExitCode {
+    let matches = match (result, &expected) {
+        (Ok(actual), Some(expected)) => eq(actual, expected),
+        (Err(_), None) => true,
+        (Ok(_), None) | (Err(_), Some(_)) => false,
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+    }
+    let mut stderr = io::stderr().lock();
+    if matches {
+        let _ = writeln!(stderr, "the result matches the expected output");
+        ExitCode::SUCCESS
+    } else if expected.is_some() {
+        let _ = writeln!(stderr, "the result does not match the expected output");
+        ExitCode::from(EXIT_MISMATCH)
+    } else {
+        let _ = writeln!(stderr, "an error was expected, but the command succeeded");
+        ExitCode::from(EXIT_MISMATCH)
+    }
+}
+
+/// Returns the exit code for a result that is not compared with an expected output.
+fn exit_code<T>(result: &Result<T>) -> ExitCode {
+    match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+const TEST_DIR: &str = "../test_vectors";
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_eth-das-kzg"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Returns the test vector of `function` whose name contains `case`.
+fn test_vector(function: &str, case: &str) -> PathBuf {
+    test_vector_in(Path::new(TEST_DIR), function, case)
+}
+
+fn test_vector_in(dir: &Path, function: &str, case: &str) -> PathBuf {
+    let dir = dir.join(function).join("kzg-mainnet");
+    let case_dir = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_str().unwrap().contains(case))
+        .unwrap();
+    case_dir.join("data.yaml")
+}
+
+#[test]
+fn test_vectors_match_their_expected_output() {
+    let cases = [
+        ("compute", "compute_cells_and_kzg_proofs", "case_valid_"),
+        (
+            "compute",
+            "compute_cells_and_kzg_proofs",
+            "case_invalid_blob_",
+        ),
+        ("verify", "verify_cell_kzg_proof_batch", "case_valid_"),
+        (
+            "verify",
+            "verify_cell_kzg_proof_batch",
+            "case_incorrect_proof_",
+        ),
+        (
+            "recover",
+            "recover_cells_and_kzg_proofs",
+            "case_valid_half_",
+        ),
+        ("recover", "recover_cells_and_kzg_proofs", "case_invalid_"),
+    ];
+    for (command, function, case) in cases {
+        let path = test_vector(function, case);
+        let output = run(&[command, path.to_str().unwrap()]);
+        assert!(
+            output.status.success(),
+            "{}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn compute_accepts_a_raw_blob() {
+    let blob_path = std::env::temp_dir().join("eth-das-kzg-test-blob");
+    fs::write(&blob_path, vec![0u8; 131072]).unwrap();
+    let output = run(&["compute", blob_path.to_str().unwrap()]);
+    fs::remove_file(&blob_path).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // The cells and proofs of the zero blob are all zero, and a proof is the point at infinity
+    let point_at_infinity = format!("0xc0{}", "00".repeat(47));
+    assert_eq!(stdout.matches(&point_at_infinity).count(), 128);
+}
+
+#[test]
+fn verify_reports_an_invalid_proof() {
+    let path = test_vector("verify_cell_kzg_proof_batch", "case_incorrect_proof_");
+    let mut yaml = fs::read_to_string(path).unwrap();
+    // Drop the expected output, so that the result is not compared with it
+    yaml.truncate(yaml.find("output:").unwrap());
+    let input_path = std::env::temp_dir().join("eth-das-kzg-test-verify.yaml");
+    fs::write(&input_path, yaml).unwrap();
+    let output = run(&["verify", input_path.to_str().unwrap()]);
+    fs::remove_file(&input_path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "invalid\n");
+}
+
+#[test]
+fn generated_test_vectors_cover_every_function() {
+    let out_dir = std::env::temp_dir().join("eth-das-kzg-test-generated-vectors");
+    let _ = fs::remove_dir_all(&out_dir);
+    let output = Command::new(env!("CARGO_BIN_EXE_generate-test-vectors"))
+        .arg(&out_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let functions = [
+        "blob_to_kzg_commitment",
+        "compute_cells",
+        "compute_cells_and_kzg_proofs",
+        "verify_cell_kzg_proof_batch",
+        "recover_cells_and_kzg_proofs",
+    ];
+    for function in functions {
+        let cases: Vec<String> = fs::read_dir(out_dir.join(function).join("kzg-mainnet"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert!(cases.iter().any(|case| case.contains("_case_valid")));
+        assert!(cases.iter().any(|case| case.contains("_case_invalid")));
+    }
+
+    // The generated vectors are read back by the command line tool, which compares them with
+    // the output of the library
+    let path = test_vector_in(
+        &out_dir,
+        "verify_cell_kzg_proof_batch",
+        "case_incorrect_proof_",
+    );
+    assert!(run(&["verify", path.to_str().unwrap()]).status.success());
+
+    fs::remove_dir_all(&out_dir).unwrap();
+}
@@ -4,8 +4,10 @@ use crate_crypto_internal_eth_kzg_bls12_381::{
     fixed_base_msm::FixedBaseMSMPrecompBLST,
     fixed_base_msm_window::FixedBaseMSMPrecompWindow,
     g1_batch_normalize, g2_batch_normalize,
+    glv::g1_lincomb_glv,
     group::Group,
     lincomb::{g1_lincomb, g1_lincomb_unchecked, g2_lincomb, g2_lincomb_unchecked},
+    subgroup_check::g1_batch_is_torsion_free,
     G1Projective, G2Projective, Scalar,
 };
 use criterion::{criterion_group, criterion_main, Criterion};
@@ -33,7 +35,7 @@ pub fn fixed_base_msm(c: &mut Criterion) {
     let fbm = FixedBaseMSMPrecompBLST::new(generators.clone(), 8);
     let scalars: Vec<_> = random_scalars(length);
     c.bench_function("bls12_381 fixed_base_msm length=64 width=8 (blst)", |b| {
-        b.iter(|| fbm.msm(scalars.clone()))
+        b.iter(|| fbm.msm(&scalars))
     });
 
     let fbm = FixedBaseMSMPrecompWindow::new(&generators, 8);
@@ -71,6 +73,40 @@ pub fn bench_msm(c: &mut Criterion) {
     });
 }
 
+pub fn bench_msm_glv(c: &mut Criterion) {
+    // The sizes of the variable base MSMs when verifying a column, and all of the cells of a blob
+    for size in [128, 8192] {
+        let scalars = random_scalars(size);
+        let g1_elements = g1_batch_normalize(&random_g1_points(size));
+
+        c.bench_function(&format!("g1 (safe) msm of size {}", size), |b| {
+            b.iter(|| g1_lincomb(&g1_elements, &scalars))
+        });
+        c.bench_function(&format!("g1 (glv) msm of size {}", size), |b| {
+            b.iter(|| g1_lincomb_glv(&g1_elements, &scalars))
+        });
+    }
+}
+
+pub fn bench_subgroup_check(c: &mut Criterion) {
+    for size in [128, 1024, 8192] {
+        let g1_elements = g1_batch_normalize(&random_g1_points(size));
+        let subset_masks: Vec<u128> = (0..size).map(|_| rand::random()).collect();
+
+        c.bench_function(&format!("g1 subgroup check of {} points", size), |b| {
+            b.iter(|| {
+                g1_elements
+                    .iter()
+                    .all(|point| bool::from(point.is_torsion_free()))
+            })
+        });
+        c.bench_function(
+            &format!("g1 batch subgroup check of {} points", size),
+            |b| b.iter(|| g1_batch_is_torsion_free(&g1_elements, &subset_masks)),
+        );
+    }
+}
+
 fn random_scalars(size: usize) -> Vec<Scalar> {
     let mut scalars = Vec::with_capacity(size);
     for _ in 0..size {
@@ -93,5 +129,12 @@ fn random_g2_points(size: usize) -> Vec<G2Projective> {
     points
 }
 
-criterion_group!(benches, batch_inversion, fixed_base_msm, bench_msm);
+criterion_group!(
+    benches,
+    batch_inversion,
+    fixed_base_msm,
+    bench_msm,
+    bench_msm_glv,
+    bench_subgroup_check
+);
 criterion_main!(benches);
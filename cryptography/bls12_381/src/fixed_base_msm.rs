@@ -42,10 +42,10 @@ impl FixedBaseMSM {
         }
     }
 
-    pub fn msm(&self, scalars: Vec<Scalar>) -> G1Projective {
+    pub fn msm(&self, scalars: &[Scalar]) -> G1Projective {
         match self {
-            Self::Precomp(precomp) => precomp.msm(&scalars),
-            Self::NoPrecomp(generators) => g1_lincomb(generators, &scalars)
+            Self::Precomp(precomp) => precomp.msm(scalars),
+            Self::NoPrecomp(generators) => g1_lincomb(generators, scalars)
                 .expect("number of generators and scalars should be equal"),
         }
     }
@@ -80,14 +80,14 @@ impl FixedBaseMSMPrecompBLST {
         }
     }
 
-    pub fn msm(&self, scalars: Vec<Scalar>) -> G1Projective {
+    pub fn msm(&self, scalars: &[Scalar]) -> G1Projective {
         use ff::PrimeField;
         let mut ret = blst::blst_p1::default();
         const NUM_BITS_SCALAR: usize = Scalar::NUM_BITS as usize;
 
         let blst_scalars: Vec<_> = scalars
-            .into_iter()
-            .map(|scalar| Into::<blst::blst_scalar>::into(scalar).b)
+            .iter()
+            .map(|scalar| Into::<blst::blst_scalar>::into(*scalar).b)
             .collect();
         let blst_scalar_ptrs: Vec<*const u8> = blst_scalars
             .iter()
@@ -137,7 +137,7 @@ mod tests {
             .expect("number of generators and number of scalars is equal");
 
         let fbm = FixedBaseMSM::new(generators, use_precomp);
-        let result = fbm.msm(scalars);
+        let result = fbm.msm(&scalars);
 
         assert_eq!(res, result);
     }
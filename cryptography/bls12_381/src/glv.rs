@@ -0,0 +1,180 @@
+//! Multi-scalar multiplication in G1 using the GLV endomorphism.
+//!
+//! G1 has an endomorphism `phi(x, y) = (beta * x, y)`, where `beta` is a cube root of unity in
+//! the base field, that acts on the group as multiplication by `lambda = z^2 - 1`. Since `lambda`
+//! has 128 bits, every scalar `k` can be split as `k = k1 + k2 * lambda` where `k1` and `k2`
+//! have at most 128 bits, so that `k * P = k1 * P + k2 * phi(P)`.
+//!
+//! This turns a multi-scalar multiplication with 255-bit scalars into one with twice as many
+//! points, but 128-bit scalars, which halves the number of doublings.
+use crate::{G1Point, G1Projective, Scalar};
+use blst::{blst_p1, blst_p1_affine, MultiPoint};
+use blstrs::Fp;
+use group::{prime::PrimeCurveAffine, Group};
+
+/// `lambda = z^2 - 1`, where `z` is the parameter of the BLS12-381 curve.
+const LAMBDA: u128 = 0xac45a4010001a40200000000ffffffff;
+
+/// The cube root of unity `beta`, for which `(beta * x, y) = lambda * (x, y)`.
+const BETA_BYTES_BE: [u8; 48] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x99, 0xec, 0x02, 0x40, 0x86, 0x63, 0xd4, 0xde, 0x85,
+    0xaa, 0x0d, 0x85, 0x7d, 0x89, 0x75, 0x9a, 0xd4, 0x89, 0x7d, 0x29, 0x65, 0x0f, 0xb8, 0x5f, 0x9b,
+    0x40, 0x94, 0x27, 0xeb, 0x4f, 0x49, 0xff, 0xfd, 0x8b, 0xfd, 0x00, 0x00, 0x00, 0x00, 0xaa, 0xac,
+];
+
+/// The number of bits in each half of a decomposed scalar.
+const NUM_BITS_HALF_SCALAR: usize = 128;
+
+/// A multi-scalar multiplication algorithm over G1 elements, which uses the GLV endomorphism.
+///
+/// Returns None if the points and the scalars are not the same length.
+///
+/// This gives the same result as `g1_lincomb`, and like it, filters out any points that are the identity.
+///
+/// Note: This is not constant time, so it should only be used with public scalars, such as
+/// the ones in the verification equations.
+pub fn g1_lincomb_glv(points: &[G1Point], scalars: &[Scalar]) -> Option<G1Projective> {
+    if points.len() != scalars.len() {
+        return None;
+    }
+
+    let beta = Fp::from_bytes_be(&BETA_BYTES_BE).expect("beta is a canonical field element");
+
+    let mut glv_points: Vec<blst_p1_affine> = Vec::with_capacity(2 * points.len());
+    let mut glv_scalars: Vec<u8> = Vec::with_capacity(2 * points.len() * NUM_BITS_HALF_SCALAR / 8);
+    // blst returns the identity element if any of the points are the identity element,
+    // so these points are filtered out.
+    for (point, scalar) in points.iter().zip(scalars) {
+        if bool::from(point.is_identity()) {
+            continue;
+        }
+        let (k1, k2) = decompose_scalar(scalar);
+
+        glv_points.push(*point.as_ref());
+        glv_scalars.extend_from_slice(&k1.to_le_bytes());
+
+        glv_points.push(*endomorphism(point, &beta).as_ref());
+        glv_scalars.extend_from_slice(&k2.to_le_bytes());
+    }
+
+    if glv_points.is_empty() {
+        return Some(G1Projective::identity());
+    }
+
+    let result: blst_p1 = glv_points.mult(&glv_scalars, NUM_BITS_HALF_SCALAR);
+    Some(G1Projective::from_raw_unchecked(
+        Fp::from_raw_unchecked(result.x.l),
+        Fp::from_raw_unchecked(result.y.l),
+        Fp::from_raw_unchecked(result.z.l),
+    ))
+}
+
+/// Computes `lambda * point` as `(beta * x, y)`.
+fn endomorphism(point: &G1Point, beta: &Fp) -> G1Point {
+    G1Point::from_raw_unchecked(point.x() * beta, point.y(), false)
+}
+
+/// Splits a scalar `k` into `(k1, k2)`, such that `k = k1 + k2 * lambda`.
+///
+/// These are the remainder and the quotient of dividing `k` by `lambda`. Both fit into 128 bits,
+/// since `k < r = lambda^2 + lambda + 1`.
+fn decompose_scalar(scalar: &Scalar) -> (u128, u128) {
+    let bytes = scalar.to_bytes_le();
+    let low = u128::from_le_bytes(bytes[..16].try_into().expect("slice has 16 bytes"));
+    let high = u128::from_le_bytes(bytes[16..].try_into().expect("slice has 16 bytes"));
+
+    // Long division, one bit at a time, starting from the most significant bit
+    let mut quotient = 0u128;
+    let mut remainder = 0u128;
+    for i in (0..256).rev() {
+        let bit = if i >= 128 {
+            (high >> (i - 128)) & 1
+        } else {
+            (low >> i) & 1
+        };
+        // The remainder is less than lambda before the shift, so it fits into 129 bits after it
+        let overflow = remainder >> 127 == 1;
+        remainder = (remainder << 1) | bit;
+        quotient <<= 1;
+        if overflow || remainder >= LAMBDA {
+            remainder = remainder.wrapping_sub(LAMBDA);
+            quotient |= 1;
+        }
+    }
+
+    (remainder, quotient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ff::{Field, PrimeField},
+        lincomb::g1_lincomb,
+    };
+    use group::Curve;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn endomorphism_is_multiplication_by_lambda() {
+        let beta = Fp::from_bytes_be(&BETA_BYTES_BE).unwrap();
+        let lambda = Scalar::from_u128(LAMBDA);
+        let point = (G1Projective::generator() * Scalar::from(7)).to_affine();
+
+        let expected = (G1Projective::from(point) * lambda).to_affine();
+        assert_eq!(endomorphism(&point, &beta), expected);
+    }
+
+    #[test]
+    fn decomposition_recombines_to_the_scalar() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let lambda = Scalar::from_u128(LAMBDA);
+        let scalars = (0..100).map(|_| Scalar::random(&mut rng)).chain([
+            Scalar::ZERO,
+            Scalar::ONE,
+            -Scalar::ONE,
+            lambda,
+            lambda - Scalar::ONE,
+        ]);
+
+        for scalar in scalars {
+            let (k1, k2) = decompose_scalar(&scalar);
+            assert!(k1 < LAMBDA);
+            assert_eq!(
+                Scalar::from_u128(k1) + Scalar::from_u128(k2) * lambda,
+                scalar
+            );
+        }
+    }
+
+    #[test]
+    fn glv_lincomb_matches_lincomb() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for length in [0, 1, 2, 33, 128] {
+            let points: Vec<G1Point> = (0..length)
+                .map(|_| G1Projective::random(&mut rng).to_affine())
+                .collect();
+            let scalars: Vec<Scalar> = (0..length).map(|_| Scalar::random(&mut rng)).collect();
+
+            assert_eq!(
+                g1_lincomb_glv(&points, &scalars),
+                g1_lincomb(&points, &scalars)
+            );
+        }
+    }
+
+    #[test]
+    fn glv_lincomb_filters_identity() {
+        let points = vec![G1Point::generator(), G1Point::identity()];
+        let scalars = vec![-Scalar::ONE, Scalar::ONE];
+
+        let result = g1_lincomb_glv(&points, &scalars).unwrap();
+        assert_eq!(result, -G1Projective::generator());
+
+        assert_eq!(
+            g1_lincomb_glv(&[G1Point::identity()], &[Scalar::ONE]),
+            Some(G1Projective::identity())
+        );
+        assert_eq!(g1_lincomb_glv(&points, &scalars[..1]), None);
+    }
+}
@@ -3,7 +3,9 @@ pub mod batch_inversion;
 mod booth_encoding;
 pub mod fixed_base_msm;
 pub mod fixed_base_msm_window;
+pub mod glv;
 pub mod lincomb;
+pub mod subgroup_check;
 
 // Re-exporting the blstrs crate
 
@@ -23,6 +25,25 @@ pub type G2Prepared = blstrs::G2Prepared;
 
 pub type Scalar = blstrs::Scalar;
 
+/// Converts a field element to its 32 byte little-endian encoding.
+///
+/// Note: The canonical encoding in the consensus specs, and the one used for blobs and cells,
+/// is big-endian (`Scalar::to_bytes_be`). This is for interoperating with libraries such as
+/// arkworks, which use little-endian encodings of field elements.
+pub fn scalar_to_bytes_le(scalar: &Scalar) -> [u8; 32] {
+    scalar.to_bytes_le()
+}
+
+/// Converts a 32 byte little-endian encoding to a field element.
+///
+/// Returns None if the encoding is not canonical, that is, if it is not less than the modulus.
+///
+/// Note: The canonical encoding in the consensus specs is big-endian (`Scalar::from_bytes_be`),
+/// see `scalar_to_bytes_le`.
+pub fn scalar_from_bytes_le(bytes: &[u8; 32]) -> Option<Scalar> {
+    Scalar::from_bytes_le(bytes).into()
+}
+
 pub fn multi_pairings(pairs: &[(&G1Point, &blstrs::G2Prepared)]) -> bool {
     use group::Group;
     use pairing::{MillerLoopResult, MultiMillerLoop};
@@ -134,6 +155,19 @@ mod tests {
         0xFF, 0xFD,
     ];
 
+    #[test]
+    fn little_endian_encoding_is_reversed_big_endian_encoding() {
+        let scalar = -Scalar::from(0x0102_0304_0506_0708u64);
+        let mut bytes = scalar.to_bytes_be();
+        bytes.reverse();
+        assert_eq!(scalar_to_bytes_le(&scalar), bytes);
+        assert_eq!(scalar_from_bytes_le(&bytes), Some(scalar));
+
+        let mut modulus = BLS12_381_R;
+        modulus.reverse();
+        assert_eq!(scalar_from_bytes_le(&modulus), None);
+    }
+
     #[test]
     fn test_reduce_bytes_to_scalar_edge_cases() {
         // Test case 1: Zero
@@ -0,0 +1,120 @@
+//! Checking that many G1 points are in the prime order subgroup at once.
+//!
+//! Every point on the curve is the sum of a point in G1 and a point in the subgroup whose order
+//! is the cofactor. A point is in G1 exactly when its second component is zero, and the sum of a
+//! subset of points is in G1 when the second components of the points in the subset add up to zero.
+//!
+//! If one of the points is not in G1, then adding it to or removing it from a subset changes
+//! whether the subset sums to a point in G1, so at most half of the subsets sum to a point in G1.
+//! Checking `NUM_SUBSETS` random subsets instead of every point therefore misses a point that
+//! is not in G1 with probability at most `2^-NUM_SUBSETS`.
+use crate::{g1_batch_normalize, G1Point, G1Projective};
+use group::Group;
+
+/// The number of random subsets whose sums are checked.
+pub const NUM_SUBSETS: usize = 128;
+
+/// The number of points whose combinations are summed together, before adding them to the subsets.
+///
+/// Note: Computing the sums of the combinations needs `2^SUBSET_CHUNK_SIZE` additions, which is
+/// balanced against the `NUM_SUBSETS` additions that are needed to add each chunk to the subsets.
+const SUBSET_CHUNK_SIZE: usize = 6;
+
+/// Returns true if all of the points are in G1, given a random mask for each point, where bit `k`
+/// of the mask determines whether the point is in the `k`'th subset.
+///
+/// Returns None if the number of points and masks are not the same.
+///
+/// Note: The masks must be unpredictable to whoever chose the points, for example by deriving
+/// them from a hash of the points.
+///
+/// Note: The points must be on the curve, which is checked when they are deserialized.
+pub fn g1_batch_is_torsion_free(points: &[G1Point], subset_masks: &[u128]) -> Option<bool> {
+    if points.len() != subset_masks.len() {
+        return None;
+    }
+
+    // For a small number of points, it is cheaper to check them one at a time.
+    if points.len() <= NUM_SUBSETS {
+        return Some(points.iter().all(|point| point.is_torsion_free().into()));
+    }
+
+    // Each point is in half of the subsets on average. Instead of adding every point to each of
+    // its subsets, the points are split into chunks, and the sums of every combination of points
+    // within a chunk are computed once. Each subset then only needs one addition per chunk.
+    let mut subset_sums = [G1Projective::identity(); NUM_SUBSETS];
+    let mut combination_sums = [G1Projective::identity(); 1 << SUBSET_CHUNK_SIZE];
+    for (points, masks) in points
+        .chunks(SUBSET_CHUNK_SIZE)
+        .zip(subset_masks.chunks(SUBSET_CHUNK_SIZE))
+    {
+        // The sum of the combination `c` is the sum of the combination without its lowest point
+        for combination in 1usize..1 << points.len() {
+            let lowest_point = combination.trailing_zeros() as usize;
+            combination_sums[combination] =
+                combination_sums[combination & (combination - 1)] + points[lowest_point];
+        }
+
+        for (k, subset_sum) in subset_sums.iter_mut().enumerate() {
+            let combination = masks.iter().enumerate().fold(0, |combination, (i, mask)| {
+                combination | (((mask >> k) & 1) as usize) << i
+            });
+            if combination != 0 {
+                *subset_sum += combination_sums[combination];
+            }
+        }
+    }
+
+    let subset_sums = g1_batch_normalize(&subset_sums);
+    Some(subset_sums.iter().all(|sum| sum.is_torsion_free().into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ff::Field, Scalar};
+    use blstrs::Fp;
+    use group::{prime::PrimeCurveAffine, Curve};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Returns a point that is on the curve, but not in G1.
+    fn point_not_in_subgroup() -> G1Point {
+        // (0, 2) is on the curve `y^2 = x^3 + 4` and has order 3
+        let point = G1Point::from_raw_unchecked(Fp::ZERO, Fp::from(2), false);
+        assert!(bool::from(point.is_on_curve()));
+        assert!(!bool::from(point.is_torsion_free()));
+        point
+    }
+
+    #[test]
+    fn batch_check_matches_individual_checks() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let num_points = 2 * NUM_SUBSETS;
+        let mut points: Vec<G1Point> = (0..num_points)
+            .map(|_| (G1Projective::generator() * Scalar::random(&mut rng)).to_affine())
+            .collect();
+        points[3] = G1Point::identity();
+        let subset_masks: Vec<u128> = (0..num_points).map(|_| rng.gen()).collect();
+
+        assert_eq!(g1_batch_is_torsion_free(&points, &subset_masks), Some(true));
+        assert_eq!(
+            g1_batch_is_torsion_free(&points[..10], &subset_masks[..10]),
+            Some(true)
+        );
+
+        for index in [0, 7, num_points - 1] {
+            let mut points = points.clone();
+            points[index] = point_not_in_subgroup();
+            assert_eq!(
+                g1_batch_is_torsion_free(&points, &subset_masks),
+                Some(false)
+            );
+            assert_eq!(
+                g1_batch_is_torsion_free(&points[..10], &subset_masks[..10]),
+                Some(index >= 10)
+            );
+        }
+
+        assert_eq!(g1_batch_is_torsion_free(&points, &subset_masks[1..]), None);
+    }
+}
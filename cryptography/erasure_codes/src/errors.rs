@@ -1,17 +1,47 @@
-#[derive(Debug)]
+/// Errors that can occur while creating a Reed-Solomon code, encoding a
+/// polynomial or recovering it from a codeword with erasures.
+#[derive(Debug, thiserror::Error)]
 pub enum RSError {
+    #[error("{parameter} must be a non-zero power of two, but got {value}")]
+    ParameterNotPowerOfTwo {
+        parameter: &'static str,
+        value: usize,
+    },
+    #[error("block size {block_size} is larger than the codeword length {codeword_length}")]
+    BlockSizeLargerThanCodeword {
+        block_size: usize,
+        codeword_length: usize,
+    },
+    #[error("codeword has length {codeword_length}, expected {expected_codeword_length}")]
+    CodewordHasInvalidLength {
+        codeword_length: usize,
+        expected_codeword_length: usize,
+    },
+    #[error("received {num_erasures} erasures, but at most {max_num_erasures_accepted} can be recovered from")]
+    TooManyErasures {
+        num_erasures: usize,
+        max_num_erasures_accepted: usize,
+    },
+    #[error("polynomial has {num_coefficients} coefficients, but at most {max_num_coefficients} are allowed")]
     PolynomialHasTooManyCoefficients {
         num_coefficients: usize,
         max_num_coefficients: usize,
     },
+    #[error("recovered polynomial has {num_coefficients} coefficients, expected {expected_num_coefficients}")]
     PolynomialHasInvalidLength {
         num_coefficients: usize,
         expected_num_coefficients: usize,
     },
+    #[error("received {num_block_erasures} block erasures, but at most {max_num_block_erasures_accepted} can be recovered from")]
     TooManyBlockErasures {
         num_block_erasures: usize,
         max_num_block_erasures_accepted: usize,
     },
+    #[error(
+        "codeword could not be decoded, it contains more than {max_num_errors_correctable} errors"
+    )]
+    TooManyErrors { max_num_errors_correctable: usize },
+    #[error("block index {block_index} is out of range for a block of size {block_size}")]
     InvalidBlockIndex {
         block_index: usize,
         block_size: usize,
@@ -1,3 +1,4 @@
 pub mod errors;
 mod reed_solomon;
-pub use reed_solomon::{BlockErasureIndices, ReedSolomon};
+pub use errors::RSError;
+pub use reed_solomon::{BlockErasureIndices, CorrectedCodeword, ReedSolomon};
@@ -1,11 +1,15 @@
-use bls12_381::{
-    batch_inversion::batch_inverse,
-    ff::{Field, PrimeField},
-    Scalar,
-};
+use bls12_381::{ff::PrimeField, Scalar};
 
 use crate::errors::RSError;
-use polynomial::{domain::Domain, poly_coeff::vanishing_poly, CosetFFT};
+use maybe_rayon::prelude::*;
+use polynomial::{
+    batch_inverse,
+    domain::Domain,
+    poly_coeff::{interpolate, vanishing_poly, Polynomial},
+    scratch::ScratchPool,
+    CosetFFT,
+};
+use std::sync::Arc;
 
 /// ErasurePattern is an abstraction created to capture the idea
 /// that erasures do not appear in completely random locations.
@@ -25,9 +29,6 @@ pub(crate) enum ErasurePattern {
     /// In the above example, we had 4 blocks and each block had an erasure at index 0.
     BlockSynchronizedErasures(BlockErasureIndices),
     /// There is no pattern to the missing erasures.
-    ///
-    /// This is used for tests.
-    #[cfg(test)]
     Random { indices: Vec<usize> },
 }
 
@@ -35,11 +36,50 @@ pub(crate) enum ErasurePattern {
 /// the index in every block that an erasure has occurred.
 type BlockErasureIndex = usize;
 
+/// The result of decoding a codeword that may contain incorrect evaluations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrectedCodeword<F = Scalar> {
+    /// The polynomial in coefficient form that generates the codeword.
+    pub poly_coefficient_form: Vec<F>,
+    /// The indices of the evaluations in the codeword that were incorrect.
+    ///
+    /// Note: This does not include the indices of missing evaluations.
+    pub corrupted_indices: Vec<usize>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct BlockErasureIndices(pub Vec<BlockErasureIndex>);
 
+/// A Reed-Solomon code over a prime field, which is the BLS12-381 scalar field by default.
+///
+/// A message is a polynomial in coefficient form with at most `poly_len` coefficients.
+/// It is encoded by evaluating it over a domain of `poly_len * expansion_factor` roots of unity,
+/// and can be recovered from any `poly_len` of those evaluations.
+///
+/// The expansion factor can be any power of two, so the rate of the code is `1 / expansion_factor`.
+/// EIP-7594 uses an expansion factor of 2.
+///
+/// Example:
+///
+/// ```
+/// use bls12_381::Scalar;
+/// use crate_crypto_internal_eth_kzg_erasure_codes::ReedSolomon;
+///
+/// let rs = ReedSolomon::try_new(4, 2, 1).unwrap();
+/// let message: Vec<_> = (0..4u64).map(Scalar::from).collect();
+/// let codeword = rs.encode(message.clone()).unwrap();
+///
+/// // Lose half of the codeword
+/// let received: Vec<_> = codeword
+///     .into_iter()
+///     .enumerate()
+///     .map(|(i, value)| (i % 2 == 0).then_some(value))
+///     .collect();
+///
+/// assert_eq!(rs.decode(&received).unwrap(), message);
+/// ```
 #[derive(Debug)]
-pub struct ReedSolomon {
+pub struct ReedSolomon<F = Scalar> {
     /// Denotes the factor by which the message/poly_len will be expanded.
     /// Example, if poly_len = 2 and expansion_factor = 4, Then the codeword will have length 4 * 2 = 8.
     expansion_factor: usize,
@@ -49,7 +89,7 @@ pub struct ReedSolomon {
     /// to a codeword.
     ///
     /// Note: This domain will have size = poly_len * expansion_factor.
-    evaluation_domain: Domain,
+    evaluation_domain: Arc<Domain<F>>,
     /// Denotes the number of scalars that we should group together in the codeword to form a block.
     ///
     /// When the ErasurePattern is BlockSynchronized, we know that every block will
@@ -65,27 +105,62 @@ pub struct ReedSolomon {
     num_blocks: usize,
     /// The domain that we will use to efficiently compute the vanishing polynomial with, when the erasure pattern
     /// being used is `BlockSynchronizedErasures`.
-    block_size_domain: Domain,
+    block_size_domain: Arc<Domain<F>>,
 
-    fft_coset_gen: CosetFFT,
+    fft_coset_gen: CosetFFT<F>,
+    /// Buffers for the intermediate steps of recovery, which are reused across calls.
+    scratch: ScratchPool<F>,
 }
 
-impl ReedSolomon {
+impl<F: PrimeField> ReedSolomon<F> {
+    /// Creates a Reed-Solomon code for polynomials with `poly_len` coefficients, whose
+    /// codewords have `poly_len * expansion_factor` evaluations.
+    ///
+    /// `block_size` is only used by `recover_polynomial_coefficient`, where erasures occur
+    /// at the same positions in every block of `block_size` evaluations. Use a `block_size`
+    /// of 1 if this structure is not needed.
+    ///
+    /// Panics if the parameters are invalid. See `try_new` for the fallible version.
     pub fn new(poly_len: usize, expansion_factor: usize, block_size: usize) -> Self {
-        assert!(expansion_factor.is_power_of_two());
-        assert!(poly_len.is_power_of_two());
-        assert!(block_size.is_power_of_two());
+        Self::try_new(poly_len, expansion_factor, block_size)
+            .expect("invalid reed-solomon parameters")
+    }
+
+    /// Creates a Reed-Solomon code, returning an error if the parameters are invalid.
+    ///
+    /// All of the parameters must be powers of two and the `block_size` must not be
+    /// larger than the codeword length.
+    pub fn try_new(
+        poly_len: usize,
+        expansion_factor: usize,
+        block_size: usize,
+    ) -> Result<Self, RSError> {
+        for (parameter, value) in [
+            ("poly_len", poly_len),
+            ("expansion_factor", expansion_factor),
+            ("block_size", block_size),
+        ] {
+            if !value.is_power_of_two() {
+                return Err(RSError::ParameterNotPowerOfTwo { parameter, value });
+            }
+        }
 
         let evaluation_size = poly_len * expansion_factor;
-        let evaluation_domain = Domain::new(evaluation_size);
+        if block_size > evaluation_size {
+            return Err(RSError::BlockSizeLargerThanCodeword {
+                block_size,
+                codeword_length: evaluation_size,
+            });
+        }
+        let evaluation_domain = Domain::get_or_init(evaluation_size);
 
         let num_blocks = evaluation_size / block_size;
 
-        let block_size_domain = Domain::new(block_size);
+        let block_size_domain = Domain::get_or_init(block_size);
 
-        let fft_coset_gen = CosetFFT::new(Scalar::MULTIPLICATIVE_GENERATOR);
+        let fft_coset_gen = CosetFFT::new(F::MULTIPLICATIVE_GENERATOR);
 
-        Self {
+        Ok(Self {
             poly_len,
             evaluation_domain,
             expansion_factor,
@@ -93,14 +168,15 @@ impl ReedSolomon {
             block_size_domain,
             num_blocks,
             fft_coset_gen,
-        }
+            scratch: ScratchPool::new(),
+        })
     }
 
     /// Returns the maximum number of known missing values that we can
     /// tolerate before are not able to recover the message.
     ///
     /// Note: we need to have at least `poly_len` evaluations
-    const fn acceptable_num_random_erasures(&self) -> usize {
+    pub const fn acceptable_num_random_erasures(&self) -> usize {
         let total_codeword_len = self.poly_len * self.expansion_factor;
         let min_num_evaluations_needed = self.poly_len;
         total_codeword_len - min_num_evaluations_needed
@@ -114,6 +190,11 @@ impl ReedSolomon {
         self.acceptable_num_random_erasures() / self.num_blocks
     }
 
+    /// The maximum number of coefficients in a polynomial that can be encoded
+    pub const fn poly_len(&self) -> usize {
+        self.poly_len
+    }
+
     /// The number of scalars in the reed solomon encoded polynomial
     pub const fn codeword_length(&self) -> usize {
         self.poly_len * self.expansion_factor
@@ -121,7 +202,7 @@ impl ReedSolomon {
 
     /// Encodes a polynomial in coefficient form by evaluating it at `poly_len * expansion_factor`
     /// points.
-    pub fn encode(&self, poly_coefficient_form: Vec<Scalar>) -> Result<Vec<Scalar>, RSError> {
+    pub fn encode(&self, poly_coefficient_form: Vec<F>) -> Result<Vec<F>, RSError> {
         if poly_coefficient_form.len() > self.poly_len {
             return Err(RSError::PolynomialHasTooManyCoefficients {
                 num_coefficients: poly_coefficient_form.len(),
@@ -138,21 +219,150 @@ impl ReedSolomon {
     /// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/_features/eip7594/polynomial-commitments-sampling.md#recover_polynomialcoeff
     pub fn recover_polynomial_coefficient(
         &self,
-        codeword_with_erasures: Vec<Scalar>,
+        codeword_with_erasures: Vec<F>,
         erasures: BlockErasureIndices,
-    ) -> Result<Vec<Scalar>, RSError> {
+    ) -> Result<Vec<F>, RSError> {
         self.recover_polynomial_coefficient_erasure_pattern(
             codeword_with_erasures,
             ErasurePattern::BlockSynchronizedErasures(erasures),
         )
     }
 
-    #[cfg(test)]
+    /// Recovers the polynomial in coefficient form from a codeword, where `None` denotes
+    /// an evaluation that is missing.
+    ///
+    /// Unlike `recover_polynomial_coefficient`, the erasures can be at any position in the
+    /// codeword. At most `acceptable_num_random_erasures` evaluations can be missing.
+    ///
+    /// Note: This only recovers from erasures, if any of the supplied evaluations are
+    /// incorrect then the recovered polynomial will be incorrect or an error is returned.
+    pub fn decode(&self, codeword: &[Option<F>]) -> Result<Vec<F>, RSError> {
+        if codeword.len() != self.codeword_length() {
+            return Err(RSError::CodewordHasInvalidLength {
+                codeword_length: codeword.len(),
+                expected_codeword_length: self.codeword_length(),
+            });
+        }
+
+        let missing_indices: Vec<_> = codeword
+            .iter()
+            .enumerate()
+            .filter_map(|(index, evaluation)| evaluation.is_none().then_some(index))
+            .collect();
+        let codeword_with_erasures = codeword
+            .iter()
+            .map(|evaluation| evaluation.unwrap_or(F::ZERO))
+            .collect();
+
+        self.recover_polynomial_coefficient_random_erasure(codeword_with_erasures, missing_indices)
+    }
+
+    /// Recovers the polynomial in coefficient form from a codeword that may contain both
+    /// missing evaluations, denoted by `None`, and incorrect evaluations.
+    ///
+    /// If `m` evaluations are present, then up to `(m - poly_len) / 2` of them can be
+    /// incorrect. The indices of the incorrect evaluations are returned alongside the
+    /// polynomial, so that the caller can identify where they came from.
+    ///
+    /// This uses Gao's decoding algorithm, which runs the extended euclidean algorithm on
+    /// the polynomial that vanishes on the received points and the polynomial that
+    /// interpolates the received values.
+    ///
+    /// Note: This is significantly slower than `decode` since the extended euclidean algorithm
+    /// is quadratic in the codeword length. It should only be used once `decode` has
+    /// produced a result that is known to be incorrect, for example because the cells
+    /// it produced did not match their commitment.
+    pub fn decode_with_errors(
+        &self,
+        codeword: &[Option<F>],
+    ) -> Result<CorrectedCodeword<F>, RSError> {
+        if codeword.len() != self.codeword_length() {
+            return Err(RSError::CodewordHasInvalidLength {
+                codeword_length: codeword.len(),
+                expected_codeword_length: self.codeword_length(),
+            });
+        }
+
+        let received: Vec<_> = codeword
+            .iter()
+            .zip(&self.evaluation_domain.roots)
+            .filter_map(|(evaluation, root)| Some((*root, (*evaluation)?)))
+            .collect();
+        let num_erasures = codeword.len() - received.len();
+        if num_erasures > self.acceptable_num_random_erasures() {
+            return Err(RSError::TooManyErasures {
+                num_erasures,
+                max_num_erasures_accepted: self.acceptable_num_random_erasures(),
+            });
+        }
+        let max_num_errors_correctable = (received.len() - self.poly_len) / 2;
+
+        // Run the extended euclidean algorithm on Z(X), the polynomial that vanishes on the
+        // received points and I(X), the polynomial that interpolates the received values.
+        //
+        // We keep track of the remainders `r_i` and the bezout coefficients `t_i` for I(X),
+        // stopping at the first remainder whose degree is less than (m + poly_len) / 2.
+        let points: Vec<_> = received.iter().map(|(point, _)| *point).collect();
+        let mut prev_remainder = Polynomial::new(vanishing_poly(&points));
+        let mut remainder = Polynomial::new(interpolate(&received));
+        let mut prev_coefficient = Polynomial::zero();
+        let mut coefficient = Polynomial::new(vec![F::ONE]);
+
+        let degree_bound = (received.len() + self.poly_len) / 2;
+        while remainder
+            .degree()
+            .is_some_and(|degree| degree >= degree_bound)
+        {
+            let (quotient, next_remainder) = prev_remainder
+                .div_rem(&remainder)
+                .expect("remainder is non-zero");
+            let next_coefficient = &prev_coefficient - &(&quotient * &coefficient);
+
+            prev_remainder = std::mem::replace(&mut remainder, next_remainder);
+            prev_coefficient = std::mem::replace(&mut coefficient, next_coefficient);
+        }
+
+        // The bezout coefficient is the error locator polynomial and the remainder is the
+        // message polynomial multiplied by the error locator polynomial.
+        let (poly, division_remainder) = remainder
+            .div_rem(&coefficient)
+            .expect("bezout coefficients are non-zero");
+        let poly_has_valid_degree = poly.degree().map_or(true, |degree| degree < self.poly_len);
+        if !division_remainder.is_zero() || !poly_has_valid_degree {
+            return Err(RSError::TooManyErrors {
+                max_num_errors_correctable,
+            });
+        }
+
+        let mut poly_coefficient_form = poly.into_coeffs();
+        poly_coefficient_form.resize(self.poly_len, F::ZERO);
+
+        // Re-encode the polynomial to find which of the received evaluations were incorrect
+        let corrected_codeword = self
+            .evaluation_domain
+            .fft_scalars(poly_coefficient_form.clone());
+        let corrupted_indices = codeword
+            .iter()
+            .zip(corrected_codeword)
+            .enumerate()
+            .filter_map(|(index, (received, expected))| {
+                received
+                    .is_some_and(|received| received != expected)
+                    .then_some(index)
+            })
+            .collect();
+
+        Ok(CorrectedCodeword {
+            poly_coefficient_form,
+            corrupted_indices,
+        })
+    }
+
     fn recover_polynomial_coefficient_random_erasure(
         &self,
-        codeword_with_erasures: Vec<Scalar>,
+        codeword_with_erasures: Vec<F>,
         random_erasure: Vec<usize>,
-    ) -> Result<Vec<Scalar>, RSError> {
+    ) -> Result<Vec<F>, RSError> {
         self.recover_polynomial_coefficient_erasure_pattern(
             codeword_with_erasures,
             ErasurePattern::Random {
@@ -180,7 +390,7 @@ impl ReedSolomon {
     fn construct_vanishing_poly_from_block_erasures(
         &self,
         block_indices: BlockErasureIndices,
-    ) -> Vec<Scalar> {
+    ) -> Vec<F> {
         assert!(block_indices.0.len() != self.block_size, "all of the blocks are missing. This should have been checked by the caller of this method");
 
         let evaluation_domain_size = self.evaluation_domain.roots.len();
@@ -213,7 +423,7 @@ impl ReedSolomon {
         // This process is happening below, ie we create a polynomial which has roots `r_i`
         // Then we expand it by `num_blocks` so that it has additional roots \omega^i * r_i
         // Where \omega is a `num_blocks` root of unity.
-        let mut z_x = vec![Scalar::ZERO; evaluation_domain_size];
+        let mut z_x = vec![F::ZERO; evaluation_domain_size];
         for (i, coeff) in vanish_poly_first_block.into_iter().enumerate() {
             // Let's compute the bounds for the array access below to argue that it is safe:
             //
@@ -239,7 +449,7 @@ impl ReedSolomon {
     fn construct_vanishing_poly_from_erasure_pattern(
         &self,
         erasures: ErasurePattern,
-    ) -> Result<Vec<Scalar>, RSError> {
+    ) -> Result<Vec<F>, RSError> {
         match erasures {
             ErasurePattern::BlockSynchronizedErasures(indices) => {
                 // Check that each block index is valid
@@ -262,14 +472,13 @@ impl ReedSolomon {
                 }
                 Ok(self.construct_vanishing_poly_from_block_erasures(indices))
             }
-            #[cfg(test)]
             ErasurePattern::Random { indices } => {
-                assert!(
-                    indices.len() <= self.acceptable_num_random_erasures(),
-                    "num random erasures = {} but tolerable erasures = {}",
-                    indices.len(),
-                    self.acceptable_num_random_erasures()
-                );
+                if indices.len() > self.acceptable_num_random_erasures() {
+                    return Err(RSError::TooManyErasures {
+                        num_erasures: indices.len(),
+                        max_num_erasures_accepted: self.acceptable_num_random_erasures(),
+                    });
+                }
                 // Computes the polynomial in coefficient form, that vanishes
                 // on all of the \omega^i roots, where `i` is taken from the indices vector
                 // and \omega is a primitive root of unity used to generate the evaluation domain.
@@ -285,51 +494,61 @@ impl ReedSolomon {
     /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/dc5f74da0e9834fa842cdcb33c64b3a1fb1ad579/specs/_features/eip7594/polynomial-commitments-sampling.md#recover_data
     fn recover_polynomial_coefficient_erasure_pattern(
         &self,
-        data_eval: Vec<Scalar>,
+        data_eval: Vec<F>,
         erasure: ErasurePattern,
-    ) -> Result<Vec<Scalar>, RSError> {
+    ) -> Result<Vec<F>, RSError> {
         // Compute Z(X) which is the polynomial that vanishes on all
         // of the missing points
-        let z_x = self.construct_vanishing_poly_from_erasure_pattern(erasure)?;
+        let mut z_x = self.construct_vanishing_poly_from_erasure_pattern(erasure)?;
+        z_x.resize(self.evaluation_domain.size(), F::ZERO);
 
         // Compute Z(X)_eval which is the vanishing polynomial evaluated
         // at the missing points
-        let z_x_eval = self.evaluation_domain.fft_scalars(z_x.clone());
+        //
+        // Note: The buffer is reused for each of the intermediate steps below, and
+        // is taken from the scratch pool so that it is also reused across calls.
+        let mut buffer = self.scratch.take(z_x.len(), F::ZERO);
+        buffer.copy_from_slice(&z_x);
+        self.evaluation_domain.fft_scalars_inplace(&mut buffer);
 
         // Compute (D * Z)(X) or (E * Z)(X) (same polynomials)
-        let ez_eval: Vec<_> = z_x_eval
-            .iter()
+        //
+        // Note: Any evaluations that were not supplied are treated as zero.
+        let num_evaluations = data_eval.len();
+        buffer
+            .maybe_par_iter_mut()
             .zip(data_eval)
-            .map(|(zx, d)| zx * d)
-            .collect();
+            .for_each(|(zx, d)| *zx *= d);
+        for zx in buffer.iter_mut().skip(num_evaluations) {
+            *zx = F::ZERO;
+        }
 
-        let dz_poly = self.evaluation_domain.ifft_scalars(ez_eval);
+        // Compute (D * Z)(X) in monomial form and then evaluate it over the coset
+        self.evaluation_domain.ifft_scalars_inplace(&mut buffer);
+        self.evaluation_domain
+            .coset_fft_scalars_inplace(&mut buffer, &self.fft_coset_gen);
 
-        let coset_dz_eval = self
-            .evaluation_domain
-            .coset_fft_scalars(dz_poly, &self.fft_coset_gen);
-        let mut inv_coset_z_x_eval = self
-            .evaluation_domain
-            .coset_fft_scalars(z_x, &self.fft_coset_gen);
+        let mut inv_coset_z_x_eval = z_x;
+        self.evaluation_domain
+            .coset_fft_scalars_inplace(&mut inv_coset_z_x_eval, &self.fft_coset_gen);
         // We know that none of the values will be zero since we are evaluating z_x
         // over a coset, that we know it has no roots in.
         batch_inverse(&mut inv_coset_z_x_eval);
-        let coset_quotient_eval: Vec<_> = coset_dz_eval
-            .iter()
+        buffer
+            .maybe_par_iter_mut()
             .zip(inv_coset_z_x_eval)
-            .map(|(d, zx_inv)| d * zx_inv)
-            .collect();
+            .for_each(|(d, zx_inv)| *d *= zx_inv);
 
-        let coefficients = self
-            .evaluation_domain
-            .coset_ifft_scalars(coset_quotient_eval, &self.fft_coset_gen);
+        self.evaluation_domain
+            .coset_ifft_scalars_inplace(&mut buffer, &self.fft_coset_gen);
+        let coefficients = buffer;
 
         // Check that the polynomial being returned has the correct degree
         //
         // The first poly_len terms should describe the polynomial and the
         // higher terms should have zero coefficients.
         for coefficient in coefficients.iter().skip(self.poly_len) {
-            if *coefficient != Scalar::ZERO {
+            if *coefficient != F::ZERO {
                 return Err(RSError::PolynomialHasInvalidLength {
                     num_coefficients: coefficients.len(),
                     expected_num_coefficients: self.poly_len,
@@ -346,7 +565,7 @@ impl ReedSolomon {
 mod tests {
     use bls12_381::{ff::Field, Scalar};
 
-    use crate::{reed_solomon::ErasurePattern, BlockErasureIndices, ReedSolomon};
+    use crate::{errors::RSError, reed_solomon::ErasurePattern, BlockErasureIndices, ReedSolomon};
 
     #[test]
     #[should_panic]
@@ -357,7 +576,7 @@ mod tests {
         const EXPANSION_FACTOR: usize = 2;
         const BLOCK_SIZE: usize = 1;
 
-        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let rs = ReedSolomon::<Scalar>::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
         let block_erasure_indices: Vec<_> = (0..BLOCK_SIZE).collect();
 
         rs.construct_vanishing_poly_from_block_erasures(BlockErasureIndices(block_erasure_indices));
@@ -369,7 +588,7 @@ mod tests {
         const EXPANSION_FACTOR: usize = 2;
         const BLOCK_SIZE: usize = 1;
 
-        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let rs = ReedSolomon::<Scalar>::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
         let poly_coeff: Vec<_> = (0..16).map(|i| -Scalar::from(i)).collect();
 
         let codewords = rs.encode(poly_coeff.clone()).unwrap();
@@ -391,7 +610,7 @@ mod tests {
         const EXPANSION_FACTOR: usize = 2;
         const BLOCK_SIZE: usize = 16;
 
-        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let rs = ReedSolomon::<Scalar>::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
         let z =
             rs.construct_vanishing_poly_from_block_erasures(BlockErasureIndices(indices.clone()));
 
@@ -423,7 +642,7 @@ mod tests {
         const EXPANSION_FACTOR: usize = 2;
         const BLOCK_SIZE: usize = 4;
 
-        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let rs = ReedSolomon::<Scalar>::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
         let got_z_x =
             rs.construct_vanishing_poly_from_block_erasures(BlockErasureIndices(indices.clone()));
         let got_z_x_lagrange_form = rs.evaluation_domain.fft_scalars(got_z_x);
@@ -452,7 +671,7 @@ mod tests {
         const EXPANSION_FACTOR: usize = 2;
         const BLOCK_SIZE: usize = 1; // Note: This is not used for random erasures
 
-        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let rs = ReedSolomon::<Scalar>::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
         let poly_coeff = (0..POLY_LEN)
             .map(|i| Scalar::from(i as u64))
             .collect::<Vec<_>>();
@@ -484,23 +703,142 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decode_random_erasures() {
+        const POLY_LEN: usize = 16;
+        const EXPANSION_FACTOR: usize = 4;
+
+        let rs = ReedSolomon::<Scalar>::try_new(POLY_LEN, EXPANSION_FACTOR, 1).unwrap();
+        let poly_coeff: Vec<_> = (0..POLY_LEN).map(|i| -Scalar::from(i as u64)).collect();
+        let codeword = rs.encode(poly_coeff.clone()).unwrap();
+
+        // Keep every third evaluation until only `poly_len` evaluations remain
+        let mut num_kept = 0;
+        let received: Vec<_> = codeword
+            .iter()
+            .enumerate()
+            .map(|(i, evaluation)| {
+                let keep = i % 3 == 0 && num_kept < POLY_LEN;
+                num_kept += usize::from(keep);
+                keep.then_some(*evaluation)
+            })
+            .collect();
+        assert_eq!(num_kept, POLY_LEN);
+        assert_eq!(rs.decode(&received).unwrap(), poly_coeff);
+
+        // Removing one more evaluation makes the codeword unrecoverable
+        let mut received = received;
+        let index = received.iter().position(Option::is_some).unwrap();
+        received[index] = None;
+        assert!(matches!(
+            rs.decode(&received),
+            Err(RSError::TooManyErasures { .. })
+        ));
+
+        assert!(matches!(
+            rs.decode(&received[1..]),
+            Err(RSError::CodewordHasInvalidLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_with_errors() {
+        const POLY_LEN: usize = 16;
+        const EXPANSION_FACTOR: usize = 4;
+
+        let rs = ReedSolomon::<Scalar>::try_new(POLY_LEN, EXPANSION_FACTOR, 1).unwrap();
+        let poly_coeff: Vec<_> = (0..POLY_LEN).map(|i| Scalar::from(i as u64 + 1)).collect();
+        let codeword: Vec<_> = rs
+            .encode(poly_coeff.clone())
+            .unwrap()
+            .into_iter()
+            .map(Some)
+            .collect();
+
+        // No errors
+        let decoded = rs.decode_with_errors(&codeword).unwrap();
+        assert_eq!(decoded.poly_coefficient_form, poly_coeff);
+        assert!(decoded.corrupted_indices.is_empty());
+
+        // Erase 10 evaluations, which leaves 54 evaluations and allows up to
+        // (54 - 16) / 2 = 19 errors to be corrected.
+        let mut received = codeword.clone();
+        for evaluation in received.iter_mut().step_by(6).take(10) {
+            *evaluation = None;
+        }
+        let corrupted_indices: Vec<_> = (0..19).map(|i| i * 3 + 1).collect();
+        for index in &corrupted_indices {
+            received[*index] = received[*index].map(|value| value + Scalar::ONE);
+        }
+
+        let decoded = rs.decode_with_errors(&received).unwrap();
+        assert_eq!(decoded.poly_coefficient_form, poly_coeff);
+        assert_eq!(decoded.corrupted_indices, corrupted_indices);
+
+        // Too many errors to correct
+        let mut received = codeword;
+        for evaluation in received.iter_mut().step_by(2) {
+            *evaluation = evaluation.map(|value| value.double());
+        }
+        assert!(matches!(
+            rs.decode_with_errors(&received),
+            Err(RSError::TooManyErrors {
+                max_num_errors_correctable: 24
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(matches!(
+            ReedSolomon::<Scalar>::try_new(3, 2, 1),
+            Err(RSError::ParameterNotPowerOfTwo {
+                parameter: "poly_len",
+                value: 3
+            })
+        ));
+        assert!(matches!(
+            ReedSolomon::<Scalar>::try_new(4, 0, 1),
+            Err(RSError::ParameterNotPowerOfTwo {
+                parameter: "expansion_factor",
+                value: 0
+            })
+        ));
+        assert!(matches!(
+            ReedSolomon::<Scalar>::try_new(4, 2, 16),
+            Err(RSError::BlockSizeLargerThanCodeword { .. })
+        ));
+    }
+
     #[test]
     fn smoke_test_recovery_upto_num_acceptable_block_erasures() {
+        check_recovery_upto_num_acceptable_block_erasures(2, 4);
+    }
+
+    #[test]
+    fn smoke_test_recovery_larger_expansion_factors() {
+        for (expansion_factor, block_size) in [(4, 4), (4, 16), (8, 8), (16, 32)] {
+            check_recovery_upto_num_acceptable_block_erasures(expansion_factor, block_size);
+        }
+    }
+
+    fn check_recovery_upto_num_acceptable_block_erasures(
+        expansion_factor: usize,
+        block_size: usize,
+    ) {
         const POLY_LEN: usize = 128;
-        const EXPANSION_FACTOR: usize = 2;
-        const BLOCK_SIZE: usize = 4;
 
-        let rs = ReedSolomon::new(POLY_LEN, EXPANSION_FACTOR, BLOCK_SIZE);
+        let rs = ReedSolomon::<Scalar>::new(POLY_LEN, expansion_factor, block_size);
         let poly_coeff = (0..POLY_LEN)
             .map(|i| Scalar::from(i as u64))
             .collect::<Vec<_>>();
 
         let original_codewords = rs.encode(poly_coeff.clone()).unwrap();
-        let num_block_erasures: Vec<_> = (0..=BLOCK_SIZE).collect();
+        let num_block_erasures: Vec<_> = (0..=block_size).collect();
 
         for num_block_erasures in num_block_erasures {
             let mut blocks: Vec<Vec<Scalar>> = original_codewords
-                .chunks(BLOCK_SIZE)
+                .chunks(block_size)
                 .map(|block| block.to_vec())
                 .collect();
 
@@ -0,0 +1,284 @@
+//! The cryptographic operations that KZG is built from, behind a trait, so that the
+//! implementation of the curve and the field can be swapped out.
+//!
+//! The field and group arithmetic, and the encodings of scalars and points, come from the `ff`
+//! and `group` traits that the associated types implement. The trait only adds the operations
+//! that a backend has a faster implementation of, or that `ff` and `group` do not cover.
+//!
+//! The `BlstBackend` uses the `bls12_381` crate, which is backed by `blst`. This is the backend
+//! that the rest of the library uses by default.
+//!
+//! The `arkworks` feature adds the `Arkworks` backend, which uses arkworks instead of `blst`. It
+//! is slower than the `BlstBackend`, and is used to cross-check it.
+
+use bls12_381::{
+    ff::PrimeField,
+    fixed_base_msm::{FixedBaseMSM, UsePrecomp},
+    g1_batch_normalize,
+    glv::g1_lincomb_glv,
+    group::{
+        prime::{PrimeCurve, PrimeCurveAffine},
+        Curve,
+    },
+    lincomb::{g1_lincomb, g2_lincomb},
+    multi_pairings, reduce_bytes_to_scalar_bias,
+    subgroup_check::g1_batch_is_torsion_free,
+    G1Point, G1Projective, G2Point, G2Prepared, G2Projective, Scalar,
+};
+use std::fmt::Debug;
+
+#[cfg(feature = "arkworks")]
+pub mod arkworks;
+#[cfg(feature = "arkworks")]
+pub use arkworks::Arkworks;
+
+/// The operations over the scalar field, G1, G2 and the pairing of BLS12-381 that KZG needs.
+///
+/// The encodings of scalars and points are the ones of the `PrimeField` and `GroupEncoding`
+/// traits. For BLS12-381, these are:
+///
+/// - The little-endian encoding of a scalar, which must be less than the modulus.
+/// - The compressed encoding of a point in the consensus specs, where decoding checks that the
+///   point is on the curve and in the prime order subgroup.
+pub trait Backend: Debug + Clone + Copy + PartialEq + Eq + Send + Sync + 'static {
+    /// An element of the scalar field.
+    type Scalar: PrimeField;
+    /// A point in G1, in affine representation.
+    type G1Point: PrimeCurveAffine<Scalar = Self::Scalar, Curve = Self::G1Projective>;
+    /// A point in G1, in projective representation.
+    type G1Projective: PrimeCurve<Scalar = Self::Scalar, Affine = Self::G1Point>;
+    /// A point in G2, in affine representation.
+    type G2Point: PrimeCurveAffine<Scalar = Self::Scalar, Curve = Self::G2Projective>;
+    /// A point in G2, in projective representation.
+    type G2Projective: PrimeCurve<Scalar = Self::Scalar, Affine = Self::G2Point>;
+    /// A point in G2 that has been prepared for computing pairings.
+    type G2Prepared: Debug + Send + Sync;
+    /// Computes multi scalar multiplications where the points are known beforehand.
+    type FixedBaseMSM: Debug + Send + Sync;
+
+    /// Computes the sum of each point multiplied by its scalar.
+    ///
+    /// Returns None if the number of points and scalars differ.
+    fn g1_lincomb(points: &[Self::G1Point], scalars: &[Self::Scalar])
+        -> Option<Self::G1Projective>;
+
+    /// Computes the sum of each point multiplied by its scalar, in variable time.
+    ///
+    /// Note: This must only be used when the scalars are public.
+    fn g1_lincomb_vartime(
+        points: &[Self::G1Point],
+        scalars: &[Self::Scalar],
+    ) -> Option<Self::G1Projective> {
+        Self::g1_lincomb(points, scalars)
+    }
+
+    /// Computes the sum of each point multiplied by its scalar.
+    ///
+    /// Returns None if the number of points and scalars differ.
+    fn g2_lincomb(points: &[Self::G2Point], scalars: &[Self::Scalar])
+        -> Option<Self::G2Projective>;
+
+    /// Converts projective points to affine points.
+    fn g1_batch_normalize(points: &[Self::G1Projective]) -> Vec<Self::G1Point> {
+        let mut affine_points = vec![Self::G1Point::identity(); points.len()];
+        Self::G1Projective::batch_normalize(points, &mut affine_points);
+        affine_points
+    }
+
+    /// Converts projective points to affine points.
+    fn g2_batch_normalize(points: &[Self::G2Projective]) -> Vec<Self::G2Point> {
+        let mut affine_points = vec![Self::G2Point::identity(); points.len()];
+        Self::G2Projective::batch_normalize(points, &mut affine_points);
+        affine_points
+    }
+
+    /// Initializes a multi scalar multiplication over `generators`, precomputing multiples of
+    /// them according to `use_precomp`.
+    fn fixed_base_msm_new(
+        generators: Vec<Self::G1Point>,
+        use_precomp: UsePrecomp,
+    ) -> Self::FixedBaseMSM;
+
+    /// Computes the sum of each generator of `msm` multiplied by its scalar.
+    ///
+    /// Panics if the number of generators and scalars differ.
+    fn fixed_base_msm(msm: &Self::FixedBaseMSM, scalars: &[Self::Scalar]) -> Self::G1Projective;
+
+    fn g2_prepare(point: &Self::G2Point) -> Self::G2Prepared;
+
+    /// Returns true if the product of the pairings of each pair is the identity.
+    fn multi_pairings(pairs: &[(&Self::G1Point, &Self::G2Prepared)]) -> bool;
+
+    /// Interprets 32 bytes as a big-endian integer, and reduces it modulo the scalar field.
+    ///
+    /// Note: This is biased, so it should only be used for Fiat-Shamir challenges, where this
+    /// is the reduction that the consensus specs use.
+    fn reduce_bytes_to_scalar(bytes: [u8; 32]) -> Self::Scalar;
+
+    /// Returns true if all of the points are in the prime order subgroup, given a random mask for
+    /// each point, where bit `k` of the mask determines whether the point is in the `k`'th of the
+    /// random subsets that are checked.
+    ///
+    /// Returns None if the number of points and masks are not the same.
+    ///
+    /// Note: The points must be on the curve, and the masks must be unpredictable to whoever
+    /// chose the points.
+    fn g1_batch_is_torsion_free(points: &[Self::G1Point], subset_masks: &[u128]) -> Option<bool>;
+}
+
+/// The backend that uses the `bls12_381` crate, which is backed by `blst`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlstBackend;
+
+impl Backend for BlstBackend {
+    type Scalar = Scalar;
+    type G1Point = G1Point;
+    type G1Projective = G1Projective;
+    type G2Point = G2Point;
+    type G2Projective = G2Projective;
+    type G2Prepared = G2Prepared;
+    type FixedBaseMSM = FixedBaseMSM;
+
+    fn g1_lincomb(points: &[G1Point], scalars: &[Scalar]) -> Option<G1Projective> {
+        g1_lincomb(points, scalars)
+    }
+
+    fn g1_lincomb_vartime(points: &[G1Point], scalars: &[Scalar]) -> Option<G1Projective> {
+        g1_lincomb_glv(points, scalars)
+    }
+
+    fn g2_lincomb(points: &[G2Point], scalars: &[Scalar]) -> Option<G2Projective> {
+        g2_lincomb(points, scalars)
+    }
+
+    fn g1_batch_normalize(points: &[G1Projective]) -> Vec<G1Point> {
+        g1_batch_normalize(points)
+    }
+
+    fn fixed_base_msm_new(generators: Vec<G1Point>, use_precomp: UsePrecomp) -> FixedBaseMSM {
+        FixedBaseMSM::new(generators, use_precomp)
+    }
+
+    fn fixed_base_msm(msm: &FixedBaseMSM, scalars: &[Scalar]) -> G1Projective {
+        msm.msm(scalars)
+    }
+
+    fn g2_prepare(point: &G2Point) -> G2Prepared {
+        G2Prepared::from(*point)
+    }
+
+    fn multi_pairings(pairs: &[(&G1Point, &G2Prepared)]) -> bool {
+        multi_pairings(pairs)
+    }
+
+    fn reduce_bytes_to_scalar(bytes: [u8; 32]) -> Scalar {
+        reduce_bytes_to_scalar_bias(bytes)
+    }
+
+    fn g1_batch_is_torsion_free(points: &[G1Point], subset_masks: &[u128]) -> Option<bool> {
+        g1_batch_is_torsion_free(points, subset_masks)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use bls12_381::{
+        ff::Field,
+        group::{Group, GroupEncoding},
+    };
+
+    /// Checks that the backend satisfies the properties that the rest of the library relies on.
+    ///
+    /// This only uses the trait, so that it can be run against every backend.
+    pub(crate) fn check_backend<B: Backend>() {
+        let two = B::Scalar::from(2);
+        let three = B::Scalar::from(3);
+        let six = B::Scalar::from(6);
+        let one = B::Scalar::ONE;
+
+        // Scalar encodings are little-endian and must be canonical
+        let mut repr = <B::Scalar as PrimeField>::Repr::default();
+        assert_eq!(repr.as_ref().len(), 32);
+        repr.as_mut()[0] = 6;
+        assert_eq!(Option::from(B::Scalar::from_repr(repr)), Some(six));
+        repr.as_mut().fill(0xff);
+        assert!(bool::from(B::Scalar::from_repr(repr).is_none()));
+
+        // Point encodings are compressed, and invalid encodings are rejected
+        let g1 = B::G1Point::generator();
+        let g1_two = (g1 * two).to_affine();
+        for point in [g1, g1_two, B::G1Point::identity()] {
+            let bytes = point.to_bytes();
+            assert_eq!(bytes.as_ref().len(), 48);
+            assert_eq!(Option::from(B::G1Point::from_bytes(&bytes)), Some(point));
+        }
+        assert!(bool::from(
+            B::G1Point::from_bytes(&Default::default()).is_none()
+        ));
+        let g2 = B::G2Point::generator();
+        assert_eq!(g2.to_bytes().as_ref().len(), 96);
+
+        // Linear combinations
+        assert_eq!(
+            B::g1_lincomb(&[g1, g1_two, B::G1Point::identity()], &[three, one, six]),
+            Some(g1 * B::Scalar::from(5))
+        );
+        assert_eq!(
+            B::g1_lincomb_vartime(&[g1, g1_two], &[three, one]),
+            Some(g1 * B::Scalar::from(5))
+        );
+        assert_eq!(B::g1_lincomb(&[g1], &[]), None);
+        assert_eq!(B::g2_lincomb(&[g2, g2], &[two, one]), Some(g2 * three));
+
+        let msm = B::fixed_base_msm_new(vec![g1, g1_two], UsePrecomp::No);
+        assert_eq!(
+            B::fixed_base_msm(&msm, &[three, one]),
+            g1 * B::Scalar::from(5)
+        );
+
+        // Batch normalization keeps the identity in its position
+        let projective = [g1 * two, B::G1Projective::identity(), g1.to_curve()];
+        assert_eq!(
+            B::g1_batch_normalize(&projective),
+            vec![g1_two, B::G1Point::identity(), g1]
+        );
+        assert_eq!(B::g2_batch_normalize(&[g2 * one]), vec![g2]);
+
+        // e(2 * G1, 3 * G2) * e(-6 * G1, G2) = 1
+        let g2_three = B::g2_prepare(&(g2 * three).to_affine());
+        let g2_gen = B::g2_prepare(&g2);
+        let neg_g1_six = (-(g1 * six)).to_affine();
+        assert!(B::multi_pairings(&[
+            (&g1_two, &g2_three),
+            (&neg_g1_six, &g2_gen)
+        ]));
+        assert!(!B::multi_pairings(&[(&g1_two, &g2_three)]));
+
+        // The reduction interprets the bytes as big-endian
+        let mut bytes = [0u8; 32];
+        bytes[31] = 6;
+        assert_eq!(B::reduce_bytes_to_scalar(bytes), six);
+        assert_eq!(B::reduce_bytes_to_scalar([0; 32]), B::Scalar::ZERO);
+    }
+
+    #[test]
+    fn blst_backend() {
+        check_backend::<BlstBackend>();
+    }
+
+    #[test]
+    fn blst_torsion_check() {
+        let points: Vec<_> = (1..=8u64)
+            .map(|i| (G1Projective::generator() * Scalar::from(i)).into())
+            .collect();
+        assert_eq!(
+            BlstBackend::g1_batch_is_torsion_free(&points, &[0b1010_1010; 8]),
+            Some(true)
+        );
+        assert_eq!(
+            BlstBackend::g1_batch_is_torsion_free(&points, &[0; 7]),
+            None
+        );
+    }
+}
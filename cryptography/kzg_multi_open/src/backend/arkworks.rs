@@ -0,0 +1,489 @@
+//! A backend that uses arkworks instead of blst.
+//!
+//! This backend is meant for cross-validating the `BlstBackend`. It is much slower, and none of
+//! its operations are constant time. None of its operations call blst, but blst is still built
+//! and linked, because the `BlstBackend` is the default of every generic type.
+//!
+//! The arkworks types do not implement the `ff` and `group` traits that the `Backend` trait
+//! relies on, so they are wrapped in the types of this module, which implement them. The
+//! encodings of scalars and points are the same as the ones of the `BlstBackend`.
+
+use super::Backend;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr as _, CurveGroup as _, VariableBaseMSM};
+use ark_ff::{BigInteger as _, Field as _, MontFp, One as _, PrimeField as _, Zero as _};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bls12_381::{
+    ff::{helpers::sqrt_ratio_generic, Field, PrimeField},
+    fixed_base_msm::UsePrecomp,
+    group::{
+        prime::{PrimeCurve, PrimeCurveAffine, PrimeGroup},
+        Curve, Group, GroupEncoding,
+    },
+};
+use rand_core::RngCore;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+#[cfg(test)]
+mod tests;
+
+/// The backend that uses arkworks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Arkworks;
+
+impl Backend for Arkworks {
+    type Scalar = ArkScalar;
+    type G1Point = ArkG1Point;
+    type G1Projective = ArkG1Projective;
+    type G2Point = ArkG2Point;
+    type G2Projective = ArkG2Projective;
+    type G2Prepared = <Bls12_381 as Pairing>::G2Prepared;
+    type FixedBaseMSM = ArkFixedBaseMSM;
+
+    fn g1_lincomb(points: &[ArkG1Point], scalars: &[ArkScalar]) -> Option<ArkG1Projective> {
+        let points: Vec<_> = points.iter().map(|point| point.0).collect();
+        msm(&points, scalars).map(ArkG1Projective)
+    }
+
+    fn g2_lincomb(points: &[ArkG2Point], scalars: &[ArkScalar]) -> Option<ArkG2Projective> {
+        let points: Vec<_> = points.iter().map(|point| point.0).collect();
+        msm(&points, scalars).map(ArkG2Projective)
+    }
+
+    fn fixed_base_msm_new(
+        generators: Vec<ArkG1Point>,
+        _use_precomp: UsePrecomp,
+    ) -> ArkFixedBaseMSM {
+        ArkFixedBaseMSM {
+            generators: generators.into_iter().map(|point| point.0).collect(),
+        }
+    }
+
+    fn fixed_base_msm(msm_ctx: &ArkFixedBaseMSM, scalars: &[ArkScalar]) -> ArkG1Projective {
+        msm(&msm_ctx.generators, scalars)
+            .map(ArkG1Projective)
+            .expect("number of generators and scalars must be equal")
+    }
+
+    fn g2_prepare(point: &ArkG2Point) -> Self::G2Prepared {
+        point.0.into()
+    }
+
+    fn multi_pairings(pairs: &[(&ArkG1Point, &Self::G2Prepared)]) -> bool {
+        let (g1_points, g2_points): (Vec<_>, Vec<_>) = pairs
+            .iter()
+            .map(|(g1_point, g2_prepared)| (g1_point.0, (*g2_prepared).clone()))
+            .unzip();
+        // The target group is written additively in arkworks, so `.0` is the field element
+        Bls12_381::multi_pairing(g1_points, g2_points).0.is_one()
+    }
+
+    fn reduce_bytes_to_scalar(bytes: [u8; 32]) -> ArkScalar {
+        ArkScalar(Fr::from_be_bytes_mod_order(&bytes))
+    }
+
+    fn g1_batch_is_torsion_free(points: &[ArkG1Point], subset_masks: &[u128]) -> Option<bool> {
+        // Each point is checked on its own, so the masks are not needed
+        (points.len() == subset_masks.len()).then(|| {
+            points
+                .iter()
+                .all(|point| point.0.is_in_correct_subgroup_assuming_on_curve())
+        })
+    }
+}
+
+/// Computes the sum of each point multiplied by its scalar, or returns None if the number of
+/// points and scalars differ.
+fn msm<G: VariableBaseMSM<ScalarField = Fr>>(
+    points: &[G::MulBase],
+    scalars: &[ArkScalar],
+) -> Option<G> {
+    let scalars: Vec<_> = scalars.iter().map(|scalar| scalar.0).collect();
+    G::msm(points, &scalars).ok()
+}
+
+/// Converts an `Option` into the `CtOption` that the `ff` and `group` traits return.
+fn ct_option<T: Default>(value: Option<T>) -> CtOption<T> {
+    let is_some = Choice::from(u8::from(value.is_some()));
+    CtOption::new(value.unwrap_or_default(), is_some)
+}
+
+/// Computes a multi scalar multiplication over the same generators for every call.
+///
+/// arkworks does not have precomputed tables for this, so each call computes a variable base
+/// multi scalar multiplication.
+#[derive(Debug, Clone)]
+pub struct ArkFixedBaseMSM {
+    generators: Vec<G1Affine>,
+}
+
+/// Implements a binary operator on the wrapper `$lhs` with a right hand side of `$rhs` or
+/// `&$rhs`, by applying it to the wrapped arkworks values and wrapping the result in `$output`.
+macro_rules! impl_binop {
+    ($lhs:ident, $rhs:ident, $output:ident, $op_trait:ident :: $op:ident) => {
+        impl $op_trait<$rhs> for $lhs {
+            type Output = $output;
+
+            fn $op(self, rhs: $rhs) -> $output {
+                $output($op_trait::$op(self.0, &rhs.0))
+            }
+        }
+
+        impl<'a> $op_trait<&'a $rhs> for $lhs {
+            type Output = $output;
+
+            fn $op(self, rhs: &'a $rhs) -> $output {
+                $output($op_trait::$op(self.0, &rhs.0))
+            }
+        }
+    };
+}
+
+/// Implements a binary operator and its assigning version on the wrapper `$lhs`, with a right
+/// hand side of `$rhs` or `&$rhs`.
+macro_rules! impl_binop_assign {
+    ($lhs:ident, $rhs:ident, $op_trait:ident :: $op:ident, $assign_trait:ident :: $assign:ident) => {
+        impl_binop!($lhs, $rhs, $lhs, $op_trait::$op);
+
+        impl $assign_trait<$rhs> for $lhs {
+            fn $assign(&mut self, rhs: $rhs) {
+                *self = $op_trait::$op(*self, &rhs);
+            }
+        }
+
+        impl<'a> $assign_trait<&'a $rhs> for $lhs {
+            fn $assign(&mut self, rhs: &'a $rhs) {
+                *self = $op_trait::$op(*self, rhs);
+            }
+        }
+    };
+}
+
+/// An element of the scalar field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ArkScalar(pub Fr);
+
+impl_binop_assign!(ArkScalar, ArkScalar, Add::add, AddAssign::add_assign);
+impl_binop_assign!(ArkScalar, ArkScalar, Sub::sub, SubAssign::sub_assign);
+impl_binop_assign!(ArkScalar, ArkScalar, Mul::mul, MulAssign::mul_assign);
+
+impl Neg for ArkScalar {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Sum for ArkScalar {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Self> for ArkScalar {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Product for ArkScalar {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl<'a> Product<&'a Self> for ArkScalar {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl From<u64> for ArkScalar {
+    fn from(value: u64) -> Self {
+        Self(Fr::from(value))
+    }
+}
+
+impl ConstantTimeEq for ArkScalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Choice::from(u8::from(self == other))
+    }
+}
+
+impl ConditionallySelectable for ArkScalar {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        if bool::from(choice) {
+            *b
+        } else {
+            *a
+        }
+    }
+}
+
+impl Field for ArkScalar {
+    const ZERO: Self = Self(MontFp!("0"));
+    const ONE: Self = Self(MontFp!("1"));
+
+    fn random(mut rng: impl RngCore) -> Self {
+        // Reducing 64 bytes makes the bias negligible
+        let mut bytes = [0u8; 64];
+        rng.fill_bytes(&mut bytes);
+        Self(Fr::from_le_bytes_mod_order(&bytes))
+    }
+
+    fn square(&self) -> Self {
+        Self(self.0.square())
+    }
+
+    fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        ct_option(self.0.inverse().map(Self))
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        ct_option(self.0.sqrt().map(Self))
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        sqrt_ratio_generic(num, div)
+    }
+}
+
+impl PrimeField for ArkScalar {
+    type Repr = [u8; 32];
+
+    fn from_repr(repr: [u8; 32]) -> CtOption<Self> {
+        // This fails if the scalar is not less than the modulus
+        ct_option(Fr::deserialize_compressed(&repr[..]).ok().map(Self))
+    }
+
+    fn to_repr(&self) -> [u8; 32] {
+        let mut repr = [0u8; 32];
+        repr.copy_from_slice(&self.0.into_bigint().to_bytes_le());
+        repr
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from(u8::from(self.0.into_bigint().is_odd()))
+    }
+
+    const MODULUS: &'static str =
+        "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+    const NUM_BITS: u32 = 255;
+    const CAPACITY: u32 = Self::NUM_BITS - 1;
+    const TWO_INV: Self = Self(MontFp!(
+        "26217937587563095239723870254092982918845276250263818911301829349969290592257"
+    ));
+    const MULTIPLICATIVE_GENERATOR: Self = Self(MontFp!("7"));
+    const S: u32 = 32;
+    const ROOT_OF_UNITY: Self = Self(MontFp!(
+        "10238227357739495823651030575849232062558860180284477541189508159991286009131"
+    ));
+    const ROOT_OF_UNITY_INV: Self = Self(MontFp!(
+        "2361660012179324004544952053205906600198439069793331036989202475535180077688"
+    ));
+    const DELTA: Self = Self(MontFp!(
+        "3793952369011177517951424454785176000433849974408744014172535497121832470999"
+    ));
+}
+
+/// Implements the `group` traits for the wrappers of the affine and projective points of a
+/// group, whose compressed encoding is `$size` bytes.
+macro_rules! impl_group {
+    ($affine:ident, $projective:ident, $compressed:ident, $size:expr) => {
+        impl_binop!($affine, ArkScalar, $projective, Mul::mul);
+        impl_binop_assign!($projective, $projective, Add::add, AddAssign::add_assign);
+        impl_binop_assign!($projective, $projective, Sub::sub, SubAssign::sub_assign);
+        impl_binop_assign!($projective, $affine, Add::add, AddAssign::add_assign);
+        impl_binop_assign!($projective, $affine, Sub::sub, SubAssign::sub_assign);
+        impl_binop_assign!($projective, ArkScalar, Mul::mul, MulAssign::mul_assign);
+
+        impl Neg for $affine {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Neg for $projective {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Sum for $projective {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::identity(), Add::add)
+            }
+        }
+
+        impl<'a> Sum<&'a Self> for $projective {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::identity(), Add::add)
+            }
+        }
+
+        /// The compressed encoding of a point.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $compressed([u8; $size]);
+
+        impl Default for $compressed {
+            fn default() -> Self {
+                Self([0; $size])
+            }
+        }
+
+        impl AsRef<[u8]> for $compressed {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl AsMut<[u8]> for $compressed {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl GroupEncoding for $affine {
+            type Repr = $compressed;
+
+            fn from_bytes(bytes: &$compressed) -> CtOption<Self> {
+                ct_option(
+                    CanonicalDeserialize::deserialize_compressed(bytes.as_ref())
+                        .ok()
+                        .map(Self),
+                )
+            }
+
+            fn from_bytes_unchecked(bytes: &$compressed) -> CtOption<Self> {
+                ct_option(
+                    CanonicalDeserialize::deserialize_compressed_unchecked(bytes.as_ref())
+                        .ok()
+                        .map(Self),
+                )
+            }
+
+            fn to_bytes(&self) -> $compressed {
+                let mut bytes = $compressed::default();
+                self.0
+                    .serialize_compressed(bytes.as_mut())
+                    .expect("buffer has the size of a compressed point");
+                bytes
+            }
+        }
+
+        impl GroupEncoding for $projective {
+            type Repr = $compressed;
+
+            fn from_bytes(bytes: &$compressed) -> CtOption<Self> {
+                let point: Option<$affine> = $affine::from_bytes(bytes).into();
+                ct_option(point.map(|point| point.to_curve()))
+            }
+
+            fn from_bytes_unchecked(bytes: &$compressed) -> CtOption<Self> {
+                let point: Option<$affine> = $affine::from_bytes_unchecked(bytes).into();
+                ct_option(point.map(|point| point.to_curve()))
+            }
+
+            fn to_bytes(&self) -> $compressed {
+                self.to_affine().to_bytes()
+            }
+        }
+
+        impl PrimeCurveAffine for $affine {
+            type Scalar = ArkScalar;
+            type Curve = $projective;
+
+            fn identity() -> Self {
+                Self(ark_ec::AffineRepr::zero())
+            }
+
+            fn generator() -> Self {
+                Self(ark_ec::AffineRepr::generator())
+            }
+
+            fn is_identity(&self) -> Choice {
+                Choice::from(u8::from(self.0.is_zero()))
+            }
+
+            fn to_curve(&self) -> $projective {
+                $projective(self.0.into_group())
+            }
+        }
+
+        impl Group for $projective {
+            type Scalar = ArkScalar;
+
+            fn random(rng: impl RngCore) -> Self {
+                Self::generator() * ArkScalar::random(rng)
+            }
+
+            fn identity() -> Self {
+                Self(ark_ff::Zero::zero())
+            }
+
+            fn generator() -> Self {
+                Self(ark_ec::Group::generator())
+            }
+
+            fn is_identity(&self) -> Choice {
+                Choice::from(u8::from(self.0.is_zero()))
+            }
+
+            fn double(&self) -> Self {
+                Self(ark_ec::Group::double(&self.0))
+            }
+        }
+
+        impl Curve for $projective {
+            type AffineRepr = $affine;
+
+            fn batch_normalize(p: &[Self], q: &mut [$affine]) {
+                assert_eq!(p.len(), q.len());
+                let p: Vec<_> = p.iter().map(|point| point.0).collect();
+                for (q, p) in q.iter_mut().zip(ark_ec::CurveGroup::normalize_batch(&p)) {
+                    *q = $affine(p);
+                }
+            }
+
+            fn to_affine(&self) -> $affine {
+                $affine(self.0.into_affine())
+            }
+        }
+
+        impl PrimeGroup for $projective {}
+
+        impl PrimeCurve for $projective {
+            type Affine = $affine;
+        }
+    };
+}
+
+/// A point in G1, in affine representation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ArkG1Point(pub G1Affine);
+
+/// A point in G1, in projective representation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ArkG1Projective(pub G1Projective);
+
+/// A point in G2, in affine representation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ArkG2Point(pub G2Affine);
+
+/// A point in G2, in projective representation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ArkG2Projective(pub G2Projective);
+
+impl_group!(ArkG1Point, ArkG1Projective, ArkG1Compressed, 48);
+impl_group!(ArkG2Point, ArkG2Projective, ArkG2Compressed, 96);
@@ -0,0 +1,308 @@
+//! Cross-validates the `BlstBackend` against arkworks, by converting the inputs of each operation
+//! to arkworks through their encodings, and checking that both libraries compute the same result.
+
+use super::{
+    ArkG1Compressed, ArkG1Point, ArkG1Projective, ArkG2Compressed, ArkG2Point, ArkScalar, Arkworks,
+};
+use crate::backend::{Backend, BlstBackend};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field as _, One, PrimeField as _};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bls12_381::{
+    ff::{Field, PrimeField},
+    fixed_base_msm::UsePrecomp,
+    group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding},
+    G1Point, G2Point, Scalar,
+};
+use polynomial::domain::Domain;
+use rand::thread_rng;
+
+// Both libraries encode scalars in little-endian order, and points in the compressed form of
+// the consensus specs.
+
+fn to_arkworks_scalar(scalar: &Scalar) -> Fr {
+    Fr::deserialize_compressed(&scalar.to_repr()[..]).unwrap()
+}
+
+fn to_arkworks_g1(point: &G1Point) -> G1Affine {
+    G1Affine::deserialize_compressed(point.to_bytes().as_ref()).unwrap()
+}
+
+fn to_arkworks_g2(point: &G2Point) -> G2Affine {
+    G2Affine::deserialize_compressed(point.to_bytes().as_ref()).unwrap()
+}
+
+fn g1_to_compressed(point: &G1Affine) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+fn g2_to_compressed(point: &G2Affine) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn generators_match() {
+    assert_eq!(
+        g1_to_compressed(&G1Affine::generator()),
+        G1Point::generator().to_bytes().as_ref()
+    );
+    assert_eq!(
+        g2_to_compressed(&G2Affine::generator()),
+        G2Point::generator().to_bytes().as_ref()
+    );
+}
+
+#[test]
+fn field_operations_match() {
+    let mut rng = thread_rng();
+    for _ in 0..100 {
+        let (a, b) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+        let (ark_a, ark_b) = (to_arkworks_scalar(&a), to_arkworks_scalar(&b));
+
+        assert_eq!(ark_a * ark_b, to_arkworks_scalar(&(a * b)));
+        assert_eq!(ark_a + ark_b, to_arkworks_scalar(&(a + b)));
+        assert_eq!(
+            ark_a.inverse(),
+            Option::<Scalar>::from(a.invert()).map(|inv| to_arkworks_scalar(&inv))
+        );
+    }
+}
+
+#[test]
+fn reduction_matches() {
+    let mut rng = thread_rng();
+    for _ in 0..100 {
+        let bytes: [u8; 32] = rand::Rng::gen(&mut rng);
+        assert_eq!(
+            Fr::from_be_bytes_mod_order(&bytes),
+            to_arkworks_scalar(&BlstBackend::reduce_bytes_to_scalar(bytes))
+        );
+    }
+}
+
+#[test]
+fn group_operations_match() {
+    let mut rng = thread_rng();
+    let points: Vec<_> = (0..16)
+        .map(|_| bls12_381::G1Projective::random(&mut rng).to_affine())
+        .collect();
+    let scalars: Vec<_> = (0..16).map(|_| Scalar::random(&mut rng)).collect();
+    let ark_points: Vec<_> = points.iter().map(to_arkworks_g1).collect();
+    let ark_scalars: Vec<_> = scalars.iter().map(to_arkworks_scalar).collect();
+    let expected = G1Projective::msm(&ark_points, &ark_scalars)
+        .unwrap()
+        .into_affine();
+
+    assert_eq!(
+        (ark_points[0] * ark_scalars[0]).into_affine(),
+        to_arkworks_g1(&(points[0] * scalars[0]).to_affine())
+    );
+    let lincomb = BlstBackend::g1_lincomb(&points, &scalars).unwrap();
+    assert_eq!(to_arkworks_g1(&lincomb.to_affine()), expected);
+    let lincomb = BlstBackend::g1_lincomb_vartime(&points, &scalars).unwrap();
+    assert_eq!(to_arkworks_g1(&lincomb.to_affine()), expected);
+    for use_precomp in [UsePrecomp::No, UsePrecomp::Yes { width: 8 }] {
+        let msm = BlstBackend::fixed_base_msm_new(points.clone(), use_precomp);
+        let sum = BlstBackend::fixed_base_msm(&msm, &scalars);
+        assert_eq!(to_arkworks_g1(&sum.to_affine()), expected);
+    }
+
+    let g2_points: Vec<_> = (0..4)
+        .map(|_| bls12_381::G2Projective::random(&mut rng).to_affine())
+        .collect();
+    let ark_g2_points: Vec<_> = g2_points.iter().map(to_arkworks_g2).collect();
+    let lincomb = BlstBackend::g2_lincomb(&g2_points, &scalars[..4]).unwrap();
+    assert_eq!(
+        to_arkworks_g2(&lincomb.to_affine()),
+        G2Projective::msm(&ark_g2_points, &ark_scalars[..4])
+            .unwrap()
+            .into_affine()
+    );
+}
+
+#[test]
+fn fft_matches() {
+    let mut rng = thread_rng();
+    for size in [1, 2, 64, 4096] {
+        let coefficients: Vec<_> = (0..size).map(|_| Scalar::random(&mut rng)).collect();
+        let ark_coefficients: Vec<_> = coefficients.iter().map(to_arkworks_scalar).collect();
+        let ark_domain = Radix2EvaluationDomain::<Fr>::new(size).unwrap();
+
+        let evaluations: Vec<_> = Domain::new(size)
+            .fft_scalars(coefficients)
+            .iter()
+            .map(to_arkworks_scalar)
+            .collect();
+        assert_eq!(ark_domain.fft(&ark_coefficients), evaluations);
+        assert_eq!(ark_domain.ifft(&evaluations), ark_coefficients);
+    }
+}
+
+#[test]
+fn pairings_match() {
+    let mut rng = thread_rng();
+    let a = Scalar::random(&mut rng);
+    let g1 = (G1Point::generator() * a).to_affine();
+    let neg_g1 = -g1;
+    let g2 = G2Point::generator();
+    let g2_prepared = BlstBackend::g2_prepare(&g2);
+
+    for pairs in [[g1, neg_g1], [g1, g1]] {
+        let (ark_g1s, ark_g2s): (Vec<_>, Vec<_>) = pairs
+            .iter()
+            .map(|g1| (to_arkworks_g1(g1), to_arkworks_g2(&g2)))
+            .unzip();
+        // The target group is written additively in arkworks, so `.0` is the field element
+        let ark_is_identity = Bls12_381::multi_pairing(ark_g1s, ark_g2s).0.is_one();
+        assert_eq!(
+            ark_is_identity,
+            BlstBackend::multi_pairings(&[(&pairs[0], &g2_prepared), (&pairs[1], &g2_prepared)])
+        );
+    }
+}
+
+#[test]
+fn arkworks_backend() {
+    crate::backend::tests::check_backend::<Arkworks>();
+}
+
+#[test]
+fn scalar_constants_match() {
+    let constants = [
+        (ArkScalar::TWO_INV, Scalar::TWO_INV),
+        (
+            ArkScalar::MULTIPLICATIVE_GENERATOR,
+            Scalar::MULTIPLICATIVE_GENERATOR,
+        ),
+        (ArkScalar::ROOT_OF_UNITY, Scalar::ROOT_OF_UNITY),
+        (ArkScalar::ROOT_OF_UNITY_INV, Scalar::ROOT_OF_UNITY_INV),
+        (ArkScalar::DELTA, Scalar::DELTA),
+    ];
+    for (ark_constant, constant) in constants {
+        assert_eq!(ark_constant.to_repr(), constant.to_repr());
+    }
+    assert_eq!(ArkScalar::MODULUS, Scalar::MODULUS);
+    assert_eq!(ArkScalar::S, Scalar::S);
+}
+
+fn to_backend_scalar(scalar: &Scalar) -> ArkScalar {
+    ArkScalar::from_repr(scalar.to_repr()).unwrap()
+}
+
+fn to_backend_g1(point: &G1Point) -> ArkG1Point {
+    let mut bytes = ArkG1Compressed::default();
+    bytes.as_mut().copy_from_slice(point.to_bytes().as_ref());
+    ArkG1Point::from_bytes(&bytes).unwrap()
+}
+
+fn to_backend_g2(point: &G2Point) -> ArkG2Point {
+    let mut bytes = ArkG2Compressed::default();
+    bytes.as_mut().copy_from_slice(point.to_bytes().as_ref());
+    ArkG2Point::from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn backend_operations_match() {
+    let mut rng = thread_rng();
+    let points: Vec<_> = (0..16)
+        .map(|_| bls12_381::G1Projective::random(&mut rng).to_affine())
+        .collect();
+    let scalars: Vec<_> = (0..16).map(|_| Scalar::random(&mut rng)).collect();
+    let ark_points: Vec<_> = points.iter().map(to_backend_g1).collect();
+    let ark_scalars: Vec<_> = scalars.iter().map(to_backend_scalar).collect();
+    let expected = BlstBackend::g1_lincomb(&points, &scalars)
+        .unwrap()
+        .to_bytes();
+
+    let lincomb = Arkworks::g1_lincomb(&ark_points, &ark_scalars).unwrap();
+    assert_eq!(lincomb.to_bytes().as_ref(), expected.as_ref());
+    let lincomb = Arkworks::g1_lincomb_vartime(&ark_points, &ark_scalars).unwrap();
+    assert_eq!(lincomb.to_bytes().as_ref(), expected.as_ref());
+    let msm = Arkworks::fixed_base_msm_new(ark_points, UsePrecomp::Yes { width: 8 });
+    let sum = Arkworks::fixed_base_msm(&msm, &ark_scalars);
+    assert_eq!(sum.to_bytes().as_ref(), expected.as_ref());
+
+    let g2_points: Vec<_> = (0..4)
+        .map(|_| bls12_381::G2Projective::random(&mut rng).to_affine())
+        .collect();
+    let ark_g2_points: Vec<_> = g2_points.iter().map(to_backend_g2).collect();
+    assert_eq!(
+        Arkworks::g2_lincomb(&ark_g2_points, &ark_scalars[..4])
+            .unwrap()
+            .to_bytes()
+            .as_ref(),
+        BlstBackend::g2_lincomb(&g2_points, &scalars[..4])
+            .unwrap()
+            .to_bytes()
+            .as_ref()
+    );
+
+    for _ in 0..100 {
+        let bytes: [u8; 32] = rand::Rng::gen(&mut rng);
+        assert_eq!(
+            Arkworks::reduce_bytes_to_scalar(bytes).to_repr(),
+            BlstBackend::reduce_bytes_to_scalar(bytes).to_repr()
+        );
+    }
+}
+
+#[test]
+fn arkworks_torsion_check() {
+    let mut rng = thread_rng();
+    let points: Vec<_> = (0..8)
+        .map(|_| ArkG1Projective::random(&mut rng).to_affine())
+        .collect();
+    assert_eq!(
+        Arkworks::g1_batch_is_torsion_free(&points, &[0b1010_1010; 8]),
+        Some(true)
+    );
+    assert_eq!(Arkworks::g1_batch_is_torsion_free(&points, &[0; 7]), None);
+
+    // A point on the curve that is not in the prime order subgroup
+    let mut bytes = ArkG1Compressed::default();
+    let x = (1..)
+        .map(|x: u64| {
+            bytes.as_mut().fill(0);
+            bytes.as_mut()[40..].copy_from_slice(&x.to_be_bytes());
+            bytes.as_mut()[0] |= 0x80;
+            ArkG1Point::from_bytes_unchecked(&bytes)
+        })
+        .find_map(Option::<ArkG1Point>::from)
+        .unwrap();
+    assert!(bool::from(ArkG1Point::from_bytes(&x.to_bytes()).is_none()));
+    let mut points = points;
+    points[3] = x;
+    assert_eq!(
+        Arkworks::g1_batch_is_torsion_free(&points, &[0b1010_1010; 8]),
+        Some(false)
+    );
+}
+
+#[test]
+fn proofs_match() {
+    use crate::{create_insecure_commit_verification_keys_with_backend, Prover, ProverInput};
+
+    let poly_len = 4096;
+    let data: Vec<_> = (0..poly_len as u64).map(|i| Scalar::from(i * i)).collect();
+    let ark_data: Vec<_> = data.iter().map(to_backend_scalar).collect();
+
+    let (commit_key, _) = create_insecure_commit_verification_keys_with_backend::<BlstBackend>();
+    let prover = Prover::new(commit_key, poly_len, 64, 2 * poly_len, UsePrecomp::No);
+    let (proofs, cells) = prover.compute_multi_opening_proofs(ProverInput::Data(data));
+
+    let (commit_key, _) = create_insecure_commit_verification_keys_with_backend::<Arkworks>();
+    let prover = Prover::new(commit_key, poly_len, 64, 2 * poly_len, UsePrecomp::No);
+    let (ark_proofs, ark_cells) = prover.compute_multi_opening_proofs(ProverInput::Data(ark_data));
+
+    for (proof, ark_proof) in proofs.iter().zip(&ark_proofs) {
+        assert_eq!(proof.to_bytes().as_ref(), ark_proof.to_bytes().as_ref());
+    }
+    let cells: Vec<_> = cells.iter().flatten().map(to_backend_scalar).collect();
+    assert_eq!(cells, ark_cells.concat());
+}
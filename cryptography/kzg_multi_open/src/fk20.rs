@@ -10,7 +10,7 @@ mod prover;
 mod toeplitz;
 mod verifier;
 
-pub use cosets::recover_evaluations_in_domain_order;
+pub use cosets::{recover_evaluations_in_domain_order, reverse_bit_order};
 pub use errors::VerifierError;
 pub use prover::{FK20Prover as Prover, Input as ProverInput};
 pub use verifier::{CommitmentIndex, CosetIndex, FK20Verifier as Verifier};
@@ -1,10 +1,11 @@
-use crate::fk20::toeplitz::{CirculantMatrix, ToeplitzMatrix};
-use bls12_381::{
-    fixed_base_msm::{FixedBaseMSM, UsePrecomp},
-    g1_batch_normalize, G1Point, G1Projective,
+use crate::{
+    backend::{Backend, BlstBackend},
+    fk20::toeplitz::ToeplitzMatrix,
 };
+use bls12_381::{ff::Field, fixed_base_msm::UsePrecomp, group::prime::PrimeCurveAffine};
 use maybe_rayon::prelude::*;
-use polynomial::domain::Domain;
+use polynomial::{domain::Domain, scratch::ScratchPool};
+use std::sync::Arc;
 
 /// BatchToeplitzMatrixVecMul allows one to compute multiple matrix vector multiplications
 /// and sum them together.
@@ -13,21 +14,23 @@ use polynomial::domain::Domain;
 ///  - The vectors being used are fixed, so we can precompute their FFTs.
 ///  - The vectors being used as group elements (curve points)
 #[derive(Debug)]
-pub struct BatchToeplitzMatrixVecMul {
+pub struct BatchToeplitzMatrixVecMul<B: Backend = BlstBackend> {
     /// This contains the number of matrix-vector multiplications that
     /// we can do in a batch.
     batch_size: usize,
-    precomputed_fft_vectors: Vec<FixedBaseMSM>,
+    precomputed_fft_vectors: Vec<B::FixedBaseMSM>,
     // This is the length of the vector that we are multiplying the matrices with.
     // and subsequently will be the length of the final result of the matrix-vector multiplication.
     size_of_vector: usize,
     /// This is the domain used in the circulant matrix-vector multiplication.
     /// It will be double the size of the length of a pre-computed vector.
-    circulant_domain: Domain,
+    circulant_domain: Arc<Domain<B::Scalar>>,
+    /// Buffers for the FFTs of the circulant matrices, which are reused across calls.
+    scratch: ScratchPool<B::Scalar>,
 }
 
-impl BatchToeplitzMatrixVecMul {
-    pub fn new(vectors: Vec<Vec<G1Point>>, use_precomp: UsePrecomp) -> Self {
+impl<B: Backend> BatchToeplitzMatrixVecMul<B> {
+    pub fn new(vectors: Vec<Vec<B::G1Point>>, use_precomp: UsePrecomp) -> Self {
         let size_of_vector = vectors[0].len();
         let vectors_all_same_length = vectors.iter().all(|v| v.len() == size_of_vector);
         assert!(
@@ -39,17 +42,17 @@ impl BatchToeplitzMatrixVecMul {
             "expected the size of the vector to be a power of two"
         );
 
-        let circulant_domain = Domain::new(size_of_vector * 2);
+        let circulant_domain = Domain::get_or_init(size_of_vector * 2);
 
         // Precompute the FFT of the vectors, since they do not change per matrix-vector multiplication
-        let vectors: Vec<Vec<G1Point>> = vectors
+        let vectors: Vec<Vec<B::G1Point>> = vectors
             .maybe_par_iter()
             .map(|vector| {
                 let vector_projective = vector
                     .iter()
-                    .map(|point| G1Projective::from(*point))
+                    .map(PrimeCurveAffine::to_curve)
                     .collect::<Vec<_>>();
-                g1_batch_normalize(&circulant_domain.fft_g1(vector_projective))
+                B::g1_batch_normalize(&circulant_domain.fft_g1(vector_projective))
             })
             .collect();
         let batch_size = vectors.len();
@@ -62,7 +65,7 @@ impl BatchToeplitzMatrixVecMul {
         // This is a trade-off between storage and computation, where storage grows exponentially.
         let precomputed_table: Vec<_> = transposed_msm_vectors
             .maybe_into_par_iter()
-            .map(|v| FixedBaseMSM::new(v, use_precomp))
+            .map(|v| B::fixed_base_msm_new(v, use_precomp))
             .collect();
 
         Self {
@@ -70,6 +73,7 @@ impl BatchToeplitzMatrixVecMul {
             circulant_domain,
             precomputed_fft_vectors: precomputed_table,
             batch_size,
+            scratch: ScratchPool::new(),
         }
     }
 
@@ -79,28 +83,43 @@ impl BatchToeplitzMatrixVecMul {
     //
     // Note: This is faster than computing the matrix vector multiplication for each Toeplitz matrix using circulant
     // matrix-vector multiplication and then summing the results since only one IFFT is done as opposed to `n`
-    pub fn sum_matrix_vector_mul(&self, matrices: Vec<ToeplitzMatrix>) -> Vec<G1Projective> {
+    pub fn sum_matrix_vector_mul(
+        &self,
+        matrices: Vec<ToeplitzMatrix<B::Scalar>>,
+    ) -> Vec<B::G1Projective> {
         assert_eq!(
             matrices.len(),
             self.batch_size,
             "expected the number of matrices to be the same as the number of vectors"
         );
 
-        // Embed Toeplitz matrices into circulant matrices
-        let circulant_matrices = matrices
-            .maybe_into_par_iter()
-            .map(CirculantMatrix::from_toeplitz);
+        let circulant_size = self.circulant_domain.size();
+
+        // Embed Toeplitz matrices into circulant matrices, and compute the FFT of each of them.
+        //
+        // Each chunk of `col_ffts` holds the FFT of one matrix.
+        let mut col_ffts = self
+            .scratch
+            .take(self.batch_size * circulant_size, B::Scalar::ZERO);
+        col_ffts
+            .maybe_par_chunks_mut(circulant_size)
+            .zip(matrices.maybe_into_par_iter())
+            .for_each(|(col_fft, matrix)| {
+                matrix.write_circulant_row(col_fft);
+                self.circulant_domain.fft_scalars_inplace(col_fft);
+            });
 
         // Perform circulant matrix-vector multiplication between all of the matrices and vectors
         // and sum them together.
         //
         // Transpose the circulant matrices so that we convert a group of hadamard products into a group of
-        // inner products.
-        let col_ffts: Vec<_> = circulant_matrices
-            .maybe_into_par_iter()
-            .map(|matrix| self.circulant_domain.fft_scalars(matrix.row))
-            .collect();
-        let msm_scalars = transpose(col_ffts);
+        // inner products. Each chunk of `msm_scalars` holds the scalars of one inner product.
+        let mut msm_scalars = self.scratch.take(col_ffts.len(), B::Scalar::ZERO);
+        for (i, col_fft) in col_ffts.chunks_exact(circulant_size).enumerate() {
+            for (j, eval) in col_fft.iter().enumerate() {
+                msm_scalars[j * self.batch_size + i] = *eval;
+            }
+        }
 
         let result: Vec<_> = {
             #[cfg(feature = "tracing")]
@@ -108,8 +127,8 @@ impl BatchToeplitzMatrixVecMul {
                 tracing::info_span!("compute fixed-base msm on matrix-vec-mul result").entered();
             self.precomputed_fft_vectors
                 .maybe_par_iter()
-                .zip(msm_scalars)
-                .map(|(points, scalars)| points.msm(scalars))
+                .zip(msm_scalars.maybe_par_chunks(self.batch_size))
+                .map(|(points, scalars)| B::fixed_base_msm(points, scalars))
                 .collect()
         };
 
@@ -201,7 +220,8 @@ mod tests {
             toeplitz_matrices.push(ToeplitzMatrix::new(row, col));
         }
 
-        let bm = BatchToeplitzMatrixVecMul::new(vectors_affine, UsePrecomp::Yes { width: 8 });
+        let bm: BatchToeplitzMatrixVecMul =
+            BatchToeplitzMatrixVecMul::new(vectors_affine, UsePrecomp::Yes { width: 8 });
         let got_result = bm.sum_matrix_vector_mul(toeplitz_matrices.clone());
 
         let mut expected_result = vec![G1Projective::identity(); got_result.len()];
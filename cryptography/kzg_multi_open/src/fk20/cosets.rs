@@ -1,4 +1,4 @@
-use bls12_381::{ff::Field, Scalar};
+use bls12_381::ff::{Field, PrimeField};
 use polynomial::domain::Domain;
 
 /// Reverses the least significant `bits` of the given number `n`.
@@ -45,17 +45,19 @@ pub fn reverse_bit_order<T>(a: &mut [T]) {
 ///   `num_points` can also be seen as the size of the domain.
 /// - num_cosets denotes how many cosets we want to generate, analogously how many proofs we want to produce.
 ///
-/// Returns a `Vec<Scalar>` containing the generated coset elements with length `num_cosets`
+/// Returns a `Vec<F>` containing the generated coset elements with length `num_cosets`
 ///
 /// Note: Setting bit_reversed to true will generate the cosets in bit-reversed order.
-pub fn coset_gens(num_points: usize, num_cosets: usize, bit_reversed: bool) -> Vec<Scalar> {
-    use bls12_381::ff::Field;
-
+pub fn coset_gens<F: PrimeField>(
+    num_points: usize,
+    num_cosets: usize,
+    bit_reversed: bool,
+) -> Vec<F> {
     // Compute the generator for the group containing all of the points.
     //
     // Note: generating the whole group, just to get the generator is inefficient
     // However, this code is not on the hot path, so we don't optimize it.
-    let domain = Domain::new(num_points);
+    let domain = Domain::<F>::get_or_init(num_points);
     let coset_gen = domain.generator;
 
     // The coset generators are just powers
@@ -103,18 +105,18 @@ pub fn coset_gens(num_points: usize, num_cosets: usize, bit_reversed: bool) -> V
 ///
 /// Note: It is the callers responsibility to ensure that there are no duplicate
 /// coset indices.
-pub fn recover_evaluations_in_domain_order(
+pub fn recover_evaluations_in_domain_order<F: Field>(
     domain_size: usize,
     coset_indices: Vec<usize>,
-    coset_evaluations: Vec<Vec<Scalar>>,
-) -> Option<(Vec<usize>, Vec<Scalar>)> {
+    coset_evaluations: Vec<Vec<F>>,
+) -> Option<(Vec<usize>, Vec<F>)> {
     assert_eq!(coset_indices.len(), coset_evaluations.len());
 
     if coset_indices.is_empty() {
         return None;
     }
 
-    let mut elements = vec![Scalar::ZERO; domain_size];
+    let mut elements = vec![F::ZERO; domain_size];
 
     // Check that each coset has the same size
     let coset_len = coset_evaluations[0].len();
@@ -181,12 +183,15 @@ pub(crate) fn generate_cosets(
     num_points: usize,
     points_per_coset: usize,
     bit_reversed: bool,
-) -> Vec<Vec<Scalar>> {
-    let subgroup = Domain::new(points_per_coset).roots;
+) -> Vec<Vec<bls12_381::Scalar>> {
+    use bls12_381::Scalar;
+
+    let subgroup_domain = Domain::<Scalar>::get_or_init(points_per_coset);
+    let subgroup = &subgroup_domain.roots;
 
     let num_cosets = num_points / points_per_coset;
 
-    let generators = coset_gens(num_points, num_cosets, bit_reversed);
+    let generators = coset_gens::<Scalar>(num_points, num_cosets, bit_reversed);
 
     // Manually generate cosets
     let mut cosets = Vec::with_capacity(generators.len());
@@ -243,7 +248,7 @@ mod tests {
         let cosets = super::generate_cosets(num_points, points_per_coset, is_bit_reversed);
 
         // Generate the cosets by reversing the full domain and grouping the bit reversed roots.
-        let mut full_domain_roots = Domain::new(num_points).roots;
+        let mut full_domain_roots = Domain::<Scalar>::new(num_points).roots.to_vec();
         reverse_bit_order(&mut full_domain_roots);
         let chunked_bit_reversed_roots: Vec<_> =
             full_domain_roots.chunks(points_per_coset).collect();
@@ -288,11 +293,11 @@ mod tests {
         reverse_bit_order(&mut bit_reversed_data);
 
         // Interpolate the bit reversed data
-        let domain = Domain::new(4096);
+        let domain = Domain::<Scalar>::new(4096);
         let poly_coeff = domain.ifft_scalars(bit_reversed_data.clone());
 
         // Evaluate the poly_coeff on a larger domain
-        let extended_domain = Domain::new(4096 * 2);
+        let extended_domain = Domain::<Scalar>::new(4096 * 2);
         let mut extended_data = extended_domain.fft_scalars(poly_coeff);
 
         // Bit reverse the extended data
@@ -310,7 +315,7 @@ mod tests {
         let original_data: Vec<_> = (0..4096).map(|i| Scalar::from(i as u64)).collect();
 
         // Interpolate the original data
-        let domain = Domain::new(4096);
+        let domain = Domain::<Scalar>::new(4096);
         let poly_coeff = domain.ifft_scalars(original_data.clone());
 
         let cosets = generate_cosets(4096 * 2, 64, false);
@@ -351,7 +356,7 @@ mod tests {
         // The original data will live at every even powered evaluation.
         //
         // Generate the evaluations using a faster method
-        let extended_evaluations = Domain::new(4096 * 2).fft_scalars(poly_coeff);
+        let extended_evaluations = Domain::<Scalar>::new(4096 * 2).fft_scalars(poly_coeff);
         let got_coset_evaluations = take_every_nth(&extended_evaluations, 128);
         assert_eq!(got_coset_evaluations, coset_evaluations);
 
@@ -391,7 +396,7 @@ mod tests {
         assert_eq!(vec_len, set_len);
 
         // When we combine the cosets, it should equal the larger subgroup
-        let full_subgroup = Domain::new(num_points).roots;
+        let full_subgroup = Domain::<Scalar>::new(num_points).roots.to_vec();
         let full_subgroup_set: HashSet<_> =
             full_subgroup.into_iter().map(|s| s.to_bytes_be()).collect();
 
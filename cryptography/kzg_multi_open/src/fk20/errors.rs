@@ -1,4 +1,5 @@
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum VerifierError {
+    #[error("multi-opening proof failed verification")]
     InvalidProof,
 }
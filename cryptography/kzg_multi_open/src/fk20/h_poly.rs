@@ -1,5 +1,5 @@
-use crate::fk20::toeplitz::ToeplitzMatrix;
-use bls12_381::{ff::Field, G1Projective, Scalar};
+use crate::{backend::Backend, fk20::toeplitz::ToeplitzMatrix};
+use bls12_381::ff::Field;
 use polynomial::poly_coeff::PolyCoeff;
 
 use super::batch_toeplitz::BatchToeplitzMatrixVecMul;
@@ -15,11 +15,11 @@ use super::batch_toeplitz::BatchToeplitzMatrixVecMul;
 ///
 /// FK20 computes the commitments to these polynomials in 3.1.1.
 #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-pub(crate) fn compute_h_poly_commitments(
-    batch_toeplitz: &BatchToeplitzMatrixVecMul,
-    mut polynomial: PolyCoeff,
+pub(crate) fn compute_h_poly_commitments<B: Backend>(
+    batch_toeplitz: &BatchToeplitzMatrixVecMul<B>,
+    mut polynomial: PolyCoeff<B::Scalar>,
     coset_size: usize,
-) -> Vec<G1Projective> {
+) -> Vec<B::G1Projective> {
     assert!(
         coset_size.is_power_of_two(),
         "expected coset_size to be a power of two, found {}",
@@ -48,7 +48,7 @@ pub(crate) fn compute_h_poly_commitments(
     let mut matrices = Vec::with_capacity(toeplitz_rows.len());
     // We want to do `coset_size` toeplitz matrix multiplications
     for row in toeplitz_rows {
-        let mut toeplitz_column = vec![Scalar::ZERO; row.len()];
+        let mut toeplitz_column = vec![B::Scalar::ZERO; row.len()];
         toeplitz_column[0] = row[0];
 
         matrices.push(ToeplitzMatrix::new(row, toeplitz_column));
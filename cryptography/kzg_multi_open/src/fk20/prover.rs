@@ -1,22 +1,24 @@
+use crate::backend::{Backend, BlstBackend};
 use crate::commit_key::CommitKey;
 use crate::fk20::batch_toeplitz::BatchToeplitzMatrixVecMul;
-use crate::fk20::cosets::reverse_bit_order;
+use crate::fk20::cosets::{log2, reverse_bit_order, reverse_bits};
 use crate::fk20::h_poly::take_every_nth;
 use bls12_381::fixed_base_msm::UsePrecomp;
-use bls12_381::group::prime::PrimeCurveAffine;
-use bls12_381::{g1_batch_normalize, G1Point, Scalar};
-use polynomial::{domain::Domain, poly_coeff::PolyCoeff};
+use bls12_381::group::{prime::PrimeCurveAffine, Curve};
+use bls12_381::{ff::Field, Scalar};
+use polynomial::{domain::Domain, poly_coeff::PolyCoeff, scratch::ScratchPool, CosetFFT};
+use std::sync::Arc;
 
 use super::h_poly::compute_h_poly_commitments;
 
 /// Input contains the various structures that we can make FK20 proofs over.
-pub enum Input {
+pub enum Input<F = Scalar> {
     /// This is akin to creating proofs over a polynomial in monomial basis.
-    PolyCoeff(Vec<Scalar>),
+    PolyCoeff(Vec<F>),
     /// Data: This is akin to creating proofs over a polynomial in lagrange basis.
     /// This variant has the useful property that the output evaluations will
     /// contain the data in the order that it was passed in.
-    Data(Vec<Scalar>),
+    Data(Vec<F>),
 }
 
 /// FK20Prover initializes all of the components needed to compute a KZG multi point
@@ -28,8 +30,8 @@ pub enum Input {
 /// See [Fk21](https://github.com/khovratovich/Kate/blob/master/Kate_amortized.pdf) for details
 /// on the scheme.
 #[derive(Debug)]
-pub struct FK20Prover {
-    batch_toeplitz: BatchToeplitzMatrixVecMul,
+pub struct FK20Prover<B: Backend = BlstBackend> {
+    batch_toeplitz: BatchToeplitzMatrixVecMul<B>,
     /// The amount of points that a single proof will attest to the opening of.
     ///
     /// Note: FK20 allows you to create a proof of an opening for multiple points.
@@ -43,17 +45,19 @@ pub struct FK20Prover {
     number_of_points_to_open: usize,
 
     /// Domain used in FK20 to create the opening proofs
-    proof_domain: Domain,
+    proof_domain: Arc<Domain<B::Scalar>>,
     /// Domain used to evaluate the polynomial at the points we want to open at.
-    evaluation_domain: Domain,
+    evaluation_domain: Arc<Domain<B::Scalar>>,
     /// Domain used for converting polynomial to monomial form.
-    poly_domain: Domain,
+    poly_domain: Arc<Domain<B::Scalar>>,
     /// Commitment key used for committing to the polynomial
     /// in monomial form.
-    commit_key: CommitKey,
+    commit_key: CommitKey<B>,
+    /// Buffers for evaluating the polynomial over the evaluation domain, which are reused across calls.
+    scratch: ScratchPool<B::Scalar>,
 }
 
-impl FK20Prover {
+impl<B: Backend> FK20Prover<B> {
     /// Initialize a FK20 struct with the given parameters.
     ///
     /// commit_key: The commitment key used to commit to polynomials.
@@ -61,7 +65,7 @@ impl FK20Prover {
     /// points_per_proof: The number of points that a single proof will attest to.
     /// number_of_points_to_open: The total number of points that we want to open a polynomial at.
     pub fn new(
-        commit_key: CommitKey,
+        commit_key: CommitKey<B>,
         polynomial_bound: usize,
         points_per_proof: usize,
         number_of_points_to_open: usize,
@@ -74,6 +78,10 @@ impl FK20Prover {
         assert!(commit_key.g1s.len() >= polynomial_bound);
         assert!(commit_key.g1s.len() > points_per_proof);
 
+        // The evaluation domain is the largest one. It is computed first, so that
+        // the smaller domains can share its roots of unity.
+        let evaluation_domain = Domain::get_or_init(number_of_points_to_open);
+
         // 1. Compute the SRS vectors that we will multiply the toeplitz matrices by.
         //
         // Skip the last `coset_size` points in the srs
@@ -97,7 +105,7 @@ impl FK20Prover {
         // will pad these.
         for srs_vector in &mut srs_vectors {
             let pad_by = srs_vector.len().next_power_of_two();
-            srs_vector.resize(pad_by, G1Point::identity());
+            srs_vector.resize(pad_by, B::G1Point::identity());
         }
 
         // Initialize structure that will allow us to do efficient sum of multiple toeplitz matrix
@@ -107,9 +115,8 @@ impl FK20Prover {
         // 2. Compute the domains needed to produce the proofs and the evaluations
         //
         let num_proofs = number_of_points_to_open / points_per_proof;
-        let proof_domain = Domain::new(num_proofs);
-        let evaluation_domain = Domain::new(number_of_points_to_open);
-        let poly_domain = Domain::new(polynomial_bound);
+        let proof_domain = Domain::get_or_init(num_proofs);
+        let poly_domain = Domain::get_or_init(polynomial_bound);
 
         Self {
             batch_toeplitz,
@@ -119,11 +126,12 @@ impl FK20Prover {
             evaluation_domain,
             poly_domain,
             commit_key,
+            scratch: ScratchPool::new(),
         }
     }
 
     /// Commit to the `Input` that we will be creating FK20 proofs over.
-    pub fn commit(&self, input: Input) -> G1Point {
+    pub fn commit(&self, input: Input<B::Scalar>) -> B::G1Point {
         let poly_coeff = match input {
             Input::PolyCoeff(poly_coeff) => poly_coeff,
             Input::Data(mut data) => {
@@ -139,7 +147,7 @@ impl FK20Prover {
         };
 
         // Commit to the interpolated polynomial.
-        self.commit_key.commit_g1(&poly_coeff).into()
+        self.commit_key.commit_g1(&poly_coeff).to_affine()
     }
 
     /// The number of proofs that will be produced.
@@ -153,8 +161,12 @@ impl FK20Prover {
     /// at all of the points we want to open at, and then use reverse bit ordering
     /// to group the evaluations into the relevant cosets.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    fn compute_coset_evaluations(&self, polynomial: PolyCoeff) -> Vec<Vec<Scalar>> {
-        let mut evaluations = self.evaluation_domain.fft_scalars(polynomial);
+    fn compute_coset_evaluations(&self, polynomial: &[B::Scalar]) -> Vec<Vec<B::Scalar>> {
+        let mut evaluations = self
+            .scratch
+            .take(self.evaluation_domain.size(), B::Scalar::ZERO);
+        evaluations[..polynomial.len()].copy_from_slice(polynomial);
+        self.evaluation_domain.fft_scalars_inplace(&mut evaluations);
         reverse_bit_order(&mut evaluations);
         evaluations
             .chunks_exact(self.coset_size)
@@ -168,7 +180,10 @@ impl FK20Prover {
     /// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/_features/eip7594/polynomial-commitments-sampling.md#compute_cells_and_kzg_proofs
     ///
     /// Returning the opening proofs and the corresponding coset evaluations.
-    pub fn compute_multi_opening_proofs(&self, input: Input) -> (Vec<G1Point>, Vec<Vec<Scalar>>) {
+    pub fn compute_multi_opening_proofs(
+        &self,
+        input: Input<B::Scalar>,
+    ) -> (Vec<B::G1Point>, Vec<Vec<B::Scalar>>) {
         // Convert data to polynomial coefficients
         let poly_coeff = match input {
             Input::PolyCoeff(polynomial) => polynomial,
@@ -181,8 +196,66 @@ impl FK20Prover {
         self.compute_multi_opening_proofs_poly_coeff(poly_coeff)
     }
 
+    /// Computes the multi-opening proof and the evaluations for a single coset.
+    ///
+    /// `coset_index` is the position of the coset in the output of `compute_multi_opening_proofs`,
+    /// so the result is equal to the proof and the evaluations at that position.
+    ///
+    /// This does not use FK20. Instead the polynomial is divided by the vanishing polynomial of the
+    /// coset and the quotient is committed to, which is cheaper when only one proof is needed.
+    ///
+    /// Panics if `coset_index` is not less than the number of proofs.
+    pub fn compute_multi_opening_proof(
+        &self,
+        input: Input<B::Scalar>,
+        coset_index: usize,
+    ) -> (B::G1Point, Vec<B::Scalar>) {
+        assert!(
+            coset_index < self.num_proofs(),
+            "coset index {coset_index} is out of range, there are only {} cosets",
+            self.num_proofs()
+        );
+
+        // Convert data to polynomial coefficients
+        let poly_coeff = match input {
+            Input::PolyCoeff(polynomial) => polynomial,
+            Input::Data(mut data) => {
+                reverse_bit_order(&mut data);
+                self.poly_domain.ifft_scalars(data)
+            }
+        };
+
+        // The cosets are in bit-reversed order, so the coset at `coset_index` is `h * H`, where
+        // `h = w^{reverse_bits(coset_index)}`, `w` generates the evaluation domain and `H` is the
+        // subgroup of size `coset_size`.
+        let num_bits = log2(self.num_proofs() as u32);
+        let coset_gen = self
+            .evaluation_domain
+            .generator
+            .pow_vartime([reverse_bits(coset_index, num_bits) as u64]);
+
+        // The vanishing polynomial of the coset is `Z(X) = X^n - h^n`, where `n` is the coset size.
+        //
+        // Dividing by it only needs a single pass over the coefficients, from the highest to the lowest.
+        let (quotient, remainder) = divide_by_binomial(
+            poly_coeff,
+            self.coset_size,
+            coset_gen.pow_vartime([self.coset_size as u64]),
+        );
+        let proof = self.commit_key.commit_g1(&quotient).to_affine();
+
+        // The polynomial agrees with the remainder on the coset, and evaluating the remainder
+        // on the coset only needs an FFT of size `n`.
+        let coset_domain = Domain::get_or_init(self.coset_size);
+        let mut evaluations = coset_domain.coset_fft_scalars(remainder, &CosetFFT::new(coset_gen));
+        // Within a coset, the evaluations are also in bit-reversed order.
+        reverse_bit_order(&mut evaluations);
+
+        (proof, evaluations)
+    }
+
     /// Extends the polynomial by computing its coset evaluations
-    pub fn extend_polynomial(&self, input: Input) -> Vec<Vec<Scalar>> {
+    pub fn extend_polynomial(&self, input: Input<B::Scalar>) -> Vec<Vec<B::Scalar>> {
         // Convert data to polynomial coefficients
         let poly_coeff = match input {
             Input::PolyCoeff(polynomial) => polynomial,
@@ -191,7 +264,7 @@ impl FK20Prover {
                 self.poly_domain.ifft_scalars(data)
             }
         };
-        self.compute_coset_evaluations(poly_coeff)
+        self.compute_coset_evaluations(&poly_coeff)
     }
 
     /// Computes multi-opening proofs over a given polynomial in coefficient form.
@@ -203,12 +276,16 @@ impl FK20Prover {
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn compute_multi_opening_proofs_poly_coeff(
         &self,
-        polynomial: PolyCoeff,
-    ) -> (Vec<G1Point>, Vec<Vec<Scalar>>) {
+        polynomial: PolyCoeff<B::Scalar>,
+    ) -> (Vec<B::G1Point>, Vec<Vec<B::Scalar>>) {
+        // Compute the evaluations first, so that the polynomial can be moved
+        // into the computation of the proofs instead of being cloned.
+        let coset_evaluations = self.compute_coset_evaluations(&polynomial);
+
         // Compute opening proofs for the polynomial
         //
         let h_poly_commitments =
-            compute_h_poly_commitments(&self.batch_toeplitz, polynomial.clone(), self.coset_size);
+            compute_h_poly_commitments(&self.batch_toeplitz, polynomial, self.coset_size);
         let mut proofs = {
             #[cfg(feature = "tracing")]
             let _span = tracing::info_span!("compute proof from h_poly_commitments").entered();
@@ -219,25 +296,48 @@ impl FK20Prover {
         // coset evaluations.
         reverse_bit_order(&mut proofs);
 
-        (
-            g1_batch_normalize(&proofs),
-            self.compute_coset_evaluations(polynomial),
-        )
+        (B::g1_batch_normalize(&proofs), coset_evaluations)
     }
 
     #[cfg(test)]
-    pub(crate) const fn batch_toeplitz_matrix(&self) -> &BatchToeplitzMatrixVecMul {
+    pub(crate) const fn batch_toeplitz_matrix(&self) -> &BatchToeplitzMatrixVecMul<B> {
         &self.batch_toeplitz
     }
 }
 
+/// Divides `polynomial` by `X^n - c`, returning the quotient and the remainder.
+///
+/// Writing `f(X) = q(X)(X^n - c) + r(X)`, the coefficients satisfy `f_i = q_{i-n} - c * q_i`
+/// for `i >= n`, and `f_i = r_i - c * q_i` for `i < n`.
+fn divide_by_binomial<F: Field>(
+    mut polynomial: PolyCoeff<F>,
+    n: usize,
+    c: F,
+) -> (PolyCoeff<F>, PolyCoeff<F>) {
+    if polynomial.len() <= n {
+        polynomial.resize(n, F::ZERO);
+        return (Vec::new(), polynomial);
+    }
+
+    // `polynomial[i]` becomes `q_{i-n}` for `i >= n` and `r_i` for `i < n`.
+    for i in (n..polynomial.len()).rev() {
+        let carry = polynomial[i] * c;
+        polynomial[i - n] += carry;
+    }
+    let quotient = polynomial.split_off(n);
+
+    (quotient, polynomial)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
     use super::{FK20Prover, Input};
     use crate::{
+        backend::{Backend, BlstBackend},
         create_insecure_commit_verification_keys,
+        create_insecure_commit_verification_keys_with_backend,
         fk20::{cosets::generate_cosets, naive as fk20naive, verifier::FK20Verifier},
         naive as kzgnaive,
     };
@@ -270,9 +370,49 @@ mod tests {
         assert_eq!(&data, &cells_flattened[..poly_len]);
     }
 
+    #[test]
+    fn single_proof_matches_fk20_proofs() {
+        let (commit_key, _) = create_insecure_commit_verification_keys();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            UsePrecomp::No,
+        );
+
+        let data: Vec<_> = (0..poly_len)
+            .map(|i| Scalar::from((i * i) as u64))
+            .collect();
+        let (proofs, cells) = fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
+
+        for coset_index in [0, 1, 2, 63, 64, 101, fk20.num_proofs() - 1] {
+            let (proof, cell) =
+                fk20.compute_multi_opening_proof(Input::Data(data.clone()), coset_index);
+            assert_eq!(proof, proofs[coset_index]);
+            assert_eq!(cell, cells[coset_index]);
+        }
+    }
+
     #[test]
     fn smoke_test_prove_verify() {
-        let (commit_key, verification_key) = create_insecure_commit_verification_keys();
+        prove_verify::<BlstBackend>();
+    }
+
+    #[cfg(feature = "arkworks")]
+    #[test]
+    fn smoke_test_prove_verify_arkworks() {
+        prove_verify::<crate::backend::Arkworks>();
+    }
+
+    fn prove_verify<B: Backend>() {
+        let (commit_key, verification_key) =
+            create_insecure_commit_verification_keys_with_backend::<B>();
 
         let poly_len = 4096;
         let num_points_to_open = 2 * poly_len;
@@ -288,7 +428,7 @@ mod tests {
         );
         let fk20_verifier = FK20Verifier::new(verification_key, num_points_to_open, num_cosets);
 
-        let data: Vec<_> = (0..poly_len).map(|i| Scalar::from(i as u64)).collect();
+        let data: Vec<_> = (0..poly_len).map(|i| B::Scalar::from(i as u64)).collect();
         let (proofs, cells) = fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
 
         let commitment = fk20.commit(Input::Data(data));
@@ -305,6 +445,127 @@ mod tests {
         assert!(valid.is_ok());
     }
 
+    #[test]
+    fn verify_openings_of_the_same_coset() {
+        let (commit_key, verification_key) = create_insecure_commit_verification_keys();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+        let num_cosets = num_points_to_open / coset_size;
+
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            UsePrecomp::No,
+        );
+        let fk20_verifier = FK20Verifier::new(verification_key, num_points_to_open, num_cosets);
+
+        // Open the same coset of several polynomials, like a column of cells from different blobs.
+        let coset_index = 5;
+        let mut commitments = Vec::new();
+        let mut coset_evals = Vec::new();
+        let mut proofs = Vec::new();
+        for k in 0..4u64 {
+            let data: Vec<_> = (0..poly_len as u64)
+                .map(|i| Scalar::from(i * k + 1))
+                .collect();
+            let (all_proofs, all_cells) =
+                fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
+            commitments.push(fk20.commit(Input::Data(data)));
+            coset_evals.push(all_cells[coset_index].clone());
+            proofs.push(all_proofs[coset_index]);
+        }
+        let commitment_indices: Vec<u64> = (0..commitments.len() as u64).collect();
+
+        let valid = fk20_verifier.verify_multi_opening_same_coset(
+            &commitments,
+            &commitment_indices,
+            coset_index as u64,
+            &coset_evals,
+            &proofs,
+        );
+        assert!(valid.is_ok());
+
+        // The openings are not valid for any other coset
+        let valid = fk20_verifier.verify_multi_opening_same_coset(
+            &commitments,
+            &commitment_indices,
+            coset_index as u64 + 1,
+            &coset_evals,
+            &proofs,
+        );
+        assert!(valid.is_err());
+
+        // Changing a single evaluation invalidates the batch
+        coset_evals[2][7] += Scalar::from(1u64);
+        let valid = fk20_verifier.verify_multi_opening_same_coset(
+            &commitments,
+            &commitment_indices,
+            coset_index as u64,
+            &coset_evals,
+            &proofs,
+        );
+        assert!(valid.is_err());
+    }
+
+    #[test]
+    fn verify_single_openings() {
+        single_openings::<BlstBackend>();
+    }
+
+    #[cfg(feature = "arkworks")]
+    #[test]
+    fn verify_single_openings_arkworks() {
+        single_openings::<crate::backend::Arkworks>();
+    }
+
+    fn single_openings<B: Backend>() {
+        let (commit_key, verification_key) =
+            create_insecure_commit_verification_keys_with_backend::<B>();
+
+        let poly_len = 4096;
+        let num_points_to_open = 2 * poly_len;
+        let coset_size = 64;
+        let num_cosets = num_points_to_open / coset_size;
+
+        let fk20 = FK20Prover::new(
+            commit_key,
+            poly_len,
+            coset_size,
+            num_points_to_open,
+            UsePrecomp::No,
+        );
+        let fk20_verifier = FK20Verifier::new(verification_key, num_points_to_open, num_cosets);
+
+        let data: Vec<_> = (0..poly_len).map(|i| B::Scalar::from(i as u64)).collect();
+        let (proofs, mut cells) = fk20.compute_multi_opening_proofs(Input::Data(data.clone()));
+        let commitment = fk20.commit(Input::Data(data));
+
+        for coset_index in [0, 1, 77, num_cosets - 1] {
+            let valid = fk20_verifier.verify_single_opening(
+                &commitment,
+                coset_index as u64,
+                &cells[coset_index],
+                &proofs[coset_index],
+            );
+            assert!(valid.is_ok());
+        }
+
+        // The opening is not valid for any other coset, or with any other proof
+        let valid = fk20_verifier.verify_single_opening(&commitment, 4, &cells[3], &proofs[3]);
+        assert!(valid.is_err());
+        let valid = fk20_verifier.verify_single_opening(&commitment, 3, &cells[3], &proofs[4]);
+        assert!(valid.is_err());
+
+        // Changing a single evaluation invalidates the opening
+        cells[3][7] += B::Scalar::from(1u64);
+        let valid = fk20_verifier.verify_single_opening(&commitment, 3, &cells[3], &proofs[3]);
+        assert!(valid.is_err());
+    }
+
     #[test]
     fn check_consistency_of_proofs_against_naive_fk20_implementation() {
         let poly_len = 4096;
@@ -1,6 +1,6 @@
 // The abstractions in this file were taken and modified from: https://github.com/EspressoSystems/jellyfish/blob/8f48813ca52d964090dbf0de62f07f5e0c7e22c6/primitives/src/toeplitz.rs#L1
 
-use bls12_381::Scalar;
+use bls12_381::{ff::Field, Scalar};
 
 /// A Toeplitz matrix is a matrix in which each descending diagonal from left to right is constant.
 /// "Constant" here means that all elements along any given diagonal have the same value.
@@ -44,12 +44,12 @@ use bls12_381::Scalar;
 /// In this example, we only store 8 elements (4 in `row` and 4 in `col`) to represent
 /// a 4x4 matrix that would normally require 16 elements.
 #[derive(Debug, Clone)]
-pub struct ToeplitzMatrix {
+pub struct ToeplitzMatrix<F = Scalar> {
     /// A vector representing the first row of the matrix.
-    row: Vec<Scalar>,
+    row: Vec<F>,
     ///  A vector representing the first column of the matrix, including the first element
     ///  (even though the first element is already included in the `row`).
-    col: Vec<Scalar>,
+    col: Vec<F>,
 }
 
 /// A circulant matrix is a special kind of Toeplitz matrix where each row is rotated one
@@ -95,6 +95,10 @@ pub struct ToeplitzMatrix {
 ///
 /// The main property of Circulant matrices that we leverage is that they are diagonalized by the Fourier
 /// transform, which allows for efficient computations.
+///
+/// Note: Outside of tests, the first row is written directly into a buffer with
+/// `ToeplitzMatrix::write_circulant_row`, so that the matrix is never allocated.
+#[cfg(test)]
 #[derive(Debug, Clone)]
 pub(crate) struct CirculantMatrix {
     /// A vector representing the first row of the matrix. This single row defines
@@ -102,8 +106,8 @@ pub(crate) struct CirculantMatrix {
     pub(crate) row: Vec<Scalar>,
 }
 
-impl ToeplitzMatrix {
-    pub fn new(row: Vec<Scalar>, col: Vec<Scalar>) -> Self {
+impl<F: Field> ToeplitzMatrix<F> {
+    pub fn new(row: Vec<F>, col: Vec<F>) -> Self {
         assert!(
             !row.is_empty() && !col.is_empty(),
             "cannot initialize ToeplitzMatrix with an empty row or column"
@@ -111,8 +115,33 @@ impl ToeplitzMatrix {
 
         Self { row, col }
     }
+
+    /// Writes the first row of the circulant matrix that this matrix is embedded into to `row`,
+    /// without allocating the circulant matrix. See `CirculantMatrix::from_toeplitz`.
+    ///
+    /// Panics if the length of `row` is not the dimension of the circulant matrix.
+    pub(crate) fn write_circulant_row(&self, row: &mut [F]) {
+        assert_eq!(
+            row.len(),
+            self.col.len() + self.row.len(),
+            "expected the row to have the dimension of the circulant matrix"
+        );
+
+        // The row is the column of this matrix, followed by its row rotated
+        // left by one and then reversed.
+        let (col, extension_col) = row.split_at_mut(self.col.len());
+        col.copy_from_slice(&self.col);
+        extension_col[0] = self.row[0];
+        for (dst, src) in extension_col[1..]
+            .iter_mut()
+            .zip(self.row[1..].iter().rev())
+        {
+            *dst = *src;
+        }
+    }
 }
 
+#[cfg(test)]
 impl CirculantMatrix {
     /// This method takes a Toeplitz matrix and embeds it into a larger circulant matrix.
     /// The resulting circulant matrix has a dimension that is twice as large as the original
@@ -126,10 +155,7 @@ impl CirculantMatrix {
             row: [tm.col, extension_col].concat(),
         }
     }
-}
 
-#[cfg(test)]
-impl CirculantMatrix {
     /// This method performs an efficient multiplication of the circulant matrix
     /// with a vector of scalars using FFT.
     ///
@@ -262,9 +288,9 @@ impl DenseMatrix {
 
 #[cfg(test)]
 mod tests {
-    use super::DenseMatrix;
+    use super::{CirculantMatrix, DenseMatrix};
     use crate::fk20::toeplitz::ToeplitzMatrix;
-    use bls12_381::Scalar;
+    use bls12_381::{ff::Field, Scalar};
 
     fn is_toeplitz(dense_matrix: &DenseMatrix) -> bool {
         let num_rows = dense_matrix.inner.len();
@@ -364,4 +390,17 @@ mod tests {
         let expected = dm.vector_mul_scalar(vector);
         assert_eq!(got, expected)
     }
+
+    #[test]
+    fn circulant_row_is_written_in_place() {
+        let col = vec![Scalar::from(1u64), Scalar::from(2u64), Scalar::from(3u64)];
+        let row = vec![Scalar::from(1u64), Scalar::from(5u64), Scalar::from(6u64)];
+
+        let tm = ToeplitzMatrix::new(row, col);
+        let mut got = vec![Scalar::ZERO; 6];
+        tm.write_circulant_row(&mut got);
+
+        let expected = CirculantMatrix::from_toeplitz(tm).row;
+        assert_eq!(got, expected)
+    }
 }
@@ -1,14 +1,21 @@
 use crate::{
+    backend::{Backend, BlstBackend},
     fk20::cosets::{coset_gens, reverse_bit_order},
     verification_key::VerificationKey,
 };
 use bls12_381::{
-    ff::Field, g1_batch_normalize, lincomb::g1_lincomb, multi_pairings,
-    reduce_bytes_to_scalar_bias, G1Point, G2Point, G2Prepared, Scalar,
+    ff::{Field, PrimeField},
+    group::{prime::PrimeCurveAffine, GroupEncoding},
+};
+use maybe_rayon::prelude::*;
+use polynomial::{
+    domain::Domain,
+    scratch::{Scratch, ScratchPool},
+    CosetFFT,
 };
-use polynomial::{domain::Domain, poly_coeff::poly_add, CosetFFT};
 use sha2::{Digest, Sha256};
 use std::mem::size_of;
+use std::sync::Arc;
 
 use super::errors::VerifierError;
 
@@ -35,52 +42,58 @@ pub type CommitmentIndex = u64;
 ///  - From the callers perspective, this distinction is not important
 ///  - We only use FK20 to create proofs
 #[derive(Debug)]
-pub struct FK20Verifier {
-    pub verification_key: VerificationKey,
-    pub coset_gens_bit_reversed: Vec<Scalar>,
-    coset_domain: Domain,
+pub struct FK20Verifier<B: Backend = BlstBackend> {
+    pub verification_key: VerificationKey<B>,
+    pub coset_gens_bit_reversed: Vec<B::Scalar>,
+    coset_domain: Arc<Domain<B::Scalar>>,
     // Pre-computations for the verification algorithm
     //
     // [tau^n]_2
-    tau_pow_n: G2Prepared,
+    tau_pow_n: B::G2Prepared,
     // [-1]_2
-    neg_g2_gen: G2Prepared,
+    neg_g2_gen: B::G2Prepared,
     // Bit reversed vector of the coset generators raised
     // to the power of `n`, needed to verify a multi opening proof.
-    pub bit_reversed_coset_gens_pow_n: Vec<Scalar>,
-    // Bit reversed vector of the coset generators that
-    // we will use to compute an inverse coset IFFT.
-    bit_reversed_coset_fft_gens: Vec<CosetFFT>,
+    pub bit_reversed_coset_gens_pow_n: Vec<B::Scalar>,
+    // Bit reversed vector of the powers `h^{-i}` of each coset generator `h`, for `i`
+    // up to the coset size. Scaling the IFFT over the domain by these powers gives the
+    // inverse coset IFFT, so they are only computed once for each of the cosets.
+    bit_reversed_coset_inv_powers: Vec<Vec<B::Scalar>>,
+    /// Buffers for interpolating the evaluations over a coset, which are reused across calls.
+    scratch: ScratchPool<B::Scalar>,
 }
 
-impl FK20Verifier {
+impl<B: Backend> FK20Verifier<B> {
     pub fn new(
-        verification_key: VerificationKey,
+        verification_key: VerificationKey<B>,
         num_points_to_open: usize,
         num_cosets: usize,
     ) -> Self {
         const BIT_REVERSED: bool = true;
-        let coset_gens = coset_gens(num_points_to_open, num_cosets, BIT_REVERSED);
+        let coset_gens = coset_gens::<B::Scalar>(num_points_to_open, num_cosets, BIT_REVERSED);
 
         let coset_size = num_points_to_open / num_cosets;
         assert!(
             verification_key.g2s.len() >= coset_size,
             "need as many g2 points as coset size"
         );
-        let coset_domain = polynomial::domain::Domain::new(verification_key.coset_size);
+        let coset_domain = Domain::get_or_init(verification_key.coset_size);
 
         let n = verification_key.coset_size;
         // [tau^n]_2
-        let tau_pow_n = G2Prepared::from(G2Point::from(verification_key.g2s[n]));
+        let tau_pow_n = B::g2_prepare(&verification_key.g2s[n]);
         // [-1]_2
-        let neg_g2_gen = G2Prepared::from(-verification_key.g2_gen());
+        let neg_g2_gen = B::g2_prepare(&-verification_key.g2_gen());
 
         let coset_gens_pow_n = coset_gens
             .iter()
             .map(|&coset_gen| coset_gen.pow_vartime([n as u64]))
             .collect();
 
-        let coset_fft_gens: Vec<_> = coset_gens.iter().map(|gen| CosetFFT::new(*gen)).collect();
+        let coset_inv_powers = CosetFFT::batch_new(&coset_gens)
+            .into_iter()
+            .map(|coset_fft| compute_powers(coset_fft.generator_inv, n))
+            .collect();
 
         Self {
             verification_key,
@@ -89,7 +102,8 @@ impl FK20Verifier {
             tau_pow_n,
             neg_g2_gen,
             bit_reversed_coset_gens_pow_n: coset_gens_pow_n,
-            bit_reversed_coset_fft_gens: coset_fft_gens,
+            bit_reversed_coset_inv_powers: coset_inv_powers,
+            scratch: ScratchPool::new(),
         }
     }
 
@@ -115,12 +129,12 @@ impl FK20Verifier {
     pub fn verify_multi_opening(
         &self,
 
-        deduplicated_commitments: &[G1Point],
+        deduplicated_commitments: &[B::G1Point],
         commitment_indices: &[CommitmentIndex],
 
         bit_reversed_coset_indices: &[CosetIndex],
-        bit_reversed_coset_evals: &[Vec<Scalar>],
-        bit_reversed_proofs: &[G1Point],
+        bit_reversed_coset_evals: &[Vec<B::Scalar>],
+        bit_reversed_proofs: &[B::G1Point],
     ) -> Result<(), VerifierError> {
         assert_eq!(
             commitment_indices.len(),
@@ -155,13 +169,15 @@ impl FK20Verifier {
             bit_reversed_proofs,
         );
         let r_powers = compute_powers(r, batch_size);
-        let num_unique_commitments = deduplicated_commitments.len();
 
         // 2. Compute a random linear combination of the proofs
         //
+        // The variable base MSMs use the GLV endomorphism, since their scalars are public,
+        // and are split across the threads of the current thread pool.
+        //
         // Safety: This unwrap can never trigger because `r_powers.len()` is `batch_size`
         // and `bit_reversed_proofs.len()` will equal `batch_size` since we must have a proof for each item in the batch.
-        let comm_random_sum_proofs = g1_lincomb(bit_reversed_proofs, &r_powers)
+        let comm_random_sum_proofs = g1_lincomb_par::<B>(bit_reversed_proofs, &r_powers)
             .expect("number of proofs and number of r_powers should be the same");
 
         // 3. Compute a weighted random linear combination of the proofs
@@ -172,11 +188,12 @@ impl FK20Verifier {
         {
             let coset_gen_pow_n =
                 self.bit_reversed_coset_gens_pow_n[*bit_reversed_coset_index as usize];
-            weighted_r_powers.push(r_power * coset_gen_pow_n);
+            weighted_r_powers.push(*r_power * coset_gen_pow_n);
         }
         // Safety: This should never panic since `bit_reversed_proofs.len()` is equal to the batch_size.
-        let random_weighted_sum_proofs = g1_lincomb(bit_reversed_proofs, &weighted_r_powers)
-            .expect("number of proofs and number of weighted_r_powers should be the same");
+        let random_weighted_sum_proofs =
+            g1_lincomb_par::<B>(bit_reversed_proofs, &weighted_r_powers)
+                .expect("number of proofs and number of weighted_r_powers should be the same");
 
         // 4. Compute a random linear combination of the commitments
         //
@@ -190,22 +207,18 @@ impl FK20Verifier {
         //
         // One can view this as trading a scalar multiplication for a field addition.
         //
-        // The extra field additions are being calculated in the for loop below.
-        let mut weights = vec![Scalar::ZERO; num_unique_commitments];
-        for (commitment_index, r_power) in commitment_indices.iter().zip(&r_powers) {
-            weights[*commitment_index as usize] += r_power;
-        }
-        // Safety: This unwrap will never trigger because the length of `weights` has been initialized
-        // to be `deduplicated_commitments.len()`.
-        //
-        // This only panics, if `deduplicated_commitments.len()` != `weights.len()`
-        let random_sum_commitments = g1_lincomb(deduplicated_commitments, &weights)
-            .expect("number of row_commitments and number of weights should be the same");
+        // The extra field additions are being calculated in `compute_random_sum_commitments`.
+        let random_sum_commitments = compute_random_sum_commitments::<B>(
+            deduplicated_commitments,
+            commitment_indices,
+            &r_powers,
+        );
 
         // 5. Compute random linear combination of the interpolation polynomials
         let random_sum_interpolation_poly = compute_sum_interpolation_poly(
             &self.coset_domain,
-            &self.bit_reversed_coset_fft_gens,
+            &self.bit_reversed_coset_inv_powers,
+            &self.scratch,
             bit_reversed_coset_evals,
             bit_reversed_coset_indices,
             &r_powers,
@@ -220,13 +233,170 @@ impl FK20Verifier {
         let pairing_input_g1 = (random_sum_commitments - comm_random_sum_interpolation_poly)
             + random_weighted_sum_proofs;
 
+        self.check_pairing(comm_random_sum_proofs, pairing_input_g1)
+    }
+
+    /// Verify multiple multi-opening proofs that are all for the same coset.
+    ///
+    /// This accepts and rejects exactly the same openings as `verify_multi_opening`, when it is
+    /// called with `bit_reversed_coset_index` as the coset index of every opening. This is the case
+    /// when verifying a column of cells, that each belong to a different blob.
+    ///
+    /// Since all of the openings share a coset, it is cheaper to verify them:
+    /// - The evaluations are combined before interpolating them, so only one coset IFFT is needed,
+    ///   instead of one for each opening.
+    /// - The weighted sum of the proofs is a multiple of the random sum of the proofs, which
+    ///   saves an MSM.
+    ///
+    /// Panics if `commitment_indices`, `bit_reversed_coset_evals` and `bit_reversed_proofs`
+    /// do not have the same length, or if the coset index is out of range.
+    pub fn verify_multi_opening_same_coset(
+        &self,
+
+        deduplicated_commitments: &[B::G1Point],
+        commitment_indices: &[CommitmentIndex],
+
+        bit_reversed_coset_index: CosetIndex,
+        bit_reversed_coset_evals: &[Vec<B::Scalar>],
+        bit_reversed_proofs: &[B::G1Point],
+    ) -> Result<(), VerifierError> {
+        assert_eq!(
+            commitment_indices.len(),
+            bit_reversed_proofs.len(),
+            "Expected to have a proof for each commitment opening"
+        );
+        assert_eq!(
+            bit_reversed_coset_evals.len(),
+            bit_reversed_proofs.len(),
+            "Expected to have a proof for each evaluation we want to prove an opening for"
+        );
+        let batch_size = bit_reversed_proofs.len();
+        let coset_index = bit_reversed_coset_index as usize;
+
+        // 1. Compute random challenges for batching the opening together.
+        //
+        // The challenge is computed in the same way as in `verify_multi_opening`.
+        let bit_reversed_coset_indices = vec![bit_reversed_coset_index; batch_size];
+        let r = compute_fiat_shamir_challenge(
+            &self.verification_key,
+            deduplicated_commitments,
+            commitment_indices,
+            &bit_reversed_coset_indices,
+            bit_reversed_coset_evals,
+            bit_reversed_proofs,
+        );
+        let r_powers = compute_powers(r, batch_size);
+
+        // 2. Compute a random linear combination of the proofs
+        let comm_random_sum_proofs = g1_lincomb_par::<B>(bit_reversed_proofs, &r_powers)
+            .expect("number of proofs and number of r_powers should be the same");
+
+        // 3. Compute a weighted random linear combination of the proofs
+        //
+        // Every proof has the same weight, so this is the random sum of the proofs, times the weight.
+        let random_weighted_sum_proofs =
+            comm_random_sum_proofs * self.bit_reversed_coset_gens_pow_n[coset_index];
+
+        // 4. Compute a random linear combination of the commitments
+        let random_sum_commitments = compute_random_sum_commitments::<B>(
+            deduplicated_commitments,
+            commitment_indices,
+            &r_powers,
+        );
+
+        // 5. Compute random linear combination of the interpolation polynomials
+        //
+        // Interpolation is linear, so interpolating the random linear combination of the evaluations
+        // gives the random linear combination of the interpolation polynomials.
+        let mut random_sum_interpolation_poly =
+            self.scratch.take(self.coset_domain.size(), B::Scalar::ZERO);
+        for (coset_evals, r_power) in bit_reversed_coset_evals.iter().zip(&r_powers) {
+            for (sum, eval) in random_sum_interpolation_poly.iter_mut().zip(coset_evals) {
+                *sum += *eval * r_power;
+            }
+        }
+        self.interpolate_coset_evals(coset_index, &mut random_sum_interpolation_poly);
+        let comm_random_sum_interpolation_poly = self
+            .verification_key
+            .commit_g1(&random_sum_interpolation_poly);
+
+        // 6. Compute pairing check
+        let pairing_input_g1 = (random_sum_commitments - comm_random_sum_interpolation_poly)
+            + random_weighted_sum_proofs;
+
+        self.check_pairing(comm_random_sum_proofs, pairing_input_g1)
+    }
+
+    /// Verify a single multi-opening proof.
+    ///
+    /// This accepts and rejects exactly the same opening as `verify_multi_opening`, when it is
+    /// called with a batch that only contains this opening.
+    ///
+    /// Since there is nothing to combine the opening with, it does not need a random challenge,
+    /// and the MSMs over the proofs and commitments become a single scalar multiplication.
+    ///
+    /// Panics if the coset index is out of range, or if the number of evaluations is not the coset size.
+    pub fn verify_single_opening(
+        &self,
+        commitment: &B::G1Point,
+        bit_reversed_coset_index: CosetIndex,
+        bit_reversed_coset_evals: &[B::Scalar],
+        proof: &B::G1Point,
+    ) -> Result<(), VerifierError> {
+        assert_eq!(
+            bit_reversed_coset_evals.len(),
+            self.coset_domain.size(),
+            "Expected to have an evaluation for each point in the coset"
+        );
+        let coset_index = bit_reversed_coset_index as usize;
+
+        // This is the pairing check of `verify_multi_opening` for a batch of one, where the
+        // only power of the random challenge is `1`.
+        let mut interpolation_poly = self.scratch.take(self.coset_domain.size(), B::Scalar::ZERO);
+        interpolation_poly.copy_from_slice(bit_reversed_coset_evals);
+        self.interpolate_coset_evals(coset_index, &mut interpolation_poly);
+        let comm_interpolation_poly = self.verification_key.commit_g1(&interpolation_poly);
+
+        let weighted_proof = *proof * self.bit_reversed_coset_gens_pow_n[coset_index];
+        let pairing_input_g1 = (commitment.to_curve() - comm_interpolation_poly) + weighted_proof;
+
+        self.check_pairing(proof.to_curve(), pairing_input_g1)
+    }
+
+    /// Computes the interpolation polynomial of the evaluations over the coset with the given index.
+    ///
+    /// The evaluations are in bit reversed order, and are overwritten by the coefficients of the
+    /// interpolation polynomial.
+    fn interpolate_coset_evals(
+        &self,
+        coset_index: usize,
+        bit_reversed_coset_evals: &mut [B::Scalar],
+    ) {
+        // Reverse the order, so it matches the fft domain
+        reverse_bit_order(bit_reversed_coset_evals);
+        self.coset_domain
+            .ifft_scalars_inplace(bit_reversed_coset_evals);
+        for (coeff, inv_power) in bit_reversed_coset_evals
+            .iter_mut()
+            .zip(&self.bit_reversed_coset_inv_powers[coset_index])
+        {
+            *coeff *= inv_power;
+        }
+    }
+
+    /// Checks that `e(random_sum_proofs, [tau^n]_2) = e(pairing_input_g1, [1]_2)`.
+    fn check_pairing(
+        &self,
+        random_sum_proofs: B::G1Projective,
+        pairing_input_g1: B::G1Projective,
+    ) -> Result<(), VerifierError> {
         // The pairings function requires elements in affine representation, so we must batch normalize the
         // pairing inputs.
-        let normalized_vectors = g1_batch_normalize(&[comm_random_sum_proofs, pairing_input_g1]);
+        let normalized_vectors = B::g1_batch_normalize(&[random_sum_proofs, pairing_input_g1]);
         let random_sum_proofs = normalized_vectors[0];
         let pairing_input_g1 = normalized_vectors[1];
 
-        let proof_valid = multi_pairings(&[
+        let proof_valid = B::multi_pairings(&[
             (&random_sum_proofs, &self.tau_pow_n),
             (&pairing_input_g1, &self.neg_g2_gen),
         ]);
@@ -238,60 +408,125 @@ impl FK20Verifier {
     }
 }
 
+/// Computes a random linear combination of the commitments, where the opening at position `k`
+/// adds `r_powers[k]` to the weight of the commitment that it refers to.
+fn compute_random_sum_commitments<B: Backend>(
+    deduplicated_commitments: &[B::G1Point],
+    commitment_indices: &[CommitmentIndex],
+    r_powers: &[B::Scalar],
+) -> B::G1Projective {
+    let mut weights = vec![B::Scalar::ZERO; deduplicated_commitments.len()];
+    for (commitment_index, r_power) in commitment_indices.iter().zip(r_powers) {
+        weights[*commitment_index as usize] += r_power;
+    }
+    // Safety: This unwrap will never trigger because the length of `weights` has been initialized
+    // to be `deduplicated_commitments.len()`.
+    //
+    // This only panics, if `deduplicated_commitments.len()` != `weights.len()`
+    g1_lincomb_par::<B>(deduplicated_commitments, &weights)
+        .expect("number of row_commitments and number of weights should be the same")
+}
+
+/// The smallest number of points in each chunk, when an MSM is split across threads.
+///
+/// Note: Below this size, splitting the MSM saves less work than it costs to
+/// distribute the chunks across threads and to add up their results.
+const MIN_PARALLEL_MSM_CHUNK_SIZE: usize = 1 << 7;
+
+/// Computes `Backend::g1_lincomb_vartime`, splitting the points into about one chunk per thread
+/// of the current thread pool.
+///
+/// Small MSMs, and every MSM when running single threaded, are computed as a single chunk.
+fn g1_lincomb_par<B: Backend>(
+    points: &[B::G1Point],
+    scalars: &[B::Scalar],
+) -> Option<B::G1Projective> {
+    if points.len() != scalars.len() {
+        return None;
+    }
+
+    let chunk_size = (points.len() / current_num_threads()).max(MIN_PARALLEL_MSM_CHUNK_SIZE);
+    if points.len() <= chunk_size {
+        return B::g1_lincomb_vartime(points, scalars);
+    }
+
+    let sum = points
+        .maybe_par_chunks(chunk_size)
+        .zip(scalars.maybe_par_chunks(chunk_size))
+        .map(|(points, scalars)| {
+            B::g1_lincomb_vartime(points, scalars)
+                .expect("chunks of points and scalars have the same length")
+        })
+        .sum();
+    Some(sum)
+}
+
 /// Computes a random challenge which will allow us to efficiently verify multiple opening proofs.
 ///
 /// Efficiently refers to being able to verify these proofs faster than verifying each proof individually.
 ///
 /// The matching function in the spec is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/_features/eip7594/polynomial-commitments-sampling.md#compute_verify_cell_kzg_proof_batch_challenge
 #[allow(clippy::manual_slice_size_calculation)]
-fn compute_fiat_shamir_challenge(
-    verification_key: &VerificationKey,
-    row_commitments: &[G1Point],
+fn compute_fiat_shamir_challenge<B: Backend>(
+    verification_key: &VerificationKey<B>,
+    row_commitments: &[B::G1Point],
     row_indices: &[u64],
     coset_indices: &[u64],
-    coset_evals: &[Vec<Scalar>],
-    proofs: &[G1Point],
-) -> Scalar {
+    coset_evals: &[Vec<B::Scalar>],
+    proofs: &[B::G1Point],
+) -> B::Scalar {
     const DOMAIN_SEP: &str = "RCKZGCBATCH__V1_";
+    let g1_point_size = <B::G1Point as GroupEncoding>::Repr::default()
+        .as_ref()
+        .len();
+    let scalar_size = <B::Scalar as PrimeField>::Repr::default().as_ref().len();
     let hash_input_size = DOMAIN_SEP.len()
             + size_of::<u64>() // polynomial bound
             + size_of::<u64>() // field elements per coset
             + size_of::<u64>() // num commitments
             + size_of::<u64>() // num cosets
-            + row_commitments.len() * G1Point::compressed_size()
+            + row_commitments.len() * g1_point_size
             + row_indices.len() * size_of::<u64>()
             + coset_indices.len() * size_of::<u64>()
-            + coset_evals.len() * verification_key.coset_size * size_of::<Scalar>()
-            + proofs.len() * G1Point::compressed_size();
+            + coset_evals.len() * verification_key.coset_size * scalar_size
+            + proofs.len() * g1_point_size;
 
-    let mut hash_input: Vec<u8> = Vec::with_capacity(hash_input_size);
+    // The input is passed to the hasher as it is serialized, instead of being collected into
+    // a buffer first, since the buffer would be larger than all of the other inputs combined.
+    let mut hasher = Sha256::new();
+    let mut hash_input_len = 0;
+    let mut hash = |bytes: &[u8]| {
+        hasher.update(bytes);
+        hash_input_len += bytes.len();
+    };
 
-    hash_input.extend(DOMAIN_SEP.as_bytes());
-    hash_input.extend((verification_key.num_coefficients_in_polynomial as u64).to_be_bytes());
-    hash_input.extend((verification_key.coset_size as u64).to_be_bytes());
+    hash(DOMAIN_SEP.as_bytes());
+    hash(&(verification_key.num_coefficients_in_polynomial as u64).to_be_bytes());
+    hash(&(verification_key.coset_size as u64).to_be_bytes());
 
     let num_commitments = row_commitments.len() as u64;
-    hash_input.extend(num_commitments.to_be_bytes());
+    hash(&num_commitments.to_be_bytes());
 
     let num_cosets = coset_indices.len() as u64;
-    hash_input.extend(num_cosets.to_be_bytes());
+    hash(&num_cosets.to_be_bytes());
 
     for commitment in row_commitments {
-        hash_input.extend(commitment.to_compressed())
+        hash(commitment.to_bytes().as_ref())
     }
 
     for k in 0..num_cosets {
-        hash_input.extend(row_indices[k as usize].to_be_bytes());
-        hash_input.extend(coset_indices[k as usize].to_be_bytes());
+        hash(&row_indices[k as usize].to_be_bytes());
+        hash(&coset_indices[k as usize].to_be_bytes());
         for eval in &coset_evals[k as usize] {
-            hash_input.extend(eval.to_bytes_be())
+            // The scalars are hashed in big-endian order, and their representation is little-endian
+            let mut eval_bytes = eval.to_repr();
+            eval_bytes.as_mut().reverse();
+            hash(eval_bytes.as_ref())
         }
-        hash_input.extend(proofs[k as usize].to_compressed())
+        hash(proofs[k as usize].to_bytes().as_ref())
     }
 
-    assert_eq!(hash_input.len(), hash_input_size);
-    let mut hasher = Sha256::new();
-    hasher.update(hash_input);
+    assert_eq!(hash_input_len, hash_input_size);
     let result: [u8; 32] = hasher.finalize().into();
 
     // For randomization, we only need a 128 bit scalar, since this is used for batch verification.
@@ -302,17 +537,15 @@ fn compute_fiat_shamir_challenge(
     // want a uniformly random 128 bit integer.
     //
     // Also there is a negligible probably that the scalar is zero, so we do not handle this case here.
-    reduce_bytes_to_scalar_bias(result)
+    B::reduce_bytes_to_scalar(result)
 }
 
 /// Computes a vector of powers of a given scalar value.
 ///
 /// Example: compute_powers(x, 5) = [1, x, x^2, x^3, x^4]
-fn compute_powers(value: Scalar, num_elements: usize) -> Vec<Scalar> {
-    use bls12_381::ff::Field;
-
+fn compute_powers<F: Field>(value: F, num_elements: usize) -> Vec<F> {
     let mut powers = Vec::with_capacity(num_elements);
-    let mut current_power = Scalar::ONE;
+    let mut current_power = F::ONE;
 
     for _ in 0..num_elements {
         powers.push(current_power);
@@ -325,38 +558,44 @@ fn compute_powers(value: Scalar, num_elements: usize) -> Vec<Scalar> {
 /// Computes `k` Interpolation polynomials and then combines
 /// them linearly using `k` values from `r_powers`.
 /// The computed value is I(X) = I_0(x) + r * I_1(x) + ... + r^{n-1} * I_{n-1}(x)
-fn compute_sum_interpolation_poly(
-    coset_domain: &Domain,
-    bit_reversed_coset_fft_gens: &[CosetFFT],
-    bit_reversed_coset_evals: &[Vec<Scalar>],
+///
+/// The sum and the interpolation polynomial of each coset are computed in buffers that are taken
+/// from `scratch`, instead of allocating new ones for every call.
+fn compute_sum_interpolation_poly<'a, F: PrimeField>(
+    coset_domain: &Domain<F>,
+    bit_reversed_coset_inv_powers: &[Vec<F>],
+    scratch: &'a ScratchPool<F>,
+    bit_reversed_coset_evals: &[Vec<F>],
     bit_reversed_coset_indices: &[CosetIndex],
-    r_powers: &[Scalar],
-) -> Vec<Scalar> {
-    let mut random_sum_interpolation_poly = Vec::new();
+    r_powers: &[F],
+) -> Scratch<'a, F> {
+    let mut random_sum_interpolation_poly = scratch.take(coset_domain.size(), F::ZERO);
+    let mut interpolation_poly = scratch.take(coset_domain.size(), F::ZERO);
 
-    for ((mut bit_reversed_coset_eval, bit_reversed_coset_index), scale_factor) in
+    for ((bit_reversed_coset_eval, bit_reversed_coset_index), scale_factor) in
         bit_reversed_coset_evals
             .iter()
-            .cloned()
             .zip(bit_reversed_coset_indices)
             .zip(r_powers)
     {
         // Reverse the order, so it matches the fft domain
-        reverse_bit_order(&mut bit_reversed_coset_eval);
-        let coset_eval = bit_reversed_coset_eval; // variable rename since we un-bit reversed the vector
-
-        // Compute the interpolation polynomial using a coset fft
-        let coset_gen = &bit_reversed_coset_fft_gens[*bit_reversed_coset_index as usize];
-        let ifft_scalars = coset_domain.coset_ifft_scalars(coset_eval, coset_gen);
-
-        // Scale the interpolation polynomial by the challenge
-        let scaled_interpolation_poly = ifft_scalars
-            .into_iter()
-            .map(|coeff| coeff * scale_factor)
-            .collect::<Vec<_>>();
-
-        random_sum_interpolation_poly =
-            poly_add(random_sum_interpolation_poly, scaled_interpolation_poly);
+        interpolation_poly.copy_from_slice(bit_reversed_coset_eval);
+        reverse_bit_order(&mut interpolation_poly);
+
+        // Compute the interpolation polynomial using an inverse coset fft, which is an
+        // ifft over the domain, followed by scaling the coefficients by the powers of the
+        // inverse coset generator.
+        coset_domain.ifft_scalars_inplace(&mut interpolation_poly);
+        let inv_powers = &bit_reversed_coset_inv_powers[*bit_reversed_coset_index as usize];
+
+        // Scale the interpolation polynomial by the challenge and add it to the sum
+        for ((sum, coeff), inv_power) in random_sum_interpolation_poly
+            .iter_mut()
+            .zip(interpolation_poly.iter())
+            .zip(inv_powers)
+        {
+            *sum += *coeff * (*inv_power * scale_factor);
+        }
     }
 
     random_sum_interpolation_poly
@@ -365,7 +604,7 @@ fn compute_sum_interpolation_poly(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bls12_381::Scalar;
+    use bls12_381::{glv::g1_lincomb_glv, G1Projective, Scalar};
 
     #[test]
     fn test_compute_powers() {
@@ -384,4 +623,25 @@ mod tests {
         let powers = compute_powers(base, 0);
         assert!(powers.is_empty());
     }
+
+    #[test]
+    fn parallel_lincomb_matches_lincomb() {
+        use bls12_381::group::{Curve, Group};
+
+        let num_points = 4 * MIN_PARALLEL_MSM_CHUNK_SIZE + 3;
+        let points: Vec<_> = (0..num_points as u64)
+            .map(|i| (G1Projective::generator() * Scalar::from(i + 1)).to_affine())
+            .collect();
+        let scalars: Vec<_> = (0..num_points as u64)
+            .map(|i| Scalar::from(i).square() - Scalar::ONE)
+            .collect();
+
+        for length in [0, 1, MIN_PARALLEL_MSM_CHUNK_SIZE + 1, num_points] {
+            assert_eq!(
+                g1_lincomb_par::<BlstBackend>(&points[..length], &scalars[..length]),
+                g1_lincomb_glv(&points[..length], &scalars[..length])
+            );
+        }
+        assert_eq!(g1_lincomb_par::<BlstBackend>(&points, &scalars[1..]), None);
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{commit_key::CommitKey, verification_key::VerificationKey};
-use bls12_381::{ff::Field, multi_pairings, G1Point, G1Projective, G2Point, G2Prepared, Scalar};
+use bls12_381::{multi_pairings, G1Point, G1Projective, G2Point, G2Prepared, Scalar};
 use polynomial::poly_coeff::{
-    lagrange_interpolate, poly_eval, poly_sub, vanishing_poly, PolyCoeff,
+    lagrange_interpolate, poly_eval, vanishing_poly, PolyCoeff, Polynomial,
 };
 
 /// This modules contains code to create and verify opening proofs in a naive way.
@@ -66,25 +66,6 @@ fn _compute_multi_opening_naive(
     polynomial: &PolyCoeff,
     points: &[Scalar],
 ) -> (G1Point, Vec<Scalar>) {
-    // Divides `self` by x-z using Ruffinis rule
-    fn divide_by_linear(poly: &[Scalar], z: Scalar) -> Vec<Scalar> {
-        let mut quotient: Vec<Scalar> = Vec::with_capacity(poly.len());
-        let mut k = Scalar::ZERO;
-
-        for coeff in poly.iter().rev() {
-            let t = *coeff + k;
-            quotient.push(t);
-            k = z * t;
-        }
-
-        // Pop off the remainder term
-        quotient.pop();
-
-        // Reverse the results as monomial form stores coefficients starting with lowest degree
-        quotient.reverse();
-        quotient
-    }
-
     let mut evaluations = Vec::new();
     for point in points {
         let evaluation = poly_eval(polynomial, point);
@@ -103,22 +84,27 @@ fn _compute_multi_opening_naive(
         .zip(evaluations.iter())
         .map(|(p, e)| (*p, *e))
         .collect();
-    let i_x = lagrange_interpolate(&coordinates).expect("lagrange interpolation failed");
+    let i_x =
+        Polynomial::new(lagrange_interpolate(&coordinates).expect("lagrange interpolation failed"));
 
     // Check that the i_x polynomial is correct, it should essentially be the polynomial that
     // evaluates to f(z_i) = I(z_i)
     for (point, evaluation) in points.iter().zip(evaluations.iter()) {
-        assert_eq!(poly_eval(&i_x, point), *evaluation);
+        assert_eq!(i_x.eval(point), *evaluation);
     }
 
-    let poly_shifted = poly_sub(polynomial.clone(), i_x);
+    let poly_shifted = Polynomial::new(polynomial.clone()) - i_x;
+    let z_x = Polynomial::new(vanishing_poly(points));
 
-    let mut quotient_poly = poly_shifted;
-    for point in points {
-        quotient_poly = divide_by_linear(&quotient_poly, *point);
-    }
+    let (quotient_poly, remainder) = poly_shifted
+        .div_rem(&z_x)
+        .expect("vanishing polynomial is non-zero");
+    assert!(remainder.is_zero());
 
-    (commit_key.commit_g1(&quotient_poly).into(), evaluations)
+    (
+        commit_key.commit_g1(quotient_poly.coeffs()).into(),
+        evaluations,
+    )
 }
 
 /// Verifies a multi-opening proof using the general formula.
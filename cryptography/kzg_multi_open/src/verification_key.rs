@@ -1,21 +1,18 @@
-use bls12_381::{
-    lincomb::{g1_lincomb, g2_lincomb},
-    G1Point, G1Projective, G2Point, G2Projective, Scalar,
-};
+use crate::backend::{Backend, BlstBackend};
 
 /// Verification Key is used to verify opening proofs made about a committed polynomial.
 #[derive(Clone, Debug)]
-pub struct VerificationKey {
+pub struct VerificationKey<B: Backend = BlstBackend> {
     /// The powers of tau G1 used in the setup
     ///
     /// ie group elements of the form `{ \tau^i G }`
-    pub g1s: Vec<G1Point>,
+    pub g1s: Vec<B::G1Point>,
     /// The powers of tau G2 used in the setup
     ///
     /// ie group elements of the form `{ \tau^i G }`
-    pub g2s: Vec<G2Point>,
+    pub g2s: Vec<B::G2Point>,
     /// The degree-0 term in the powers of tau G2 elements.
-    pub g2_gen: G2Point,
+    pub g2_gen: B::G2Point,
     /// This is the number of points that will be a
     /// opened at any one time. Another way to think
     /// of this, is that its the number of points a
@@ -32,10 +29,10 @@ pub struct VerificationKey {
     pub num_coefficients_in_polynomial: usize,
 }
 
-impl VerificationKey {
+impl<B: Backend> VerificationKey<B> {
     pub fn new(
-        g1s: Vec<G1Point>,
-        g2s: Vec<G2Point>,
+        g1s: Vec<B::G1Point>,
+        g2s: Vec<B::G2Point>,
         coset_size: usize,
         num_coefficients_in_polynomial: usize,
     ) -> Self {
@@ -56,21 +53,21 @@ impl VerificationKey {
     }
 
     /// Commit to a polynomial in monomial form using the G2 group elements
-    pub fn commit_g2(&self, polynomial: &[Scalar]) -> G2Projective {
+    pub fn commit_g2(&self, polynomial: &[B::Scalar]) -> B::G2Projective {
         assert!(self.g2s.len() >= polynomial.len());
-        g2_lincomb(&self.g2s[0..polynomial.len()], polynomial)
+        B::g2_lincomb(&self.g2s[0..polynomial.len()], polynomial)
             .expect("number of g2 points is equal to the number of coefficients in the polynomial")
     }
 
     /// Commit to a polynomial in monomial form using the G1 group elements
-    pub fn commit_g1(&self, polynomial: &[Scalar]) -> G1Projective {
+    pub fn commit_g1(&self, polynomial: &[B::Scalar]) -> B::G1Projective {
         assert!(self.g1s.len() >= polynomial.len());
-        g1_lincomb(&self.g1s[0..polynomial.len()], polynomial)
+        B::g1_lincomb(&self.g1s[0..polynomial.len()], polynomial)
             .expect("number of g1 points is equal to the number of coefficients in the polynomial")
     }
 
     /// Returns the degree-0 element in the G2 powers of tau list
-    pub const fn g2_gen(&self) -> G2Point {
+    pub const fn g2_gen(&self) -> B::G2Point {
         self.g2_gen
     }
 }
@@ -1,19 +1,53 @@
+use crate::batch_inverse;
 use bls12_381::{ff::Field, Scalar};
 
 /// CosetFFt contains a generator(coset) element that can be used
 /// to compute a coset FFT and its inverse which consequently can be used to
 /// compute a coset IFFT
 #[derive(Debug, Clone)]
-pub struct CosetFFT {
-    pub generator: Scalar,
-    pub generator_inv: Scalar,
+pub struct CosetFFT<F = Scalar> {
+    pub generator: F,
+    pub generator_inv: F,
 }
 
-impl CosetFFT {
-    pub fn new(gen: Scalar) -> Self {
+impl<F: Field> CosetFFT<F> {
+    pub fn new(gen: F) -> Self {
         Self {
             generator: gen,
             generator_inv: gen.invert().expect("cosets should be non-zero"),
         }
     }
+
+    /// Creates a `CosetFFT` for each of the generators, using a single batch inversion
+    /// to compute all of the inverse generators.
+    pub fn batch_new(generators: &[F]) -> Vec<Self> {
+        let mut generators_inv = generators.to_vec();
+        batch_inverse(&mut generators_inv);
+
+        generators
+            .iter()
+            .zip(generators_inv)
+            .map(|(&generator, generator_inv)| Self {
+                generator,
+                generator_inv,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_new_matches_new() {
+        let generators: Vec<_> = (1..10u64).map(Scalar::from).collect();
+        let coset_ffts = CosetFFT::batch_new(&generators);
+
+        for (generator, coset_fft) in generators.into_iter().zip(coset_ffts) {
+            let expected = CosetFFT::new(generator);
+            assert_eq!(coset_fft.generator, expected.generator);
+            assert_eq!(coset_fft.generator_inv, expected.generator_inv);
+        }
+    }
 }
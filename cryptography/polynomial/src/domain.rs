@@ -1,42 +1,55 @@
+use crate::batch_inverse;
 use crate::coset_fft::CosetFFT;
-use crate::fft::{fft_g1_inplace, fft_scalar_inplace, precompute_twiddle_factors};
+use crate::fft::{fft_g1_inplace, fft_scalar_inplace, precompute_bit_reversal_swaps};
 use crate::poly_coeff::PolyCoeff;
 use bls12_381::ff::{Field, PrimeField};
-use bls12_381::{
-    group::Group,
-    {G1Projective, Scalar},
-};
+use bls12_381::{group::Group, Scalar};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::iter::StepBy;
+use std::ops::{Index, MulAssign};
+use std::slice;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+/// A value in one of the caches, whose type depends on the field that it was computed for.
+type ErasedArc = Arc<dyn Any + Send + Sync>;
 
 /// A struct representing a set of points that are roots of unity,
 /// which allows us to efficiently evaluate and interpolate polynomial
 /// over these points using FFT.
 #[derive(Debug, Clone)]
-pub struct Domain {
+pub struct Domain<F = Scalar> {
     /// roots of unity
-    pub roots: Vec<Scalar>,
+    pub roots: Roots<F>,
     /// size of the domain as a scalar
-    pub domain_size: Scalar,
+    pub domain_size: F,
     /// Inverse of the domain size as a scalar
-    pub domain_size_inv: Scalar,
+    pub domain_size_inv: F,
     /// Generator for this domain
     // Element has order `domain_size`
-    pub generator: Scalar,
+    pub generator: F,
     /// Inverse of the generator for the domain
     /// This is cached for IFFT
-    pub generator_inv: Scalar,
+    pub generator_inv: F,
     /// Precomputed values for the generator to speed up
     /// the forward FFT
-    twiddle_factors: Vec<Scalar>,
+    twiddle_factors: Vec<F>,
     /// Precomputed values for the generator to speed up
     /// the backward FFT
-    twiddle_factors_inv: Vec<Scalar>,
+    twiddle_factors_inv: Vec<F>,
+    /// The pairs of positions that are swapped to put the input of an FFT
+    /// into bit-reversed order, which is the same for every FFT over this domain
+    bit_reversal_swaps: Vec<(u32, u32)>,
 }
 
-impl Domain {
+impl<F: PrimeField> Domain<F> {
     pub fn new(size: usize) -> Self {
         // We are using roots of unity, so the
         // size of the domain will be padded to
-        // the next power of two
+        // the next power of two.
+        //
+        // Use `MixedRadixDomain` if the domain size
+        // must not be padded.
         let size = if size.is_power_of_two() {
             size
         } else {
@@ -46,19 +59,19 @@ impl Domain {
         let generator = Self::compute_generator_for_size(size);
         let generator_inv = generator.invert().expect("generator should not be zero");
 
-        let size_as_scalar = Scalar::from(size as u64);
+        let size_as_scalar = F::from(size as u64);
         let size_as_scalar_inv = size_as_scalar.invert().expect("size should not be zero");
 
-        let mut roots = Vec::with_capacity(size);
-        roots.push(Scalar::ONE);
-
-        for i in 1..size {
-            let prev_root = roots[i - 1];
-            roots.push(prev_root * generator)
-        }
+        let roots = Roots::new(size);
 
-        let twiddle_factors = precompute_twiddle_factors(&generator, size);
-        let twiddle_factors_inv = precompute_twiddle_factors(&generator_inv, size);
+        // The twiddle factors are `generator^(size / 2^(s + 1))` for each stage `s` of the FFT,
+        // which are roots of the domain, so they are read from the table instead of recomputed.
+        let log_size = size.trailing_zeros();
+        let twiddle_factors = (0..log_size).map(|s| roots[size >> (s + 1)]).collect();
+        let twiddle_factors_inv = (0..log_size)
+            .map(|s| roots[size - (size >> (s + 1))])
+            .collect();
+        let bit_reversal_swaps = precompute_bit_reversal_swaps(size);
 
         Self {
             roots,
@@ -68,17 +81,47 @@ impl Domain {
             generator_inv,
             twiddle_factors,
             twiddle_factors_inv,
+            bit_reversal_swaps,
         }
     }
 
+    /// Returns the domain for the given size, computing it the first time
+    /// that size is requested.
+    ///
+    /// Domains are cached for the lifetime of the process, so the roots of unity
+    /// and twiddle factors for each size are only computed once, regardless of how many
+    /// contexts are created.
+    ///
+    /// Note: Like `Domain::new`, the size will be padded to the next power of two.
+    pub fn get_or_init(size: usize) -> Arc<Self> {
+        // A static is shared between all of the fields that this is instantiated with,
+        // so the domains are keyed by the type of the field as well as the size.
+        static DOMAINS: OnceLock<Mutex<HashMap<(TypeId, usize), ErasedArc>>> = OnceLock::new();
+
+        let size = size.next_power_of_two();
+        let mut domains = DOMAINS
+            .get_or_init(Default::default)
+            .lock()
+            // The cache is never left in an inconsistent state, so we can ignore poisoning.
+            .unwrap_or_else(PoisonError::into_inner);
+
+        domains
+            .entry((TypeId::of::<F>(), size))
+            .or_insert_with(|| Arc::new(Self::new(size)))
+            .clone()
+            .downcast()
+            .expect("infallible: domains are keyed by the type of their field")
+    }
+
     /// Computes an n'th root of unity for a given `n`
-    fn compute_generator_for_size(size: usize) -> Scalar {
+    fn compute_generator_for_size(size: usize) -> F {
         assert!(size.is_power_of_two());
 
         let log_size_of_group = size.trailing_zeros();
         assert!(
             log_size_of_group <= Self::two_adicity(),
-            "two adicity is 32 but group size needed is 2^{log_size_of_group}"
+            "two adicity is {} but group size needed is 2^{log_size_of_group}",
+            Self::two_adicity()
         );
 
         // We now want to compute the generator which has order `size`
@@ -88,46 +131,77 @@ impl Domain {
     }
 
     /// The largest root of unity that we can use for the domain
-    const fn largest_root_of_unity() -> Scalar {
-        Scalar::ROOT_OF_UNITY
+    const fn largest_root_of_unity() -> F {
+        F::ROOT_OF_UNITY
     }
 
     /// The largest power of two that we can use for the domain
     const fn two_adicity() -> u32 {
-        32
+        F::S
     }
 
     /// The size of the domain
     ///
     /// Note: This is always a power of two
-    pub(crate) fn size(&self) -> usize {
+    pub const fn size(&self) -> usize {
         self.roots.len()
     }
 
     /// Evaluates a polynomial at the points in the domain
-    pub fn fft_scalars(&self, mut polynomial: PolyCoeff) -> Vec<Scalar> {
+    pub fn fft_scalars(&self, mut polynomial: PolyCoeff<F>) -> Vec<F> {
         // Pad the polynomial with zeroes, so that it is the same size as the
         // domain.
-        polynomial.resize(self.size(), Scalar::ZERO);
+        polynomial.resize(self.size(), F::ZERO);
 
-        fft_scalar_inplace(&self.twiddle_factors, &mut polynomial);
+        self.fft_scalars_inplace(&mut polynomial);
 
         polynomial
     }
 
+    /// Evaluates a polynomial at the points in the domain, overwriting the
+    /// coefficients with the evaluations.
+    ///
+    /// Panics if the length of `polynomial` is not equal to the size of the domain.
+    pub fn fft_scalars_inplace(&self, polynomial: &mut [F]) {
+        self.assert_domain_sized(polynomial.len());
+        fft_scalar_inplace(&self.twiddle_factors, &self.bit_reversal_swaps, polynomial);
+    }
+
     /// Evaluates a polynomial at the points in the domain multiplied by a coset
     /// generator `g`.
-    pub fn coset_fft_scalars(&self, mut points: PolyCoeff, coset: &CosetFFT) -> Vec<Scalar> {
+    pub fn coset_fft_scalars(&self, mut points: PolyCoeff<F>, coset: &CosetFFT<F>) -> Vec<F> {
         // Pad the polynomial with zeroes, so that it is the same size as the
         // domain.
-        points.resize(self.size(), Scalar::ZERO);
+        points.resize(self.size(), F::ZERO);
 
-        let mut coset_scale = Scalar::ONE;
-        for point in &mut points {
-            *point *= coset_scale;
-            coset_scale *= coset.generator;
-        }
-        fft_scalar_inplace(&self.twiddle_factors, &mut points);
+        self.coset_fft_scalars_inplace(&mut points, coset);
+
+        points
+    }
+
+    /// Evaluates a polynomial at the points in the domain multiplied by a coset
+    /// generator `g`, overwriting the coefficients with the evaluations.
+    ///
+    /// Panics if the length of `points` is not equal to the size of the domain.
+    pub fn coset_fft_scalars_inplace(&self, points: &mut [F], coset: &CosetFFT<F>) {
+        self.assert_domain_sized(points.len());
+        scale_by_powers(points, coset.generator);
+        fft_scalar_inplace(&self.twiddle_factors, &self.bit_reversal_swaps, points);
+    }
+
+    /// Computes a FFT for the group elements(elliptic curve points) over the coset of
+    /// the domain defined by the coset generator `g`.
+    pub fn coset_fft_g1<G: Group<Scalar = F>>(
+        &self,
+        mut points: Vec<G>,
+        coset: &CosetFFT<F>,
+    ) -> Vec<G> {
+        // Pad the vector of points with zeroes, so that it is the same size as the
+        // domain.
+        points.resize(self.size(), G::identity());
+
+        scale_by_powers(&mut points, coset.generator);
+        fft_g1_inplace(&self.twiddle_factors, &self.bit_reversal_swaps, &mut points);
 
         points
     }
@@ -137,21 +211,43 @@ impl Domain {
     /// Note: Thinking about an FFT as multiple inner products between powers of the elements
     /// in the domain and the input polynomial makes this easier to visualize.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    pub fn fft_g1(&self, mut points: Vec<G1Projective>) -> Vec<G1Projective> {
+    pub fn fft_g1<G: Group<Scalar = F>>(&self, mut points: Vec<G>) -> Vec<G> {
         // Pad the vector of points with zeroes, so that it is the same size as the
         // domain.
-        points.resize(self.size(), G1Projective::identity());
+        points.resize(self.size(), G::identity());
 
-        fft_g1_inplace(&self.twiddle_factors, &mut points);
+        self.fft_g1_inplace(&mut points);
 
         points
     }
 
+    /// Computes a FFT for the group elements(elliptic curve points) using the roots in the domain,
+    /// overwriting the input with the result.
+    ///
+    /// Panics if the length of `points` is not equal to the size of the domain.
+    pub fn fft_g1_inplace<G: Group<Scalar = F>>(&self, points: &mut [G]) {
+        self.assert_domain_sized(points.len());
+        fft_g1_inplace(&self.twiddle_factors, &self.bit_reversal_swaps, points);
+    }
+
     /// Computes an IFFT for the group elements(elliptic curve points) using the roots in the domain.
-    pub fn ifft_g1(&self, points: Vec<G1Projective>) -> Vec<G1Projective> {
+    pub fn ifft_g1<G: Group<Scalar = F>>(&self, points: Vec<G>) -> Vec<G> {
         self.ifft_g1_take_n(points, None)
     }
 
+    /// Computes an IFFT for the group elements(elliptic curve points) using the roots in the domain,
+    /// overwriting the input with the result.
+    ///
+    /// Panics if the length of `points` is not equal to the size of the domain.
+    pub fn ifft_g1_inplace<G: Group<Scalar = F>>(&self, points: &mut [G]) {
+        self.assert_domain_sized(points.len());
+        fft_g1_inplace(&self.twiddle_factors_inv, &self.bit_reversal_swaps, points);
+
+        for element in points {
+            *element *= self.domain_size_inv
+        }
+    }
+
     /// Computes an IFFT for the group elements(elliptic curve points) using the roots in the domain.
     ///
     /// `n`:  indicates how many points we would like to return. Passing `None` will return be equivalent
@@ -160,78 +256,297 @@ impl Domain {
     /// This is useful for saving computation on the final scalar multiplication that happens after the
     /// initial FFT is done.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    pub fn ifft_g1_take_n(
+    pub fn ifft_g1_take_n<G: Group<Scalar = F>>(
         &self,
-        mut points: Vec<G1Projective>,
+        mut points: Vec<G>,
         n: Option<usize>,
-    ) -> Vec<G1Projective> {
+    ) -> Vec<G> {
         // Pad the vector with zeroes, so that it is the same size as the
         // domain.
-        points.resize(self.size(), G1Projective::identity());
+        points.resize(self.size(), G::identity());
 
-        fft_g1_inplace(&self.twiddle_factors_inv, &mut points);
+        fft_g1_inplace(
+            &self.twiddle_factors_inv,
+            &self.bit_reversal_swaps,
+            &mut points,
+        );
 
         // Truncate the result if a value of `n` was supplied.
-        let mut ifft_g1 = match n {
-            Some(num_to_take) => {
-                assert!(num_to_take < points.len());
-                points[0..num_to_take].to_vec()
-            }
-            None => points,
-        };
+        if let Some(num_to_take) = n {
+            assert!(num_to_take < points.len());
+            points.truncate(num_to_take);
+        }
 
-        for element in &mut ifft_g1 {
+        for element in &mut points {
             *element *= self.domain_size_inv
         }
 
-        ifft_g1
+        points
     }
 
     /// Interpolates the points over the domain to get a polynomial
     /// in monomial form.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    pub fn ifft_scalars(&self, mut points: Vec<Scalar>) -> Vec<Scalar> {
+    pub fn ifft_scalars(&self, mut points: Vec<F>) -> Vec<F> {
         // Pad the vector with zeroes, so that it is the same size as the
         // domain.
-        points.resize(self.size(), Scalar::ZERO);
+        points.resize(self.size(), F::ZERO);
 
-        fft_scalar_inplace(&self.twiddle_factors_inv, &mut points);
+        self.ifft_scalars_inplace(&mut points);
 
-        for element in &mut points {
+        points
+    }
+
+    /// Interpolates the points over the domain to get a polynomial in monomial form,
+    /// overwriting the evaluations with the coefficients.
+    ///
+    /// Panics if the length of `points` is not equal to the size of the domain.
+    pub fn ifft_scalars_inplace(&self, points: &mut [F]) {
+        self.assert_domain_sized(points.len());
+        fft_scalar_inplace(&self.twiddle_factors_inv, &self.bit_reversal_swaps, points);
+
+        for element in points {
             *element *= self.domain_size_inv
         }
+    }
+
+    /// Interpolates a polynomial over the coset of a domain
+    pub fn coset_ifft_scalars(&self, mut points: Vec<F>, coset: &CosetFFT<F>) -> Vec<F> {
+        // Pad the vector with zeroes, so that it is the same size as the
+        // domain.
+        points.resize(self.size(), F::ZERO);
+
+        self.coset_ifft_scalars_inplace(&mut points, coset);
 
         points
     }
 
-    /// Interpolates a polynomial over the coset of a domain
-    pub fn coset_ifft_scalars(&self, points: Vec<Scalar>, coset: &CosetFFT) -> Vec<Scalar> {
-        let mut coset_coeffs = self.ifft_scalars(points);
+    /// Interpolates a polynomial over the coset of a domain, overwriting the evaluations
+    /// with the coefficients.
+    ///
+    /// Panics if the length of `points` is not equal to the size of the domain.
+    pub fn coset_ifft_scalars_inplace(&self, points: &mut [F], coset: &CosetFFT<F>) {
+        self.ifft_scalars_inplace(points);
+        scale_by_powers(points, coset.generator_inv);
+    }
+
+    /// Computes an IFFT for the group elements(elliptic curve points) over the coset of
+    /// the domain defined by the coset generator `g`.
+    pub fn coset_ifft_g1<G: Group<Scalar = F>>(
+        &self,
+        points: Vec<G>,
+        coset: &CosetFFT<F>,
+    ) -> Vec<G> {
+        let mut coset_points = self.ifft_g1(points);
+        scale_by_powers(&mut coset_points, coset.generator_inv);
+        coset_points
+    }
+
+    /// Panics if `len` does not match the size of the domain.
+    ///
+    /// Note: The in-place methods cannot pad their input, so it must already be
+    /// the same size as the domain.
+    fn assert_domain_sized(&self, len: usize) {
+        assert_eq!(
+            len,
+            self.size(),
+            "input must be the same size as the domain"
+        );
+    }
+}
 
-        let mut coset_scale = Scalar::ONE;
-        for element in &mut coset_coeffs {
-            *element *= coset_scale;
-            coset_scale *= coset.generator_inv;
+/// The roots of unity of a domain, in order of increasing powers of the generator.
+///
+/// The roots of unity of size `m` are every `n/m`'th root of unity of size `n`, for any `n`
+/// that is a multiple of `m`. So instead of each domain storing its own roots, the domains share
+/// the table of the largest size that has been requested, and index into it with a stride.
+#[derive(Debug, Clone)]
+pub struct Roots<F = Scalar> {
+    table: Arc<Vec<F>>,
+    stride: usize,
+    len: usize,
+}
+
+impl<F: PrimeField> Roots<F> {
+    /// Returns the roots of unity of the given size, which must be a power of two.
+    fn new(size: usize) -> Self {
+        // Like the domains, the tables are keyed by the type of their field.
+        static LARGEST_TABLES: OnceLock<Mutex<HashMap<TypeId, ErasedArc>>> = OnceLock::new();
+
+        let mut largest_tables = LARGEST_TABLES
+            .get_or_init(Default::default)
+            .lock()
+            // The table is only replaced once it has been computed, so we can ignore poisoning.
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let largest_table = largest_tables.get(&TypeId::of::<F>()).map(|table| {
+            table
+                .clone()
+                .downcast::<Vec<F>>()
+                .expect("infallible: tables are keyed by the type of their field")
+        });
+        let table = match largest_table {
+            Some(table) if table.len() >= size => table,
+            _ => {
+                let table = Arc::new(Self::compute_table(size));
+                largest_tables.insert(TypeId::of::<F>(), table.clone());
+                table
+            }
+        };
+
+        Self {
+            stride: table.len() / size,
+            table,
+            len: size,
         }
-        coset_coeffs
+    }
+
+    fn compute_table(size: usize) -> Vec<F> {
+        let generator = Domain::<F>::compute_generator_for_size(size);
+
+        let mut roots = Vec::with_capacity(size);
+        roots.push(F::ONE);
+
+        for i in 1..size {
+            let prev_root = roots[i - 1];
+            roots.push(prev_root * generator)
+        }
+
+        roots
+    }
+
+    /// The number of roots of unity
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> StepBy<slice::Iter<'_, F>> {
+        self.table.iter().step_by(self.stride)
+    }
+
+    pub fn to_vec(&self) -> Vec<F> {
+        self.iter().copied().collect()
+    }
+}
+
+impl<F> Index<usize> for Roots<F> {
+    type Output = F;
+
+    fn index(&self, index: usize) -> &F {
+        assert!(
+            index < self.len,
+            "index {index} is out of bounds for {} roots of unity",
+            self.len
+        );
+        &self.table[index * self.stride]
+    }
+}
+
+impl<'a, F: PrimeField> IntoIterator for &'a Roots<F> {
+    type Item = &'a F;
+    type IntoIter = StepBy<slice::Iter<'a, F>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
+impl<F: PrimeField> PartialEq for Roots<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<F: PrimeField> Eq for Roots<F> {}
+
+/// Multiplies the i'th element by `generator^i`.
+fn scale_by_powers<F: Field, T: MulAssign<F>>(elements: &mut [T], generator: F) {
+    let mut scale = F::ONE;
+    for element in elements {
+        *element *= scale;
+        scale *= generator;
+    }
+}
+
+/// Evaluates a polynomial in evaluation form at an arbitrary point `z`, without
+/// converting it to monomial form.
+///
+/// `evals` are the evaluations of the polynomial over the roots of unity in `domain`,
+/// in the same (non bit-reversed) order as `domain.roots`.
+///
+/// This uses the barycentric formula:
+///
+/// f(z) = (z^n - 1) / n * \sum_i (f(w^i) * w^i) / (z - w^i)
+///
+/// Panics if the number of evaluations does not equal the size of the domain.
+pub fn evaluate_in_evaluation_form<F: PrimeField>(evals: &[F], z: F, domain: &Domain<F>) -> F {
+    assert_eq!(
+        evals.len(),
+        domain.size(),
+        "number of evaluations must equal the domain size"
+    );
+
+    // If `z` is in the domain, then the formula will divide by zero,
+    // however we already have the evaluation.
+    if let Some(index) = domain.roots.iter().position(|root| *root == z) {
+        return evals[index];
+    }
+
+    let mut denominators: Vec<_> = domain.roots.iter().map(|root| z - root).collect();
+    batch_inverse(&mut denominators);
+
+    let mut result = F::ZERO;
+    for ((eval, root), denominator_inv) in evals.iter().zip(&domain.roots).zip(denominators) {
+        result += *eval * root * denominator_inv;
+    }
+
+    let z_pow_n_minus_one = z.pow_vartime([domain.size() as u64]) - F::ONE;
+    result * z_pow_n_minus_one * domain.domain_size_inv
+}
+
 #[cfg(test)]
 mod tests {
     use crate::poly_coeff::poly_eval;
 
     use super::*;
+    use bls12_381::G1Projective;
+
+    #[test]
+    fn get_or_init_returns_cached_domain() {
+        let domain = Domain::<Scalar>::get_or_init(16);
+        assert!(Arc::ptr_eq(&domain, &Domain::get_or_init(16)));
+        // Sizes are padded in the same way as `Domain::new`
+        assert!(Arc::ptr_eq(&domain, &Domain::get_or_init(9)));
+        assert_eq!(domain.roots, Domain::new(16).roots);
+    }
+
+    #[test]
+    fn smaller_domains_share_the_roots_of_larger_domains() {
+        let large_domain = Domain::<Scalar>::new(1 << 10);
+        let small_domain = Domain::<Scalar>::new(8);
+
+        // The table of the small domain is at least as large as the one of the large domain,
+        // since it was requested afterwards.
+        assert!(small_domain.roots.table.len() >= large_domain.roots.len());
+        assert_eq!(small_domain.roots.to_vec(), Roots::compute_table(8));
+        assert_eq!(small_domain.roots.iter().len(), 8);
+        assert_eq!(small_domain.roots[1], small_domain.generator);
+        assert_eq!(large_domain.roots[1 << 7], small_domain.generator);
+    }
 
     #[test]
     fn largest_root_of_unity_has_correct_order() {
-        let root = Domain::largest_root_of_unity();
-        let order = 2u64.pow(Domain::two_adicity());
+        let root = Domain::<Scalar>::largest_root_of_unity();
+        let order = 2u64.pow(Domain::<Scalar>::two_adicity());
 
         assert_eq!(root.pow_vartime([order]), Scalar::ONE);
 
         // Check that it is indeed a primitive root of unity
-        for i in 0..Domain::two_adicity() {
+        for i in 0..Domain::<Scalar>::two_adicity() {
             assert_ne!(root.pow_vartime([2u64.pow(i)]), Scalar::ONE);
         }
     }
@@ -255,6 +570,24 @@ mod tests {
         assert_eq!(got_evals, evaluations);
     }
 
+    #[test]
+    fn barycentric_evaluation_matches_monomial_evaluation() {
+        let domain = Domain::new(16);
+        let polynomial: Vec<_> = (0..16).map(|i| Scalar::from(i * 7 + 3)).collect();
+        let evals = domain.fft_scalars(polynomial.clone());
+
+        // A point outside of the domain
+        let z = Scalar::from(123456789u64);
+        assert_eq!(
+            evaluate_in_evaluation_form(&evals, z, &domain),
+            poly_eval(&polynomial, &z)
+        );
+
+        // A point inside of the domain
+        let z = domain.roots[5];
+        assert_eq!(evaluate_in_evaluation_form(&evals, z, &domain), evals[5]);
+    }
+
     #[test]
     fn test_polynomial_coset_fft() {
         let polynomial: Vec<_> = (0..32).map(|i| -Scalar::from(i)).collect();
@@ -267,6 +600,69 @@ mod tests {
         assert_eq!(got_poly, polynomial);
     }
 
+    #[test]
+    fn coset_fft_evaluates_over_the_coset() {
+        let polynomial: Vec<_> = (0..16).map(|i| Scalar::from(i * 5 + 1)).collect();
+
+        let domain = Domain::new(16);
+        let coset_fft = CosetFFT::new(Scalar::from(7u64));
+        let coset_evals = domain.coset_fft_scalars(polynomial.clone(), &coset_fft);
+
+        for (root, eval) in domain.roots.iter().zip(coset_evals) {
+            let coset_point = root * coset_fft.generator;
+            assert_eq!(poly_eval(&polynomial, &coset_point), eval);
+        }
+    }
+
+    #[test]
+    fn coset_fft_g1_roundtrip() {
+        let domain = Domain::new(8);
+        let coset_fft = CosetFFT::new(Scalar::MULTIPLICATIVE_GENERATOR);
+        let points: Vec<_> = (0..8)
+            .map(|_| G1Projective::random(&mut rand::thread_rng()))
+            .collect();
+
+        let coset_points = domain.coset_fft_g1(points.clone(), &coset_fft);
+        assert_eq!(domain.coset_ifft_g1(coset_points, &coset_fft), points);
+    }
+
+    #[test]
+    fn inplace_ffts_match_owned_ffts() {
+        let domain = Domain::new(16);
+        let coset_fft = CosetFFT::new(Scalar::MULTIPLICATIVE_GENERATOR);
+        let polynomial: Vec<_> = (0..16).map(|i| Scalar::from(i * 3 + 2)).collect();
+
+        let mut buffer = polynomial.clone();
+        domain.fft_scalars_inplace(&mut buffer);
+        assert_eq!(buffer, domain.fft_scalars(polynomial.clone()));
+        domain.ifft_scalars_inplace(&mut buffer);
+        assert_eq!(buffer, polynomial);
+
+        domain.coset_fft_scalars_inplace(&mut buffer, &coset_fft);
+        assert_eq!(
+            buffer,
+            domain.coset_fft_scalars(polynomial.clone(), &coset_fft)
+        );
+        domain.coset_ifft_scalars_inplace(&mut buffer, &coset_fft);
+        assert_eq!(buffer, polynomial);
+
+        let points: Vec<_> = (0..16)
+            .map(|_| G1Projective::random(&mut rand::thread_rng()))
+            .collect();
+        let mut buffer = points.clone();
+        domain.fft_g1_inplace(&mut buffer);
+        assert_eq!(buffer, domain.fft_g1(points.clone()));
+        domain.ifft_g1_inplace(&mut buffer);
+        assert_eq!(buffer, points);
+    }
+
+    #[test]
+    #[should_panic(expected = "input must be the same size as the domain")]
+    fn inplace_fft_rejects_incorrectly_sized_input() {
+        let domain = Domain::new(16);
+        domain.fft_scalars_inplace(&mut [Scalar::ONE; 8]);
+    }
+
     #[test]
     fn fft_g1_smoke_test() {
         fn naive_msm(points: &[G1Projective], scalars: &[Scalar]) -> G1Projective {
@@ -1,74 +1,110 @@
-use bls12_381::{ff::Field, group::Group, G1Projective, Scalar};
+use bls12_381::{ff::PrimeField, group::Group};
+use maybe_rayon::prelude::*;
 use std::ops::{Add, Mul, Neg, Sub};
 
-trait FFTElement:
+/// The elements that an FFT over the field `F` can be computed for, which are the field
+/// elements themselves and the points of a group whose scalar field is `F`.
+trait FFTElement<F>:
     Sized
     + Copy
     + PartialEq
     + Eq
     + Add<Output = Self>
     + Sub<Output = Self>
-    + Mul<Scalar, Output = Self>
+    + Mul<F, Output = Self>
     + Neg<Output = Self>
+    + Send
+    + Sync
 {
-    fn zero() -> Self;
 }
 
-impl FFTElement for Scalar {
-    fn zero() -> Self {
-        Self::ZERO
-    }
+impl<F, T> FFTElement<F> for T where
+    T: Sized
+        + Copy
+        + PartialEq
+        + Eq
+        + Add<Output = Self>
+        + Sub<Output = Self>
+        + Mul<F, Output = Self>
+        + Neg<Output = Self>
+        + Send
+        + Sync
+{
 }
 
-impl FFTElement for G1Projective {
-    fn zero() -> Self {
-        Self::identity()
-    }
-}
+/// The smallest FFT size for which the butterflies in each round are computed in parallel.
+///
+/// Note: Below this size, the overhead of distributing the work across threads
+/// outweighs the cost of the FFT itself.
+const PARALLEL_FFT_THRESHOLD: usize = 1 << 10;
 
-fn fft_inplace<T: FFTElement>(twiddle_factors: &[Scalar], a: &mut [T]) {
+fn fft_inplace<F: PrimeField, T: FFTElement<F>>(
+    twiddle_factors: &[F],
+    bit_reversal_swaps: &[(u32, u32)],
+    a: &mut [T],
+    zero: T,
+) {
     let n = a.len();
     let log_n = log2_pow2(n);
     assert_eq!(n, 1 << log_n);
 
-    for k in 0..n {
-        let rk = bitreverse(k as u32, log_n) as usize;
-        if k < rk {
-            a.swap(rk, k);
-        }
+    for &(k, rk) in bit_reversal_swaps {
+        a.swap(k as usize, rk as usize);
     }
 
     let mut m = 1;
     for s in 0..log_n {
         let w_m = twiddle_factors[s as usize];
-        for k in (0..n).step_by(2 * m) {
-            let mut w = Scalar::ONE;
-            for j in 0..m {
-                let t = if w == Scalar::ONE {
-                    a[k + j + m]
-                } else if w == -Scalar::ONE {
-                    -a[k + j + m]
-                } else if a[k + j + m] == T::zero() {
-                    T::zero()
-                } else {
-                    a[k + j + m] * w
-                };
-                let u = a[k + j];
-                a[k + j] = u + t;
-                a[k + j + m] = u - t;
-                w *= w_m;
-            }
+        let butterflies = |chunk: &mut [T]| butterfly_chunk(chunk, m, w_m, zero);
+
+        // Each chunk of size `2m` is independent of the others in this round.
+        //
+        // Note: When this is called inside of a thread pool, the number of threads
+        // used is determined by that thread pool.
+        if n < PARALLEL_FFT_THRESHOLD {
+            a.chunks_mut(2 * m).for_each(butterflies);
+        } else {
+            a.maybe_par_chunks_mut(2 * m).for_each(butterflies);
         }
         m *= 2;
     }
 }
 
-pub(crate) fn fft_scalar_inplace(twiddle_factors: &[Scalar], a: &mut [Scalar]) {
-    fft_inplace(twiddle_factors, a);
+/// Applies the butterflies for a single round of the FFT to a chunk of size `2m`.
+fn butterfly_chunk<F: PrimeField, T: FFTElement<F>>(chunk: &mut [T], m: usize, w_m: F, zero: T) {
+    let (lo, hi) = chunk.split_at_mut(m);
+    let mut w = F::ONE;
+    for (u, v) in lo.iter_mut().zip(hi) {
+        let t = if w == F::ONE {
+            *v
+        } else if w == -F::ONE {
+            -*v
+        } else if *v == zero {
+            zero
+        } else {
+            *v * w
+        };
+        let u_val = *u;
+        *u = u_val + t;
+        *v = u_val - t;
+        w *= w_m;
+    }
 }
 
-pub(crate) fn fft_g1_inplace(twiddle_factors: &[Scalar], a: &mut [G1Projective]) {
-    fft_inplace(twiddle_factors, a);
+pub(crate) fn fft_scalar_inplace<F: PrimeField>(
+    twiddle_factors: &[F],
+    bit_reversal_swaps: &[(u32, u32)],
+    a: &mut [F],
+) {
+    fft_inplace(twiddle_factors, bit_reversal_swaps, a, F::ZERO);
+}
+
+pub(crate) fn fft_g1_inplace<G: Group>(
+    twiddle_factors: &[G::Scalar],
+    bit_reversal_swaps: &[(u32, u32)],
+    a: &mut [G],
+) {
+    fft_inplace(twiddle_factors, bit_reversal_swaps, a, G::identity());
 }
 
 fn bitreverse(mut n: u32, l: u32) -> u32 {
@@ -84,9 +120,53 @@ const fn log2_pow2(n: usize) -> u32 {
     n.trailing_zeros()
 }
 
-pub(crate) fn precompute_twiddle_factors<F: Field>(omega: &F, n: usize) -> Vec<F> {
+/// Computes the pairs of positions `(k, bitreverse(k))` with `k < bitreverse(k)`, for an FFT of size `n`.
+///
+/// Swapping each pair puts the elements into bit-reversed order, which the FFT
+/// needs before its first round. Every other position is its own bit reversal.
+pub(crate) fn precompute_bit_reversal_swaps(n: usize) -> Vec<(u32, u32)> {
     let log_n = log2_pow2(n);
-    (0..log_n)
-        .map(|s| omega.pow([(n / (1 << (s + 1))) as u64]))
+    (0..n as u32)
+        .map(|k| (k, bitreverse(k, log_n)))
+        .filter(|&(k, rk)| k < rk)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Domain;
+    use crate::poly_coeff::poly_eval;
+    use bls12_381::Scalar;
+
+    #[test]
+    fn fft_above_parallel_threshold_evaluates_polynomial() {
+        let n = PARALLEL_FFT_THRESHOLD * 2;
+        let domain = Domain::new(n);
+        let polynomial: Vec<_> = (0..n).map(|i| Scalar::from(i as u64 * 13 + 5)).collect();
+
+        let evaluations = domain.fft_scalars(polynomial.clone());
+        for index in [0, 1, n / 2, n - 1] {
+            assert_eq!(
+                evaluations[index],
+                poly_eval(&polynomial, &domain.roots[index])
+            );
+        }
+
+        assert_eq!(domain.ifft_scalars(evaluations), polynomial);
+    }
+
+    #[test]
+    fn bit_reversal_swaps_permute_into_bit_reversed_order() {
+        for log_n in 0..6 {
+            let n = 1usize << log_n;
+            let mut indices: Vec<u32> = (0..n as u32).collect();
+            for (k, rk) in precompute_bit_reversal_swaps(n) {
+                indices.swap(k as usize, rk as usize);
+            }
+
+            let expected: Vec<u32> = (0..n as u32).map(|k| bitreverse(k, log_n)).collect();
+            assert_eq!(indices, expected);
+        }
+    }
+}
@@ -1,6 +1,28 @@
 mod coset_fft;
 pub mod domain;
 mod fft;
+pub mod mixed_radix;
 pub mod poly_coeff;
+pub mod scratch;
+mod subproduct_tree;
 
 pub use coset_fft::CosetFFT;
+
+use maybe_rayon::prelude::*;
+
+/// The number of elements that are inverted together when batch inversion
+/// is split across threads.
+///
+/// Each chunk requires its own field inversion.
+const PARALLEL_BATCH_INVERSE_CHUNK_SIZE: usize = 1 << 10;
+
+/// Inverts a slice of scalars in place using Montgomery's trick, which
+/// requires a single field inversion for every chunk of
+/// `PARALLEL_BATCH_INVERSE_CHUNK_SIZE` elements.
+///
+/// Panics if any of the elements are zero.
+pub fn batch_inverse<F: bls12_381::ff::Field>(elements: &mut [F]) {
+    elements
+        .maybe_par_chunks_mut(PARALLEL_BATCH_INVERSE_CHUNK_SIZE)
+        .for_each(bls12_381::batch_inversion::batch_inverse);
+}
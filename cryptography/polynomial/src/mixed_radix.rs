@@ -0,0 +1,250 @@
+use crate::poly_coeff::PolyCoeff;
+use bls12_381::ff::{Field, PrimeField};
+use bls12_381::Scalar;
+
+/// A domain of roots of unity whose size does not need to be a power of two.
+///
+/// The size of the domain must divide `r - 1`, where `r` is the order of the scalar field,
+/// since that is the order of the multiplicative group. For BLS12-381, this allows sizes such
+/// as `3 * 2^k` or `11 * 19 * 2^k` in addition to powers of two.
+///
+/// Note: The power of two `Domain` should be preferred whenever the size allows it, since
+/// the radix-2 FFT is faster. This domain uses a mixed-radix Cooley-Tukey FFT, whose cost
+/// grows with the size of the prime factors of the domain size.
+#[derive(Debug, Clone)]
+pub struct MixedRadixDomain {
+    /// roots of unity
+    pub roots: Vec<Scalar>,
+    /// Inverse of the domain size as a scalar
+    pub domain_size_inv: Scalar,
+    /// Generator for this domain
+    // Element has order `domain_size`
+    pub generator: Scalar,
+    /// Inverse of the generator for the domain
+    pub generator_inv: Scalar,
+    /// The prime factors of the domain size, with multiplicity.
+    ///
+    /// Each of these is the radix used for one layer of the FFT.
+    radices: Vec<usize>,
+}
+
+impl MixedRadixDomain {
+    /// Creates a domain of the given size.
+    ///
+    /// Returns `None` if `size` is zero, or if it does not divide the order of the
+    /// multiplicative group, in which case there is no root of unity of that order.
+    pub fn new(size: usize) -> Option<Self> {
+        if size == 0 {
+            return None;
+        }
+
+        let generator = compute_generator_for_size(size)?;
+        let generator_inv = generator.invert().expect("generator should not be zero");
+        let domain_size_inv = Scalar::from(size as u64)
+            .invert()
+            .expect("size should not be zero");
+
+        let mut roots = Vec::with_capacity(size);
+        roots.push(Scalar::ONE);
+        for i in 1..size {
+            roots.push(roots[i - 1] * generator);
+        }
+
+        Some(Self {
+            roots,
+            domain_size_inv,
+            generator,
+            generator_inv,
+            radices: prime_factors(size),
+        })
+    }
+
+    /// The size of the domain
+    pub fn size(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Evaluates a polynomial at the points in the domain
+    ///
+    /// Panics if the polynomial has more coefficients than the size of the domain.
+    pub fn fft_scalars(&self, mut polynomial: PolyCoeff) -> Vec<Scalar> {
+        assert!(
+            polynomial.len() <= self.size(),
+            "polynomial has more coefficients than the domain size"
+        );
+        // Pad the polynomial with zeroes, so that it is the same size as the
+        // domain.
+        polynomial.resize(self.size(), Scalar::ZERO);
+
+        self.fft(&polynomial, 1, &self.radices, false)
+    }
+
+    /// Interpolates the points over the domain to get a polynomial
+    /// in monomial form.
+    ///
+    /// Panics if there are more points than the size of the domain.
+    pub fn ifft_scalars(&self, mut points: Vec<Scalar>) -> Vec<Scalar> {
+        assert!(
+            points.len() <= self.size(),
+            "number of points is larger than the domain size"
+        );
+        // Pad the vector with zeroes, so that it is the same size as the
+        // domain.
+        points.resize(self.size(), Scalar::ZERO);
+
+        let mut coeffs = self.fft(&points, 1, &self.radices, true);
+        for coeff in &mut coeffs {
+            *coeff *= self.domain_size_inv;
+        }
+        coeffs
+    }
+
+    /// Returns `w^exponent` where `w` is the generator of the domain, or its inverse
+    /// if `inverse` is true.
+    fn root_pow(&self, exponent: usize, inverse: bool) -> Scalar {
+        let n = self.size();
+        let exponent = exponent % n;
+        if inverse {
+            self.roots[(n - exponent) % n]
+        } else {
+            self.roots[exponent]
+        }
+    }
+
+    /// Recursive decimation-in-time Cooley-Tukey FFT.
+    ///
+    /// `input` has length `n` and is transformed using the root of unity `w^stride`,
+    /// which has order `n`. The first radix `p` splits the input into `p` interleaved
+    /// subsequences of length `n/p`, which are transformed recursively and then combined.
+    fn fft(
+        &self,
+        input: &[Scalar],
+        stride: usize,
+        radices: &[usize],
+        inverse: bool,
+    ) -> Vec<Scalar> {
+        let n = input.len();
+        let Some((&p, remaining_radices)) = radices.split_first() else {
+            debug_assert_eq!(n, 1);
+            return input.to_vec();
+        };
+        let m = n / p;
+
+        let sub_ffts: Vec<Vec<Scalar>> = (0..p)
+            .map(|j| {
+                let subsequence: Vec<_> = input.iter().skip(j).step_by(p).copied().collect();
+                self.fft(&subsequence, stride * p, remaining_radices, inverse)
+            })
+            .collect();
+
+        // X[k] = \sum_j w_n^{jk} * Y_j[k mod m]
+        (0..n)
+            .map(|k| {
+                sub_ffts
+                    .iter()
+                    .enumerate()
+                    .map(|(j, sub_fft)| sub_fft[k % m] * self.root_pow(j * k * stride, inverse))
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+/// Computes a primitive root of unity of order `size`, if one exists.
+///
+/// Since `Scalar::MULTIPLICATIVE_GENERATOR` generates the multiplicative group, which has
+/// order `r - 1`, the element `g^((r - 1) / size)` has order exactly `size`.
+fn compute_generator_for_size(size: usize) -> Option<Scalar> {
+    // r - 1 as little-endian 64-bit limbs
+    let group_order_bytes = (-Scalar::ONE).to_repr();
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(group_order_bytes.as_ref().chunks(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("chunk has 8 bytes"));
+    }
+
+    // Long division of (r - 1) by `size`, starting from the most significant limb
+    let divisor = size as u128;
+    let mut remainder = 0u128;
+    for limb in limbs.iter_mut().rev() {
+        let dividend = (remainder << 64) | (*limb as u128);
+        *limb = (dividend / divisor) as u64;
+        remainder = dividend % divisor;
+    }
+    if remainder != 0 {
+        return None;
+    }
+
+    Some(Scalar::MULTIPLICATIVE_GENERATOR.pow_vartime(limbs))
+}
+
+/// Returns the prime factors of `n` with multiplicity, in ascending order.
+fn prime_factors(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    while p * p <= n {
+        while n % p == 0 {
+            factors.push(p);
+            n /= p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Domain;
+    use crate::poly_coeff::poly_eval;
+
+    #[test]
+    fn generator_has_correct_order() {
+        for size in [3, 11, 12, 19, 96, 209] {
+            let domain = MixedRadixDomain::new(size).unwrap();
+            assert_eq!(domain.generator.pow_vartime([size as u64]), Scalar::ONE);
+            for p in prime_factors(size) {
+                let exponent = (size / p) as u64;
+                assert_ne!(domain.generator.pow_vartime([exponent]), Scalar::ONE);
+            }
+        }
+    }
+
+    #[test]
+    fn sizes_that_do_not_divide_the_group_order_are_rejected() {
+        for size in [0, 5, 7, 9, 10] {
+            assert!(MixedRadixDomain::new(size).is_none());
+        }
+    }
+
+    #[test]
+    fn fft_evaluates_polynomial_over_the_domain() {
+        for size in [1, 3, 12, 22, 96] {
+            let domain = MixedRadixDomain::new(size).unwrap();
+            let polynomial: Vec<_> = (0..size).map(|i| Scalar::from(i as u64 * 7 + 1)).collect();
+
+            let evaluations = domain.fft_scalars(polynomial.clone());
+            for (root, evaluation) in domain.roots.iter().zip(&evaluations) {
+                assert_eq!(poly_eval(&polynomial, root), *evaluation);
+            }
+
+            assert_eq!(domain.ifft_scalars(evaluations), polynomial);
+        }
+    }
+
+    #[test]
+    fn power_of_two_sizes_match_radix_2_domain() {
+        let size = 32;
+        let mixed_radix_domain = MixedRadixDomain::new(size).unwrap();
+        let domain = Domain::new(size);
+        assert_eq!(mixed_radix_domain.roots, domain.roots.to_vec());
+
+        let polynomial: Vec<_> = (0..size).map(|i| -Scalar::from(i as u64)).collect();
+        assert_eq!(
+            mixed_radix_domain.fft_scalars(polynomial.clone()),
+            domain.fft_scalars(polynomial)
+        );
+    }
+}
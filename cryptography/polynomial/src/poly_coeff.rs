@@ -1,78 +1,308 @@
-use bls12_381::ff::Field;
+use crate::domain::Domain;
+use crate::subproduct_tree::{vanishing_poly_subproduct_tree, SubproductTree};
+use bls12_381::ff::PrimeField;
 use bls12_381::Scalar;
+use std::ops::{Add, Mul, Neg, Sub};
 
 // This file contains methods on a polynomial in coefficient form.
 
 /// A polynomial in monomial form where the lowest degree term is first
 /// Layout: x^0 * a_0 + x^1 * a_1 + ... + x^(n-1) * a_(n-1)
-pub type PolyCoeff = Vec<Scalar>;
+pub type PolyCoeff<F = Scalar> = Vec<F>;
 
 /// For two polynomials, `f(x)` and `g(x)`, this method computes
 /// the result of `f(x) + g(x)` and returns the result.
 ///
 /// Note: Polynomials can be of different lengths.
-pub fn poly_add(a: PolyCoeff, b: PolyCoeff) -> PolyCoeff {
+pub fn poly_add<F: PrimeField>(a: PolyCoeff<F>, b: PolyCoeff<F>) -> PolyCoeff<F> {
     let (smaller_poly, mut larger_poly) = if a.len() < b.len() { (a, b) } else { (b, a) };
+    poly_add_assign(&mut larger_poly, &smaller_poly);
+    larger_poly
+}
 
-    for i in 0..smaller_poly.len() {
-        larger_poly[i] += smaller_poly[i];
+/// For two polynomials, `f(x)` and `g(x)`, this method computes
+/// the result of `f(x) + g(x)` without taking ownership of either polynomial.
+///
+/// Note: Polynomials can be of different lengths.
+pub fn poly_add_ref<F: PrimeField>(a: &[F], b: &[F]) -> PolyCoeff<F> {
+    let (smaller_poly, larger_poly) = if a.len() < b.len() { (a, b) } else { (b, a) };
+    let mut result = larger_poly.to_vec();
+    poly_add_assign(&mut result, smaller_poly);
+    result
+}
+
+/// For two polynomials, `f(x)` and `g(x)`, this method computes
+/// the result of `f(x) + g(x)` and stores the result in `f(x)`.
+///
+/// Note: Polynomials can be of different lengths. If `g(x)` has more
+/// coefficients than `f(x)`, then `f(x)` will be extended.
+pub fn poly_add_assign<F: PrimeField>(a: &mut PolyCoeff<F>, b: &[F]) {
+    if a.len() < b.len() {
+        a.resize(b.len(), F::ZERO);
     }
 
-    larger_poly
+    for (a_coeff, b_coeff) in a.iter_mut().zip(b) {
+        *a_coeff += b_coeff;
+    }
 }
 
 /// For a polynomial, `f(x)`, this method computes the result of `-f(x)`
 /// and returns the result.
-pub fn poly_neg(mut a: PolyCoeff) -> PolyCoeff {
-    for element in &mut a {
+pub fn poly_neg<F: PrimeField>(mut a: PolyCoeff<F>) -> PolyCoeff<F> {
+    poly_neg_assign(&mut a);
+    a
+}
+
+/// For a polynomial, `f(x)`, this method computes the result of `-f(x)`
+/// without taking ownership of the polynomial.
+pub fn poly_neg_ref<F: PrimeField>(a: &[F]) -> PolyCoeff<F> {
+    a.iter().map(|coeff| -*coeff).collect()
+}
+
+/// For a polynomial, `f(x)`, this method computes the result of `-f(x)`
+/// and stores the result in `f(x)`.
+pub fn poly_neg_assign<F: PrimeField>(a: &mut [F]) {
+    for element in a {
         *element = -*element;
     }
-    a
 }
 
 /// For two polynomials, `f(x)` and `g(x)`, this method computes
 /// the result of `f(x) - g(x)` and returns the result.
 ///
 /// Note: Polynomials can be of different lengths
-pub fn poly_sub(a: PolyCoeff, b: PolyCoeff) -> PolyCoeff {
-    let neg_b = poly_neg(b);
-    poly_add(a, neg_b)
+pub fn poly_sub<F: PrimeField>(mut a: PolyCoeff<F>, b: PolyCoeff<F>) -> PolyCoeff<F> {
+    poly_sub_assign(&mut a, &b);
+    a
+}
+
+/// For two polynomials, `f(x)` and `g(x)`, this method computes
+/// the result of `f(x) - g(x)` without taking ownership of either polynomial.
+///
+/// Note: Polynomials can be of different lengths
+pub fn poly_sub_ref<F: PrimeField>(a: &[F], b: &[F]) -> PolyCoeff<F> {
+    let mut result = a.to_vec();
+    poly_sub_assign(&mut result, b);
+    result
+}
+
+/// For two polynomials, `f(x)` and `g(x)`, this method computes
+/// the result of `f(x) - g(x)` and stores the result in `f(x)`.
+///
+/// Note: Polynomials can be of different lengths. If `g(x)` has more
+/// coefficients than `f(x)`, then `f(x)` will be extended.
+pub fn poly_sub_assign<F: PrimeField>(a: &mut PolyCoeff<F>, b: &[F]) {
+    if a.len() < b.len() {
+        a.resize(b.len(), F::ZERO);
+    }
+
+    for (a_coeff, b_coeff) in a.iter_mut().zip(b) {
+        *a_coeff -= b_coeff;
+    }
 }
 
 /// Given a polynomial `f(x)` and a scalar `z`. This method will compute
 /// the result of `f(z)` and return the result.
-pub fn poly_eval(poly: &PolyCoeff, value: &Scalar) -> Scalar {
-    let mut result = Scalar::ZERO;
+pub fn poly_eval<F: PrimeField>(poly: &[F], value: &F) -> F {
+    let mut result = F::ZERO;
     for coeff in poly.iter().rev() {
         result = result * value + coeff;
     }
     result
 }
 
+/// Given a polynomial `f(x)` and a list of points `z_i`, this method will compute
+/// `f(z_i)` for every point and return the results in the same order.
+///
+/// Note: For a large number of points, this uses a subproduct tree to evaluate the
+/// polynomial at all of the points at once, instead of evaluating at each point
+/// individually. If the points are the roots of unity, then an FFT should be used instead.
+pub fn evaluate_at_points<F: PrimeField>(poly: &[F], points: &[F]) -> Vec<F> {
+    if points.len() < SUBPRODUCT_TREE_THRESHOLD {
+        points.iter().map(|point| poly_eval(poly, point)).collect()
+    } else {
+        SubproductTree::new(points).evaluate(poly)
+    }
+}
+
+/// The number of coefficients that the smaller polynomial must have, before
+/// `poly_mul` switches from schoolbook multiplication to FFT-based multiplication.
+///
+/// Note: Below this threshold, the cost of computing the domain and the three FFTs
+/// outweighs the quadratic cost of schoolbook multiplication.
+const FFT_MUL_THRESHOLD: usize = 64;
+
 /// For two polynomials, `f(x)` and `g(x)`, this method computes
 /// the result of `f(x) * g(x)` and returns the result.
-pub fn poly_mul(a: PolyCoeff, b: PolyCoeff) -> PolyCoeff {
-    let mut result = vec![Scalar::ZERO; a.len() + b.len() - 1];
+///
+/// Note: For large polynomials, this will use an FFT to compute the product.
+pub fn poly_mul<F: PrimeField>(a: PolyCoeff<F>, b: PolyCoeff<F>) -> PolyCoeff<F> {
+    poly_mul_ref(&a, &b)
+}
+
+/// For two polynomials, `f(x)` and `g(x)`, this method computes
+/// the result of `f(x) * g(x)` without taking ownership of either polynomial.
+///
+/// Note: For large polynomials, this will use an FFT to compute the product.
+pub fn poly_mul_ref<F: PrimeField>(a: &[F], b: &[F]) -> PolyCoeff<F> {
+    if a.len().min(b.len()) < FFT_MUL_THRESHOLD {
+        poly_mul_naive(a, b)
+    } else {
+        poly_mul_fft(a, b)
+    }
+}
+
+/// Computes `f(x) * g(x)` using schoolbook multiplication in O(n*m).
+fn poly_mul_naive<F: PrimeField>(a: &[F], b: &[F]) -> PolyCoeff<F> {
+    let mut result = vec![F::ZERO; a.len() + b.len() - 1];
     for (i, a_coeff) in a.iter().enumerate() {
         for (j, b_coeff) in b.iter().enumerate() {
-            result[i + j] += a_coeff * b_coeff;
+            result[i + j] += *a_coeff * b_coeff;
         }
     }
     result
 }
 
+/// Computes `f(x) * g(x)` in O(n log n) by evaluating both polynomials over a
+/// domain that is large enough to hold the product, multiplying the evaluations
+/// pointwise and then interpolating the result.
+fn poly_mul_fft<F: PrimeField>(a: &[F], b: &[F]) -> PolyCoeff<F> {
+    let num_coefficients = a.len() + b.len() - 1;
+    let domain = Domain::new(num_coefficients);
+
+    // The coefficients are copied into buffers that are already the size of the domain,
+    // so that the FFTs can be done in place.
+    let padded = |poly: &[F]| {
+        let mut buffer = vec![F::ZERO; domain.size()];
+        buffer[..poly.len()].copy_from_slice(poly);
+        buffer
+    };
+    let mut a_evals = padded(a);
+    let mut b_evals = padded(b);
+    domain.fft_scalars_inplace(&mut a_evals);
+    domain.fft_scalars_inplace(&mut b_evals);
+    for (b_eval, a_eval) in b_evals.iter_mut().zip(&a_evals) {
+        *b_eval *= a_eval;
+    }
+
+    domain.ifft_scalars_inplace(&mut b_evals);
+    b_evals.truncate(num_coefficients);
+    b_evals
+}
+
+/// For two polynomials, `f(x)` and `g(x)`, this method computes the quotient `q(x)`
+/// and the remainder `r(x)` such that `f(x) = q(x) * g(x) + r(x)`, where the degree
+/// of `r(x)` is less than the degree of `g(x)`.
+///
+/// Returns `None` if `g(x)` is the zero polynomial.
+///
+/// Note: Trailing zero coefficients in `g(x)` are ignored. The remainder will always have
+/// exactly `deg(g)` coefficients.
+pub fn poly_div<F: PrimeField>(
+    dividend: &[F],
+    divisor: &[F],
+) -> Option<(PolyCoeff<F>, PolyCoeff<F>)> {
+    let divisor_degree = divisor.iter().rposition(|coeff| *coeff != F::ZERO)?;
+    let divisor = &divisor[..=divisor_degree];
+
+    let mut remainder = dividend.to_vec();
+    if remainder.len() <= divisor_degree {
+        remainder.resize(divisor_degree, F::ZERO);
+        return Some((Vec::new(), remainder));
+    }
+
+    let leading_coeff_inv = divisor[divisor_degree]
+        .invert()
+        .expect("leading coefficient is non-zero");
+
+    let mut quotient = vec![F::ZERO; remainder.len() - divisor_degree];
+    for i in (0..quotient.len()).rev() {
+        let quotient_coeff = remainder[i + divisor_degree] * leading_coeff_inv;
+        for (j, divisor_coeff) in divisor.iter().enumerate() {
+            remainder[i + j] -= quotient_coeff * divisor_coeff;
+        }
+        quotient[i] = quotient_coeff;
+    }
+
+    remainder.truncate(divisor_degree);
+    Some((quotient, remainder))
+}
+
+/// For a polynomial `f(x)` and a scalar `z`, this method computes the quotient `q(x)`
+/// such that `f(x) = q(x) * (x - z) + f(z)` using synthetic division.
+///
+/// Returns the quotient and the remainder, which is equal to `f(z)`.
+///
+/// Note: This is what is needed to compute a KZG opening proof at a single point.
+pub fn divide_by_linear<F: PrimeField>(poly: &[F], z: F) -> (PolyCoeff<F>, F) {
+    let mut quotient: Vec<F> = Vec::with_capacity(poly.len());
+    let mut k = F::ZERO;
+
+    for coeff in poly.iter().rev() {
+        let t = *coeff + k;
+        quotient.push(t);
+        k = z * t;
+    }
+
+    // Pop off the remainder term
+    let remainder = quotient.pop().unwrap_or(F::ZERO);
+
+    // Reverse the results as monomial form stores coefficients starting with lowest degree
+    quotient.reverse();
+    (quotient, remainder)
+}
+
 /// Given a list of points, this method will compute the polynomial
 /// Z(x) which is equal to zero when evaluated at each point.
 ///
 /// Example: vanishing_poly([1, 2, 3]) = (x - 1)(x - 2)(x - 3)
-pub fn vanishing_poly(roots: &[Scalar]) -> PolyCoeff {
-    let mut poly = vec![Scalar::ONE];
+///
+/// Note: For a large number of points, the linear factors are multiplied together
+/// using a subproduct tree, so that the large products can make use of FFT multiplication.
+pub fn vanishing_poly<F: PrimeField>(roots: &[F]) -> PolyCoeff<F> {
+    if roots.len() < FFT_MUL_THRESHOLD {
+        vanishing_poly_naive(roots)
+    } else {
+        vanishing_poly_subproduct_tree(roots)
+    }
+}
+
+/// Computes the vanishing polynomial by multiplying in one linear factor at a time.
+fn vanishing_poly_naive<F: PrimeField>(roots: &[F]) -> PolyCoeff<F> {
+    let mut poly = vec![F::ONE];
     for root in roots {
-        poly = poly_mul(poly, vec![-root, Scalar::ONE]);
+        poly = poly_mul(poly, vec![-*root, F::ONE]);
     }
     poly
 }
 
+/// The number of points above which `interpolate` switches from naive lagrange
+/// interpolation to interpolation using a subproduct tree.
+const SUBPRODUCT_TREE_THRESHOLD: usize = 32;
+
+/// Interpolates a set of points to a given polynomial in monomial form.
+///
+/// Given a list of points (x_i, y_i), this method will return the lowest degree polynomial
+/// in monomial form that passes through all the points. The returned polynomial will
+/// have as many coefficients as there are points.
+///
+/// Panics if the x-coordinates of the points are not distinct.
+///
+/// Note: If the points are the roots of unity, then an IFFT should be used instead.
+pub fn interpolate<F: PrimeField>(points: &[(F, F)]) -> PolyCoeff<F> {
+    match points {
+        [] => Vec::new(),
+        [(_, y)] => vec![*y],
+        _ if points.len() < SUBPRODUCT_TREE_THRESHOLD => {
+            lagrange_interpolate(points).expect("enough points were provided for interpolation")
+        }
+        _ => {
+            let (xs, ys): (Vec<_>, Vec<_>) = points.iter().copied().unzip();
+            SubproductTree::new(&xs).interpolate(&ys)
+        }
+    }
+}
+
 /// Interpolates a set of points to a given polynomial in monomial form.
 ///
 /// Given a list of points (x_i, y_i), this method will return the lowest degree polynomial
@@ -83,19 +313,19 @@ pub fn vanishing_poly(roots: &[Scalar]) -> PolyCoeff {
 //
 // Note: This method is only used for testing. Our domain will always be the roots
 // of unity, so we use IFFT to interpolate.
-pub fn lagrange_interpolate(points: &[(Scalar, Scalar)]) -> Option<Vec<Scalar>> {
+pub fn lagrange_interpolate<F: PrimeField>(points: &[(F, F)]) -> Option<Vec<F>> {
     let max_degree_plus_one = points.len();
     assert!(
         max_degree_plus_one >= 2,
         "should interpolate for degree >= 1"
     );
-    let mut coeffs = vec![Scalar::ZERO; max_degree_plus_one];
+    let mut coeffs = vec![F::ZERO; max_degree_plus_one];
     // external iterator
     for (k, p_k) in points.iter().enumerate() {
         let (x_k, y_k) = p_k;
         // coeffs from 0 to max_degree - 1
-        let mut contribution = vec![Scalar::ZERO; max_degree_plus_one];
-        let mut denominator = Scalar::ONE;
+        let mut contribution = vec![F::ZERO; max_degree_plus_one];
+        let mut denominator = F::ONE;
         let mut max_contribution_degree = 0;
         // internal iterator
         for (j, p_j) in points.iter().enumerate() {
@@ -115,9 +345,9 @@ pub fn lagrange_interpolate(points: &[(Scalar, Scalar)]) -> Option<Vec<Scalar>>
                     .expect("must have enough coefficients") -= x_j;
                 *contribution
                     .get_mut(1)
-                    .expect("must have enough coefficients") += Scalar::from(1u64);
+                    .expect("must have enough coefficients") += F::from(1u64);
             } else {
-                let mul_by_minus_x_j: Vec<Scalar> = contribution
+                let mul_by_minus_x_j: Vec<F> = contribution
                     .iter()
                     .map(|el| {
                         let mut tmp = *el;
@@ -127,7 +357,7 @@ pub fn lagrange_interpolate(points: &[(Scalar, Scalar)]) -> Option<Vec<Scalar>>
                     })
                     .collect();
 
-                contribution.insert(0, Scalar::ZERO);
+                contribution.insert(0, F::ZERO);
                 contribution.truncate(max_degree_plus_one);
 
                 assert_eq!(mul_by_minus_x_j.len(), max_degree_plus_one);
@@ -155,6 +385,144 @@ pub fn lagrange_interpolate(points: &[(Scalar, Scalar)]) -> Option<Vec<Scalar>>
     Some(coeffs)
 }
 
+/// A polynomial in monomial form where the lowest degree term is first.
+///
+/// Unlike `PolyCoeff<F>`, trailing zero coefficients are always removed, so the number
+/// of coefficients is always one more than the degree of the polynomial.
+///
+/// Note: `PolyCoeff<F>` is still used where the number of coefficients is meaningful,
+/// for example, when it needs to match the size of an FFT domain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Polynomial<F = Scalar> {
+    coeffs: PolyCoeff<F>,
+}
+
+impl<F: PrimeField> Polynomial<F> {
+    /// Creates a polynomial from its coefficients, removing any trailing zeroes.
+    pub fn new(mut coeffs: PolyCoeff<F>) -> Self {
+        let num_coeffs = coeffs
+            .iter()
+            .rposition(|coeff| *coeff != F::ZERO)
+            .map_or(0, |index| index + 1);
+        coeffs.truncate(num_coeffs);
+        Self { coeffs }
+    }
+
+    /// Returns the zero polynomial.
+    pub const fn zero() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+
+    /// Returns true if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Returns the degree of the polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Returns the coefficients of the polynomial, lowest degree term first.
+    pub fn coeffs(&self) -> &[F] {
+        &self.coeffs
+    }
+
+    /// Returns the coefficients of the polynomial, lowest degree term first.
+    pub fn into_coeffs(self) -> PolyCoeff<F> {
+        self.coeffs
+    }
+
+    /// Evaluates the polynomial at `value`.
+    pub fn eval(&self, value: &F) -> F {
+        poly_eval(&self.coeffs, value)
+    }
+
+    /// Divides `self` by `divisor`, returning the quotient and the remainder.
+    ///
+    /// Returns `None` if `divisor` is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        let (quotient, remainder) = poly_div(&self.coeffs, &divisor.coeffs)?;
+        Some((Self::new(quotient), Self::new(remainder)))
+    }
+}
+
+impl<F: PrimeField> From<PolyCoeff<F>> for Polynomial<F> {
+    fn from(coeffs: PolyCoeff<F>) -> Self {
+        Self::new(coeffs)
+    }
+}
+
+impl<F: PrimeField> From<Polynomial<F>> for PolyCoeff<F> {
+    fn from(poly: Polynomial<F>) -> Self {
+        poly.coeffs
+    }
+}
+
+impl<F: PrimeField> Add for Polynomial<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(poly_add(self.coeffs, rhs.coeffs))
+    }
+}
+
+impl<F: PrimeField> Add for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Polynomial::new(poly_add_ref(&self.coeffs, &rhs.coeffs))
+    }
+}
+
+impl<F: PrimeField> Sub for Polynomial<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(poly_sub(self.coeffs, rhs.coeffs))
+    }
+}
+
+impl<F: PrimeField> Sub for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Polynomial::new(poly_sub_ref(&self.coeffs, &rhs.coeffs))
+    }
+}
+
+impl<F: PrimeField> Mul for Polynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+        Self::new(poly_mul(self.coeffs, rhs.coeffs))
+    }
+}
+
+impl<F: PrimeField> Mul for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+        Polynomial::new(poly_mul_ref(&self.coeffs, &rhs.coeffs))
+    }
+}
+
+impl<F: PrimeField> Neg for Polynomial<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            coeffs: poly_neg(self.coeffs),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,6 +569,31 @@ mod tests {
         assert_eq!(poly_sub(a, b), c);
     }
 
+    #[test]
+    fn borrowed_and_in_place_ops_match_owned_ops() {
+        let a = vec![Scalar::from(2), Scalar::from(3)];
+        let b = vec![Scalar::from(4), Scalar::from(5), Scalar::from(6)];
+
+        let expected_sum = poly_add(a.clone(), b.clone());
+        assert_eq!(poly_add_ref(&a, &b), expected_sum);
+        assert_eq!(poly_add_ref(&b, &a), expected_sum);
+        let mut sum = a.clone();
+        poly_add_assign(&mut sum, &b);
+        assert_eq!(sum, expected_sum);
+
+        let expected_diff = poly_sub(a.clone(), b.clone());
+        assert_eq!(poly_sub_ref(&a, &b), expected_diff);
+        let mut diff = a;
+        poly_sub_assign(&mut diff, &b);
+        assert_eq!(diff, expected_diff);
+
+        let expected_neg = poly_neg(b.clone());
+        assert_eq!(poly_neg_ref(&b), expected_neg);
+        let mut neg = b;
+        poly_neg_assign(&mut neg);
+        assert_eq!(neg, expected_neg);
+    }
+
     #[test]
     fn polynomial_evaluation() {
         // f(x) = 1 + 2x + 3x^2
@@ -210,6 +603,19 @@ mod tests {
         assert!(poly_eval(&poly, &value) == naive_poly_eval(&poly, &value));
     }
 
+    #[test]
+    fn multipoint_evaluation_matches_single_point_evaluation() {
+        let poly: Vec<_> = (0..100).map(|i| Scalar::from(i * 17 + 4)).collect();
+
+        for num_points in [0, 3, SUBPRODUCT_TREE_THRESHOLD * 4 + 3] {
+            let points: Vec<_> = (0..num_points)
+                .map(|i| -Scalar::from(i as u64 * 5 + 9))
+                .collect();
+            let expected: Vec<_> = points.iter().map(|z| poly_eval(&poly, z)).collect();
+            assert_eq!(evaluate_at_points(&poly, &points), expected);
+        }
+    }
+
     #[test]
     fn polynomial_multiplication() {
         // f(x) = 1 + 2x + 3x^2
@@ -226,6 +632,60 @@ mod tests {
         assert_eq!(poly_mul(a, b), expected);
     }
 
+    #[test]
+    fn fft_polynomial_multiplication_matches_naive() {
+        let a: Vec<_> = (0..100).map(|i| Scalar::from(i as u64 + 1)).collect();
+        let b: Vec<_> = (0..FFT_MUL_THRESHOLD + 3)
+            .map(|i| -Scalar::from(i as u64))
+            .collect();
+
+        let expected = poly_mul_naive(&a, &b);
+        assert_eq!(poly_mul_fft(&a, &b), expected);
+        assert_eq!(poly_mul_ref(&a, &b), expected);
+        assert_eq!(poly_mul(a, b), expected);
+    }
+
+    #[test]
+    fn polynomial_division() {
+        // (x^2 - 1) / (x - 1) = x + 1
+        let dividend = vec![-Scalar::ONE, Scalar::ZERO, Scalar::ONE];
+        let divisor = vec![-Scalar::ONE, Scalar::ONE, Scalar::ZERO];
+        let (quotient, remainder) = poly_div(&dividend, &divisor).unwrap();
+        assert_eq!(quotient, vec![Scalar::ONE, Scalar::ONE]);
+        assert_eq!(remainder, vec![Scalar::ZERO]);
+
+        // f(x) = q(x) * g(x) + r(x)
+        let dividend: Vec<_> = (0..10).map(|i| Scalar::from(i * i + 7)).collect();
+        let divisor: Vec<_> = (0..4).map(|i| -Scalar::from(i + 2)).collect();
+        let (quotient, remainder) = poly_div(&dividend, &divisor).unwrap();
+        assert_eq!(remainder.len(), divisor.len() - 1);
+        assert_eq!(poly_add(poly_mul(quotient, divisor), remainder), dividend);
+
+        // Dividing by a polynomial of larger degree leaves the dividend as the remainder
+        let (quotient, remainder) = poly_div(&dividend[..2], &dividend).unwrap();
+        assert!(quotient.is_empty());
+        assert_eq!(
+            poly_sub_ref(&remainder, &dividend[..2]),
+            vec![Scalar::ZERO; 9]
+        );
+
+        // Dividing by zero is not possible
+        assert!(poly_div(&dividend, &[Scalar::ZERO, Scalar::ZERO]).is_none());
+    }
+
+    #[test]
+    fn divide_by_linear_matches_poly_div() {
+        let poly: Vec<_> = (0..10).map(|i| Scalar::from(i * 3 + 1)).collect();
+        let z = Scalar::from(5u64);
+
+        let (quotient, remainder) = divide_by_linear(&poly, z);
+        assert_eq!(remainder, poly_eval(&poly, &z));
+
+        let (expected_quotient, expected_remainder) = poly_div(&poly, &[-z, Scalar::ONE]).unwrap();
+        assert_eq!(quotient, expected_quotient);
+        assert_eq!(vec![remainder], expected_remainder);
+    }
+
     #[test]
     fn vanishing_polynomial_smoke_test() {
         // f(x) = (x - 1)(x - 2)(x - 3) = x^3 - 6x^2 + 11x - 6
@@ -245,6 +705,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn interpolate_matches_lagrange_interpolate() {
+        assert!(interpolate::<Scalar>(&[]).is_empty());
+        assert_eq!(
+            interpolate(&[(Scalar::from(3u64), Scalar::from(7u64))]),
+            vec![Scalar::from(7u64)]
+        );
+
+        for num_points in [2, SUBPRODUCT_TREE_THRESHOLD + 5] {
+            let points: Vec<_> = (0..num_points)
+                .map(|i| {
+                    (
+                        Scalar::from(i as u64 * 3 + 1),
+                        Scalar::from(i as u64 * i as u64),
+                    )
+                })
+                .collect();
+
+            let poly = interpolate(&points);
+            assert_eq!(poly, lagrange_interpolate(&points).unwrap());
+            for (x, y) in &points {
+                assert_eq!(poly_eval(&poly, x), *y);
+            }
+        }
+    }
+
+    #[test]
+    fn vanishing_polynomial_subproduct_tree_matches_naive() {
+        let roots: Vec<_> = (0..FFT_MUL_THRESHOLD * 3 + 1)
+            .map(|i| Scalar::from(i as u64 * 11 + 2))
+            .collect();
+
+        let poly = vanishing_poly(&roots);
+        assert_eq!(poly, vanishing_poly_naive(&roots));
+        assert_eq!(poly.len(), roots.len() + 1);
+    }
+
+    #[test]
+    fn polynomial_newtype_normalizes_and_tracks_degree() {
+        let zero = Polynomial::new(vec![Scalar::ZERO, Scalar::ZERO]);
+        assert!(zero.is_zero());
+        assert_eq!(zero.degree(), None);
+        assert_eq!(zero, Polynomial::zero());
+
+        // f(x) = 1 + 2x
+        let f = Polynomial::new(vec![Scalar::from(1), Scalar::from(2), Scalar::ZERO]);
+        assert_eq!(f.degree(), Some(1));
+        assert_eq!(f.coeffs(), &[Scalar::from(1), Scalar::from(2)]);
+
+        // g(x) = 3 - 2x
+        let g = Polynomial::new(vec![Scalar::from(3), -Scalar::from(2)]);
+
+        // f + g = 4, the degree drops since the leading terms cancel
+        let sum = &f + &g;
+        assert_eq!(sum.degree(), Some(0));
+        assert_eq!(sum, Polynomial::new(vec![Scalar::from(4)]));
+
+        assert!((&f - &f).is_zero());
+        assert_eq!(-(f.clone() - g.clone()), g.clone() - f.clone());
+
+        let product = &f * &g;
+        assert_eq!(product.degree(), Some(2));
+        let (quotient, remainder) = product.div_rem(&g).unwrap();
+        assert_eq!(quotient, f);
+        assert!(remainder.is_zero());
+
+        assert!((f * Polynomial::zero()).is_zero());
+    }
+
     #[test]
     fn polynomial_interpolation_smoke_test() {
         // f(x) = 1 + 2x + 3x^2
@@ -0,0 +1,113 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{Mutex, PoisonError},
+};
+
+/// A pool of buffers that are reused across calls, instead of allocating new ones each time.
+///
+/// The pool is meant to be held by a context that is shared between threads. Each thread
+/// takes its own buffer from the pool, which is returned to the pool when it is dropped.
+/// A buffer is only allocated when all of the buffers in the pool are in use, so the pool
+/// holds at most as many buffers as have been in use at the same time.
+///
+/// Note: The buffers keep their capacity, so the memory is not released until the pool is dropped.
+pub struct ScratchPool<T> {
+    buffers: Mutex<Vec<Vec<T>>>,
+}
+
+impl<T> Default for ScratchPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for ScratchPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScratchPool")
+            .field("num_buffers", &self.lock().len())
+            .finish()
+    }
+}
+
+impl<T> ScratchPool<T> {
+    /// Creates a pool that does not contain any buffers.
+    pub const fn new() -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Vec<T>>> {
+        // A panic while the lock is held cannot leave the list of buffers in an invalid state.
+        self.buffers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Clone> ScratchPool<T> {
+    /// Takes a buffer from the pool, which contains `len` copies of `value`.
+    ///
+    /// The buffer is returned to the pool when it is dropped.
+    pub fn take(&self, len: usize, value: T) -> Scratch<'_, T> {
+        let mut buffer = self.lock().pop().unwrap_or_default();
+        buffer.resize(len, value);
+        Scratch { buffer, pool: self }
+    }
+}
+
+/// A buffer that was taken from a `ScratchPool`.
+pub struct Scratch<'a, T> {
+    buffer: Vec<T>,
+    pool: &'a ScratchPool<T>,
+}
+
+impl<T> Deref for Scratch<'_, T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl<T> DerefMut for Scratch<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl<T> Drop for Scratch<'_, T> {
+    fn drop(&mut self) {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        self.pool.lock().push(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScratchPool;
+
+    #[test]
+    fn buffers_are_reused() {
+        let pool = ScratchPool::new();
+
+        let mut first = pool.take(4, 0u64);
+        first[3] = 7;
+        let first_ptr = first.as_ptr();
+        drop(first);
+
+        // The buffer is reused and its elements are reset
+        let second = pool.take(4, 0u64);
+        assert_eq!(second.as_ptr(), first_ptr);
+        assert_eq!(&*second, &[0; 4]);
+
+        // A new buffer is allocated while the other one is in use
+        let third = pool.take(2, 1u64);
+        assert_ne!(third.as_ptr(), first_ptr);
+        assert_eq!(&*third, &[1; 2]);
+
+        drop(second);
+        drop(third);
+        assert_eq!(pool.lock().len(), 2);
+    }
+}
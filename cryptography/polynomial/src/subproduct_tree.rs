@@ -0,0 +1,178 @@
+use crate::batch_inverse;
+use crate::poly_coeff::{poly_add_assign, poly_div, poly_mul_ref, PolyCoeff};
+use bls12_381::ff::PrimeField;
+use maybe_rayon::prelude::*;
+
+/// A subproduct tree over a set of points `{x_i}`.
+///
+/// The leaves of the tree are the linear polynomials `(x - x_i)` and each internal
+/// node is the product of its two children. The root is therefore the vanishing
+/// polynomial `Z(x) = \prod (x - x_i)`.
+///
+/// Note: If a layer has an odd number of nodes, the last node is carried up to the
+/// next layer unchanged.
+pub(crate) struct SubproductTree<F> {
+    /// `layers[0]` contains the leaves and the last layer contains only the root.
+    layers: Vec<Vec<PolyCoeff<F>>>,
+}
+
+impl<F: PrimeField> SubproductTree<F> {
+    /// Builds the subproduct tree for the given points.
+    ///
+    /// Panics if `points` is empty.
+    pub(crate) fn new(points: &[F]) -> Self {
+        assert!(
+            !points.is_empty(),
+            "subproduct tree needs at least one point"
+        );
+
+        let mut layers = vec![linear_factors(points)];
+
+        while layers.last().expect("at least one layer").len() > 1 {
+            let next_layer = multiply_pairs(layers.last().expect("at least one layer"));
+            layers.push(next_layer);
+        }
+
+        Self { layers }
+    }
+
+    /// Returns the vanishing polynomial for the points in the tree.
+    pub(crate) fn root(&self) -> &PolyCoeff<F> {
+        &self.layers.last().expect("at least one layer")[0]
+    }
+
+    /// Evaluates `poly` at every point in the tree.
+    ///
+    /// This reduces the polynomial modulo each node going down the tree, so that
+    /// at the leaves we are left with `poly mod (x - x_i) = poly(x_i)`.
+    pub(crate) fn evaluate(&self, poly: &[F]) -> Vec<F> {
+        let mut remainders = vec![reduce(poly, self.root())];
+
+        for layer in self.layers.iter().rev().skip(1) {
+            remainders = layer
+                .iter()
+                .enumerate()
+                .map(|(i, node)| reduce(&remainders[i / 2], node))
+                .collect();
+        }
+
+        remainders
+            .into_iter()
+            .map(|remainder| remainder[0])
+            .collect()
+    }
+
+    /// Computes the lowest degree polynomial `f(x)` such that `f(x_i) = y_i` for all
+    /// points `x_i` in the tree.
+    ///
+    /// Panics if the number of evaluations is not equal to the number of points, or
+    /// if the points are not distinct.
+    pub(crate) fn interpolate(&self, evaluations: &[F]) -> PolyCoeff<F> {
+        let leaves = &self.layers[0];
+        assert_eq!(
+            leaves.len(),
+            evaluations.len(),
+            "number of evaluations must equal the number of points"
+        );
+
+        // The lagrange weights are y_i / Z'(x_i)
+        let vanishing_poly_derivative = derivative(self.root());
+        let mut weights = self.evaluate(&vanishing_poly_derivative);
+        assert!(
+            !weights.contains(&F::ZERO),
+            "points used for interpolation must be distinct"
+        );
+        batch_inverse(&mut weights);
+
+        let mut nodes: Vec<PolyCoeff<F>> = weights
+            .into_iter()
+            .zip(evaluations)
+            .map(|(weight, y)| vec![weight * y])
+            .collect();
+
+        // Combine going up the tree, where for two children:
+        // f_parent = f_left * Z_right + f_right * Z_left
+        //
+        // The nodes of the previous layer are moved into the next layer, or only borrowed,
+        // so that no polynomial is cloned.
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let mut values = nodes.into_iter();
+            let mut next_value = || {
+                values
+                    .next()
+                    .expect("one value for each vanishing polynomial")
+            };
+            nodes = layer
+                .chunks(2)
+                .map(|vanishing_polys| match vanishing_polys {
+                    [left_z, right_z] => {
+                        let (left, right) = (next_value(), next_value());
+                        let mut parent = poly_mul_ref(&left, right_z);
+                        poly_add_assign(&mut parent, &poly_mul_ref(&right, left_z));
+                        parent
+                    }
+                    [_] => next_value(),
+                    _ => unreachable!("chunks of two"),
+                })
+                .collect();
+        }
+
+        let mut result = nodes.pop().expect("root node");
+        result.resize(evaluations.len(), F::ZERO);
+        result
+    }
+}
+
+/// Computes the polynomial that vanishes on all of the points, by multiplying the
+/// linear factors `(x - x_i)` together pairwise, one layer of the subproduct tree at a time.
+///
+/// Unlike `SubproductTree::new`, this only keeps the current layer in memory.
+///
+/// Panics if `points` is empty.
+pub(crate) fn vanishing_poly_subproduct_tree<F: PrimeField>(points: &[F]) -> PolyCoeff<F> {
+    assert!(
+        !points.is_empty(),
+        "subproduct tree needs at least one point"
+    );
+
+    let mut layer = linear_factors(points);
+    while layer.len() > 1 {
+        layer = multiply_pairs(&layer);
+    }
+    layer.pop().expect("root node")
+}
+
+/// Returns the linear polynomials `(x - x_i)` for each point.
+fn linear_factors<F: PrimeField>(points: &[F]) -> Vec<PolyCoeff<F>> {
+    points.iter().map(|x| vec![-*x, F::ONE]).collect()
+}
+
+/// Computes the next layer of a subproduct tree by multiplying adjacent nodes.
+///
+/// Note: The multiplications in a layer are independent, so they are done in parallel
+/// when the `multithreaded` feature is enabled.
+fn multiply_pairs<F: PrimeField>(layer: &[PolyCoeff<F>]) -> Vec<PolyCoeff<F>> {
+    layer
+        .maybe_par_chunks(2)
+        .map(|pair| match pair {
+            [left, right] => poly_mul_ref(left, right),
+            [single] => single.clone(),
+            _ => unreachable!("chunks of two"),
+        })
+        .collect()
+}
+
+/// Computes `poly mod divisor`.
+fn reduce<F: PrimeField>(poly: &[F], divisor: &[F]) -> PolyCoeff<F> {
+    let (_, remainder) = poly_div(poly, divisor).expect("nodes in the tree are non-zero");
+    remainder
+}
+
+/// Computes the formal derivative of a polynomial.
+fn derivative<F: PrimeField>(poly: &[F]) -> PolyCoeff<F> {
+    poly.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, coeff)| *coeff * F::from(i as u64))
+        .collect()
+}
@@ -1,15 +1,13 @@
 use bls12_381::Scalar;
 use criterion::{criterion_group, criterion_main, Criterion};
 use rust_eth_kzg::{
-    constants::{BYTES_PER_BLOB, CELLS_PER_EXT_BLOB},
-    Bytes48Ref, Cell, CellIndex, CellRef, DASContext, KZGCommitment, KZGProof, ThreadCount,
-    TrustedSetup,
+    constants::{BYTES_PER_BLOB, CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_BLOB},
+    AggregateVerifier, Bytes48Ref, Cell, CellIndex, CellRef, DASContext, KZGCommitment, KZGProof,
+    ThreadCount, TrustedSetup,
 };
 
-const POLYNOMIAL_LEN: usize = 4096;
-
 fn dummy_blob() -> [u8; BYTES_PER_BLOB] {
-    let polynomial: Vec<_> = (0..POLYNOMIAL_LEN)
+    let polynomial: Vec<_> = (0..FIELD_ELEMENTS_PER_BLOB)
         .map(|i| -Scalar::from(i as u64))
         .collect();
     let blob: Vec<_> = polynomial
@@ -30,6 +28,7 @@ fn dummy_commitment_cells_and_proofs() -> (
     (commitment, ctx.compute_cells_and_kzg_proofs(&blob).unwrap())
 }
 
+#[cfg(feature = "multithreaded")]
 const THREAD_COUNTS: [ThreadCount; 5] = [
     ThreadCount::Single,
     ThreadCount::Multi(4),
@@ -37,6 +36,21 @@ const THREAD_COUNTS: [ThreadCount; 5] = [
     ThreadCount::Multi(16),
     ThreadCount::Multi(32),
 ];
+// Without the `multithreaded` feature, everything runs on the calling thread.
+#[cfg(not(feature = "multithreaded"))]
+const THREAD_COUNTS: [ThreadCount; 1] = [ThreadCount::Single];
+
+fn new_context(trusted_setup: &TrustedSetup, num_threads: ThreadCount) -> DASContext {
+    let use_precomp = bls12_381::fixed_base_msm::UsePrecomp::Yes { width: 8 };
+    #[cfg(feature = "multithreaded")]
+    let ctx = DASContext::with_threads(trusted_setup, num_threads, use_precomp);
+    #[cfg(not(feature = "multithreaded"))]
+    let ctx = {
+        let _ = num_threads;
+        DASContext::new(trusted_setup, use_precomp)
+    };
+    ctx
+}
 
 pub fn bench_compute_cells_and_kzg_proofs(c: &mut Criterion) {
     let trusted_setup = TrustedSetup::default();
@@ -44,11 +58,7 @@ pub fn bench_compute_cells_and_kzg_proofs(c: &mut Criterion) {
     let blob = dummy_blob();
 
     for num_threads in THREAD_COUNTS {
-        let ctx = DASContext::with_threads(
-            &trusted_setup,
-            num_threads,
-            bls12_381::fixed_base_msm::UsePrecomp::Yes { width: 8 },
-        );
+        let ctx = new_context(&trusted_setup, num_threads);
         c.bench_function(
             &format!(
                 "computing cells_and_kzg_proofs - NUM_THREADS: {:?}",
@@ -59,11 +69,29 @@ pub fn bench_compute_cells_and_kzg_proofs(c: &mut Criterion) {
     }
 }
 
+pub fn bench_compute_cell_and_kzg_proof(c: &mut Criterion) {
+    let trusted_setup = TrustedSetup::default();
+
+    let blob = dummy_blob();
+
+    let cell_index = CellIndex::try_from(0u64).unwrap();
+    for num_threads in THREAD_COUNTS {
+        let ctx = new_context(&trusted_setup, num_threads);
+        c.bench_function(
+            &format!(
+                "computing a single cell_and_kzg_proof - NUM_THREADS: {:?}",
+                num_threads
+            ),
+            |b| b.iter(|| ctx.compute_cell_and_kzg_proof(&blob, cell_index)),
+        );
+    }
+}
+
 pub fn bench_recover_cells_and_compute_kzg_proofs(c: &mut Criterion) {
     let trusted_setup = TrustedSetup::default();
 
     let (_, (cells, _)) = dummy_commitment_cells_and_proofs();
-    let cell_indices: Vec<u64> = (0..cells.len()).map(|x| x as u64).collect();
+    let cell_indices: Vec<CellIndex> = CellIndex::all().collect();
 
     // Worse case is when half of the cells are missing
     let half_cell_indices = &cell_indices[..CELLS_PER_EXT_BLOB / 2];
@@ -74,11 +102,7 @@ pub fn bench_recover_cells_and_compute_kzg_proofs(c: &mut Criterion) {
         .collect::<Vec<_>>();
 
     for num_threads in THREAD_COUNTS {
-        let ctx = DASContext::with_threads(
-            &trusted_setup,
-            num_threads,
-            bls12_381::fixed_base_msm::UsePrecomp::Yes { width: 8 },
-        );
+        let ctx = new_context(&trusted_setup, num_threads);
         c.bench_function(
             &format!(
                 "worse-case recover_cells_and_kzg_proofs - NUM_THREADS: {:?}",
@@ -99,16 +123,12 @@ pub fn bench_verify_cell_kzg_proof_batch(c: &mut Criterion) {
     let (commitment, (cells, proofs)) = dummy_commitment_cells_and_proofs();
 
     let commitments = vec![&commitment; CELLS_PER_EXT_BLOB];
-    let cell_indices: Vec<CellIndex> = (0..CELLS_PER_EXT_BLOB).map(|x| x as CellIndex).collect();
+    let cell_indices: Vec<CellIndex> = CellIndex::all().collect();
     let cell_refs: Vec<CellRef> = cells.iter().map(|cell| cell.as_ref()).collect();
     let proof_refs: Vec<Bytes48Ref> = proofs.iter().collect();
 
     for num_threads in THREAD_COUNTS {
-        let ctx = DASContext::with_threads(
-            &trusted_setup,
-            num_threads,
-            bls12_381::fixed_base_msm::UsePrecomp::Yes { width: 8 },
-        );
+        let ctx = new_context(&trusted_setup, num_threads);
         c.bench_function(
             &format!(
                 "verify_cell_kzg_proof_batch - NUM_THREADS: {:?}",
@@ -128,16 +148,80 @@ pub fn bench_verify_cell_kzg_proof_batch(c: &mut Criterion) {
     }
 }
 
+pub fn bench_verify_column(c: &mut Criterion) {
+    let trusted_setup = TrustedSetup::default();
+
+    let (commitment, (cells, proofs)) = dummy_commitment_cells_and_proofs();
+
+    // Every cell in the column is the first cell of the same blob
+    const NUM_BLOBS: usize = 128;
+    let commitments = vec![&commitment; NUM_BLOBS];
+    let cell_refs: Vec<CellRef> = vec![cells[0].as_ref(); NUM_BLOBS];
+    let proof_refs: Vec<Bytes48Ref> = vec![&proofs[0]; NUM_BLOBS];
+
+    for num_threads in THREAD_COUNTS {
+        let ctx = new_context(&trusted_setup, num_threads);
+        c.bench_function(
+            &format!("verify_column - NUM_THREADS: {:?}", num_threads),
+            |b| {
+                b.iter(|| {
+                    ctx.verify_column(
+                        commitments.clone(),
+                        CellIndex::try_from(0u64).unwrap(),
+                        cell_refs.clone(),
+                        proof_refs.clone(),
+                    )
+                })
+            },
+        );
+    }
+}
+
+pub fn bench_verify_aggregate(c: &mut Criterion) {
+    let trusted_setup = TrustedSetup::default();
+
+    let (commitment, (cells, proofs)) = dummy_commitment_cells_and_proofs();
+
+    // The cells of the blob are split into batches, as if they had arrived in separate messages
+    const NUM_BATCHES: usize = 16;
+    const BATCH_SIZE: usize = CELLS_PER_EXT_BLOB / NUM_BATCHES;
+    let mut aggregate = AggregateVerifier::new();
+    for batch in 0..NUM_BATCHES {
+        let range = batch * BATCH_SIZE..(batch + 1) * BATCH_SIZE;
+        aggregate
+            .add_batch(
+                vec![&commitment; BATCH_SIZE],
+                range
+                    .clone()
+                    .map(|x| CellIndex::try_from(x).unwrap())
+                    .collect(),
+                cells[range.clone()]
+                    .iter()
+                    .map(|cell| cell.as_ref())
+                    .collect(),
+                proofs[range].iter().collect(),
+            )
+            .unwrap();
+    }
+
+    for num_threads in THREAD_COUNTS {
+        let ctx = new_context(&trusted_setup, num_threads);
+        c.bench_function(
+            &format!(
+                "verify_aggregate - NUM_BATCHES: {NUM_BATCHES} - NUM_THREADS: {:?}",
+                num_threads
+            ),
+            |b| b.iter(|| ctx.verify_aggregate(&aggregate)),
+        );
+    }
+}
+
 pub fn bench_init_context(c: &mut Criterion) {
     const NUM_THREADS: ThreadCount = ThreadCount::Single;
     c.bench_function("Initialize context", |b| {
         b.iter(|| {
             let trusted_setup = TrustedSetup::default();
-            DASContext::with_threads(
-                &trusted_setup,
-                NUM_THREADS,
-                bls12_381::fixed_base_msm::UsePrecomp::Yes { width: 8 },
-            )
+            new_context(&trusted_setup, NUM_THREADS)
         })
     });
 }
@@ -146,7 +230,10 @@ criterion_group!(
     benches,
     bench_init_context,
     bench_compute_cells_and_kzg_proofs,
+    bench_compute_cell_and_kzg_proof,
     bench_recover_cells_and_compute_kzg_proofs,
-    bench_verify_cell_kzg_proof_batch
+    bench_verify_cell_kzg_proof_batch,
+    bench_verify_column,
+    bench_verify_aggregate
 );
 criterion_main!(benches);
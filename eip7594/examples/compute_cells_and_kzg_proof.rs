@@ -1,5 +1,8 @@
 use bls12_381::Scalar;
-use rust_eth_kzg::{constants::BYTES_PER_BLOB, DASContext, ThreadCount, TrustedSetup};
+use rust_eth_kzg::{
+    constants::{BYTES_PER_BLOB, FIELD_ELEMENTS_PER_BLOB},
+    DASContext, ThreadCount, TrustedSetup,
+};
 use std::time::Instant;
 use tracing_forest::util::LevelFilter;
 use tracing_forest::ForestLayer;
@@ -7,10 +10,8 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, Registry};
 
-const POLYNOMIAL_LEN: usize = 4096;
-
 fn dummy_blob() -> [u8; BYTES_PER_BLOB] {
-    let polynomial: Vec<_> = (0..POLYNOMIAL_LEN)
+    let polynomial: Vec<_> = (0..FIELD_ELEMENTS_PER_BLOB)
         .map(|i| -Scalar::from(i as u64))
         .collect();
     let blob: Vec<_> = polynomial
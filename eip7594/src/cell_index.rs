@@ -0,0 +1,141 @@
+use crate::{constants::CELLS_PER_EXT_BLOB, errors::CellIndexOutOfRange};
+use std::fmt;
+
+/// CellIndex is reference to the coset/set of points that were used to create that Cell,
+/// on a particular polynomial, f(x).
+///
+/// Note: Since the verifier and prover both know what cosets will be used
+/// to evaluate the polynomials being used in opening proofs, the protocol
+/// only requires an index to reference them.
+///
+/// A CellIndex is always less than `CELLS_PER_EXT_BLOB`, which is checked when it is
+/// converted from a `u64` or a `usize`.
+///
+/// ```
+/// use rust_eth_kzg::{constants::CELLS_PER_EXT_BLOB, CellIndex};
+///
+/// let cell_index = CellIndex::try_from(5u64).unwrap();
+/// assert_eq!(u64::from(cell_index), 5);
+///
+/// assert!(CellIndex::try_from(CELLS_PER_EXT_BLOB).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CellIndex(u64);
+
+impl CellIndex {
+    /// Returns the cell index, or None if it is not less than `CELLS_PER_EXT_BLOB`.
+    pub const fn new(index: u64) -> Option<Self> {
+        if index < CELLS_PER_EXT_BLOB as u64 {
+            Some(Self(index))
+        } else {
+            None
+        }
+    }
+
+    /// Creates a cell index from an index that is known to be less than `CELLS_PER_EXT_BLOB`.
+    pub(crate) const fn new_unchecked(index: u64) -> Self {
+        debug_assert!(index < CELLS_PER_EXT_BLOB as u64);
+        Self(index)
+    }
+
+    /// Returns the cell index as a `u64`.
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the cell index as a `usize`, which can be used to index into the cells and
+    /// proofs of an extended blob.
+    pub const fn as_usize(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Returns all of the cell indices of an extended blob, in ascending order.
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator {
+        (0..CELLS_PER_EXT_BLOB).map(|index| Self::new_unchecked(index as u64))
+    }
+
+    /// Converts each of the indices to a cell index, returning an error for the first
+    /// index that is out of range.
+    ///
+    /// This is meant for the bindings, which receive the cell indices as integers.
+    pub fn try_from_u64s(indices: &[u64]) -> Result<Vec<Self>, CellIndexOutOfRange> {
+        indices.iter().map(|&index| Self::try_from(index)).collect()
+    }
+}
+
+impl TryFrom<u64> for CellIndex {
+    type Error = CellIndexOutOfRange;
+
+    fn try_from(index: u64) -> Result<Self, Self::Error> {
+        Self::new(index).ok_or(CellIndexOutOfRange {
+            cell_index: index,
+            max_number_of_cells: CELLS_PER_EXT_BLOB as u64,
+        })
+    }
+}
+
+impl TryFrom<usize> for CellIndex {
+    type Error = CellIndexOutOfRange;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        // A usize that does not fit in a u64 is also out of range
+        Self::try_from(u64::try_from(index).unwrap_or(u64::MAX))
+    }
+}
+
+impl From<CellIndex> for u64 {
+    fn from(cell_index: CellIndex) -> Self {
+        cell_index.as_u64()
+    }
+}
+
+impl From<CellIndex> for usize {
+    fn from(cell_index: CellIndex) -> Self {
+        cell_index.as_usize()
+    }
+}
+
+impl fmt::Display for CellIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_are_range_checked() {
+        let last = CellIndex::try_from(CELLS_PER_EXT_BLOB - 1).unwrap();
+        assert_eq!(last.as_usize(), CELLS_PER_EXT_BLOB - 1);
+        assert_eq!(CellIndex::try_from(0u64), Ok(CellIndex::new_unchecked(0)));
+
+        for index in [CELLS_PER_EXT_BLOB as u64, u64::MAX] {
+            assert_eq!(
+                CellIndex::try_from(index),
+                Err(CellIndexOutOfRange {
+                    cell_index: index,
+                    max_number_of_cells: CELLS_PER_EXT_BLOB as u64,
+                })
+            );
+        }
+        assert!(CellIndex::try_from(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn try_from_u64s_reports_the_first_invalid_index() {
+        let cell_indices = CellIndex::try_from_u64s(&[0, 3, 127]).unwrap();
+        let cell_indices: Vec<u64> = cell_indices.into_iter().map(u64::from).collect();
+        assert_eq!(cell_indices, vec![0, 3, 127]);
+
+        let err = CellIndex::try_from_u64s(&[0, 128, 200]).unwrap_err();
+        assert_eq!(err.cell_index, 128);
+    }
+
+    #[test]
+    fn all_cell_indices() {
+        let all: Vec<u64> = CellIndex::all().map(u64::from).collect();
+        assert_eq!(all, (0..CELLS_PER_EXT_BLOB as u64).collect::<Vec<_>>());
+    }
+}
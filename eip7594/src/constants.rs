@@ -36,11 +36,26 @@ pub const EXPANSION_FACTOR: usize = 2;
 /// The number of field elements needed to represent an extended blob.
 pub const FIELD_ELEMENTS_PER_EXT_BLOB: usize = EXPANSION_FACTOR * FIELD_ELEMENTS_PER_BLOB;
 
+/// The number of bytes needed to represent an extended blob.
+pub const BYTES_PER_EXT_BLOB: usize = FIELD_ELEMENTS_PER_EXT_BLOB * BYTES_PER_FIELD_ELEMENT;
+
 /// The number of cells in an extension blob.
 ///
 /// Note: A cell is simply a list of `FIELD_ELEMENTS_PER_CELL` field elements.
 pub const CELLS_PER_EXT_BLOB: usize = FIELD_ELEMENTS_PER_EXT_BLOB / FIELD_ELEMENTS_PER_CELL;
 
+/// The generator of the multiplicative group of the scalar field, whose powers give the roots
+/// of unity of every domain.
+///
+/// Note: This is originally specified in the 4844 specs. See `cosets::domain_generator`.
+pub const PRIMITIVE_ROOT_OF_UNITY: u64 = 7;
+
+/// The number of custody groups that the columns of an extended blob are divided into.
+///
+/// Note: This is originally specified in the das-core specs. Each group contains
+/// `CELLS_PER_EXT_BLOB / NUMBER_OF_CUSTODY_GROUPS` columns.
+pub const NUMBER_OF_CUSTODY_GROUPS: usize = 128;
+
 /// The number of proofs for an extension blob.
 ///
 /// Note: Each Cell comes with its own proof.
@@ -54,6 +69,11 @@ pub(crate) const BYTES_PER_G1_POINT: usize = 48;
 /// Note: commitments are G1 elements.
 pub const BYTES_PER_COMMITMENT: usize = BYTES_PER_G1_POINT;
 
+/// The number of bytes needed to represent a proof.
+///
+/// Note: proofs are G1 elements.
+pub const BYTES_PER_PROOF: usize = BYTES_PER_G1_POINT;
+
 /// The recommended precomputation width to use if UsePrecomp
 /// is set to Yes.
 ///
@@ -0,0 +1,210 @@
+//! Helpers for mapping a cell to the points that its field elements are evaluations at, as
+//! specified in the `polynomial-commitments-sampling` section of the consensus specs.
+//!
+//! The extended blob contains the evaluations of the blob's polynomial at the
+//! `FIELD_ELEMENTS_PER_EXT_BLOB`'th roots of unity, in bit-reversed order. This means that the
+//! field element at position `p` of the extended blob is the evaluation at `w^reverse_bits(p)`,
+//! where `w` is the generator of the roots of unity.
+//!
+//! Cell `i` contains the field elements at positions `i * FIELD_ELEMENTS_PER_CELL` up to
+//! `(i + 1) * FIELD_ELEMENTS_PER_CELL`. Because of the bit-reversed order, these are the points
+//! of a coset of the `FIELD_ELEMENTS_PER_CELL`'th roots of unity, which is why each cell can be
+//! opened with a single proof.
+//!
+//! The roots of unity of each domain are also exposed, so that other tools can compute
+//! evaluations that are compatible with the ones computed by this library.
+
+use crate::{
+    constants::{BYTES_PER_FIELD_ELEMENT, FIELD_ELEMENTS_PER_CELL, FIELD_ELEMENTS_PER_EXT_BLOB},
+    CellIndex,
+};
+use bls12_381::{
+    ff::{Field, PrimeField},
+    Scalar,
+};
+use kzg_multi_open::reverse_bit_order;
+use polynomial::domain::Domain;
+use std::{collections::HashMap, sync::OnceLock};
+
+/// Returns the coset for the given cell, which are the points that the field elements of the
+/// cell are evaluations at, in the same order as the field elements.
+///
+/// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/_features/eip7594/polynomial-commitments-sampling.md#coset_for_cell
+pub fn coset_for_cell(cell_index: CellIndex) -> &'static [Scalar; FIELD_ELEMENTS_PER_CELL] {
+    let start = cell_index.as_usize() * FIELD_ELEMENTS_PER_CELL;
+    roots_of_unity_brp()[start..start + FIELD_ELEMENTS_PER_CELL]
+        .try_into()
+        .expect("infallible: slice should have length equal to FIELD_ELEMENTS_PER_CELL")
+}
+
+/// Returns the coset shift for the given cell, which is the first point of its coset.
+///
+/// The other points of the coset are the coset shift multiplied by each of the
+/// `FIELD_ELEMENTS_PER_CELL`'th roots of unity.
+///
+/// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/_features/eip7594/polynomial-commitments-sampling.md#coset_shift_for_cell
+pub fn coset_shift_for_cell(cell_index: CellIndex) -> Scalar {
+    coset_for_cell(cell_index)[0]
+}
+
+/// Returns the cell whose coset contains the point, along with the position of the point in
+/// the cell.
+///
+/// This is the inverse of `coset_for_cell`. Returns None if the point is not one of the
+/// `FIELD_ELEMENTS_PER_EXT_BLOB`'th roots of unity, in which case no cell has an evaluation at it.
+pub fn cell_for_point(point: &Scalar) -> Option<(CellIndex, usize)> {
+    let position = *positions_of_roots().get(&point.to_bytes_le())?;
+    let cell_index = CellIndex::new_unchecked((position / FIELD_ELEMENTS_PER_CELL) as u64);
+    Some((cell_index, position % FIELD_ELEMENTS_PER_CELL))
+}
+
+/// Returns the generator of the `domain_size`'th roots of unity, which is
+/// `PRIMITIVE_ROOT_OF_UNITY^((MODULUS - 1) / domain_size)`.
+///
+/// The domains that the library uses have `FIELD_ELEMENTS_PER_BLOB`, `FIELD_ELEMENTS_PER_EXT_BLOB`
+/// and `FIELD_ELEMENTS_PER_CELL` points.
+///
+/// Panics if `domain_size` is not a power of two, or is greater than `2^32`, which is the largest
+/// power of two that divides `MODULUS - 1`.
+pub fn domain_generator(domain_size: usize) -> Scalar {
+    assert!(
+        domain_size.is_power_of_two() && domain_size.trailing_zeros() <= Scalar::S,
+        "domain size must be a power of two that is at most 2^{}, found {domain_size}",
+        Scalar::S
+    );
+
+    // `ROOT_OF_UNITY` generates the `2^S`'th roots of unity
+    let exponent = 1u64 << (Scalar::S - domain_size.trailing_zeros());
+    Scalar::ROOT_OF_UNITY.pow_vartime([exponent])
+}
+
+/// Returns the `domain_size`'th roots of unity, which are the powers of `domain_generator(domain_size)`
+/// in ascending order.
+///
+/// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/deneb/polynomial-commitments.md#compute_roots_of_unity
+///
+/// Panics under the same conditions as `domain_generator`.
+pub fn compute_roots_of_unity(domain_size: usize) -> Vec<Scalar> {
+    let generator = domain_generator(domain_size);
+    std::iter::successors(Some(Scalar::ONE), |root| Some(root * generator))
+        .take(domain_size)
+        .collect()
+}
+
+/// Returns the `FIELD_ELEMENTS_PER_EXT_BLOB`'th roots of unity in bit-reversed order, which are
+/// the points that the field elements of the extended blob are evaluations at.
+///
+/// The field elements of a blob are the evaluations at the first half of these points.
+pub fn roots_of_unity_brp() -> &'static [Scalar] {
+    static ROOTS_OF_UNITY_BRP: OnceLock<Vec<Scalar>> = OnceLock::new();
+    ROOTS_OF_UNITY_BRP.get_or_init(|| {
+        let mut roots = Domain::get_or_init(FIELD_ELEMENTS_PER_EXT_BLOB)
+            .roots
+            .to_vec();
+        reverse_bit_order(&mut roots);
+        roots
+    })
+}
+
+/// Returns the position of each root of unity in the extended blob, keyed by its encoding,
+/// since field elements cannot be hashed.
+fn positions_of_roots() -> &'static HashMap<[u8; BYTES_PER_FIELD_ELEMENT], usize> {
+    static POSITIONS_OF_ROOTS: OnceLock<HashMap<[u8; BYTES_PER_FIELD_ELEMENT], usize>> =
+        OnceLock::new();
+    POSITIONS_OF_ROOTS.get_or_init(|| {
+        roots_of_unity_brp()
+            .iter()
+            .enumerate()
+            .map(|(position, root)| (root.to_bytes_le(), position))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{FIELD_ELEMENTS_PER_BLOB, PRIMITIVE_ROOT_OF_UNITY};
+    use crate::{constants::BYTES_PER_BLOB, serialization::serialize_scalars_to_bytes, DASContext};
+
+    #[test]
+    fn domain_generator_matches_the_specs() {
+        // `ROOT_OF_UNITY` is `MULTIPLICATIVE_GENERATOR^((MODULUS - 1) / 2^S)`, which is why
+        // `domain_generator` is a power of `PRIMITIVE_ROOT_OF_UNITY`
+        assert_eq!(
+            Scalar::MULTIPLICATIVE_GENERATOR,
+            Scalar::from(PRIMITIVE_ROOT_OF_UNITY)
+        );
+
+        for domain_size in [
+            1,
+            FIELD_ELEMENTS_PER_CELL,
+            FIELD_ELEMENTS_PER_BLOB,
+            FIELD_ELEMENTS_PER_EXT_BLOB,
+        ] {
+            let generator = domain_generator(domain_size);
+            assert_eq!(generator, Domain::get_or_init(domain_size).generator);
+            assert_eq!(generator.pow_vartime([domain_size as u64]), Scalar::ONE);
+            assert_eq!(
+                compute_roots_of_unity(domain_size),
+                Domain::get_or_init(domain_size).roots.to_vec()
+            );
+        }
+        assert_eq!(domain_generator(2), -Scalar::ONE);
+    }
+
+    #[test]
+    #[should_panic]
+    fn domain_size_must_be_a_power_of_two() {
+        domain_generator(FIELD_ELEMENTS_PER_BLOB + 1);
+    }
+
+    #[test]
+    fn blob_is_the_first_half_of_the_extended_domain() {
+        let mut blob_roots = compute_roots_of_unity(FIELD_ELEMENTS_PER_BLOB);
+        reverse_bit_order(&mut blob_roots);
+        assert_eq!(
+            &roots_of_unity_brp()[..FIELD_ELEMENTS_PER_BLOB],
+            &blob_roots[..]
+        );
+    }
+
+    #[test]
+    fn cosets_match_the_evaluations_of_the_extended_blob() {
+        // The blob of the polynomial `f(x) = x` contains the points of the first half of the
+        // cells, so its extended blob contains the points of all of the cells.
+        let points: Vec<Scalar> = CellIndex::all()
+            .flat_map(|cell_index| coset_for_cell(cell_index).to_vec())
+            .collect();
+        let blob: Box<[u8; BYTES_PER_BLOB]> =
+            serialize_scalars_to_bytes(&points[..FIELD_ELEMENTS_PER_EXT_BLOB / 2])
+                .into_boxed_slice()
+                .try_into()
+                .unwrap();
+
+        let ctx = DASContext::default();
+        assert_eq!(ctx.compute_extended_blob_scalars(&blob).unwrap(), points);
+    }
+
+    #[test]
+    fn coset_shift_generates_the_coset() {
+        let subgroup = Domain::get_or_init(FIELD_ELEMENTS_PER_CELL).roots.to_vec();
+        for cell_index in [0u64, 1, 63, 64, 127].map(|index| CellIndex::try_from(index).unwrap()) {
+            let shift = coset_shift_for_cell(cell_index);
+            let mut coset: Vec<Scalar> = subgroup.iter().map(|root| shift * root).collect();
+            reverse_bit_order(&mut coset);
+            assert_eq!(&coset[..], &coset_for_cell(cell_index)[..]);
+        }
+    }
+
+    #[test]
+    fn cell_for_point_is_the_inverse_of_coset_for_cell() {
+        for cell_index in CellIndex::all() {
+            for (position, point) in coset_for_cell(cell_index).iter().enumerate() {
+                assert_eq!(cell_for_point(point), Some((cell_index, position)));
+            }
+        }
+
+        assert_eq!(cell_for_point(&Scalar::ZERO), None);
+        assert_eq!(cell_for_point(&Scalar::from(2)), None);
+    }
+}
@@ -0,0 +1,168 @@
+//! Helpers for computing which columns a node custodies, as specified in the
+//! `das-core` section of the consensus specs.
+//!
+//! Every client needs the same deterministic assignment of columns to nodes, so
+//! it lives next to the cryptography that operates on those columns.
+
+use crate::{
+    constants::{CELLS_PER_EXT_BLOB, NUMBER_OF_CUSTODY_GROUPS},
+    errors::CustodyError,
+    CellIndex,
+};
+use sha2::{Digest, Sha256};
+
+/// NodeId is the 256-bit identifier of a node in the discovery protocol.
+///
+/// Note: The bytes are the big-endian encoding of the `uint256` that the specs
+/// call `NodeID`, which is how discv5 represents a node id.
+pub type NodeId = [u8; 32];
+
+/// CustodyIndex identifies a custody group, which is the unit that nodes custody
+/// and advertise in their metadata.
+pub type CustodyIndex = u64;
+
+/// The number of columns in each custody group.
+const COLUMNS_PER_GROUP: usize = CELLS_PER_EXT_BLOB / NUMBER_OF_CUSTODY_GROUPS;
+
+/// Returns the custody groups of a node, sorted in ascending order.
+///
+/// This is `get_custody_groups` from the specs.
+pub fn get_custody_groups(
+    node_id: &NodeId,
+    custody_group_count: u64,
+) -> Result<Vec<CustodyIndex>, CustodyError> {
+    if custody_group_count > NUMBER_OF_CUSTODY_GROUPS as u64 {
+        return Err(CustodyError::CustodyGroupCountTooLarge {
+            custody_group_count,
+            max_custody_group_count: NUMBER_OF_CUSTODY_GROUPS as u64,
+        });
+    }
+
+    // Skip the computation if the node custodies all of the groups
+    if custody_group_count == NUMBER_OF_CUSTODY_GROUPS as u64 {
+        return Ok((0..NUMBER_OF_CUSTODY_GROUPS as u64).collect());
+    }
+
+    let mut current_id = *node_id;
+    let mut custody_groups = Vec::with_capacity(custody_group_count as usize);
+    while (custody_groups.len() as u64) < custody_group_count {
+        let custody_group = custody_group_for_id(&current_id);
+        if !custody_groups.contains(&custody_group) {
+            custody_groups.push(custody_group);
+        }
+        increment_wrapping(&mut current_id);
+    }
+
+    custody_groups.sort_unstable();
+    Ok(custody_groups)
+}
+
+/// Returns the columns that belong to a custody group, sorted in ascending order.
+///
+/// This is `compute_columns_for_custody_group` from the specs.
+pub fn compute_columns_for_custody_group(
+    custody_group: CustodyIndex,
+) -> Result<Vec<CellIndex>, CustodyError> {
+    if custody_group >= NUMBER_OF_CUSTODY_GROUPS as u64 {
+        return Err(CustodyError::CustodyGroupOutOfRange {
+            custody_group,
+            number_of_custody_groups: NUMBER_OF_CUSTODY_GROUPS as u64,
+        });
+    }
+
+    // The largest column is `CELLS_PER_EXT_BLOB - NUMBER_OF_CUSTODY_GROUPS + custody_group`
+    Ok((0..COLUMNS_PER_GROUP as u64)
+        .map(|i| CellIndex::new_unchecked(NUMBER_OF_CUSTODY_GROUPS as u64 * i + custody_group))
+        .collect())
+}
+
+/// Returns the columns that a node custodies, sorted in ascending order.
+///
+/// These are the columns of all of the groups returned by `get_custody_groups`.
+pub fn get_custody_columns(
+    node_id: &NodeId,
+    custody_group_count: u64,
+) -> Result<Vec<CellIndex>, CustodyError> {
+    let mut columns = Vec::with_capacity(custody_group_count as usize * COLUMNS_PER_GROUP);
+    for custody_group in get_custody_groups(node_id, custody_group_count)? {
+        columns.extend(compute_columns_for_custody_group(custody_group)?);
+    }
+
+    columns.sort_unstable();
+    Ok(columns)
+}
+
+/// Hashes the little-endian encoding of the id and reduces the first 8 bytes of
+/// the digest to a custody group.
+fn custody_group_for_id(id: &NodeId) -> CustodyIndex {
+    let mut id_le = *id;
+    id_le.reverse();
+
+    let digest = Sha256::digest(id_le);
+    let prefix: [u8; 8] = digest[0..8].try_into().expect("digest is 32 bytes");
+    u64::from_le_bytes(prefix) % NUMBER_OF_CUSTODY_GROUPS as u64
+}
+
+/// Adds one to the big-endian id, wrapping around to zero after `2^256 - 1`.
+fn increment_wrapping(id: &mut NodeId) {
+    for byte in id.iter_mut().rev() {
+        let (incremented, overflowed) = byte.overflowing_add(1);
+        *byte = incremented;
+        if !overflowed {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custody_groups_match_the_specs() {
+        let zero = [0u8; 32];
+        assert_eq!(get_custody_groups(&zero, 0).unwrap(), Vec::<u64>::new());
+        assert_eq!(get_custody_groups(&zero, 1).unwrap(), vec![102]);
+        assert_eq!(get_custody_groups(&zero, 4).unwrap(), vec![1, 17, 87, 102]);
+        assert_eq!(
+            get_custody_groups(&[1u8; 32], 8).unwrap(),
+            vec![25, 36, 44, 60, 80, 92, 114, 127]
+        );
+
+        // The id wraps around to zero, so this overlaps with the groups of the zero id
+        assert_eq!(
+            get_custody_groups(&[0xff; 32], 4).unwrap(),
+            vec![1, 47, 87, 102]
+        );
+
+        let all_groups = get_custody_groups(&zero, NUMBER_OF_CUSTODY_GROUPS as u64).unwrap();
+        assert_eq!(
+            all_groups,
+            (0..NUMBER_OF_CUSTODY_GROUPS as u64).collect::<Vec<_>>()
+        );
+
+        assert!(matches!(
+            get_custody_groups(&zero, NUMBER_OF_CUSTODY_GROUPS as u64 + 1),
+            Err(CustodyError::CustodyGroupCountTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn custody_columns_cover_every_column_once() {
+        let node_id = [7u8; 32];
+        let columns = get_custody_columns(&node_id, NUMBER_OF_CUSTODY_GROUPS as u64).unwrap();
+        assert_eq!(columns, CellIndex::all().collect::<Vec<_>>());
+
+        let groups = get_custody_groups(&node_id, 4).unwrap();
+        let columns = get_custody_columns(&node_id, 4).unwrap();
+        assert_eq!(columns.len(), 4 * COLUMNS_PER_GROUP);
+        for column in columns {
+            assert!(groups.contains(&(column.as_u64() % NUMBER_OF_CUSTODY_GROUPS as u64)));
+        }
+
+        assert!(matches!(
+            compute_columns_for_custody_group(NUMBER_OF_CUSTODY_GROUPS as u64),
+            Err(CustodyError::CustodyGroupOutOfRange { .. })
+        ));
+    }
+}
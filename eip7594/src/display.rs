@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// The number of bytes at the start and at the end of the bytes that `Debug` shows.
+const NUM_DEBUG_BYTES: usize = 4;
+
+/// Hex formats the bytes of a commitment, proof, cell or blob, so that they can be logged.
+///
+/// `Display` shows all of the bytes as a `0x` prefixed hex string, and `Debug` only shows the
+/// first and last few bytes, along with the number of bytes.
+///
+/// Note: The public byte types are type aliases for byte arrays, which is why they are
+/// wrapped in this type instead of implementing `Display` themselves.
+///
+/// ```
+/// use rust_eth_kzg::{Hex, KZGCommitment};
+///
+/// let commitment: KZGCommitment = [0xab; 48];
+/// assert_eq!(format!("{:?}", Hex(&commitment)), "0xabababab..abababab (48 bytes)");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Hex<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0;
+        if bytes.len() <= 2 * NUM_DEBUG_BYTES {
+            return fmt::Display::fmt(self, f);
+        }
+
+        write!(
+            f,
+            "0x{}..{} ({} bytes)",
+            hex::encode(&bytes[..NUM_DEBUG_BYTES]),
+            hex::encode(&bytes[bytes.len() - NUM_DEBUG_BYTES..]),
+            bytes.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_shows_all_bytes() {
+        assert_eq!(Hex(&[]).to_string(), "0x");
+        assert_eq!(Hex(&[0x01, 0xef]).to_string(), "0x01ef");
+
+        let bytes: Vec<u8> = (0..48).collect();
+        assert_eq!(
+            Hex(&bytes).to_string(),
+            format!("0x{}", hex::encode(&bytes))
+        );
+    }
+
+    #[test]
+    fn debug_truncates_long_bytes() {
+        assert_eq!(format!("{:?}", Hex(&[0x01, 0xef])), "0x01ef");
+        assert_eq!(
+            format!("{:?}", Hex(&[0x11; 2 * NUM_DEBUG_BYTES])),
+            "0x1111111111111111"
+        );
+
+        let bytes: Vec<u8> = (0..48).collect();
+        assert_eq!(
+            format!("{:?}", Hex(&bytes)),
+            "0x00010203..2c2d2e2f (48 bytes)"
+        );
+    }
+}
@@ -1,16 +1,105 @@
-use crate::CellIndex;
+use crate::Hex;
 use erasure_codes::errors::RSError;
 
-/// Errors that can occur either during proving, verification or serialization.
-#[derive(Debug)]
+/// Errors that can occur either during proving, verification, serialization or
+/// while loading the trusted setup.
+#[derive(Debug, thiserror::Error)]
 pub enum Error {
-    Prover(ProverError),
-    Verifier(VerifierError),
-    Recovery(RecoveryError),
-    Serialization(SerializationError),
+    #[error("prover error: {0}")]
+    Prover(#[from] ProverError),
+    #[error("verifier error: {0}")]
+    Verifier(#[from] VerifierError),
+    #[error("recovery error: {0}")]
+    Recovery(#[from] RecoveryError),
+    #[error("serialization error: {0}")]
+    Serialization(#[from] SerializationError),
+    #[error("trusted setup error: {0}")]
+    TrustedSetup(#[from] TrustedSetupError),
+}
+
+/// The category of an error, which decides how the language bindings report it.
+///
+/// Every binding converts errors through `Error::kind`, instead of matching on the error
+/// variants itself, so that all of them classify a new variant in the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// A proof failed verification.
+    InvalidProof,
+    /// An input, or the number of inputs, has the wrong length.
+    BadLength,
+    /// An input could not be deserialized, for example because it does not encode a valid
+    /// field element or point.
+    BadEncoding,
+    /// An input is well formed but not valid for the function, for example because a cell
+    /// index is out of range or is repeated, or because the context was created without the
+    /// prover or verifier that the function needs.
+    BadArgument,
+    /// The trusted setup is malformed, or contains an invalid point.
+    BadTrustedSetup,
+    /// An error that was not caused by the inputs.
+    Internal,
+}
+
+impl ErrorKind {
+    /// Returns a short name for the kind, which does not change between releases.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidProof => "invalid_proof",
+            Self::BadLength => "bad_length",
+            Self::BadEncoding => "bad_encoding",
+            Self::BadArgument => "bad_argument",
+            Self::BadTrustedSetup => "bad_trusted_setup",
+            Self::Internal => "internal",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 impl Error {
+    /// Returns the category of the error.
+    ///
+    /// This is the single table that the language bindings use to report errors.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Verifier(err) => match err {
+                VerifierError::InvalidProof | VerifierError::FK20(_) => ErrorKind::InvalidProof,
+                VerifierError::BatchVerificationInputsMustHaveSameLength { .. }
+                | VerifierError::CellHasInvalidLength { .. } => ErrorKind::BadLength,
+                VerifierError::CellIndexOutOfRange { .. }
+                | VerifierError::InvalidCommitmentIndex { .. }
+                | VerifierError::VerifierNotAvailable => ErrorKind::BadArgument,
+                VerifierError::PolynomialHasInvalidLength { .. } => ErrorKind::Internal,
+            },
+            Self::Prover(
+                ProverError::CellIndexOutOfRange { .. }
+                | ProverError::FieldElementIndexOutOfRange { .. }
+                | ProverError::ProverNotAvailable,
+            ) => ErrorKind::BadArgument,
+            Self::Prover(ProverError::RecoveryFailure(err)) | Self::Recovery(err) => match err {
+                RecoveryError::NotEnoughCellsToReconstruct { .. }
+                | RecoveryError::NumCellIndicesNotEqualToNumCells { .. }
+                | RecoveryError::TooManyCellsReceived { .. } => ErrorKind::BadLength,
+                RecoveryError::CellIndexOutOfRange { .. } | RecoveryError::CellIndicesNotUnique => {
+                    ErrorKind::BadArgument
+                }
+                RecoveryError::ReedSolomon(_) => ErrorKind::Internal,
+            },
+            Self::Serialization(err) => match err {
+                SerializationError::ScalarHasInvalidLength { .. }
+                | SerializationError::BlobHasInvalidLength { .. }
+                | SerializationError::G1PointHasInvalidLength { .. } => ErrorKind::BadLength,
+                SerializationError::CouldNotDeserializeScalar { .. }
+                | SerializationError::CouldNotDeserializeG1Point { .. } => ErrorKind::BadEncoding,
+            },
+            Self::TrustedSetup(_) => ErrorKind::BadTrustedSetup,
+        }
+    }
+
     /// Returns true if the reason for the error was due to a proof failing verification.
     ///
     /// Note: This distinction in practice, is not meaningful for the caller and is mainly
@@ -24,106 +113,190 @@ impl Error {
     }
 }
 
-impl From<ProverError> for Error {
-    fn from(value: ProverError) -> Self {
-        Self::Prover(value)
-    }
-}
-impl From<VerifierError> for Error {
-    fn from(value: VerifierError) -> Self {
-        Self::Verifier(value)
-    }
-}
-impl From<SerializationError> for Error {
-    fn from(value: SerializationError) -> Self {
-        Self::Serialization(value)
-    }
-}
-impl From<RecoveryError> for Error {
-    fn from(value: RecoveryError) -> Self {
-        Self::Recovery(value)
+/// Converts the result of a verification function into a boolean.
+///
+/// A proof that failed verification is not an error from the callers perspective,
+/// so it is mapped to `Ok(false)`. Every other error is returned as is.
+///
+/// This is the mapping that all of the language bindings use, so that they agree
+/// on which errors are reported as an invalid proof.
+pub fn verification_result_to_bool(verification_result: Result<(), Error>) -> Result<bool, Error> {
+    match verification_result {
+        Ok(()) => Ok(true),
+        Err(err) if err.invalid_proof() => Ok(false),
+        Err(err) => Err(err),
     }
 }
 
 /// Errors that can occur while calling a method in the Prover API
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum ProverError {
-    RecoveryFailure(RecoveryError),
-}
-
-impl From<RecoveryError> for ProverError {
-    fn from(value: RecoveryError) -> Self {
-        Self::RecoveryFailure(value)
-    }
+    #[error("{0}")]
+    RecoveryFailure(#[from] RecoveryError),
+    #[error("cell index {cell_index} is out of range, there are only {max_number_of_cells} cells")]
+    CellIndexOutOfRange {
+        cell_index: u64,
+        max_number_of_cells: u64,
+    },
+    #[error("field element index {index} is out of range, there are only {max_number_of_field_elements} field elements in a blob")]
+    FieldElementIndexOutOfRange {
+        index: usize,
+        max_number_of_field_elements: usize,
+    },
+    #[error("the context was created without a prover")]
+    ProverNotAvailable,
 }
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 /// Errors that can occur while calling the recovery procedure
 pub enum RecoveryError {
+    #[error("received {num_cells_received} cells, but at least {min_cells_needed} are needed to reconstruct the blob")]
     NotEnoughCellsToReconstruct {
         num_cells_received: usize,
         min_cells_needed: usize,
     },
+    #[error(
+        "received {num_cell_indices} cell indices and {num_cells} cells, expected them to be equal"
+    )]
     NumCellIndicesNotEqualToNumCells {
         num_cell_indices: usize,
         num_cells: usize,
     },
+    #[error("received {num_cells_received} cells, but at most {max_cells_needed} are needed")]
     TooManyCellsReceived {
         num_cells_received: usize,
         max_cells_needed: usize,
     },
+    #[error("cell index {cell_index} is out of range, there are only {max_number_of_cells} cells")]
     CellIndexOutOfRange {
-        cell_index: CellIndex,
+        cell_index: u64,
         max_number_of_cells: u64,
     },
+    #[error("cell indices are not unique")]
     CellIndicesNotUnique,
-    ReedSolomon(RSError),
-}
-
-impl From<RSError> for RecoveryError {
-    fn from(value: RSError) -> Self {
-        Self::ReedSolomon(value)
-    }
+    #[error("reed-solomon decoding failed: {0}")]
+    ReedSolomon(#[from] RSError),
 }
 
 /// Errors that can occur while calling a method in the Verifier API
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum VerifierError {
+    #[error("cell index {cell_index} is out of range, there are only {max_number_of_cells} cells")]
     CellIndexOutOfRange {
-        cell_index: CellIndex,
+        cell_index: u64,
         max_number_of_cells: u64,
     },
+    #[error("commitment index {commitment_index} is out of range, there are only {max_number_of_commitments} commitments")]
     InvalidCommitmentIndex {
         commitment_index: u64,
         max_number_of_commitments: u64,
     },
+    #[error("invalid proof")]
     InvalidProof,
+    #[error("batch verification inputs must have the same length: commitment indices = {commitment_indices_len}, cell indices = {cell_indices_len}, cells = {cells_len}, proofs = {proofs_len}")]
     BatchVerificationInputsMustHaveSameLength {
         commitment_indices_len: usize,
         cell_indices_len: usize,
         cells_len: usize,
         proofs_len: usize,
     },
-    FK20(kzg_multi_open::VerifierError),
+    #[error("{0}")]
+    FK20(#[from] kzg_multi_open::VerifierError),
+    #[error(
+        "cell has {num_field_elements} field elements, expected {expected_num_field_elements}"
+    )]
+    CellHasInvalidLength {
+        num_field_elements: usize,
+        expected_num_field_elements: usize,
+    },
+    #[error(
+        "polynomial has {num_coefficients} coefficients, expected {expected_num_coefficients}"
+    )]
     PolynomialHasInvalidLength {
         num_coefficients: usize,
         expected_num_coefficients: usize,
     },
+    #[error("the context was created without a verifier")]
+    VerifierNotAvailable,
+}
+
+/// The error returned when converting an integer that is not less than `CELLS_PER_EXT_BLOB`
+/// into a `CellIndex`.
+///
+/// It converts into the `CellIndexOutOfRange` variant of the prover, verifier and recovery
+/// errors, so that callers can report it as part of the operation that received the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("cell index {cell_index} is out of range, there are only {max_number_of_cells} cells")]
+pub struct CellIndexOutOfRange {
+    pub cell_index: u64,
+    pub max_number_of_cells: u64,
 }
 
-impl From<kzg_multi_open::VerifierError> for VerifierError {
-    fn from(value: kzg_multi_open::VerifierError) -> Self {
-        Self::FK20(value)
+impl From<CellIndexOutOfRange> for ProverError {
+    fn from(err: CellIndexOutOfRange) -> Self {
+        Self::CellIndexOutOfRange {
+            cell_index: err.cell_index,
+            max_number_of_cells: err.max_number_of_cells,
+        }
+    }
+}
+
+impl From<CellIndexOutOfRange> for RecoveryError {
+    fn from(err: CellIndexOutOfRange) -> Self {
+        Self::CellIndexOutOfRange {
+            cell_index: err.cell_index,
+            max_number_of_cells: err.max_number_of_cells,
+        }
+    }
+}
+
+impl From<CellIndexOutOfRange> for VerifierError {
+    fn from(err: CellIndexOutOfRange) -> Self {
+        Self::CellIndexOutOfRange {
+            cell_index: err.cell_index,
+            max_number_of_cells: err.max_number_of_cells,
+        }
     }
 }
 
 /// Errors that can occur during deserialization of untrusted input from the public API
 /// or the trusted setup.
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum SerializationError {
+    #[error("could not deserialize scalar: {}", Hex(bytes))]
     CouldNotDeserializeScalar { bytes: Vec<u8> },
+    #[error("could not deserialize G1 point: {}", Hex(bytes))]
     CouldNotDeserializeG1Point { bytes: Vec<u8> },
+    #[error("scalar bytes have an invalid length of {length}")]
     ScalarHasInvalidLength { bytes: Vec<u8>, length: usize },
+    #[error("blob has an invalid length of {length}")]
     BlobHasInvalidLength { bytes: Vec<u8>, length: usize },
+    #[error("G1 point has an invalid length of {length}")]
     G1PointHasInvalidLength { bytes: Vec<u8>, length: usize },
 }
+
+/// Errors that can occur while computing the custody groups and columns of a node.
+#[derive(Debug, thiserror::Error)]
+pub enum CustodyError {
+    #[error("custody group count {custody_group_count} is too large, there are only {max_custody_group_count} custody groups")]
+    CustodyGroupCountTooLarge {
+        custody_group_count: u64,
+        max_custody_group_count: u64,
+    },
+    #[error("custody group {custody_group} is out of range, there are only {number_of_custody_groups} custody groups")]
+    CustodyGroupOutOfRange {
+        custody_group: u64,
+        number_of_custody_groups: u64,
+    },
+}
+
+/// Errors that can occur while parsing and validating a trusted setup.
+#[derive(Debug, thiserror::Error)]
+pub enum TrustedSetupError {
+    #[error("could not parse json into a trusted setup: {reason}")]
+    MalformedJson { reason: String },
+    #[error("trusted setup contains an invalid G1 point: {point}")]
+    InvalidG1Point { point: String },
+    #[error("trusted setup contains an invalid G2 point: {point}")]
+    InvalidG2Point { point: String },
+}
@@ -1,23 +1,60 @@
-#[cfg(all(feature = "singlethreaded", feature = "multithreaded"))]
-compile_error!("feature_a and feature_b cannot be enabled simultaneously");
+#[cfg(all(
+    feature = "singlethreaded",
+    any(feature = "multithreaded", feature = "global-threadpool")
+))]
+compile_error!(
+    "the `singlethreaded` feature cannot be enabled together with `multithreaded` or `global-threadpool`"
+);
 
+mod cell_index;
 pub mod constants;
+pub mod cosets;
+pub mod das;
+mod display;
 mod errors;
 mod prover;
 mod recovery;
-mod serialization;
+pub mod serialization;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 mod trusted_setup;
 mod verifier;
 #[macro_use]
 pub(crate) mod macros;
 
 pub use bls12_381::fixed_base_msm::UsePrecomp;
+/// The types of the points and field elements, for the functions that take them instead of
+/// their serialized bytes.
+pub use bls12_381::{G1Point, Scalar};
+/// Arkworks is a backend that uses arkworks instead of blst. It is slower than the `BlstBackend`.
+#[cfg(feature = "arkworks")]
+pub use kzg_multi_open::backend::Arkworks;
+/// Backend is the implementation of the curve and the field that the contexts are generic over.
+/// The `BlstBackend` is used by default.
+pub use kzg_multi_open::backend::{Backend, BlstBackend};
 // Exported types
 //
-pub use errors::Error;
+/// CellIndex identifies one of the cells in an extended blob.
+pub use cell_index::CellIndex;
+/// Hex formats the public byte types as hex strings, for logs and error messages.
+pub use display::Hex;
+pub use errors::{
+    verification_result_to_bool, CellIndexOutOfRange, CustodyError, Error, ErrorKind, ProverError,
+    RecoveryError, SerializationError, TrustedSetupError, VerifierError,
+};
+/// BlobCommitter computes the commitment to a blob from chunks of its bytes.
+pub use prover::BlobCommitter;
+/// RecoveryAccumulator collects cells as they arrive, until there are enough of them to recover the blob.
+pub use recovery::RecoveryAccumulator;
 /// TrustedSetup contains the Structured Reference String(SRS)
 /// needed to make and verify proofs.
-pub use trusted_setup::TrustedSetup;
+pub use trusted_setup::{TrustedSetup, EMBEDDED_TRUSTED_SETUP_ID};
+/// AggregateVerifier collects independent batches of cells, so that they can be verified with a single multi-pairing.
+pub use verifier::AggregateVerifier;
+/// VerifierContext contains the precomputed data needed to verify proofs.
+///
+/// This can be used on its own, when a full `DASContext` is not needed.
+pub use verifier::VerifierContext;
 /// BlobRef denotes a references to an opaque Blob.
 ///
 /// Note: This library never returns a Blob, which is why we
@@ -29,6 +66,10 @@ pub type BlobRef<'a> = &'a [u8; BYTES_PER_BLOB];
 /// purported KZGProof or a purported KZGCommitment.
 pub type Bytes48Ref<'a> = &'a [u8; 48];
 
+/// Bytes32Ref denotes a reference to an untrusted field element,
+/// such as one of the field elements of a blob.
+pub type Bytes32Ref<'a> = &'a [u8; BYTES_PER_FIELD_ELEMENT];
+
 /// Cell contains a group of evaluations on a coset that one would like to
 /// make and verify opening proofs about.
 ///
@@ -44,23 +85,27 @@ pub type CellRef<'a> = &'a [u8; BYTES_PER_CELL];
 /// KZGProof denotes a 48 byte commitment to a polynomial
 /// that one can use to prove that a polynomial f(x) was
 /// correctly evaluated on a coset `H` and returned a set of points.
-pub type KZGProof = [u8; BYTES_PER_COMMITMENT];
+pub type KZGProof = [u8; BYTES_PER_PROOF];
 
 /// KZGCommitment denotes a 48 byte commitment to a polynomial f(x)
 /// that we would like to make and verify opening proofs about.
 pub type KZGCommitment = [u8; BYTES_PER_COMMITMENT];
 
-/// CellIndex is reference to the coset/set of points that were used to create that Cell,
-/// on a particular polynomial, f(x).
+/// ExtendedBlob contains the evaluations of a blob's polynomial over the whole extended
+/// domain, which are the field elements of all of the cells, one cell after the other.
 ///
-/// Note: Since the verifier and prover both know what cosets will be used
-/// to evaluate the polynomials being used in opening proofs, the protocol
-/// only requires an index to reference them.
-pub type CellIndex = kzg_multi_open::CosetIndex;
+/// Note: These are heap allocated.
+pub type ExtendedBlob = Box<[u8; BYTES_PER_EXT_BLOB]>;
+
+/// CellsAndKZGProofs contains all of the cells in an extended blob, along with
+/// the KZG proof for each cell.
+pub type CellsAndKZGProofs = ([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]);
 
-use constants::{BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_COMMITMENT};
+use constants::{
+    BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_COMMITMENT, BYTES_PER_EXT_BLOB,
+    BYTES_PER_FIELD_ELEMENT, BYTES_PER_PROOF, CELLS_PER_EXT_BLOB,
+};
 use prover::ProverContext;
-use verifier::VerifierContext;
 
 #[cfg(feature = "multithreaded")]
 use rayon::ThreadPool;
@@ -96,13 +141,40 @@ impl From<ThreadCount> for usize {
     }
 }
 
+/// ContextMode indicates which parts of a `DASContext` should be created.
+///
+/// The prover is much more expensive to create and store than the verifier, so callers
+/// that only verify proofs, or only create them, can skip the part that they do not need.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ContextMode {
+    /// Creates both the prover and the verifier.
+    #[default]
+    Both,
+    /// Creates only the prover. Verification methods return an error.
+    ProverOnly,
+    /// Creates only the verifier. Proving and recovery methods return an error.
+    VerifierOnly,
+}
+
+impl ContextMode {
+    const fn has_prover(self) -> bool {
+        matches!(self, Self::Both | Self::ProverOnly)
+    }
+
+    const fn has_verifier(self) -> bool {
+        matches!(self, Self::Both | Self::VerifierOnly)
+    }
+}
+
 /// The context that will be used to create and verify opening proofs.
 #[derive(Debug)]
-pub struct DASContext {
+pub struct DASContext<B: Backend = BlstBackend> {
     #[cfg(feature = "multithreaded")]
     thread_pool: Arc<ThreadPool>,
-    pub prover_ctx: ProverContext,
-    pub verifier_ctx: VerifierContext,
+    /// The prover, or `None` if the context was created without one.
+    prover_ctx: Option<ProverContext<B>>,
+    /// The verifier, or `None` if the context was created without one.
+    verifier_ctx: Option<VerifierContext<B>>,
 }
 
 #[cfg(feature = "multithreaded")]
@@ -129,7 +201,57 @@ impl DASContext {
         num_threads: ThreadCount,
         use_precomp: UsePrecomp,
     ) -> Self {
-        #[cfg(feature = "multithreaded")]
+        Self::with_threads_and_mode(trusted_setup, num_threads, use_precomp, ContextMode::Both)
+    }
+
+    /// Creates a context with only the parts that are needed for `mode`.
+    ///
+    /// `use_precomp` is ignored if the context is created without a prover.
+    #[cfg(feature = "multithreaded")]
+    pub fn with_threads_and_mode(
+        trusted_setup: &TrustedSetup,
+        num_threads: ThreadCount,
+        use_precomp: UsePrecomp,
+        mode: ContextMode,
+    ) -> Self {
+        Self::with_backend(trusted_setup, num_threads, use_precomp, mode)
+    }
+
+    #[cfg(not(feature = "multithreaded"))]
+    pub fn new(
+        trusted_setup: &TrustedSetup,
+        // This parameter indicates whether we should allocate memory
+        // in order to speed up proof creation. Heuristics show that
+        // if pre-computations are desired, one should set the
+        // width value to `8` for optimal storage and performance tradeoffs.
+        use_precomp: UsePrecomp,
+    ) -> Self {
+        Self::with_mode(trusted_setup, use_precomp, ContextMode::Both)
+    }
+
+    /// Creates a context with only the parts that are needed for `mode`.
+    ///
+    /// `use_precomp` is ignored if the context is created without a prover.
+    #[cfg(not(feature = "multithreaded"))]
+    pub fn with_mode(
+        trusted_setup: &TrustedSetup,
+        use_precomp: UsePrecomp,
+        mode: ContextMode,
+    ) -> Self {
+        Self::with_backend(trusted_setup, use_precomp, mode)
+    }
+}
+
+impl<B: Backend> DASContext<B> {
+    /// Creates a context that uses the backend `B`, with only the parts that are needed
+    /// for `mode`.
+    #[cfg(feature = "multithreaded")]
+    pub fn with_backend(
+        trusted_setup: &TrustedSetup,
+        num_threads: ThreadCount,
+        use_precomp: UsePrecomp,
+        mode: ContextMode,
+    ) -> Self {
         let thread_pool = std::sync::Arc::new(
             rayon::ThreadPoolBuilder::new()
                 .num_threads(num_threads.into())
@@ -138,33 +260,95 @@ impl DASContext {
         );
 
         Self {
-            #[cfg(feature = "multithreaded")]
             thread_pool,
-            prover_ctx: ProverContext::new(trusted_setup, use_precomp),
-            verifier_ctx: VerifierContext::new(trusted_setup),
+            prover_ctx: mode
+                .has_prover()
+                .then(|| ProverContext::with_backend(trusted_setup, use_precomp)),
+            verifier_ctx: mode
+                .has_verifier()
+                .then(|| VerifierContext::with_backend(trusted_setup)),
         }
     }
 
+    /// Creates a context that uses the backend `B`, with only the parts that are needed
+    /// for `mode`.
     #[cfg(not(feature = "multithreaded"))]
-    pub fn new(
+    pub fn with_backend(
         trusted_setup: &TrustedSetup,
-        // This parameter indicates whether we should allocate memory
-        // in order to speed up proof creation. Heuristics show that
-        // if pre-computations are desired, one should set the
-        // width value to `8` for optimal storage and performance tradeoffs.
         use_precomp: UsePrecomp,
+        mode: ContextMode,
     ) -> Self {
-        DASContext {
-            prover_ctx: ProverContext::new(trusted_setup, use_precomp),
-            verifier_ctx: VerifierContext::new(trusted_setup),
+        Self {
+            prover_ctx: mode
+                .has_prover()
+                .then(|| ProverContext::with_backend(trusted_setup, use_precomp)),
+            verifier_ctx: mode
+                .has_verifier()
+                .then(|| VerifierContext::with_backend(trusted_setup)),
+        }
+    }
+
+    /// Runs `task` on the thread pool of this context, without waiting for it to finish.
+    ///
+    /// This allows callers, such as the language bindings, to run expensive operations
+    /// on the context without blocking the calling thread.
+    #[cfg(feature = "multithreaded")]
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.thread_pool.spawn(task);
+    }
+
+    /// Returns the number of threads in the thread pool of this context.
+    #[cfg_attr(not(feature = "multithreaded"), allow(clippy::missing_const_for_fn))]
+    pub fn num_threads(&self) -> usize {
+        #[cfg(feature = "multithreaded")]
+        let num_threads = self.thread_pool.current_num_threads();
+        #[cfg(not(feature = "multithreaded"))]
+        let num_threads = 1;
+        num_threads
+    }
+
+    /// Returns the prover.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context was created without a prover, see `try_prover_ctx`.
+    pub const fn prover_ctx(&self) -> &ProverContext<B> {
+        match &self.prover_ctx {
+            Some(prover_ctx) => prover_ctx,
+            None => panic!(
+                "the context was created with ContextMode::VerifierOnly, so it has no prover"
+            ),
+        }
+    }
+
+    /// Returns the verifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context was created without a verifier, see `try_verifier_ctx`.
+    pub const fn verifier_ctx(&self) -> &VerifierContext<B> {
+        match &self.verifier_ctx {
+            Some(verifier_ctx) => verifier_ctx,
+            None => panic!(
+                "the context was created with ContextMode::ProverOnly, so it has no verifier"
+            ),
         }
     }
 
-    pub const fn prover_ctx(&self) -> &ProverContext {
-        &self.prover_ctx
+    /// Returns the prover, or an error if the context was created without one.
+    pub fn try_prover_ctx(&self) -> Result<&ProverContext<B>, ProverError> {
+        self.prover_ctx
+            .as_ref()
+            .ok_or(ProverError::ProverNotAvailable)
     }
 
-    pub const fn verifier_ctx(&self) -> &VerifierContext {
-        &self.verifier_ctx
+    /// Returns the verifier, or an error if the context was created without one.
+    pub fn try_verifier_ctx(&self) -> Result<&VerifierContext<B>, VerifierError> {
+        self.verifier_ctx
+            .as_ref()
+            .ok_or(VerifierError::VerifierNotAvailable)
     }
 }
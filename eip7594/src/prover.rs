@@ -1,32 +1,41 @@
-use bls12_381::fixed_base_msm::UsePrecomp;
+use bls12_381::{
+    fixed_base_msm::UsePrecomp,
+    group::{Curve, Group},
+};
 use erasure_codes::ReedSolomon;
 use kzg_multi_open::{
+    backend::{Backend, BlstBackend},
     commit_key::CommitKey,
     {Prover, ProverInput},
 };
+use maybe_rayon::prelude::*;
 
 use crate::{
     constants::{
-        CELLS_PER_EXT_BLOB, EXPANSION_FACTOR, FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL,
-        FIELD_ELEMENTS_PER_EXT_BLOB,
+        BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB, EXPANSION_FACTOR,
+        FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL, FIELD_ELEMENTS_PER_EXT_BLOB,
     },
-    errors::Error,
+    errors::{Error, ProverError, SerializationError},
     recovery::recover_polynomial_coeff,
     serialization::{
-        deserialize_blob_to_scalars, serialize_cells, serialize_cells_and_proofs,
-        serialize_g1_compressed,
+        deserialize_blob, deserialize_g1, deserialize_scalar, serialize_cells,
+        serialize_cells_and_proofs, serialize_g1_compressed, serialize_scalars_to_bytes,
+        serialize_scalars_to_cell,
     },
     trusted_setup::TrustedSetup,
-    with_optional_threadpool, BlobRef, Cell, CellIndex, CellRef, DASContext, KZGCommitment,
-    KZGProof,
+    with_optional_threadpool, BlobRef, Bytes32Ref, Bytes48Ref, Cell, CellIndex, CellRef,
+    CellsAndKZGProofs, DASContext, ExtendedBlob, KZGCommitment, KZGProof,
 };
 
 /// Context object that is used to call functions in the prover API.
 /// This includes, computing the commitments, proofs and cells.
 #[derive(Debug)]
-pub struct ProverContext {
-    kzg_multipoint_prover: Prover,
-    rs: ReedSolomon,
+pub struct ProverContext<B: Backend = BlstBackend> {
+    kzg_multipoint_prover: Prover<B>,
+    rs: ReedSolomon<B::Scalar>,
+    /// The G1 points in lagrange form, in bit-reversed order, which are used to update commitments.
+    g1_lagrange: Vec<B::G1Point>,
+    use_precomp: UsePrecomp,
 }
 
 impl Default for ProverContext {
@@ -38,6 +47,13 @@ impl Default for ProverContext {
 
 impl ProverContext {
     pub fn new(trusted_setup: &TrustedSetup, use_precomp: UsePrecomp) -> Self {
+        Self::with_backend(trusted_setup, use_precomp)
+    }
+}
+
+impl<B: Backend> ProverContext<B> {
+    /// Creates a prover that uses the backend `B`.
+    pub fn with_backend(trusted_setup: &TrustedSetup, use_precomp: UsePrecomp) -> Self {
         let commit_key = CommitKey::from(trusted_setup);
 
         // The number of points that we will make an opening proof for,
@@ -67,22 +83,147 @@ impl ProverContext {
         Self {
             kzg_multipoint_prover,
             rs,
+            g1_lagrange: trusted_setup.g1_lagrange_points(),
+            use_precomp,
+        }
+    }
+
+    /// Returns whether pre-computations were used to speed up proof creation,
+    /// and the width of the pre-computed tables if so.
+    pub const fn use_precomp(&self) -> UsePrecomp {
+        self.use_precomp
+    }
+}
+
+/// Computes the KZG commitment to a blob from chunks of its bytes, so that callers that
+/// receive a blob in pieces do not need to hold all of it before committing to it.
+///
+/// The chunks can have any length, and a field element may be split across chunks.
+/// The commitment is the same as the one returned by `DASContext::blob_to_kzg_commitment`.
+///
+/// Note: A call to `update` that returns an error does not change the state, so the
+/// committer can still be used with the correct bytes afterwards.
+#[derive(Debug, Clone)]
+pub struct BlobCommitter<'a, B: Backend = BlstBackend> {
+    g1_lagrange: &'a [B::G1Point],
+    commitment: B::G1Projective,
+    /// The number of field elements that have been added to the commitment.
+    num_field_elements: usize,
+    /// The bytes of a field element that is split across chunks.
+    partial_field_element: [u8; BYTES_PER_FIELD_ELEMENT],
+    partial_len: usize,
+}
+
+impl<'a, B: Backend> BlobCommitter<'a, B> {
+    fn new(prover_ctx: &'a ProverContext<B>) -> Self {
+        Self {
+            g1_lagrange: &prover_ctx.g1_lagrange,
+            commitment: B::G1Projective::identity(),
+            num_field_elements: 0,
+            partial_field_element: [0; BYTES_PER_FIELD_ELEMENT],
+            partial_len: 0,
+        }
+    }
+
+    /// Returns the number of bytes of the blob that have been added.
+    pub const fn num_bytes(&self) -> usize {
+        self.num_field_elements * BYTES_PER_FIELD_ELEMENT + self.partial_len
+    }
+
+    /// Adds the next chunk of the blob to the commitment.
+    ///
+    /// Returns an error if the chunk contains a field element that is not canonical,
+    /// or if the chunk would make the blob longer than `BYTES_PER_BLOB`.
+    pub fn update(&mut self, chunk: &[u8]) -> Result<(), SerializationError> {
+        let num_bytes = self.num_bytes() + chunk.len();
+        if num_bytes > BYTES_PER_BLOB {
+            // Note: The previous chunks are not kept, so the error does not contain the blob bytes.
+            return Err(SerializationError::BlobHasInvalidLength {
+                bytes: Vec::new(),
+                length: num_bytes,
+            });
+        }
+
+        // Deserialize all of the field elements that the chunk completes, before changing any
+        // state, so that an invalid chunk leaves the committer as it was.
+        let mut field_element = self.partial_field_element;
+        let head_len = (BYTES_PER_FIELD_ELEMENT - self.partial_len) % BYTES_PER_FIELD_ELEMENT;
+        let (head, chunk) = chunk.split_at(head_len.min(chunk.len()));
+        field_element[self.partial_len..self.partial_len + head.len()].copy_from_slice(head);
+        let completes_partial =
+            self.partial_len > 0 && self.partial_len + head.len() == BYTES_PER_FIELD_ELEMENT;
+
+        let field_elements = chunk.chunks_exact(BYTES_PER_FIELD_ELEMENT);
+        let tail = field_elements.remainder();
+        let mut scalars = Vec::with_capacity(field_elements.len() + 1);
+        if completes_partial {
+            scalars.push(deserialize_scalar(&field_element)?);
+        }
+        for bytes32 in field_elements {
+            scalars.push(deserialize_scalar(bytes32)?);
+        }
+
+        // Add the field elements to the commitment, and keep the bytes of the incomplete one
+        self.add_scalars(&scalars);
+        if completes_partial || self.partial_len == 0 {
+            self.partial_field_element[..tail.len()].copy_from_slice(tail);
+            self.partial_len = tail.len();
+        } else {
+            self.partial_field_element = field_element;
+            self.partial_len += head.len();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the commitment to the blob.
+    ///
+    /// Returns an error if fewer than `BYTES_PER_BLOB` bytes have been added.
+    pub fn finalize(self) -> Result<KZGCommitment, SerializationError> {
+        let num_bytes = self.num_bytes();
+        if num_bytes != BYTES_PER_BLOB {
+            return Err(SerializationError::BlobHasInvalidLength {
+                bytes: Vec::new(),
+                length: num_bytes,
+            });
+        }
+
+        Ok(serialize_g1_compressed(&self.commitment.to_affine()))
+    }
+
+    fn add_scalars(&mut self, scalars: &[B::Scalar]) {
+        if scalars.is_empty() {
+            return;
         }
+
+        let start = self.num_field_elements;
+        let points = &self.g1_lagrange[start..start + scalars.len()];
+        self.commitment += B::g1_lincomb(points, scalars)
+            .expect("number of points is equal to the number of scalars");
+        self.num_field_elements += scalars.len();
     }
 }
 
-impl DASContext {
+impl<B: Backend> DASContext<B> {
+    /// Returns a `BlobCommitter`, which computes the KZG commitment to a blob from chunks of its bytes.
+    ///
+    /// Returns an error if the context was created without a prover.
+    pub fn blob_committer(&self) -> Result<BlobCommitter<'_, B>, Error> {
+        Ok(BlobCommitter::new(self.try_prover_ctx()?))
+    }
+
     /// Computes the KZG commitment to the polynomial represented by the blob.
     ///
     /// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/deneb/polynomial-commitments.md#blob_to_kzg_commitment
     pub fn blob_to_kzg_commitment(&self, blob: BlobRef) -> Result<KZGCommitment, Error> {
         with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
             // Deserialize the blob into scalars.
-            let scalars = deserialize_blob_to_scalars(blob)?;
+            let scalars = deserialize_blob(blob)?;
 
             // Compute commitment
-            let commitment = self
-                .prover_ctx
+            let commitment = prover_ctx
                 .kzg_multipoint_prover
                 .commit(ProverInput::Data(scalars));
 
@@ -91,6 +232,71 @@ impl DASContext {
         })
     }
 
+    /// Computes the KZG commitment for each of the given blobs.
+    ///
+    /// Note: When the `multithreaded` feature is enabled, the blobs are processed in parallel
+    /// using the context's thread pool.
+    pub fn blob_to_kzg_commitment_batch(
+        &self,
+        blobs: Vec<BlobRef>,
+    ) -> Result<Vec<KZGCommitment>, Error> {
+        with_optional_threadpool!(self, {
+            blobs
+                .maybe_into_par_iter()
+                .map(|blob| self.blob_to_kzg_commitment(blob))
+                .collect()
+        })
+    }
+
+    /// Updates the KZG commitment to a blob after some of its field elements have changed.
+    ///
+    /// Each update is `(index, old, new)`, where `index` is the position of the field element
+    /// in the blob and `old` and `new` are its previous and new values. Since commitments are
+    /// linear in the blob, this only costs a multi-scalar multiplication with one point for each
+    /// update, instead of committing to the whole blob again.
+    ///
+    /// Note: The previous values are not checked against the commitment, so a wrong `old` value
+    /// results in the commitment to a different blob. If an index appears more than once, the
+    /// changes are applied one after the other.
+    pub fn update_commitment(
+        &self,
+        commitment: Bytes48Ref,
+        updates: Vec<(usize, Bytes32Ref, Bytes32Ref)>,
+    ) -> Result<KZGCommitment, Error> {
+        with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
+            // Deserialization
+            //
+            let commitment: B::G1Point = deserialize_g1(commitment)?;
+
+            let mut points = Vec::with_capacity(updates.len());
+            let mut deltas = Vec::with_capacity(updates.len());
+            for (index, old, new) in updates {
+                let point = prover_ctx.g1_lagrange.get(index).ok_or(
+                    ProverError::FieldElementIndexOutOfRange {
+                        index,
+                        max_number_of_field_elements: FIELD_ELEMENTS_PER_BLOB,
+                    },
+                )?;
+                let old: B::Scalar = deserialize_scalar(old)?;
+                let new: B::Scalar = deserialize_scalar(new)?;
+
+                points.push(*point);
+                deltas.push(new - old);
+            }
+
+            // Computation
+            //
+            let commitment_delta = B::g1_lincomb(&points, &deltas)
+                .expect("number of points is equal to the number of deltas");
+
+            Ok(serialize_g1_compressed(
+                &(commitment_delta + commitment).to_affine(),
+            ))
+        })
+    }
+
     /// Computes the cells and the KZG proofs for the given blob.
     ///
     /// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/_features/eip7594/polynomial-commitments-sampling.md#compute_cells_and_kzg_proofs
@@ -99,17 +305,18 @@ impl DASContext {
         blob: BlobRef,
     ) -> Result<([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]), Error> {
         with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
             #[cfg(feature = "tracing")]
             let _span = tracing::info_span!("compute_cells_and_kzg_proofs").entered();
 
             // Deserialization
             //
-            let scalars = deserialize_blob_to_scalars(blob)?;
+            let scalars = deserialize_blob(blob)?;
 
             // Computation
             //
-            let (proofs, cells) = self
-                .prover_ctx
+            let (proofs, cells) = prover_ctx
                 .kzg_multipoint_prover
                 .compute_multi_opening_proofs(ProverInput::Data(scalars));
 
@@ -117,17 +324,113 @@ impl DASContext {
         })
     }
 
+    /// Computes the cells and the KZG proofs for the blob whose field elements are `evaluations`.
+    ///
+    /// This gives the same output as `compute_cells_and_kzg_proofs`, for callers that construct
+    /// the field elements of a blob themselves, so that they do not need to serialize them into
+    /// a blob for them to be deserialized again.
+    ///
+    /// Returns an error if the context was created without a prover.
+    pub fn compute_cells_and_kzg_proofs_from_evaluations(
+        &self,
+        evaluations: &[B::Scalar; FIELD_ELEMENTS_PER_BLOB],
+    ) -> Result<CellsAndKZGProofs, Error> {
+        with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
+            let (proofs, cells) = prover_ctx
+                .kzg_multipoint_prover
+                .compute_multi_opening_proofs(ProverInput::Data(evaluations.to_vec()));
+
+            Ok(serialize_cells_and_proofs(cells, proofs))
+        })
+    }
+
+    /// Computes the cells and the KZG proofs for the polynomial with the given coefficients.
+    ///
+    /// The blob of a polynomial is its evaluations over the roots of unity in bit-reversed order,
+    /// so this gives the same output as `compute_cells_and_kzg_proofs` on that blob, without
+    /// computing the blob first.
+    ///
+    /// Returns an error if the context was created without a prover.
+    pub fn compute_cells_and_kzg_proofs_from_coefficients(
+        &self,
+        coefficients: &[B::Scalar; FIELD_ELEMENTS_PER_BLOB],
+    ) -> Result<CellsAndKZGProofs, Error> {
+        with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
+            let (proofs, cells) = prover_ctx
+                .kzg_multipoint_prover
+                .compute_multi_opening_proofs(ProverInput::PolyCoeff(coefficients.to_vec()));
+
+            Ok(serialize_cells_and_proofs(cells, proofs))
+        })
+    }
+
+    /// Computes the cells and the KZG proofs for each of the given blobs.
+    ///
+    /// Note: When the `multithreaded` feature is enabled, the blobs are processed in parallel
+    /// using the context's thread pool.
+    pub fn compute_cells_and_kzg_proofs_batch(
+        &self,
+        blobs: Vec<BlobRef>,
+    ) -> Result<Vec<CellsAndKZGProofs>, Error> {
+        with_optional_threadpool!(self, {
+            blobs
+                .maybe_into_par_iter()
+                .map(|blob| self.compute_cells_and_kzg_proofs(blob))
+                .collect()
+        })
+    }
+
+    /// Computes a single cell of the given blob and its KZG proof.
+    ///
+    /// This returns the same cell and proof as `compute_cells_and_kzg_proofs` does at position
+    /// `cell_index`, without computing the other cells and proofs. It is meant for callers that
+    /// only serve a few columns, since computing all of the proofs with FK20 is faster when
+    /// more than a handful of cells are needed.
+    pub fn compute_cell_and_kzg_proof(
+        &self,
+        blob: BlobRef,
+        cell_index: CellIndex,
+    ) -> Result<(Cell, KZGProof), Error> {
+        with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!("compute_cell_and_kzg_proof").entered();
+
+            // Deserialization
+            //
+            let scalars = deserialize_blob(blob)?;
+
+            // Computation
+            //
+            let (proof, coset_evaluations) = prover_ctx
+                .kzg_multipoint_prover
+                .compute_multi_opening_proof(ProverInput::Data(scalars), cell_index.as_usize());
+
+            let cell = serialize_scalars_to_cell(&coset_evaluations)
+                .into_boxed_slice()
+                .try_into()
+                .expect("infallible: Vec<u8> should have length equal to BYTES_PER_CELL");
+            Ok((cell, serialize_g1_compressed(&proof)))
+        })
+    }
+
     /// Computes the cells for the given blob.
     pub fn compute_cells(&self, blob: BlobRef) -> Result<[Cell; CELLS_PER_EXT_BLOB], Error> {
         with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
             // Deserialization
             //
-            let scalars = deserialize_blob_to_scalars(blob)?;
+            let scalars = deserialize_blob(blob)?;
 
             // Computation
             //
-            let extended_blob = self
-                .prover_ctx
+            let extended_blob = prover_ctx
                 .kzg_multipoint_prover
                 .extend_polynomial(ProverInput::Data(scalars));
 
@@ -135,6 +438,40 @@ impl DASContext {
         })
     }
 
+    /// Computes the extended blob for the given blob, which contains the field elements of all
+    /// of the cells, one cell after the other.
+    ///
+    /// The field elements are in the same order as in the cells returned by `compute_cells`.
+    /// Since the cells are in bit-reversed order, the first half of the extended blob is the
+    /// blob itself, and the second half is the extension.
+    pub fn compute_extended_blob(&self, blob: BlobRef) -> Result<ExtendedBlob, Error> {
+        let extended_blob = self.compute_extended_blob_scalars(blob)?;
+        Ok(serialize_scalars_to_bytes(&extended_blob)
+            .into_boxed_slice()
+            .try_into()
+            .expect("infallible: Vec<u8> should have length equal to BYTES_PER_EXT_BLOB"))
+    }
+
+    /// Computes the extended blob like `compute_extended_blob`, but returns its
+    /// `FIELD_ELEMENTS_PER_EXT_BLOB` field elements, instead of their serialized bytes.
+    pub fn compute_extended_blob_scalars(&self, blob: BlobRef) -> Result<Vec<B::Scalar>, Error> {
+        with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
+            // Deserialization
+            //
+            let scalars = deserialize_blob(blob)?;
+
+            // Computation
+            //
+            let extended_blob = prover_ctx
+                .kzg_multipoint_prover
+                .extend_polynomial(ProverInput::Data(scalars));
+
+            Ok(extended_blob.into_iter().flatten().collect())
+        })
+    }
+
     /// Recovers the cells and computes the KZG proofs, given a subset of cells.
     ///
     /// Use erasure decoding to recover the polynomial corresponding to the cells
@@ -147,18 +484,36 @@ impl DASContext {
         cells: Vec<CellRef>,
     ) -> Result<([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]), Error> {
         with_optional_threadpool!(self, {
+            let prover_ctx = self.try_prover_ctx()?;
+
             // Recover polynomial
             //
-            let poly_coeff = recover_polynomial_coeff(&self.prover_ctx.rs, cell_indices, cells)?;
+            let poly_coeff = recover_polynomial_coeff(&prover_ctx.rs, cell_indices, cells)?;
 
             // Compute proofs and evaluation sets
             //
-            let (proofs, coset_evaluations) = self
-                .prover_ctx
+            let (proofs, coset_evaluations) = prover_ctx
                 .kzg_multipoint_prover
                 .compute_multi_opening_proofs(ProverInput::PolyCoeff(poly_coeff));
 
             Ok(serialize_cells_and_proofs(coset_evaluations, proofs))
         })
     }
+
+    /// Recovers the cells and computes the KZG proofs for each of the given blobs, given a
+    /// subset of the cells of each blob as `(cell_indices, cells)`.
+    ///
+    /// Note: When the `multithreaded` feature is enabled, the blobs are recovered in parallel
+    /// using the context's thread pool.
+    pub fn recover_cells_and_kzg_proofs_batch(
+        &self,
+        blobs: Vec<(Vec<CellIndex>, Vec<CellRef>)>,
+    ) -> Result<Vec<CellsAndKZGProofs>, Error> {
+        with_optional_threadpool!(self, {
+            blobs
+                .maybe_into_par_iter()
+                .map(|(cell_indices, cells)| self.recover_cells_and_kzg_proofs(cell_indices, cells))
+                .collect()
+        })
+    }
 }
@@ -1,21 +1,110 @@
 use std::collections::HashSet;
 
-use bls12_381::Scalar;
+use bls12_381::ff::PrimeField;
 use erasure_codes::{BlockErasureIndices, ReedSolomon};
-use kzg_multi_open::recover_evaluations_in_domain_order;
+use kzg_multi_open::{backend::Backend, recover_evaluations_in_domain_order};
 
 use crate::{
-    constants::{CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_EXT_BLOB},
+    constants::{CELLS_PER_EXT_BLOB, EXPANSION_FACTOR, FIELD_ELEMENTS_PER_EXT_BLOB},
     errors::{Error, RecoveryError},
     serialization::deserialize_cells,
-    CellIndex, CellRef,
+    Cell, CellIndex, CellRef, DASContext, KZGProof,
 };
 
-pub(crate) fn recover_polynomial_coeff(
-    rs: &ReedSolomon,
+/// Collects the cells for a single extended blob as they arrive, so that recovery
+/// can be performed once enough of them are present.
+///
+/// Note: Cells are not verified when they are added. The caller should verify each
+/// cell against its proof before adding it, since recovery will otherwise
+/// produce cells for the wrong blob.
+#[derive(Debug, Clone)]
+pub struct RecoveryAccumulator {
+    cells: Vec<Option<Cell>>,
+    num_cells: usize,
+}
+
+impl Default for RecoveryAccumulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecoveryAccumulator {
+    /// The minimum number of cells needed to recover the extended blob.
+    pub const MIN_CELLS_NEEDED: usize = CELLS_PER_EXT_BLOB / EXPANSION_FACTOR;
+
+    /// Creates an accumulator that does not contain any cells.
+    pub fn new() -> Self {
+        Self {
+            cells: vec![None; CELLS_PER_EXT_BLOB],
+            num_cells: 0,
+        }
+    }
+
+    /// Adds a cell to the accumulator.
+    ///
+    /// Returns true if adding this cell crossed the threshold at which recovery becomes
+    /// possible, so that the caller is only notified once.
+    ///
+    /// Note: If a cell with the same index has already been added, the cell is ignored.
+    pub fn add_cell(&mut self, cell_index: CellIndex, cell: CellRef) -> bool {
+        let slot = &mut self.cells[cell_index.as_usize()];
+        if slot.is_some() {
+            return false;
+        }
+
+        *slot = Some(Box::new(*cell));
+        self.num_cells += 1;
+
+        self.num_cells == Self::MIN_CELLS_NEEDED
+    }
+
+    /// Returns true if a cell with the given index has been added.
+    pub fn contains(&self, cell_index: CellIndex) -> bool {
+        self.cells[cell_index.as_usize()].is_some()
+    }
+
+    /// Returns the indices of the cells that have been added, in ascending order.
+    pub fn cell_indices(&self) -> Vec<CellIndex> {
+        CellIndex::all()
+            .filter(|cell_index| self.contains(*cell_index))
+            .collect()
+    }
+
+    /// Returns the number of cells that have been added.
+    pub const fn num_cells(&self) -> usize {
+        self.num_cells
+    }
+
+    /// Returns true if enough cells have been added to recover the extended blob.
+    pub const fn can_recover(&self) -> bool {
+        self.num_cells >= Self::MIN_CELLS_NEEDED
+    }
+}
+
+impl<B: Backend> DASContext<B> {
+    /// Recovers all of the cells and computes the KZG proofs, using the cells
+    /// that have been added to the accumulator.
+    ///
+    /// Returns an error if not enough cells have been added. See `RecoveryAccumulator::can_recover`.
+    pub fn recover_cells_and_kzg_proofs_from_accumulator(
+        &self,
+        accumulator: &RecoveryAccumulator,
+    ) -> Result<([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]), Error> {
+        let (cell_indices, cells) = CellIndex::all()
+            .zip(&accumulator.cells)
+            .filter_map(|(cell_index, cell)| Some((cell_index, cell.as_deref()?)))
+            .unzip();
+
+        self.recover_cells_and_kzg_proofs(cell_indices, cells)
+    }
+}
+
+pub(crate) fn recover_polynomial_coeff<F: PrimeField>(
+    rs: &ReedSolomon<F>,
     cell_indices: Vec<CellIndex>,
     cells: Vec<CellRef>,
-) -> Result<Vec<Scalar>, Error> {
+) -> Result<Vec<F>, Error> {
     // Validation
     //
     validation::recover_polynomial_coeff(&cell_indices, &cells)?;
@@ -23,10 +112,7 @@ pub(crate) fn recover_polynomial_coeff(
     // Deserialization
     //
     let coset_evaluations = deserialize_cells(cells)?;
-    let cell_indices: Vec<usize> = cell_indices
-        .into_iter()
-        .map(|index| index as usize)
-        .collect();
+    let cell_indices: Vec<usize> = cell_indices.into_iter().map(CellIndex::as_usize).collect();
 
     // Computation
     //
@@ -97,16 +183,6 @@ mod validation {
             });
         }
 
-        // Check that the Cell indices are within the expected range
-        for cell_index in cell_indices {
-            if *cell_index >= (CELLS_PER_EXT_BLOB as u64) {
-                return Err(RecoveryError::CellIndexOutOfRange {
-                    cell_index: *cell_index,
-                    max_number_of_cells: CELLS_PER_EXT_BLOB as u64,
-                });
-            }
-        }
-
         // Check that each cell has the right amount of bytes
         //
         // This should be infallible.
@@ -130,8 +206,8 @@ mod validation {
         // Check that we don't have too many cells
         // ie more than we initially generated from the blob
         //
-        // Note: Since we check that there are no duplicates and that all cell_indices
-        // are between 0 and CELLS_PER_EXT_BLOB. This check should never fail.
+        // Note: Since we check that there are no duplicates and a `CellIndex` is always
+        // between 0 and CELLS_PER_EXT_BLOB. This check should never fail.
         // It is kept here to be compliant with the specs.
         if cell_indices.len() > CELLS_PER_EXT_BLOB {
             return Err(RecoveryError::TooManyCellsReceived {
@@ -154,17 +230,72 @@ mod validation {
     mod tests {
 
         use super::are_cell_indices_unique;
+        use crate::CellIndex;
 
         #[test]
         fn test_cell_indices_unique() {
-            let cell_indices = vec![1, 2, 3];
-            assert!(are_cell_indices_unique(&cell_indices));
-            let cell_indices = vec![];
-            assert!(are_cell_indices_unique(&cell_indices));
-            let cell_indices = vec![1, 1, 2, 3];
-            assert!(!are_cell_indices_unique(&cell_indices));
-            let cell_indices = vec![0, 0, 0];
-            assert!(!are_cell_indices_unique(&cell_indices));
+            let are_unique = |indices: &[u64]| {
+                are_cell_indices_unique(&CellIndex::try_from_u64s(indices).unwrap())
+            };
+            assert!(are_unique(&[1, 2, 3]));
+            assert!(are_unique(&[]));
+            assert!(!are_unique(&[1, 1, 2, 3]));
+            assert!(!are_unique(&[0, 0, 0]));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RecoveryAccumulator;
+    use crate::{
+        constants::BYTES_PER_BLOB,
+        errors::{Error, RecoveryError},
+        CellIndex, DASContext,
+    };
+
+    #[test]
+    fn test_recovery_accumulator() {
+        let ctx = DASContext::default();
+
+        let mut blob = vec![0u8; BYTES_PER_BLOB];
+        for (i, field_element) in blob.chunks_mut(32).enumerate() {
+            field_element[24..].copy_from_slice(&(i as u64).to_be_bytes());
+        }
+        let (cells, proofs) = ctx
+            .compute_cells_and_kzg_proofs(blob.as_slice().try_into().unwrap())
+            .unwrap();
+
+        let mut accumulator = RecoveryAccumulator::new();
+        assert!(matches!(
+            ctx.recover_cells_and_kzg_proofs_from_accumulator(&accumulator),
+            Err(Error::Recovery(
+                RecoveryError::NotEnoughCellsToReconstruct { .. }
+            ))
+        ));
+
+        // Add every other cell, which is exactly the threshold needed for recovery
+        for (cell_index, cell) in CellIndex::all().zip(&cells).skip(1).step_by(2) {
+            assert!(!accumulator.can_recover());
+            let crossed_threshold = accumulator.add_cell(cell_index, cell);
+            assert_eq!(crossed_threshold, accumulator.can_recover());
+        }
+        assert_eq!(
+            accumulator.num_cells(),
+            RecoveryAccumulator::MIN_CELLS_NEEDED
+        );
+        let [first, second] = [0u64, 1].map(|index| CellIndex::try_from(index).unwrap());
+        assert!(accumulator.contains(second));
+        assert!(!accumulator.contains(first));
+
+        // Adding more cells, or the same cell again does not report the threshold again
+        assert!(!accumulator.add_cell(second, &cells[1]));
+        assert!(!accumulator.add_cell(first, &cells[0]));
+
+        let (recovered_cells, recovered_proofs) = ctx
+            .recover_cells_and_kzg_proofs_from_accumulator(&accumulator)
+            .unwrap();
+        assert_eq!(recovered_cells, cells);
+        assert_eq!(recovered_proofs, proofs);
+    }
+}
@@ -1,15 +1,38 @@
+//! Conversions between the serialized bytes of the public API and the field elements and points
+//! that they encode.
+//!
+//! These are the encoding rules of the consensus specs, which clients also need when they handle
+//! the sidecars themselves:
+//!
+//! - A field element is 32 bytes in big-endian order, and must be less than the modulus. The
+//!   functions that end in `_le` use little-endian order instead, for interoperating with
+//!   libraries that use it, but it is never used on the wire.
+//! - A blob or a cell is its field elements, one after the other.
+//! - A commitment or a proof is a compressed G1 point, which must be on the curve and in the
+//!   prime order subgroup.
 use crate::{
     constants::{
         BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1_POINT,
         CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_CELL,
     },
-    Cell, KZGProof,
+    Cell, CellRef, KZGProof,
 };
-use bls12_381::{G1Point, Scalar};
+use bls12_381::{
+    ff::PrimeField, group::GroupEncoding, subgroup_check::NUM_SUBSETS, G1Point, Scalar,
+};
+use kzg_multi_open::backend::{Backend, BlstBackend};
+use sha2::{Digest, Sha256};
 
 pub use crate::errors::SerializationError;
 
-fn deserialize_bytes_to_scalars(bytes: &[u8]) -> Result<Vec<Scalar>, SerializationError> {
+fn deserialize_bytes_to_scalars<F: PrimeField>(bytes: &[u8]) -> Result<Vec<F>, SerializationError> {
+    deserialize_bytes_to_scalars_with(bytes, scalar_from_bytes_be)
+}
+
+fn deserialize_bytes_to_scalars_with<F>(
+    bytes: &[u8],
+    from_bytes: fn(&[u8; BYTES_PER_FIELD_ELEMENT]) -> Option<F>,
+) -> Result<Vec<F>, SerializationError> {
     // Check that the bytes are a multiple of the scalar size
     if bytes.len() % BYTES_PER_FIELD_ELEMENT != 0 {
         return Err(SerializationError::ScalarHasInvalidLength {
@@ -22,73 +45,257 @@ fn deserialize_bytes_to_scalars(bytes: &[u8]) -> Result<Vec<Scalar>, Serializati
 
     let mut scalars = Vec::with_capacity(bytes32s.len());
     for bytes32 in bytes32s {
-        scalars.push(deserialize_bytes_to_scalar(bytes32)?)
+        scalars.push(deserialize_bytes_to_scalar_with(bytes32, from_bytes)?)
     }
     Ok(scalars)
 }
 
-pub(crate) fn deserialize_blob_to_scalars(
-    blob_bytes: &[u8],
-) -> Result<Vec<Scalar>, SerializationError> {
+fn check_blob_length(blob_bytes: &[u8]) -> Result<(), SerializationError> {
     if blob_bytes.len() != BYTES_PER_BLOB {
         return Err(SerializationError::BlobHasInvalidLength {
             length: blob_bytes.len(),
             bytes: blob_bytes.to_vec(),
         });
     }
+    Ok(())
+}
+
+/// Deserializes the field elements of a blob.
+///
+/// Returns an error if the blob does not have `BYTES_PER_BLOB` bytes, or if any of its
+/// field elements are not canonical.
+pub fn deserialize_blob_to_scalars(blob_bytes: &[u8]) -> Result<Vec<Scalar>, SerializationError> {
+    deserialize_blob(blob_bytes)
+}
+
+/// Deserializes the field elements of a blob, for any field.
+pub(crate) fn deserialize_blob<F: PrimeField>(
+    blob_bytes: &[u8],
+) -> Result<Vec<F>, SerializationError> {
+    check_blob_length(blob_bytes)?;
     deserialize_bytes_to_scalars(blob_bytes)
 }
-pub(crate) fn deserialize_cell_to_scalars(
-    cell_bytes: &[u8],
+
+/// Deserializes the field elements of a blob, where each of them is encoded in little-endian
+/// order, instead of the canonical big-endian order.
+///
+/// This is for blobs that were serialized by libraries such as arkworks, which use
+/// little-endian encodings of field elements. The bytes of such a blob are not a valid blob
+/// for the rest of this library, or for the consensus specs.
+pub fn deserialize_blob_to_scalars_le(
+    blob_bytes: &[u8],
 ) -> Result<Vec<Scalar>, SerializationError> {
+    check_blob_length(blob_bytes)?;
+    deserialize_bytes_to_scalars_with(blob_bytes, bls12_381::scalar_from_bytes_le)
+}
+
+/// Serializes field elements, such as the field elements of a blob, one after the other.
+pub fn serialize_scalars_to_bytes<F: PrimeField>(scalars: &[F]) -> Vec<u8> {
+    scalars.iter().flat_map(scalar_to_bytes_be).collect()
+}
+
+/// Serializes field elements, such as the field elements of a blob, one after the other,
+/// where each of them is encoded in little-endian order.
+///
+/// Note: This is not the canonical encoding of a blob, which uses big-endian order, see
+/// `deserialize_blob_to_scalars_le`.
+pub fn serialize_scalars_to_bytes_le(scalars: &[Scalar]) -> Vec<u8> {
+    scalars
+        .iter()
+        .flat_map(bls12_381::scalar_to_bytes_le)
+        .collect()
+}
+
+/// Deserializes the field elements of a cell.
+///
+/// Returns an error if any of its field elements are not canonical.
+pub fn deserialize_cell_to_scalars(cell_bytes: CellRef) -> Result<Vec<Scalar>, SerializationError> {
+    deserialize_cell(cell_bytes)
+}
+
+/// Deserializes the field elements of a cell, for any field.
+pub(crate) fn deserialize_cell<F: PrimeField>(
+    cell_bytes: CellRef,
+) -> Result<Vec<F>, SerializationError> {
     deserialize_bytes_to_scalars(cell_bytes)
 }
 
-pub(crate) fn deserialize_bytes_to_scalar(
+/// Deserializes a field element from its 32 byte big-endian encoding.
+///
+/// Returns an error if it does not have 32 bytes, or if it is not less than the modulus.
+pub fn deserialize_bytes_to_scalar(scalar_bytes: &[u8]) -> Result<Scalar, SerializationError> {
+    deserialize_scalar(scalar_bytes)
+}
+
+/// Deserializes a field element from its 32 byte big-endian encoding, for any field.
+pub(crate) fn deserialize_scalar<F: PrimeField>(
     scalar_bytes: &[u8],
-) -> Result<Scalar, SerializationError> {
-    let bytes32 = scalar_bytes.try_into().expect("infallible: expected blob chunks to be exactly {SCALAR_SERIALIZED_SIZE} bytes, since blob was a multiple of {SCALAR_SERIALIZED_SIZE");
+) -> Result<F, SerializationError> {
+    deserialize_bytes_to_scalar_with(scalar_bytes, scalar_from_bytes_be)
+}
 
+/// Deserializes a field element from its 32 byte little-endian encoding.
+///
+/// Note: The canonical encoding is big-endian, see `deserialize_blob_to_scalars_le`.
+pub fn deserialize_bytes_to_scalar_le(scalar_bytes: &[u8]) -> Result<Scalar, SerializationError> {
+    deserialize_bytes_to_scalar_with(scalar_bytes, bls12_381::scalar_from_bytes_le)
+}
+
+fn scalar_from_bytes_be<F: PrimeField>(bytes: &[u8; BYTES_PER_FIELD_ELEMENT]) -> Option<F> {
+    // The representation of a field element is its little-endian encoding
+    let mut repr = F::Repr::default();
+    repr.as_mut().copy_from_slice(bytes);
+    repr.as_mut().reverse();
     // Convert the CtOption into Option
-    let option_scalar: Option<Scalar> = Scalar::from_bytes_be(bytes32).into();
-    match option_scalar {
-        Some(scalar) => Ok(scalar),
-        None => Err(SerializationError::CouldNotDeserializeScalar {
-            bytes: scalar_bytes.to_vec(),
-        }),
-    }
+    F::from_repr(repr).into()
 }
 
-pub(crate) fn deserialize_compressed_g1(point_bytes: &[u8]) -> Result<G1Point, SerializationError> {
-    let point_bytes = match point_bytes.try_into() {
-        Ok(bytes) => bytes,
-        Err(_) => {
-            return Err(SerializationError::G1PointHasInvalidLength {
-                length: point_bytes.len(),
-                bytes: point_bytes.to_vec(),
-            })
-        }
+fn scalar_to_bytes_be<F: PrimeField>(scalar: &F) -> [u8; BYTES_PER_FIELD_ELEMENT] {
+    let mut bytes = [0u8; BYTES_PER_FIELD_ELEMENT];
+    bytes.copy_from_slice(scalar.to_repr().as_ref());
+    bytes.reverse();
+    bytes
+}
+
+fn deserialize_bytes_to_scalar_with<F>(
+    scalar_bytes: &[u8],
+    from_bytes: fn(&[u8; BYTES_PER_FIELD_ELEMENT]) -> Option<F>,
+) -> Result<F, SerializationError> {
+    let Ok(bytes32) = scalar_bytes.try_into() else {
+        return Err(SerializationError::ScalarHasInvalidLength {
+            length: scalar_bytes.len(),
+            bytes: scalar_bytes.to_vec(),
+        });
     };
 
-    let opt_g1: Option<G1Point> = Option::from(G1Point::from_compressed(point_bytes));
+    from_bytes(bytes32).ok_or_else(|| SerializationError::CouldNotDeserializeScalar {
+        bytes: scalar_bytes.to_vec(),
+    })
+}
+
+/// Deserializes a compressed G1 point, such as a commitment or a proof.
+///
+/// Returns an error if it does not have 48 bytes, if it is not a valid encoding of a point
+/// on the curve, or if the point is not in the prime order subgroup.
+pub fn deserialize_compressed_g1(point_bytes: &[u8]) -> Result<G1Point, SerializationError> {
+    deserialize_g1(point_bytes)
+}
+
+/// Deserializes a compressed G1 point, for any curve implementation.
+pub(crate) fn deserialize_g1<G: GroupEncoding>(
+    point_bytes: &[u8],
+) -> Result<G, SerializationError> {
+    deserialize_compressed_g1_with(point_bytes, |bytes| G::from_bytes(bytes).into())
+}
+
+/// Deserializes a compressed G1 point, without checking that it is in the prime order subgroup.
+///
+/// Note: This should only be used for points that were serialized by this library.
+pub(crate) fn deserialize_compressed_g1_unchecked<G: GroupEncoding>(
+    point_bytes: &[u8],
+) -> Result<G, SerializationError> {
+    deserialize_compressed_g1_with(point_bytes, |bytes| G::from_bytes_unchecked(bytes).into())
+}
+
+fn deserialize_compressed_g1_with<G: GroupEncoding>(
+    point_bytes: &[u8],
+    from_compressed: impl Fn(&G::Repr) -> Option<G>,
+) -> Result<G, SerializationError> {
+    let mut repr = G::Repr::default();
+    if point_bytes.len() != BYTES_PER_G1_POINT || point_bytes.len() != repr.as_ref().len() {
+        return Err(SerializationError::G1PointHasInvalidLength {
+            length: point_bytes.len(),
+            bytes: point_bytes.to_vec(),
+        });
+    }
+    repr.as_mut().copy_from_slice(point_bytes);
+
+    let opt_g1 = from_compressed(&repr);
     opt_g1.ok_or_else(|| SerializationError::CouldNotDeserializeG1Point {
         bytes: point_bytes.to_vec(),
     })
 }
-pub(crate) fn serialize_g1_compressed(point: &G1Point) -> [u8; BYTES_PER_G1_POINT] {
-    point.to_compressed()
+
+/// Serializes a G1 point, such as a commitment or a proof, in compressed form.
+pub fn serialize_g1_compressed<G: GroupEncoding>(point: &G) -> [u8; BYTES_PER_G1_POINT] {
+    let mut bytes = [0u8; BYTES_PER_G1_POINT];
+    bytes.copy_from_slice(point.to_bytes().as_ref());
+    bytes
 }
 
-pub(crate) fn deserialize_compressed_g1_points(
+/// Deserializes compressed G1 points, checking that they are in the prime order subgroup.
+///
+/// The subgroup checks are the most expensive part of deserializing a point, so for a large
+/// number of points, they are checked together using random subsets of the points. If that
+/// check fails, the points are deserialized one at a time, to find the point that is invalid.
+///
+/// This accepts and rejects the same points as `deserialize_compressed_g1`.
+pub fn deserialize_compressed_g1_points(
     points: Vec<&[u8; BYTES_PER_G1_POINT]>,
 ) -> Result<Vec<G1Point>, SerializationError> {
+    deserialize_g1_points::<BlstBackend>(points)
+}
+
+/// Deserializes compressed G1 points like `deserialize_compressed_g1_points`, using the
+/// batched subgroup check of the backend `B`.
+pub(crate) fn deserialize_g1_points<B: Backend>(
+    points: Vec<&[u8; BYTES_PER_G1_POINT]>,
+) -> Result<Vec<B::G1Point>, SerializationError> {
+    if points.len() > NUM_SUBSETS {
+        if let Ok(g1_points) = deserialize_compressed_g1_points_unchecked(points.clone()) {
+            let subset_masks = subgroup_check_subset_masks(&points);
+            if B::g1_batch_is_torsion_free(&g1_points, &subset_masks) == Some(true) {
+                return Ok(g1_points);
+            }
+        }
+    }
+
+    points
+        .into_iter()
+        .map(|point| deserialize_g1(point))
+        .collect()
+}
+
+/// Derives the random subsets for the batched subgroup check from a hash of the points, so that
+/// they cannot be predicted by whoever chose the points.
+fn subgroup_check_subset_masks(points: &[&[u8; BYTES_PER_G1_POINT]]) -> Vec<u128> {
+    const DOMAIN_SEP: &str = "RCKZGSUBGROUPCHECK_V1_";
+
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN_SEP.as_bytes());
+    hasher.update((points.len() as u64).to_be_bytes());
+    for point in points {
+        hasher.update(point);
+    }
+    let seed = hasher.finalize();
+
+    (0..points.len() as u64)
+        .map(|i| {
+            let hash = Sha256::new()
+                .chain_update(seed)
+                .chain_update(i.to_be_bytes())
+                .finalize();
+            u128::from_be_bytes(hash[..16].try_into().expect("hash has 32 bytes"))
+        })
+        .collect()
+}
+
+/// Deserializes compressed G1 points, without checking that they are in the prime order subgroup.
+///
+/// Note: This should only be used for points that were serialized by this library.
+pub(crate) fn deserialize_compressed_g1_points_unchecked<G: GroupEncoding>(
+    points: Vec<&[u8; BYTES_PER_G1_POINT]>,
+) -> Result<Vec<G>, SerializationError> {
     points
         .into_iter()
-        .map(|point| deserialize_compressed_g1(point))
+        .map(|point| deserialize_compressed_g1_unchecked(point))
         .collect()
 }
 
-pub(crate) fn serialize_scalars_to_cell(scalars: &[Scalar]) -> Vec<u8> {
+/// Serializes the field elements of a cell.
+///
+/// Panics if there are not exactly `FIELD_ELEMENTS_PER_CELL` field elements.
+pub fn serialize_scalars_to_cell<F: PrimeField>(scalars: &[F]) -> Vec<u8> {
     assert_eq!(
         scalars.len(),
         FIELD_ELEMENTS_PER_CELL,
@@ -97,23 +304,19 @@ pub(crate) fn serialize_scalars_to_cell(scalars: &[Scalar]) -> Vec<u8> {
 
     let mut bytes = Vec::with_capacity(FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEMENT);
     for scalar in scalars {
-        bytes.extend_from_slice(&scalar.to_bytes_be());
+        bytes.extend_from_slice(&scalar_to_bytes_be(scalar));
     }
     bytes
 }
 
-pub(crate) fn deserialize_cells(
+pub(crate) fn deserialize_cells<F: PrimeField>(
     cells: Vec<&[u8; BYTES_PER_CELL]>,
-) -> Result<Vec<Vec<Scalar>>, SerializationError> {
-    cells
-        .into_iter()
-        .map(AsRef::as_ref)
-        .map(deserialize_cell_to_scalars)
-        .collect()
+) -> Result<Vec<Vec<F>>, SerializationError> {
+    cells.into_iter().map(deserialize_cell).collect()
 }
 
 /// Converts a set of scalars (evaluations) to the `Cell` type.
-pub(crate) fn coset_evaluations_to_cells<T: AsRef<[Scalar]>>(
+pub(crate) fn coset_evaluations_to_cells<F: PrimeField, T: AsRef<[F]>>(
     evaluations: impl Iterator<Item = T>,
 ) -> [Cell; CELLS_PER_EXT_BLOB] {
     let cells: Vec<Cell> = evaluations
@@ -130,9 +333,9 @@ pub(crate) fn coset_evaluations_to_cells<T: AsRef<[Scalar]>>(
         .unwrap_or_else(|_| panic!("expected {} number of cells", CELLS_PER_EXT_BLOB))
 }
 
-pub(crate) fn serialize_cells_and_proofs(
-    coset_evaluations: Vec<Vec<Scalar>>,
-    proofs: Vec<G1Point>,
+pub(crate) fn serialize_cells_and_proofs<F: PrimeField, G: GroupEncoding>(
+    coset_evaluations: Vec<Vec<F>>,
+    proofs: Vec<G>,
 ) -> ([Cell; CELLS_PER_EXT_BLOB], [KZGProof; CELLS_PER_EXT_BLOB]) {
     // Serialize the evaluation sets into `Cell`s.
     let cells = serialize_cells(coset_evaluations);
@@ -146,7 +349,9 @@ pub(crate) fn serialize_cells_and_proofs(
     (cells, proofs)
 }
 
-pub(crate) fn serialize_cells(coset_evaluations: Vec<Vec<Scalar>>) -> [Cell; CELLS_PER_EXT_BLOB] {
+pub(crate) fn serialize_cells<F: PrimeField>(
+    coset_evaluations: Vec<Vec<F>>,
+) -> [Cell; CELLS_PER_EXT_BLOB] {
     // Serialize the evaluation sets into `Cell`s.
     coset_evaluations_to_cells(coset_evaluations.into_iter())
 }
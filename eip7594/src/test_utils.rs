@@ -0,0 +1,350 @@
+//! Helpers for testing this library and the clients that wrap it.
+//!
+//! This contains loaders for the consensus spec test vectors, which are in the `test_vectors`
+//! folder, and generators for random inputs, both valid and invalid.
+//!
+//! Note: The test vectors are trusted, so malformed YAML is returned as an error, but a malformed
+//! hex string panics.
+use crate::{
+    constants::{BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB},
+    Bytes48Ref, Cell, CellRef, DASContext, KZGCommitment, KZGProof,
+};
+use bls12_381::{ff::Field, group::Group, G1Point, G1Projective, Scalar};
+use rand::{Rng, RngCore};
+use serde::Deserialize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Data from the test input could also be malformed,
+/// So we use this type to represent that.
+/// For example, although a proof should be 48 bytes, the test input
+/// could give us 47.
+pub type UnsafeBytes = Vec<u8>;
+
+/// Returns the paths of all of the files in `dir` and its subdirectories.
+///
+/// Panics if there are no files, since that means that the path to the test vectors is wrong.
+pub fn collect_test_files<P: AsRef<Path>>(dir: P) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    _collect_test_files(dir, &mut files)?;
+
+    // Check that the directory is not empty
+    assert!(!files.is_empty());
+
+    Ok(files)
+}
+fn _collect_test_files<P: AsRef<Path>>(dir: P, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            _collect_test_files(path, files)?;
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn remove_hex_prefix(s: &str) -> &str {
+    if let Some(stripped) = s.strip_prefix("0x") {
+        stripped
+    } else {
+        panic!(
+            "hex strings in ethereum are assumed to be prefixed with a 0x.
+                If this is not the case, it is not a bug, however it is cause for concern,
+                if there are discrepancies."
+        );
+    }
+}
+
+/// Decodes a `0x` prefixed hex string.
+///
+/// Panics if the string is not prefixed with `0x`, or is not valid hex.
+pub fn bytes_from_hex(bytes: &str) -> Vec<u8> {
+    let bytes = remove_hex_prefix(bytes);
+    hex::decode(bytes).unwrap()
+}
+
+fn bytes_from_hex_list(list: &[String]) -> Vec<UnsafeBytes> {
+    list.iter().map(|bytes| bytes_from_hex(bytes)).collect()
+}
+
+#[derive(Deserialize)]
+struct YamlTestVector<I, O> {
+    input: I,
+    output: Option<O>,
+}
+
+/// The cells and proofs that are the output of a test vector.
+#[derive(Debug, Clone)]
+pub struct KZGProofsAndCells {
+    pub proofs: Vec<UnsafeBytes>,
+    pub cells: Vec<UnsafeBytes>,
+}
+
+impl KZGProofsAndCells {
+    fn from_yaml((cells, proofs): (Vec<String>, Vec<String>)) -> Self {
+        Self {
+            proofs: bytes_from_hex_list(&proofs),
+            cells: bytes_from_hex_list(&cells),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BlobInput {
+    blob: String,
+}
+
+/// A test vector for `blob_to_kzg_commitment`.
+///
+/// The commitment is None if the blob is invalid.
+#[derive(Debug, Clone)]
+pub struct BlobToKZGCommitmentTestVector {
+    pub blob: UnsafeBytes,
+    pub commitment: Option<UnsafeBytes>,
+}
+
+impl FromStr for BlobToKZGCommitmentTestVector {
+    type Err = serde_yaml::Error;
+
+    fn from_str(yaml_data: &str) -> Result<Self, Self::Err> {
+        let test_vector: YamlTestVector<BlobInput, String> = serde_yaml::from_str(yaml_data)?;
+        Ok(Self {
+            blob: bytes_from_hex(&test_vector.input.blob),
+            commitment: test_vector
+                .output
+                .map(|commitment| bytes_from_hex(&commitment)),
+        })
+    }
+}
+
+/// A test vector for `compute_cells_and_kzg_proofs`.
+///
+/// The cells and proofs are None if the blob is invalid.
+#[derive(Debug, Clone)]
+pub struct ComputeCellsAndKZGProofsTestVector {
+    pub blob: UnsafeBytes,
+    pub proofs_and_cells: Option<KZGProofsAndCells>,
+}
+
+impl FromStr for ComputeCellsAndKZGProofsTestVector {
+    type Err = serde_yaml::Error;
+
+    fn from_str(yaml_data: &str) -> Result<Self, Self::Err> {
+        let test_vector: YamlTestVector<BlobInput, (Vec<String>, Vec<String>)> =
+            serde_yaml::from_str(yaml_data)?;
+        Ok(Self {
+            blob: bytes_from_hex(&test_vector.input.blob),
+            proofs_and_cells: test_vector.output.map(KZGProofsAndCells::from_yaml),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RecoverInput {
+    cell_indices: Vec<u64>,
+    cells: Vec<String>,
+}
+
+/// A test vector for `recover_cells_and_kzg_proofs`.
+///
+/// The cells and proofs are None if the cells cannot be recovered.
+#[derive(Debug, Clone)]
+pub struct RecoverCellsAndKZGProofsTestVector {
+    pub input_cell_indices: Vec<u64>,
+    pub input_cells: Vec<UnsafeBytes>,
+    pub proofs_and_cells: Option<KZGProofsAndCells>,
+}
+
+impl FromStr for RecoverCellsAndKZGProofsTestVector {
+    type Err = serde_yaml::Error;
+
+    fn from_str(yaml_data: &str) -> Result<Self, Self::Err> {
+        let test_vector: YamlTestVector<RecoverInput, (Vec<String>, Vec<String>)> =
+            serde_yaml::from_str(yaml_data)?;
+        Ok(Self {
+            input_cell_indices: test_vector.input.cell_indices,
+            input_cells: bytes_from_hex_list(&test_vector.input.cells),
+            proofs_and_cells: test_vector.output.map(KZGProofsAndCells::from_yaml),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct VerifyInput {
+    commitments: Vec<String>,
+    cell_indices: Vec<u64>,
+    cells: Vec<String>,
+    proofs: Vec<String>,
+}
+
+/// A test vector for `verify_cell_kzg_proof_batch`.
+///
+/// The output is None if the inputs are malformed, otherwise it is whether the proofs are valid.
+#[derive(Debug, Clone)]
+pub struct VerifyCellKZGProofBatchTestVector {
+    pub commitments: Vec<UnsafeBytes>,
+    pub cell_indices: Vec<u64>,
+    pub cells: Vec<UnsafeBytes>,
+    pub proofs: Vec<UnsafeBytes>,
+    pub output: Option<bool>,
+}
+
+impl FromStr for VerifyCellKZGProofBatchTestVector {
+    type Err = serde_yaml::Error;
+
+    fn from_str(yaml_data: &str) -> Result<Self, Self::Err> {
+        let test_vector: YamlTestVector<VerifyInput, bool> = serde_yaml::from_str(yaml_data)?;
+        Ok(Self {
+            commitments: bytes_from_hex_list(&test_vector.input.commitments),
+            cell_indices: test_vector.input.cell_indices,
+            cells: bytes_from_hex_list(&test_vector.input.cells),
+            proofs: bytes_from_hex_list(&test_vector.input.proofs),
+            output: test_vector.output,
+        })
+    }
+}
+
+/// A field element that is not canonical, since it is the modulus of the scalar field.
+pub const NON_CANONICAL_FIELD_ELEMENT: [u8; BYTES_PER_FIELD_ELEMENT] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// A compressed point that is on the curve, but not in the prime order subgroup, so it is
+/// not a valid commitment or proof.
+///
+/// This is the point with `x = 5`.
+pub const POINT_NOT_IN_SUBGROUP: [u8; 48] = {
+    let mut bytes = [0u8; 48];
+    // The compression flag
+    bytes[0] = 0x80;
+    bytes[47] = 5;
+    bytes
+};
+
+/// Returns a blob where every field element is random.
+pub fn random_blob(rng: &mut impl RngCore) -> Box<[u8; BYTES_PER_BLOB]> {
+    let mut blob = Box::new([0u8; BYTES_PER_BLOB]);
+    for field_element in blob.chunks_exact_mut(BYTES_PER_FIELD_ELEMENT) {
+        field_element.copy_from_slice(&Scalar::random(&mut *rng).to_bytes_be());
+    }
+    blob
+}
+
+/// A random blob, along with its commitment, and all of its cells and their proofs.
+#[derive(Debug, Clone)]
+pub struct ValidCells {
+    pub blob: Box<[u8; BYTES_PER_BLOB]>,
+    pub commitment: KZGCommitment,
+    pub cells: [Cell; CELLS_PER_EXT_BLOB],
+    pub proofs: [KZGProof; CELLS_PER_EXT_BLOB],
+}
+
+/// Returns a random blob, along with its commitment, cells and proofs, which are all valid.
+pub fn random_valid_cells(ctx: &DASContext, rng: &mut impl RngCore) -> ValidCells {
+    let blob = random_blob(rng);
+    let commitment = ctx
+        .blob_to_kzg_commitment(&blob)
+        .expect("random blobs are valid");
+    let (cells, proofs) = ctx
+        .compute_cells_and_kzg_proofs(&blob)
+        .expect("random blobs are valid");
+    ValidCells {
+        blob,
+        commitment,
+        cells,
+        proofs,
+    }
+}
+
+/// Returns a copy of the cell, where a random field element has been replaced by a different one.
+///
+/// The cell can still be deserialized, but it is not consistent with the proof of the original cell.
+pub fn corrupt_cell(cell: CellRef, rng: &mut impl RngCore) -> Cell {
+    let mut corrupted = Box::new(*cell);
+    let index = rng.gen_range(0..cell.len() / BYTES_PER_FIELD_ELEMENT);
+    let field_element =
+        &mut corrupted[index * BYTES_PER_FIELD_ELEMENT..(index + 1) * BYTES_PER_FIELD_ELEMENT];
+    let original: [u8; BYTES_PER_FIELD_ELEMENT] = field_element
+        .try_into()
+        .expect("slice has the size of a field element");
+    let changed = Option::<Scalar>::from(Scalar::from_bytes_be(&original)).unwrap_or(Scalar::ZERO)
+        + Scalar::ONE;
+    field_element.copy_from_slice(&changed.to_bytes_be());
+    corrupted
+}
+
+/// Returns a different valid commitment or proof, by adding the generator to the given one.
+///
+/// The point can still be deserialized, but it does not open the original commitment, or it
+/// is not the commitment that the original proof opens.
+///
+/// Panics if the bytes are not a valid point.
+pub fn corrupt_point(point: Bytes48Ref) -> [u8; 48] {
+    let point = Option::<G1Point>::from(G1Point::from_compressed(point))
+        .expect("point should be a valid compressed point");
+    G1Point::from(G1Projective::from(point) + G1Projective::generator()).to_compressed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CellIndex;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn valid_cells_verify_and_corrupted_cells_do_not() {
+        let ctx = DASContext::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        let valid = random_valid_cells(&ctx, &mut rng);
+
+        let index = 9;
+        let cell: CellRef = &valid.cells[index];
+        let verify = |commitment: Bytes48Ref, cell: CellRef, proof: Bytes48Ref| {
+            ctx.verify_cell_kzg_proof(commitment, CellIndex::try_from(index).unwrap(), cell, proof)
+        };
+        assert!(verify(&valid.commitment, cell, &valid.proofs[index]).is_ok());
+
+        let corrupted = corrupt_cell(cell, &mut rng);
+        assert_ne!(&corrupted, &valid.cells[index]);
+        let err = verify(&valid.commitment, &corrupted, &valid.proofs[index]).unwrap_err();
+        assert!(err.invalid_proof());
+
+        let err = verify(
+            &valid.commitment,
+            cell,
+            &corrupt_point(&valid.proofs[index]),
+        )
+        .unwrap_err();
+        assert!(err.invalid_proof());
+        let err = verify(
+            &corrupt_point(&valid.commitment),
+            cell,
+            &valid.proofs[index],
+        )
+        .unwrap_err();
+        assert!(err.invalid_proof());
+
+        let err = verify(&POINT_NOT_IN_SUBGROUP, cell, &valid.proofs[index]).unwrap_err();
+        assert!(!err.invalid_proof());
+    }
+
+    #[test]
+    fn non_canonical_field_element_is_rejected() {
+        assert!(bool::from(
+            Scalar::from_bytes_be(&NON_CANONICAL_FIELD_ELEMENT).is_none()
+        ));
+        let mut modulus_minus_one = NON_CANONICAL_FIELD_ELEMENT;
+        modulus_minus_one[BYTES_PER_FIELD_ELEMENT - 1] -= 1;
+        assert_eq!(
+            Scalar::from_bytes_be(&modulus_minus_one).unwrap(),
+            -Scalar::ONE
+        );
+    }
+}
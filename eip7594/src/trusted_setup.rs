@@ -1,11 +1,25 @@
-use bls12_381::{G1Point, G2Point};
-use kzg_multi_open::{commit_key::CommitKey, verification_key::VerificationKey};
+use bls12_381::{group::GroupEncoding, G1Point};
+use kzg_multi_open::{
+    backend::{Backend, BlstBackend},
+    commit_key::CommitKey,
+    reverse_bit_order,
+    verification_key::VerificationKey,
+};
 use serde::Deserialize;
 
-use crate::constants::{FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL};
+use crate::{
+    constants::{FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_CELL},
+    errors::TrustedSetupError,
+};
 
 const TRUSTED_SETUP_JSON: &str = include_str!("../data/trusted_setup_4096.json");
 
+/// Identifies the trusted setup that is embedded in the library, which is
+/// the one returned by `TrustedSetup::default`.
+///
+/// This is the mainnet trusted setup from the consensus specs.
+pub const EMBEDDED_TRUSTED_SETUP_ID: &str = "consensus-specs/mainnet/trusted_setup_4096";
+
 #[derive(Deserialize, Debug, PartialEq, Eq)]
 pub struct TrustedSetup {
     /// G1 Monomial represents a list of uncompressed
@@ -42,19 +56,34 @@ enum SubgroupCheck {
     NoCheck,
 }
 
-impl From<&TrustedSetup> for CommitKey {
+impl<B: Backend> From<&TrustedSetup> for CommitKey<B> {
     fn from(setup: &TrustedSetup) -> Self {
-        setup.to_commit_key(SubgroupCheck::NoCheck)
+        setup
+            .to_commit_key(SubgroupCheck::NoCheck)
+            .expect("trusted setup has malformed g1 points")
     }
 }
 
-impl From<&TrustedSetup> for VerificationKey {
+impl<B: Backend> From<&TrustedSetup> for VerificationKey<B> {
     fn from(setup: &TrustedSetup) -> Self {
-        setup.to_verification_key(SubgroupCheck::NoCheck)
+        setup
+            .to_verification_key(SubgroupCheck::NoCheck)
+            .expect("trusted setup has malformed points")
     }
 }
 
 impl TrustedSetup {
+    /// Returns the G1 points in lagrange form, which are used to update commitments.
+    ///
+    /// Note: The points are returned in bit-reversed order, like the field elements of a blob,
+    /// so the commitment to a blob is the linear combination of these points with its field elements.
+    pub(crate) fn g1_lagrange_points<G: GroupEncoding>(&self) -> Vec<G> {
+        let mut points = deserialize_g1_points(&self.g1_lagrange, SubgroupCheck::NoCheck)
+            .expect("trusted setup has malformed g1 lagrange points");
+        reverse_bit_order(&mut points);
+        points
+    }
+
     /// Parse a Json string in the format specified by the ethereum trusted setup.
     ///
     /// The file that is being used on mainnet is located here: https://github.com/ethereum/consensus-specs/blob/389b2ddfb954731da7ccf4c0ef89fab2d4575b99/presets/mainnet/trusted_setups/trusted_setup_4096.json
@@ -78,46 +107,114 @@ impl TrustedSetup {
     }
     */
     pub fn from_json(json: &str) -> Self {
-        let trusted_setup = Self::from_json_unchecked(json);
-        trusted_setup.validate_trusted_setup();
-        trusted_setup
+        // Note: it is fine to panic here since this method is called on startup
+        // and we want to fail fast if the trusted setup is malformed.
+        Self::try_from_json(json).expect("could not load trusted setup")
+    }
+
+    /// Parse a Json string in the format specified by the ethereum trusted setup
+    /// and check that all of the points are valid and in the correct subgroup.
+    ///
+    /// This is the fallible version of [`TrustedSetup::from_json`].
+    pub fn try_from_json(json: &str) -> Result<Self, TrustedSetupError> {
+        let trusted_setup = Self::try_from_json_unchecked(json)?;
+        trusted_setup.validate_trusted_setup()?;
+        Ok(trusted_setup)
     }
+
     /// Parse a Json string in the format specified by the ethereum trusted setup.
     ///
     /// This method does not check that the points are in the correct subgroup.
     pub fn from_json_unchecked(json: &str) -> Self {
         // Note: it is fine to panic here since this method is called on startup
         // and we want to fail fast if the trusted setup is malformed.
-        serde_json::from_str(json)
+        Self::try_from_json_unchecked(json)
             .expect("could not parse json string into a TrustedSetup structure")
     }
 
-    /// This validates that the points in the trusted setup are in the correct subgroup.
+    /// Parse a Json string in the format specified by the ethereum trusted setup.
     ///
-    /// Panics if any of the points are not in the correct subgroup
-    fn validate_trusted_setup(&self) {
-        self.to_commit_key(SubgroupCheck::Check);
-        self.to_verification_key(SubgroupCheck::Check);
+    /// This method does not check that the points are in the correct subgroup.
+    pub fn try_from_json_unchecked(json: &str) -> Result<Self, TrustedSetupError> {
+        serde_json::from_str(json).map_err(|err| TrustedSetupError::MalformedJson {
+            reason: err.to_string(),
+        })
+    }
+
+    /// This validates that the points in the trusted setup are in the correct subgroup.
+    fn validate_trusted_setup(&self) -> Result<(), TrustedSetupError> {
+        self.check_num_points()?;
+        // The points have the same encoding in every backend, so it is enough to check them with one
+        self.to_commit_key::<BlstBackend>(SubgroupCheck::Check)?;
+        self.to_verification_key::<BlstBackend>(SubgroupCheck::Check)?;
+        deserialize_g1_points::<G1Point, _>(&self.g1_lagrange, SubgroupCheck::Check)?;
+        Ok(())
+    }
+
+    /// Checks that the setup has the number of points that are needed to create a context,
+    /// which would otherwise panic when the setup is used.
+    fn check_num_points(&self) -> Result<(), TrustedSetupError> {
+        let lengths = [
+            (
+                "g1_monomial",
+                self.g1_monomial.len(),
+                FIELD_ELEMENTS_PER_BLOB,
+            ),
+            (
+                "g1_lagrange",
+                self.g1_lagrange.len(),
+                FIELD_ELEMENTS_PER_BLOB,
+            ),
+            (
+                "g2_monomial",
+                self.g2_monomial.len(),
+                FIELD_ELEMENTS_PER_CELL + 1,
+            ),
+        ];
+        for (name, found, expected) in lengths {
+            if found != expected {
+                return Err(TrustedSetupError::MalformedJson {
+                    reason: format!("expected {expected} points in {name}, found {found}"),
+                });
+            }
+        }
+        Ok(())
     }
 
-    fn to_commit_key(&self, subgroup_check: SubgroupCheck) -> CommitKey {
-        let points = deserialize_g1_points(&self.g1_monomial, subgroup_check);
-        CommitKey::new(points)
+    fn to_commit_key<B: Backend>(
+        &self,
+        subgroup_check: SubgroupCheck,
+    ) -> Result<CommitKey<B>, TrustedSetupError> {
+        let points = deserialize_g1_points(&self.g1_monomial, subgroup_check)?;
+        Ok(CommitKey::new(points))
     }
 
-    fn to_verification_key(&self, subgroup_check: SubgroupCheck) -> VerificationKey {
-        let g2_points = deserialize_g2_points(&self.g2_monomial, subgroup_check);
+    fn to_verification_key<B: Backend>(
+        &self,
+        subgroup_check: SubgroupCheck,
+    ) -> Result<VerificationKey<B>, TrustedSetupError> {
+        let g2_points = deserialize_g2_points(&self.g2_monomial, subgroup_check)?;
         let num_g2_points = g2_points.len();
         // The setup needs as many g1 elements for the verification key as g2 elements, in order
         // to commit to the remainder/interpolation polynomial.
-        let g1_points = deserialize_g1_points(&self.g1_monomial[..num_g2_points], subgroup_check);
+        let g1_points = deserialize_g1_points(
+            self.g1_monomial.get(..num_g2_points).ok_or_else(|| {
+                TrustedSetupError::MalformedJson {
+                    reason: format!(
+                        "expected at least {num_g2_points} g1 points, found {}",
+                        self.g1_monomial.len()
+                    ),
+                }
+            })?,
+            subgroup_check,
+        )?;
 
-        VerificationKey::new(
+        Ok(VerificationKey::new(
             g1_points,
             g2_points,
             FIELD_ELEMENTS_PER_CELL,
             FIELD_ELEMENTS_PER_BLOB,
-        )
+        ))
     }
 
     /// Loads the official trusted setup file being used on mainnet from the embedded data folder.
@@ -126,68 +223,94 @@ impl TrustedSetup {
     }
 }
 
-/// Deserialize G1 points from hex strings without checking that the element
+/// Decodes a `0x` prefixed hex string into the encoding of a point.
+fn decode_hex_point<G: GroupEncoding>(point_hex_str: &str) -> Option<G::Repr> {
+    let point_hex_str_without_0x = point_hex_str.strip_prefix("0x")?;
+    let bytes = hex::decode(point_hex_str_without_0x).ok()?;
+
+    let mut repr = G::Repr::default();
+    if bytes.len() != repr.as_ref().len() {
+        return None;
+    }
+    repr.as_mut().copy_from_slice(&bytes);
+    Some(repr)
+}
+
+/// Deserialize G1 points from hex strings, optionally checking that the element
 /// is in the correct subgroup.
-fn deserialize_g1_points<T: AsRef<str>>(
+fn deserialize_g1_points<G: GroupEncoding, T: AsRef<str>>(
     g1_points_hex_str: &[T],
     check: SubgroupCheck,
-) -> Vec<G1Point> {
-    let mut g1_points = Vec::new();
-    for g1_hex_str in g1_points_hex_str {
-        let g1_hex_str = g1_hex_str.as_ref();
-
-        let g1_hex_str_without_0x = g1_hex_str
-            .strip_prefix("0x")
-            .expect("expected hex points to be prefixed with `0x`");
-        let g1_point_bytes: [u8; 48] = hex::decode(g1_hex_str_without_0x)
-            .expect("trusted setup has malformed g1 points")
-            .try_into()
-            .expect("expected 48 bytes for G1 point");
-
-        let point = match check {
-            SubgroupCheck::Check => {
-                G1Point::from_compressed(&g1_point_bytes).expect("invalid g1 point")
-            }
-            SubgroupCheck::NoCheck => {
-                G1Point::from_compressed_unchecked(&g1_point_bytes).expect("invalid g1 point")
-            }
-        };
-
-        g1_points.push(point)
-    }
+) -> Result<Vec<G>, TrustedSetupError> {
+    g1_points_hex_str
+        .iter()
+        .map(|g1_hex_str| {
+            let g1_hex_str = g1_hex_str.as_ref();
+            let invalid_point = || TrustedSetupError::InvalidG1Point {
+                point: g1_hex_str.to_string(),
+            };
 
-    g1_points
+            let g1_point_bytes = decode_hex_point::<G>(g1_hex_str).ok_or_else(invalid_point)?;
+            let point = match check {
+                SubgroupCheck::Check => G::from_bytes(&g1_point_bytes),
+                SubgroupCheck::NoCheck => G::from_bytes_unchecked(&g1_point_bytes),
+            };
+            Option::from(point).ok_or_else(invalid_point)
+        })
+        .collect()
 }
 
-/// Deserialize G2 points from hex strings without checking that the element
+/// Deserialize G2 points from hex strings, optionally checking that the element
 /// is in the correct subgroup.
-fn deserialize_g2_points<T: AsRef<str>>(
+fn deserialize_g2_points<G: GroupEncoding, T: AsRef<str>>(
     g2_points_hex_str: &[T],
     subgroup_check: SubgroupCheck,
-) -> Vec<G2Point> {
-    let mut g2_points = Vec::new();
-    for g2_hex_str in g2_points_hex_str {
-        let g2_hex_str = g2_hex_str.as_ref();
-        let g2_hex_str_without_0x = g2_hex_str
-            .strip_prefix("0x")
-            .expect("expected hex points to be prefixed with `0x`");
-        let g2_point_bytes: [u8; 96] = hex::decode(g2_hex_str_without_0x)
-            .expect("trusted setup has malformed g2 points")
-            .try_into()
-            .expect("expected 96 bytes for G2 point");
-
-        let point = match subgroup_check {
-            SubgroupCheck::Check => G2Point::from_compressed(&g2_point_bytes).unwrap(),
-            SubgroupCheck::NoCheck => G2Point::from_compressed_unchecked(&g2_point_bytes).unwrap(),
-        };
-        g2_points.push(point)
-    }
+) -> Result<Vec<G>, TrustedSetupError> {
+    g2_points_hex_str
+        .iter()
+        .map(|g2_hex_str| {
+            let g2_hex_str = g2_hex_str.as_ref();
+            let invalid_point = || TrustedSetupError::InvalidG2Point {
+                point: g2_hex_str.to_string(),
+            };
 
-    g2_points
+            let g2_point_bytes = decode_hex_point::<G>(g2_hex_str).ok_or_else(invalid_point)?;
+            let point = match subgroup_check {
+                SubgroupCheck::Check => G::from_bytes(&g2_point_bytes),
+                SubgroupCheck::NoCheck => G::from_bytes_unchecked(&g2_point_bytes),
+            };
+            Option::from(point).ok_or_else(invalid_point)
+        })
+        .collect()
 }
 
 #[test]
 fn test_embedded_setup_has_points_in_correct_subgroup() {
     let setup = TrustedSetup::default();
-    setup.validate_trusted_setup();
+    setup.validate_trusted_setup().unwrap();
+}
+
+#[test]
+fn test_malformed_trusted_setup_is_an_error() {
+    assert!(matches!(
+        TrustedSetup::try_from_json("not json"),
+        Err(TrustedSetupError::MalformedJson { .. })
+    ));
+
+    let mut setup = TrustedSetup::default();
+    setup.g2_monomial[0] = "0x00".to_string();
+    let json = format!(
+        r#"{{"g1_monomial": {:?}, "g1_lagrange": {:?}, "g2_monomial": {:?}}}"#,
+        setup.g1_monomial, setup.g1_lagrange, setup.g2_monomial
+    );
+    assert!(matches!(
+        TrustedSetup::try_from_json(&json),
+        Err(TrustedSetupError::InvalidG2Point { .. })
+    ));
+
+    // A setup without any points used to panic, instead of returning an error
+    assert!(matches!(
+        TrustedSetup::try_from_json(r#"{"g1_monomial": [], "g1_lagrange": [], "g2_monomial": []}"#),
+        Err(TrustedSetupError::MalformedJson { .. })
+    ));
 }
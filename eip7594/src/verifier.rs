@@ -3,18 +3,25 @@ use std::collections::HashMap;
 pub use crate::errors::VerifierError;
 
 use crate::{
-    constants::{CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_EXT_BLOB},
+    constants::{CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_CELL, FIELD_ELEMENTS_PER_EXT_BLOB},
     errors::Error,
-    serialization::{deserialize_cells, deserialize_compressed_g1_points},
+    serialization::{
+        deserialize_cell, deserialize_cells, deserialize_compressed_g1_points_unchecked,
+        deserialize_g1, deserialize_g1_points, serialize_g1_compressed, SerializationError,
+    },
     trusted_setup::TrustedSetup,
-    with_optional_threadpool, Bytes48Ref, CellIndex, CellRef, DASContext,
+    with_optional_threadpool, Bytes48Ref, CellIndex, CellRef, DASContext, KZGCommitment,
+};
+use kzg_multi_open::{
+    backend::{Backend, BlstBackend},
+    verification_key::VerificationKey,
+    CommitmentIndex, CosetIndex, Verifier,
 };
-use kzg_multi_open::{verification_key::VerificationKey, Verifier};
 
 /// The context object that is used to call functions in the verifier API.
 #[derive(Debug)]
-pub struct VerifierContext {
-    kzg_multipoint_verifier: Verifier,
+pub struct VerifierContext<B: Backend = BlstBackend> {
+    kzg_multipoint_verifier: Verifier<B>,
 }
 
 impl Default for VerifierContext {
@@ -26,6 +33,13 @@ impl Default for VerifierContext {
 
 impl VerifierContext {
     pub fn new(trusted_setup: &TrustedSetup) -> Self {
+        Self::with_backend(trusted_setup)
+    }
+}
+
+impl<B: Backend> VerifierContext<B> {
+    /// Creates a verifier that uses the backend `B`.
+    pub fn with_backend(trusted_setup: &TrustedSetup) -> Self {
         let verification_key = VerificationKey::from(trusted_setup);
 
         let multipoint_verifier = Verifier::new(
@@ -40,6 +54,10 @@ impl VerifierContext {
     }
 }
 
+/// Deserializes the commitments or the proofs of a batch, with or without the subgroup check.
+type DeserializePoints<B> =
+    fn(Vec<Bytes48Ref>) -> Result<Vec<<B as Backend>::G1Point>, SerializationError>;
+
 /// Deduplicates a vector and creates a mapping of original indices to deduplicated indices.
 ///
 /// This function takes a vector of items and returns two vectors:
@@ -67,10 +85,295 @@ fn deduplicate_with_indices<T: Eq + std::hash::Hash + Clone>(input: Vec<T>) -> (
     (unique_items, indices)
 }
 
-impl DASContext {
+impl<B: Backend> VerifierContext<B> {
     /// Given a collection of commitments, cells and proofs, this functions verifies that
     /// the cells are consistent with the commitments using their respective KZG proofs.
     ///
+    /// This is the same as `DASContext::verify_cell_kzg_proof_batch`, but it only needs the
+    /// verifier context, which is much cheaper to create than a full `DASContext`.
+    ///
+    /// Note: Unlike the method on `DASContext`, this does not run on the thread pool of a context.
+    pub fn verify_cell_kzg_proof_batch(
+        &self,
+        commitments: Vec<Bytes48Ref>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        self.verify_cell_kzg_proof_batch_with(
+            commitments,
+            cell_indices,
+            cells,
+            proofs_bytes,
+            deserialize_g1_points::<B>,
+        )
+    }
+
+    /// Verifies that a single cell is consistent with the commitment, using its KZG proof.
+    ///
+    /// This accepts and rejects the same inputs as `verify_cell_kzg_proof_batch` with a batch
+    /// of one cell, but is cheaper, since a single opening does not need to be combined with
+    /// others using a random challenge.
+    ///
+    /// Note: Unlike the method on `DASContext`, this does not run on the thread pool of a context.
+    pub fn verify_cell_kzg_proof(
+        &self,
+        commitment: Bytes48Ref,
+        cell_index: CellIndex,
+        cell: CellRef,
+        proof_bytes: Bytes48Ref,
+    ) -> Result<(), Error> {
+        // Validation
+        //
+        validation::verify_cell_kzg_proof_batch(
+            &[commitment],
+            &[0],
+            &[cell_index],
+            &[cell],
+            &[proof_bytes],
+        )?;
+
+        // Deserialization
+        //
+        let commitment_ = deserialize_g1(commitment)?;
+        let proof_ = deserialize_g1(proof_bytes)?;
+        let coset_evals = deserialize_cell(cell)?;
+
+        // Computation
+        //
+        let ok = self.kzg_multipoint_verifier.verify_single_opening(
+            &commitment_,
+            cell_index.as_u64(),
+            &coset_evals,
+            &proof_,
+        );
+
+        ok.map_err(VerifierError::from).map_err(Into::into)
+    }
+
+    /// Verifies a batch of cells like `verify_cell_kzg_proof_batch`, but without checking that
+    /// the commitments and proofs are in the prime order subgroup of G1.
+    ///
+    /// The subgroup checks are the most expensive part of verifying a small batch. They can
+    /// be skipped when the caller knows that the commitments and proofs were computed by this
+    /// library, for example when verifying cells that it has just recovered.
+    ///
+    /// Note: The result is unspecified for points that are not in the prime order subgroup, so
+    /// this must not be used for commitments and proofs that were received from the network.
+    pub fn verify_cell_kzg_proof_batch_unchecked(
+        &self,
+        commitments: Vec<Bytes48Ref>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        self.verify_cell_kzg_proof_batch_with(
+            commitments,
+            cell_indices,
+            cells,
+            proofs_bytes,
+            deserialize_compressed_g1_points_unchecked::<B::G1Point>,
+        )
+    }
+
+    fn verify_cell_kzg_proof_batch_with(
+        &self,
+        commitments: Vec<Bytes48Ref>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+        deserialize_points: DeserializePoints<B>,
+    ) -> Result<(), Error> {
+        let (deduplicated_commitments, row_indices) = deduplicate_with_indices(commitments);
+        // Validation
+        //
+        validation::verify_cell_kzg_proof_batch(
+            &deduplicated_commitments,
+            &row_indices,
+            &cell_indices,
+            &cells,
+            &proofs_bytes,
+        )?;
+
+        // If there are no inputs, we return early with no error
+        //
+        if cells.is_empty() {
+            return Ok(());
+        }
+
+        // Deserialization
+        //
+        let row_commitments_ = deserialize_points(deduplicated_commitments)?;
+        let proofs_ = deserialize_points(proofs_bytes)?;
+        let coset_evals = deserialize_cells(cells)?;
+
+        // Computation
+        //
+        let ok = self.kzg_multipoint_verifier.verify_multi_opening(
+            &row_commitments_,
+            &row_indices,
+            &coset_indices(&cell_indices),
+            &coset_evals,
+            &proofs_,
+        );
+
+        ok.map_err(VerifierError::from).map_err(Into::into)
+    }
+
+    /// Verifies a batch of cells like `verify_cell_kzg_proof_batch`, but takes the commitments and
+    /// proofs as points, and each cell as its field elements, instead of their serialized bytes.
+    ///
+    /// This is for callers that have just computed these values, such as when verifying the cells
+    /// of a blob that was recovered, so that they are not serialized and then deserialized again.
+    ///
+    /// Note: The points are not checked to be in the prime order subgroup, which is the case for
+    /// every point that was deserialized with the subgroup check or computed from such points.
+    pub fn verify_cell_kzg_proof_batch_scalars(
+        &self,
+        commitments: &[B::G1Point],
+        cell_indices: &[CellIndex],
+        cells: &[&[B::Scalar]],
+        proofs: &[B::G1Point],
+    ) -> Result<(), Error> {
+        // Deduplicate the commitments by their serialization, since points cannot be hashed
+        let mut commitment_positions = HashMap::new();
+        let mut deduplicated_commitments = Vec::new();
+        let row_indices: Vec<CommitmentIndex> = commitments
+            .iter()
+            .map(|commitment| {
+                *commitment_positions
+                    .entry(serialize_g1_compressed(commitment))
+                    .or_insert_with(|| {
+                        deduplicated_commitments.push(*commitment);
+                        (deduplicated_commitments.len() - 1) as CommitmentIndex
+                    })
+            })
+            .collect();
+        // Validation
+        //
+        validation::verify_cell_kzg_proof_batch(
+            &deduplicated_commitments,
+            &row_indices,
+            cell_indices,
+            cells,
+            proofs,
+        )?;
+        for cell in cells {
+            if cell.len() != FIELD_ELEMENTS_PER_CELL {
+                return Err(VerifierError::CellHasInvalidLength {
+                    num_field_elements: cell.len(),
+                    expected_num_field_elements: FIELD_ELEMENTS_PER_CELL,
+                }
+                .into());
+            }
+        }
+
+        // If there are no inputs, we return early with no error
+        //
+        if cells.is_empty() {
+            return Ok(());
+        }
+
+        // Computation
+        //
+        let coset_evals: Vec<Vec<B::Scalar>> = cells.iter().map(|cell| cell.to_vec()).collect();
+        let ok = self.kzg_multipoint_verifier.verify_multi_opening(
+            &deduplicated_commitments,
+            &row_indices,
+            &coset_indices(cell_indices),
+            &coset_evals,
+            proofs,
+        );
+
+        ok.map_err(VerifierError::from).map_err(Into::into)
+    }
+
+    /// Verifies all of the batches that have been added to the aggregate, using a single
+    /// random linear combination and multi-pairing.
+    ///
+    /// This succeeds exactly when `verify_cell_kzg_proof_batch` succeeds for every batch
+    /// that was added. An empty aggregate is valid.
+    ///
+    /// Note: Unlike the method on `DASContext`, this does not run on the thread pool of a context.
+    pub fn verify_aggregate(&self, aggregate: &AggregateVerifier<B>) -> Result<(), Error> {
+        if aggregate.cell_indices.is_empty() {
+            return Ok(());
+        }
+
+        let ok = self.kzg_multipoint_verifier.verify_multi_opening(
+            &aggregate.commitments,
+            &aggregate.commitment_indices,
+            &aggregate.cell_indices,
+            &aggregate.coset_evals,
+            &aggregate.proofs,
+        );
+
+        ok.map_err(VerifierError::from).map_err(Into::into)
+    }
+
+    /// Verifies a column of cells, where each cell is at `column_index` of a different blob.
+    ///
+    /// This accepts and rejects the same inputs as `verify_cell_kzg_proof_batch` called with
+    /// `column_index` as the index of every cell, as in a `DataColumnSidecar`. Since all of the
+    /// cells are on the same coset, it is cheaper than verifying them as a general batch.
+    ///
+    /// Note: Unlike the method on `DASContext`, this does not run on the thread pool of a context.
+    pub fn verify_column(
+        &self,
+        commitments: Vec<Bytes48Ref>,
+        column_index: CellIndex,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        let (deduplicated_commitments, row_indices) = deduplicate_with_indices(commitments);
+        let cell_indices = vec![column_index; cells.len()];
+        // Validation
+        //
+        validation::verify_cell_kzg_proof_batch(
+            &deduplicated_commitments,
+            &row_indices,
+            &cell_indices,
+            &cells,
+            &proofs_bytes,
+        )?;
+
+        // If there are no inputs, we return early with no error
+        //
+        if cells.is_empty() {
+            return Ok(());
+        }
+
+        // Deserialization
+        //
+        let row_commitments_ = deserialize_g1_points::<B>(deduplicated_commitments)?;
+        let proofs_ = deserialize_g1_points::<B>(proofs_bytes)?;
+        let coset_evals = deserialize_cells(cells)?;
+
+        // Computation
+        //
+        let ok = self
+            .kzg_multipoint_verifier
+            .verify_multi_opening_same_coset(
+                &row_commitments_,
+                &row_indices,
+                column_index.as_u64(),
+                &coset_evals,
+                &proofs_,
+            );
+
+        ok.map_err(VerifierError::from).map_err(Into::into)
+    }
+}
+
+impl<B: Backend> DASContext<B> {
+    /// Given a collection of commitments, cells and proofs, this functions verifies that
+    /// the cells are consistent with the commitments using their respective KZG proofs.
+    ///
+    /// The random weights that combine the openings are derived from a hash of the inputs
+    /// (Fiat-Shamir), as in the specs, rather than sampled from an RNG. Verifying the same inputs
+    /// therefore does the same computation on every run and machine, which makes a disagreement
+    /// with another implementation reproducible.
+    ///
     /// The matching function in the specs is: https://github.com/ethereum/consensus-specs/blob/13ac373a2c284dc66b48ddd2ef0a10537e4e0de6/specs/_features/eip7594/polynomial-commitments-sampling.md#verify_cell_kzg_proof_batch
     pub fn verify_cell_kzg_proof_batch(
         &self,
@@ -80,94 +383,271 @@ impl DASContext {
         proofs_bytes: Vec<Bytes48Ref>,
     ) -> Result<(), Error> {
         with_optional_threadpool!(self, {
-            let (deduplicated_commitments, row_indices) = deduplicate_with_indices(commitments);
-            // Validation
-            //
-            validation::verify_cell_kzg_proof_batch(
-                &deduplicated_commitments,
-                &row_indices,
-                &cell_indices,
-                &cells,
-                &proofs_bytes,
-            )?;
-
-            // If there are no inputs, we return early with no error
-            //
-            if cells.is_empty() {
-                return Ok(());
-            }
+            self.try_verifier_ctx()?.verify_cell_kzg_proof_batch(
+                commitments,
+                cell_indices,
+                cells,
+                proofs_bytes,
+            )
+        })
+    }
 
-            // Deserialization
-            //
-            let row_commitments_ = deserialize_compressed_g1_points(deduplicated_commitments)?;
-            let proofs_ = deserialize_compressed_g1_points(proofs_bytes)?;
-            let coset_evals = deserialize_cells(cells)?;
-
-            // Computation
-            //
-            let ok = self
-                .verifier_ctx
-                .kzg_multipoint_verifier
-                .verify_multi_opening(
-                    &row_commitments_,
-                    &row_indices,
-                    &cell_indices,
-                    &coset_evals,
-                    &proofs_,
-                );
-
-            ok.map_err(VerifierError::from).map_err(Into::into)
+    /// Verifies that a single cell is consistent with the commitment, using its KZG proof.
+    ///
+    /// This is cheaper than calling `verify_cell_kzg_proof_batch` with a batch of one cell, see
+    /// `VerifierContext::verify_cell_kzg_proof`.
+    pub fn verify_cell_kzg_proof(
+        &self,
+        commitment: Bytes48Ref,
+        cell_index: CellIndex,
+        cell: CellRef,
+        proof_bytes: Bytes48Ref,
+    ) -> Result<(), Error> {
+        with_optional_threadpool!(self, {
+            self.try_verifier_ctx()?.verify_cell_kzg_proof(
+                commitment,
+                cell_index,
+                cell,
+                proof_bytes,
+            )
         })
     }
+
+    /// Verifies a batch of cells without checking that the commitments and proofs are in the
+    /// prime order subgroup, see `VerifierContext::verify_cell_kzg_proof_batch_unchecked`.
+    ///
+    /// Note: This must only be used for commitments and proofs that were computed by this library.
+    pub fn verify_cell_kzg_proof_batch_unchecked(
+        &self,
+        commitments: Vec<Bytes48Ref>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        with_optional_threadpool!(self, {
+            self.try_verifier_ctx()?
+                .verify_cell_kzg_proof_batch_unchecked(
+                    commitments,
+                    cell_indices,
+                    cells,
+                    proofs_bytes,
+                )
+        })
+    }
+
+    /// Verifies a batch of cells that are given as points and field elements, instead of their
+    /// serialized bytes, see `VerifierContext::verify_cell_kzg_proof_batch_scalars`.
+    pub fn verify_cell_kzg_proof_batch_scalars(
+        &self,
+        commitments: &[B::G1Point],
+        cell_indices: &[CellIndex],
+        cells: &[&[B::Scalar]],
+        proofs: &[B::G1Point],
+    ) -> Result<(), Error> {
+        with_optional_threadpool!(self, {
+            self.try_verifier_ctx()?
+                .verify_cell_kzg_proof_batch_scalars(commitments, cell_indices, cells, proofs)
+        })
+    }
+
+    /// Verifies a column of cells, where each cell is at `column_index` of a different blob.
+    ///
+    /// This is an optimized version of `verify_cell_kzg_proof_batch` for the case where all of the
+    /// cell indices are equal, see `VerifierContext::verify_column`.
+    pub fn verify_column(
+        &self,
+        commitments: Vec<Bytes48Ref>,
+        column_index: CellIndex,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        with_optional_threadpool!(self, {
+            self.try_verifier_ctx()?
+                .verify_column(commitments, column_index, cells, proofs_bytes)
+        })
+    }
+
+    /// Verifies all of the batches that have been added to the aggregate, see
+    /// `VerifierContext::verify_aggregate`.
+    pub fn verify_aggregate(&self, aggregate: &AggregateVerifier<B>) -> Result<(), Error> {
+        with_optional_threadpool!(self, self.try_verifier_ctx()?.verify_aggregate(aggregate))
+    }
+}
+
+/// Collects independent batches of cells, such as the cells from several gossip messages,
+/// so that they can be verified together with `verify_aggregate`.
+///
+/// Verifying the batches together costs a single multi-pairing, instead of one for each batch.
+/// Each batch is validated and deserialized when it is added, so a malformed batch is rejected
+/// by `add_batch` and does not affect the batches that have already been added.
+///
+/// Note: `verify_aggregate` only reports whether all of the batches are valid. If it fails, the
+/// batches need to be verified on their own to find the ones that are invalid.
+#[derive(Debug, Clone)]
+pub struct AggregateVerifier<B: Backend = BlstBackend> {
+    /// The position of each commitment in `commitments`, so that a commitment that appears in
+    /// several batches is only included once.
+    commitment_positions: HashMap<KZGCommitment, CommitmentIndex>,
+    commitments: Vec<B::G1Point>,
+    commitment_indices: Vec<CommitmentIndex>,
+    cell_indices: Vec<CosetIndex>,
+    coset_evals: Vec<Vec<B::Scalar>>,
+    proofs: Vec<B::G1Point>,
+    num_batches: usize,
+}
+
+impl Default for AggregateVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregateVerifier {
+    /// Creates an aggregate that does not contain any batches.
+    pub fn new() -> Self {
+        Self::with_backend()
+    }
+}
+
+impl<B: Backend> AggregateVerifier<B> {
+    /// Creates an aggregate that does not contain any batches, for a verifier that uses the
+    /// backend `B`.
+    pub fn with_backend() -> Self {
+        Self {
+            commitment_positions: HashMap::new(),
+            commitments: Vec::new(),
+            commitment_indices: Vec::new(),
+            cell_indices: Vec::new(),
+            coset_evals: Vec::new(),
+            proofs: Vec::new(),
+            num_batches: 0,
+        }
+    }
+
+    /// Adds a batch, which takes the same inputs as `verify_cell_kzg_proof_batch`.
+    ///
+    /// Returns an error if `verify_cell_kzg_proof_batch` would have rejected the batch before
+    /// checking its proofs, in which case the batch is not added.
+    pub fn add_batch(
+        &mut self,
+        commitments: Vec<Bytes48Ref>,
+        cell_indices: Vec<CellIndex>,
+        cells: Vec<CellRef>,
+        proofs_bytes: Vec<Bytes48Ref>,
+    ) -> Result<(), Error> {
+        let (deduplicated_commitments, row_indices) = deduplicate_with_indices(commitments);
+        // Validation
+        //
+        validation::verify_cell_kzg_proof_batch(
+            &deduplicated_commitments,
+            &row_indices,
+            &cell_indices,
+            &cells,
+            &proofs_bytes,
+        )?;
+
+        // Deserialization
+        //
+        let row_commitments_ = deserialize_g1_points::<B>(deduplicated_commitments.clone())?;
+        let proofs_ = deserialize_g1_points::<B>(proofs_bytes)?;
+        let coset_evals = deserialize_cells(cells)?;
+
+        // Map the commitment indices of the batch to the commitments of the aggregate
+        //
+        let positions: Vec<CommitmentIndex> = deduplicated_commitments
+            .into_iter()
+            .zip(row_commitments_)
+            .map(|(bytes, commitment)| {
+                *self.commitment_positions.entry(*bytes).or_insert_with(|| {
+                    self.commitments.push(commitment);
+                    (self.commitments.len() - 1) as CommitmentIndex
+                })
+            })
+            .collect();
+        self.commitment_indices.extend(
+            row_indices
+                .into_iter()
+                .map(|row_index| positions[row_index as usize]),
+        );
+        self.cell_indices
+            .extend(cell_indices.into_iter().map(CellIndex::as_u64));
+        self.coset_evals.extend(coset_evals);
+        self.proofs.extend(proofs_);
+        self.num_batches += 1;
+
+        Ok(())
+    }
+
+    /// Returns the number of batches that have been added.
+    pub const fn num_batches(&self) -> usize {
+        self.num_batches
+    }
+
+    /// Returns the total number of cells in the batches that have been added.
+    pub fn num_cells(&self) -> usize {
+        self.cell_indices.len()
+    }
+
+    /// Removes all of the batches, keeping the allocated memory so that the aggregate can be reused.
+    pub fn clear(&mut self) {
+        self.commitment_positions.clear();
+        self.commitments.clear();
+        self.commitment_indices.clear();
+        self.cell_indices.clear();
+        self.coset_evals.clear();
+        self.proofs.clear();
+        self.num_batches = 0;
+    }
+}
+
+/// Converts the cell indices to the coset indices that the multi-opening verifier takes.
+fn coset_indices(cell_indices: &[CellIndex]) -> Vec<CosetIndex> {
+    cell_indices
+        .iter()
+        .map(|cell_index| cell_index.as_u64())
+        .collect()
 }
 
 mod validation {
     use kzg_multi_open::CommitmentIndex;
 
-    use crate::{
-        constants::CELLS_PER_EXT_BLOB, verifier::VerifierError, Bytes48Ref, CellIndex, CellRef,
-    };
+    use crate::{verifier::VerifierError, CellIndex};
 
     /// Validation logic for `verify_cell_kzg_proof_batch`
-    pub fn verify_cell_kzg_proof_batch(
-        deduplicated_commitments_bytes: &[Bytes48Ref],
+    ///
+    /// The commitments, cells and proofs are either their serialized bytes, or their
+    /// deserialized values.
+    pub fn verify_cell_kzg_proof_batch<C, P>(
+        deduplicated_commitments: &[P],
         commitment_indices: &[CommitmentIndex],
         cell_indices: &[CellIndex],
-        cells: &[CellRef],
-        proofs_bytes: &[Bytes48Ref],
+        cells: &[C],
+        proofs: &[P],
     ) -> Result<(), VerifierError> {
         // All inputs must have the same length according to the specs.
         let same_length = (commitment_indices.len() == cell_indices.len())
             & (commitment_indices.len() == cells.len())
-            & (commitment_indices.len() == proofs_bytes.len());
+            & (commitment_indices.len() == proofs.len());
         if !same_length {
             return Err(VerifierError::BatchVerificationInputsMustHaveSameLength {
                 commitment_indices_len: commitment_indices.len(),
                 cell_indices_len: cell_indices.len(),
                 cells_len: cells.len(),
-                proofs_len: proofs_bytes.len(),
+                proofs_len: proofs.len(),
             });
         }
 
         // Check that the commitment indices are within the correct range
         for commitment_index in commitment_indices {
-            if *commitment_index >= deduplicated_commitments_bytes.len() as u64 {
+            if *commitment_index >= deduplicated_commitments.len() as u64 {
                 return Err(VerifierError::InvalidCommitmentIndex {
                     commitment_index: *commitment_index,
-                    max_number_of_commitments: deduplicated_commitments_bytes.len() as u64,
+                    max_number_of_commitments: deduplicated_commitments.len() as u64,
                 });
             }
         }
 
-        // Check that cell indices are in the correct range
-        for cell_index in cell_indices {
-            if *cell_index >= CELLS_PER_EXT_BLOB as u64 {
-                return Err(VerifierError::CellIndexOutOfRange {
-                    cell_index: *cell_index,
-                    max_number_of_cells: CELLS_PER_EXT_BLOB as u64,
-                });
-            }
-        }
+        // Note: The cell indices do not need to be checked, since a `CellIndex` is always in range
 
         Ok(())
     }
@@ -0,0 +1,68 @@
+//! Checks that a `DASContext` that uses the `Arkworks` backend computes the same commitments,
+//! cells and proofs as the default context, and that it can verify and recover them.
+#![cfg(feature = "arkworks")]
+
+use rust_eth_kzg::{
+    constants::CELLS_PER_EXT_BLOB, test_utils::random_blob, Arkworks, CellIndex, CellRef,
+    ContextMode, DASContext, TrustedSetup, UsePrecomp,
+};
+
+fn arkworks_context() -> DASContext<Arkworks> {
+    let trusted_setup = TrustedSetup::default();
+    DASContext::with_backend(
+        &trusted_setup,
+        #[cfg(feature = "multithreaded")]
+        rust_eth_kzg::ThreadCount::SensibleDefault,
+        UsePrecomp::No,
+        ContextMode::Both,
+    )
+}
+
+#[test]
+fn arkworks_round_trip() {
+    let ctx = arkworks_context();
+    let blst_ctx = DASContext::default();
+    let blob = random_blob(&mut rand::thread_rng());
+
+    let commitment = ctx.blob_to_kzg_commitment(&blob).unwrap();
+    assert_eq!(commitment, blst_ctx.blob_to_kzg_commitment(&blob).unwrap());
+
+    let (cells, proofs) = ctx.compute_cells_and_kzg_proofs(&blob).unwrap();
+    let (blst_cells, blst_proofs) = blst_ctx.compute_cells_and_kzg_proofs(&blob).unwrap();
+    assert_eq!(cells, blst_cells);
+    assert_eq!(proofs, blst_proofs);
+
+    let cell_indices: Vec<_> = (0..CELLS_PER_EXT_BLOB as u64)
+        .map(|index| CellIndex::new(index).unwrap())
+        .collect();
+    let cell_refs: Vec<CellRef> = cells.iter().map(|cell| &**cell).collect();
+    ctx.verify_cell_kzg_proof_batch(
+        vec![&commitment; CELLS_PER_EXT_BLOB],
+        cell_indices.clone(),
+        cell_refs.clone(),
+        proofs.iter().collect(),
+    )
+    .unwrap();
+
+    // The proof of one cell does not verify another cell
+    let mut wrong_proofs: Vec<_> = proofs.iter().collect();
+    wrong_proofs.swap(0, 1);
+    assert!(ctx
+        .verify_cell_kzg_proof_batch(
+            vec![&commitment; CELLS_PER_EXT_BLOB],
+            cell_indices.clone(),
+            cell_refs.clone(),
+            wrong_proofs,
+        )
+        .is_err());
+
+    // Half of the cells are enough to recover all of them
+    let (recovered_cells, recovered_proofs) = ctx
+        .recover_cells_and_kzg_proofs(
+            cell_indices.into_iter().step_by(2).collect(),
+            cell_refs.into_iter().step_by(2).collect(),
+        )
+        .unwrap();
+    assert_eq!(recovered_cells, cells);
+    assert_eq!(recovered_proofs, proofs);
+}
@@ -1,59 +1,7 @@
-use common::collect_test_files;
-use rust_eth_kzg::constants::BYTES_PER_BLOB;
-use serde_::TestVector;
+use rust_eth_kzg::constants::{BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT, FIELD_ELEMENTS_PER_BLOB};
+use rust_eth_kzg::test_utils::{collect_test_files, BlobToKZGCommitmentTestVector as TestVector};
 use std::fs;
 
-mod common;
-
-mod serde_ {
-
-    use crate::common::UnsafeBytes;
-
-    use super::common::bytes_from_hex;
-    use serde::Deserialize;
-
-    #[derive(Deserialize)]
-    struct YamlInput {
-        blob: String,
-    }
-
-    type YamlOutput = String;
-
-    #[derive(Deserialize)]
-    struct YamlTestVector {
-        input: YamlInput,
-        output: Option<YamlOutput>,
-    }
-
-    pub struct TestVector {
-        pub blob: UnsafeBytes,
-        pub commitment: Option<UnsafeBytes>,
-    }
-
-    impl TestVector {
-        pub fn from_str(yaml_data: &str) -> Self {
-            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
-            Self::from(yaml_test_vector)
-        }
-    }
-
-    impl From<YamlTestVector> for TestVector {
-        fn from(yaml_test_vector: YamlTestVector) -> Self {
-            let input = yaml_test_vector.input.blob;
-            let output = yaml_test_vector.output;
-
-            let input = bytes_from_hex(&input);
-
-            let commitment = output.map(|commitment| bytes_from_hex(&commitment));
-
-            Self {
-                blob: input,
-                commitment,
-            }
-        }
-    }
-}
-
 const TEST_DIR: &str = "../test_vectors/blob_to_kzg_commitment";
 #[test]
 fn test_blob_to_kzg_commitment() {
@@ -63,7 +11,7 @@ fn test_blob_to_kzg_commitment() {
 
     for test_file in test_files {
         let yaml_data = fs::read_to_string(test_file).unwrap();
-        let test = TestVector::from_str(&yaml_data);
+        let test = yaml_data.parse::<TestVector>().unwrap();
 
         //
         let blob: &[u8; BYTES_PER_BLOB] = match (&test.blob[..]).try_into() {
@@ -88,3 +36,155 @@ fn test_blob_to_kzg_commitment() {
         };
     }
 }
+
+#[test]
+fn test_blob_to_kzg_commitment_batch() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    let mut valid_blobs = Vec::new();
+    let mut expected_commitments = Vec::new();
+    let mut invalid_blobs = Vec::new();
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        let Ok(blob) = <[u8; BYTES_PER_BLOB]>::try_from(test.blob) else {
+            continue;
+        };
+        match test.commitment {
+            Some(commitment) => {
+                valid_blobs.push(blob);
+                expected_commitments.push(commitment);
+            }
+            None => invalid_blobs.push(blob),
+        }
+    }
+
+    let commitments = ctx
+        .blob_to_kzg_commitment_batch(valid_blobs.iter().collect())
+        .unwrap();
+    assert_eq!(commitments.len(), expected_commitments.len());
+    for (commitment, expected_commitment) in commitments.iter().zip(expected_commitments) {
+        assert_eq!(&commitment[..], &expected_commitment);
+    }
+
+    // A single invalid blob causes the whole batch to fail
+    if let Some(invalid_blob) = invalid_blobs.first() {
+        let mut blobs: Vec<_> = valid_blobs.iter().collect();
+        blobs.push(invalid_blob);
+        assert!(ctx.blob_to_kzg_commitment_batch(blobs).is_err());
+    }
+}
+
+#[test]
+fn test_update_commitment() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        let (Ok(blob), Some(commitment)) =
+            (<[u8; BYTES_PER_BLOB]>::try_from(test.blob), test.commitment)
+        else {
+            continue;
+        };
+        let commitment = commitment.try_into().unwrap();
+
+        // Change the first, a middle and the last field element of the blob
+        let mut new_blob = blob;
+        let indices = [0, 1234, FIELD_ELEMENTS_PER_BLOB - 1];
+        for (i, &index) in indices.iter().enumerate() {
+            let start = index * BYTES_PER_FIELD_ELEMENT;
+            new_blob[start..start + BYTES_PER_FIELD_ELEMENT].fill(0);
+            new_blob[start + BYTES_PER_FIELD_ELEMENT - 1] = i as u8 + 1;
+        }
+        let field_element = |blob: &[u8; BYTES_PER_BLOB], index: usize| -> [u8; 32] {
+            let start = index * BYTES_PER_FIELD_ELEMENT;
+            blob[start..start + BYTES_PER_FIELD_ELEMENT]
+                .try_into()
+                .unwrap()
+        };
+        let old_values: Vec<_> = indices.iter().map(|&i| field_element(&blob, i)).collect();
+        let new_values: Vec<_> = indices
+            .iter()
+            .map(|&i| field_element(&new_blob, i))
+            .collect();
+        let updates = indices
+            .iter()
+            .zip(&old_values)
+            .zip(&new_values)
+            .map(|((&index, old), new)| (index, old, new))
+            .collect();
+
+        let updated_commitment = ctx.update_commitment(&commitment, updates).unwrap();
+        assert_eq!(
+            updated_commitment,
+            ctx.blob_to_kzg_commitment(&new_blob).unwrap()
+        );
+
+        // No updates leaves the commitment unchanged
+        assert_eq!(
+            ctx.update_commitment(&commitment, Vec::new()).unwrap(),
+            commitment
+        );
+
+        // The index must be within the blob
+        let zero = [0u8; BYTES_PER_FIELD_ELEMENT];
+        assert!(ctx
+            .update_commitment(&commitment, vec![(FIELD_ELEMENTS_PER_BLOB, &zero, &zero)])
+            .is_err());
+
+        // The values must be canonical field elements
+        let non_canonical = [0xff; BYTES_PER_FIELD_ELEMENT];
+        assert!(ctx
+            .update_commitment(&commitment, vec![(0, &zero, &non_canonical)])
+            .is_err());
+    }
+}
+
+#[test]
+fn test_blob_committer() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        // Use chunk sizes that split field elements across chunks, as well as ones that do not
+        for chunk_size in [1000, 7, BYTES_PER_FIELD_ELEMENT, BYTES_PER_BLOB] {
+            let mut committer = ctx.blob_committer().unwrap();
+            let result = test
+                .blob
+                .chunks(chunk_size)
+                .try_for_each(|chunk| committer.update(chunk))
+                .and_then(|()| committer.finalize());
+
+            match result {
+                Ok(commitment) => assert_eq!(&commitment[..], test.commitment.as_ref().unwrap()),
+                Err(_) => assert!(test.commitment.is_none()),
+            }
+        }
+    }
+
+    // The blob must have exactly `BYTES_PER_BLOB` bytes
+    let mut committer = ctx.blob_committer().unwrap();
+    committer.update(&[0; BYTES_PER_BLOB - 1]).unwrap();
+    assert!(committer.update(&[0; 2]).is_err());
+    assert_eq!(committer.num_bytes(), BYTES_PER_BLOB - 1);
+    assert!(committer.clone().finalize().is_err());
+    committer.update(&[0]).unwrap();
+    assert!(committer.finalize().is_ok());
+
+    // A chunk with an invalid field element does not change the committer
+    let mut committer = ctx.blob_committer().unwrap();
+    committer.update(&[0; 10]).unwrap();
+    assert!(committer.update(&[0xff; 60]).is_err());
+    assert_eq!(committer.num_bytes(), 10);
+}
@@ -1,76 +1,16 @@
-use common::collect_test_files;
-use serde_::TestVector;
+use bls12_381::ff::Field;
+use rust_eth_kzg::test_utils::{
+    collect_test_files, ComputeCellsAndKZGProofsTestVector as TestVector,
+};
+use rust_eth_kzg::{
+    constants::{
+        BYTES_PER_BLOB, BYTES_PER_EXT_BLOB, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB,
+        FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENTS_PER_EXT_BLOB,
+    },
+    CellIndex, Scalar,
+};
 use std::fs;
 
-mod common;
-
-mod serde_ {
-    use crate::common::UnsafeBytes;
-
-    use super::common::bytes_from_hex;
-    use serde::Deserialize;
-
-    #[derive(Deserialize)]
-    struct YamlInput {
-        blob: String,
-    }
-
-    type YamlOutput = (Vec<String>, Vec<String>);
-
-    #[derive(Deserialize)]
-    struct YamlTestVector {
-        input: YamlInput,
-        output: Option<YamlOutput>,
-    }
-
-    #[derive(Debug, Clone)]
-    pub struct KZGProofsAndCells {
-        pub proofs: Vec<UnsafeBytes>,
-        pub cells: Vec<UnsafeBytes>,
-    }
-
-    pub struct TestVector {
-        pub blob: UnsafeBytes,
-        pub proofs_and_cells: Option<KZGProofsAndCells>,
-    }
-
-    impl TestVector {
-        pub fn from_str(yaml_data: &str) -> Self {
-            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
-            Self::from(yaml_test_vector)
-        }
-    }
-
-    impl From<YamlTestVector> for TestVector {
-        fn from(yaml_test_vector: YamlTestVector) -> Self {
-            let input = yaml_test_vector.input.blob;
-            let output = yaml_test_vector.output;
-
-            let input = bytes_from_hex(&input);
-
-            let output = match output {
-                Some((cells, kzg_proofs)) => {
-                    let kzg_proofs: Vec<_> = kzg_proofs
-                        .iter()
-                        .map(|proof| bytes_from_hex(proof))
-                        .collect();
-                    let cells: Vec<_> = cells.iter().map(|cell| bytes_from_hex(cell)).collect();
-                    Some((kzg_proofs, cells))
-                }
-                None => None,
-            };
-
-            Self {
-                blob: input,
-                proofs_and_cells: output.map(|out| KZGProofsAndCells {
-                    proofs: out.0,
-                    cells: out.1,
-                }),
-            }
-        }
-    }
-}
-
 const TEST_DIR: &str = "../test_vectors/compute_cells_and_kzg_proofs";
 #[test]
 fn test_compute_cells_and_kzg_proofs() {
@@ -80,7 +20,7 @@ fn test_compute_cells_and_kzg_proofs() {
 
     for test_file in test_files {
         let yaml_data = fs::read_to_string(test_file).unwrap();
-        let test = TestVector::from_str(&yaml_data);
+        let test = yaml_data.parse::<TestVector>().unwrap();
 
         let blob = match test.blob.try_into() {
             Ok(blob) => blob,
@@ -122,3 +62,161 @@ fn test_compute_cells_and_kzg_proofs() {
         };
     }
 }
+
+#[test]
+fn test_compute_cells_and_kzg_proofs_from_scalars() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    let mut num_blobs = 0;
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        let Ok(blob) = <[u8; BYTES_PER_BLOB]>::try_from(test.blob) else {
+            continue;
+        };
+        let Ok(expected) = ctx.compute_cells_and_kzg_proofs(&blob) else {
+            continue;
+        };
+
+        let evaluations: Vec<Scalar> = blob
+            .chunks_exact(BYTES_PER_FIELD_ELEMENT)
+            .map(|bytes| Scalar::from_bytes_be(bytes.try_into().unwrap()).unwrap())
+            .collect();
+        let evaluations: [Scalar; FIELD_ELEMENTS_PER_BLOB] = evaluations.try_into().unwrap();
+        assert_eq!(
+            ctx.compute_cells_and_kzg_proofs_from_evaluations(&evaluations)
+                .unwrap(),
+            expected
+        );
+        num_blobs += 1;
+    }
+    assert!(num_blobs > 0);
+
+    // The constant polynomial evaluates to the same field element everywhere, so its blob is
+    // that field element repeated
+    let constant = Scalar::from(42u64);
+    let mut coefficients = [Scalar::ZERO; FIELD_ELEMENTS_PER_BLOB];
+    coefficients[0] = constant;
+    let blob: Vec<u8> = (0..FIELD_ELEMENTS_PER_BLOB)
+        .flat_map(|_| constant.to_bytes_be())
+        .collect();
+    assert_eq!(
+        ctx.compute_cells_and_kzg_proofs_from_coefficients(&coefficients)
+            .unwrap(),
+        ctx.compute_cells_and_kzg_proofs(&blob.try_into().unwrap())
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_compute_cell_and_kzg_proof() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        let Ok(blob) = <[u8; BYTES_PER_BLOB]>::try_from(test.blob) else {
+            continue;
+        };
+
+        let Some(expected_proofs_and_cells) = test.proofs_and_cells else {
+            let cell_index = CellIndex::try_from(0u64).unwrap();
+            assert!(ctx.compute_cell_and_kzg_proof(&blob, cell_index).is_err());
+            continue;
+        };
+
+        for cell_index in [0, 1, 64, CELLS_PER_EXT_BLOB - 1] {
+            let (cell, proof) = ctx
+                .compute_cell_and_kzg_proof(&blob, CellIndex::try_from(cell_index).unwrap())
+                .unwrap();
+            assert_eq!(&cell[..], expected_proofs_and_cells.cells[cell_index]);
+            assert_eq!(&proof[..], expected_proofs_and_cells.proofs[cell_index]);
+        }
+
+        assert!(CellIndex::try_from(CELLS_PER_EXT_BLOB).is_err());
+    }
+}
+
+#[test]
+fn test_compute_extended_blob() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        let Ok(blob) = <[u8; BYTES_PER_BLOB]>::try_from(test.blob) else {
+            continue;
+        };
+
+        let Some(expected_proofs_and_cells) = test.proofs_and_cells else {
+            assert!(ctx.compute_extended_blob(&blob).is_err());
+            assert!(ctx.compute_extended_blob_scalars(&blob).is_err());
+            continue;
+        };
+
+        // The extended blob is the concatenation of the cells, and starts with the blob
+        let extended_blob = ctx.compute_extended_blob(&blob).unwrap();
+        assert_eq!(extended_blob.len(), BYTES_PER_EXT_BLOB);
+        assert_eq!(
+            extended_blob.to_vec(),
+            expected_proofs_and_cells.cells.concat()
+        );
+        assert_eq!(&extended_blob[..BYTES_PER_BLOB], &blob[..]);
+
+        let scalars = ctx.compute_extended_blob_scalars(&blob).unwrap();
+        assert_eq!(scalars.len(), FIELD_ELEMENTS_PER_EXT_BLOB);
+        for (scalar, bytes) in scalars
+            .iter()
+            .zip(extended_blob.chunks_exact(BYTES_PER_FIELD_ELEMENT))
+        {
+            assert_eq!(scalar.to_bytes_be(), bytes);
+        }
+    }
+}
+
+#[test]
+fn test_compute_cells_and_kzg_proofs_batch() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    let mut valid_blobs = Vec::new();
+    let mut invalid_blobs = Vec::new();
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        let Ok(blob) = <[u8; BYTES_PER_BLOB]>::try_from(test.blob) else {
+            continue;
+        };
+        if test.proofs_and_cells.is_some() {
+            valid_blobs.push(blob);
+        } else {
+            invalid_blobs.push(blob);
+        }
+    }
+
+    let results = ctx
+        .compute_cells_and_kzg_proofs_batch(valid_blobs.iter().collect())
+        .unwrap();
+    assert_eq!(results.len(), valid_blobs.len());
+    for (blob, result) in valid_blobs.iter().zip(results) {
+        assert_eq!(result, ctx.compute_cells_and_kzg_proofs(blob).unwrap());
+    }
+
+    // A single invalid blob causes the whole batch to fail
+    if let Some(invalid_blob) = invalid_blobs.first() {
+        let mut blobs: Vec<_> = valid_blobs.iter().collect();
+        blobs.push(invalid_blob);
+        assert!(ctx.compute_cells_and_kzg_proofs_batch(blobs).is_err());
+    }
+}
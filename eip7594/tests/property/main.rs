@@ -0,0 +1,33 @@
+//! Property-based tests for the round trips of the public API.
+//!
+//! The strategies in `strategies` generate blobs, cells and cell indices, and can be reused by
+//! other property tests. Failing cases are shrunk, so a failure reports a minimal blob, such as
+//! one with a single non-zero field element.
+//!
+//! The properties that compute proofs are expensive in debug builds, so they run a few cases by
+//! default. Set `PROPTEST_CASES` to run more of them.
+
+mod prove_verify;
+mod recovery;
+mod serialization;
+mod strategies;
+
+use proptest::test_runner::Config;
+use rust_eth_kzg::DASContext;
+use std::sync::OnceLock;
+
+/// Returns a context that is shared by all of the properties, since it is expensive to create.
+fn ctx() -> &'static DASContext {
+    static CTX: OnceLock<DASContext> = OnceLock::new();
+    CTX.get_or_init(DASContext::default)
+}
+
+/// Returns the config of a property that computes proofs, which runs `cases` cases unless
+/// `PROPTEST_CASES` is set.
+fn expensive_config(cases: u32) -> Config {
+    if std::env::var_os("PROPTEST_CASES").is_some() {
+        Config::default()
+    } else {
+        Config::with_cases(cases)
+    }
+}
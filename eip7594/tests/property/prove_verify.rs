@@ -0,0 +1,73 @@
+use crate::{ctx, expensive_config, strategies};
+use proptest::prelude::*;
+use rust_eth_kzg::constants::{BYTES_PER_CELL, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB};
+
+proptest! {
+    #![proptest_config(expensive_config(4))]
+
+    #[test]
+    fn proofs_of_computed_cells_verify(
+        blob in strategies::blob(),
+        cell_indices in strategies::cell_indices(1..=CELLS_PER_EXT_BLOB),
+    ) {
+        let commitment = ctx().blob_to_kzg_commitment(&blob).unwrap();
+        let (cells, proofs) = ctx().compute_cells_and_kzg_proofs(&blob).unwrap();
+
+        let result = ctx().verify_cell_kzg_proof_batch(
+            vec![&commitment; cell_indices.len()],
+            cell_indices.clone(),
+            cell_indices.iter().map(|&i| &*cells[i.as_usize()]).collect(),
+            cell_indices.iter().map(|&i| &proofs[i.as_usize()]).collect(),
+        );
+        prop_assert!(result.is_ok(), "{:?}", result);
+
+        // Computing the cells on their own gives the same cells
+        prop_assert_eq!(ctx().compute_cells(&blob).unwrap(), cells);
+    }
+
+    #[test]
+    fn modified_cell_fails_verification(
+        blob in strategies::blob(),
+        cell_index in strategies::cell_index(),
+        field_element_index in 0..BYTES_PER_CELL / BYTES_PER_FIELD_ELEMENT,
+        field_element in strategies::field_element(),
+    ) {
+        let commitment = ctx().blob_to_kzg_commitment(&blob).unwrap();
+        let (cells, proofs) = ctx().compute_cells_and_kzg_proofs(&blob).unwrap();
+
+        let mut cell = cells[cell_index.as_usize()].clone();
+        let start = field_element_index * BYTES_PER_FIELD_ELEMENT;
+        prop_assume!(cell[start..start + BYTES_PER_FIELD_ELEMENT] != field_element);
+        cell[start..start + BYTES_PER_FIELD_ELEMENT].copy_from_slice(&field_element);
+
+        let result = ctx().verify_cell_kzg_proof_batch(
+            vec![&commitment],
+            vec![cell_index],
+            vec![&cell],
+            vec![&proofs[cell_index.as_usize()]],
+        );
+        prop_assert!(matches!(&result, Err(err) if err.invalid_proof()), "{:?}", result);
+    }
+
+    #[test]
+    fn streamed_commitment_matches_commitment(
+        blob in strategies::blob(),
+        chunk_sizes in strategies::chunk_sizes(),
+    ) {
+        let mut committer = ctx().blob_committer().unwrap();
+        let mut remaining = &blob[..];
+        for &chunk_size in chunk_sizes.iter().cycle() {
+            if remaining.is_empty() {
+                break;
+            }
+            let (chunk, rest) = remaining.split_at(chunk_size.min(remaining.len()));
+            committer.update(chunk).unwrap();
+            remaining = rest;
+        }
+
+        prop_assert_eq!(
+            committer.finalize().unwrap(),
+            ctx().blob_to_kzg_commitment(&blob).unwrap()
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{ctx, expensive_config, strategies};
+use proptest::prelude::*;
+use rust_eth_kzg::{constants::CELLS_PER_EXT_BLOB, Error};
+
+proptest! {
+    #![proptest_config(expensive_config(4))]
+
+    #[test]
+    fn recovering_from_any_half_gives_the_same_cells_and_proofs(
+        blob in strategies::blob(),
+        cell_indices in strategies::cell_indices(CELLS_PER_EXT_BLOB / 2..=CELLS_PER_EXT_BLOB),
+    ) {
+        let (cells, proofs) = ctx().compute_cells_and_kzg_proofs(&blob).unwrap();
+
+        let (recovered_cells, recovered_proofs) = ctx()
+            .recover_cells_and_kzg_proofs(
+                cell_indices.clone(),
+                cell_indices.iter().map(|&i| &*cells[i.as_usize()]).collect(),
+            )
+            .unwrap();
+
+        prop_assert_eq!(recovered_cells, cells);
+        prop_assert_eq!(recovered_proofs, proofs);
+    }
+
+    #[test]
+    fn recovering_from_less_than_half_fails(
+        blob in strategies::blob(),
+        cell_indices in strategies::cell_indices(0..=CELLS_PER_EXT_BLOB / 2 - 1),
+    ) {
+        let cells = ctx().compute_cells(&blob).unwrap();
+
+        let result = ctx().recover_cells_and_kzg_proofs(
+            cell_indices.clone(),
+            cell_indices.iter().map(|&i| &*cells[i.as_usize()]).collect(),
+        );
+        prop_assert!(matches!(result, Err(Error::Recovery(_))), "{:?}", result.err());
+    }
+}
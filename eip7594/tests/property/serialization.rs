@@ -0,0 +1,143 @@
+use crate::{ctx, expensive_config, strategies};
+use bls12_381::{ff::Field, group::Group, G1Point, G1Projective, Scalar};
+use proptest::prelude::*;
+use rust_eth_kzg::{
+    constants::{BYTES_PER_BLOB, BYTES_PER_CELL, CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_BLOB},
+    serialization::{
+        deserialize_blob_to_scalars, deserialize_blob_to_scalars_le, deserialize_bytes_to_scalar,
+        deserialize_bytes_to_scalar_le, deserialize_compressed_g1, serialize_g1_compressed,
+        serialize_scalars_to_bytes, serialize_scalars_to_bytes_le,
+    },
+    test_utils::POINT_NOT_IN_SUBGROUP,
+    CellIndex, Error,
+};
+
+#[test]
+fn point_not_in_subgroup_is_on_the_curve() {
+    let point = Option::<G1Point>::from(G1Point::from_compressed_unchecked(&POINT_NOT_IN_SUBGROUP))
+        .unwrap();
+    assert!(!bool::from(point.is_torsion_free()));
+    assert!(deserialize_compressed_g1(&POINT_NOT_IN_SUBGROUP).is_err());
+}
+
+#[test]
+fn serialized_values_must_have_the_right_length() {
+    assert!(deserialize_bytes_to_scalar(&[0u8; 31]).is_err());
+    assert!(deserialize_blob_to_scalars(&[0u8; BYTES_PER_BLOB - 1]).is_err());
+    assert!(deserialize_compressed_g1(&[0xc0; 47]).is_err());
+    assert_eq!(
+        deserialize_blob_to_scalars(&[0u8; BYTES_PER_BLOB]).unwrap(),
+        vec![Scalar::ZERO; FIELD_ELEMENTS_PER_BLOB]
+    );
+}
+
+proptest! {
+    #[test]
+    fn field_element_round_trip(field_element in strategies::field_element()) {
+        let scalar = deserialize_bytes_to_scalar(&field_element).unwrap();
+        prop_assert_eq!(scalar.to_bytes_be(), field_element);
+    }
+
+    #[test]
+    fn non_canonical_field_element_is_rejected(field_element in strategies::field_element()) {
+        // Adding the modulus to a canonical field element gives a non-canonical encoding of it,
+        // as long as the result fits in 32 bytes
+        let modulus = hex::decode("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001").unwrap();
+        let mut non_canonical = [0u8; 32];
+        let mut carry = 0u16;
+        for i in (0..32).rev() {
+            let sum = field_element[i] as u16 + modulus[i] as u16 + carry;
+            non_canonical[i] = sum as u8;
+            carry = sum >> 8;
+        }
+        prop_assume!(carry == 0);
+
+        prop_assert!(deserialize_bytes_to_scalar(&non_canonical).is_err());
+    }
+
+    #[test]
+    fn field_element_little_endian_round_trip(field_element in strategies::field_element()) {
+        let scalar = deserialize_bytes_to_scalar(&field_element).unwrap();
+        let bytes_le = serialize_scalars_to_bytes_le(&[scalar]);
+
+        let mut reversed = field_element;
+        reversed.reverse();
+        prop_assert_eq!(&bytes_le[..], &reversed[..]);
+        prop_assert_eq!(deserialize_bytes_to_scalar_le(&bytes_le).unwrap(), scalar);
+    }
+
+    #[test]
+    fn blob_round_trip(blob in strategies::blob()) {
+        let scalars = deserialize_blob_to_scalars(&blob[..]).unwrap();
+        prop_assert_eq!(&serialize_scalars_to_bytes(&scalars)[..], &blob[..]);
+
+        let blob_le = serialize_scalars_to_bytes_le(&scalars);
+        prop_assert_eq!(deserialize_blob_to_scalars_le(&blob_le).unwrap(), scalars);
+    }
+
+    #[test]
+    fn g1_point_round_trip(field_element in strategies::field_element()) {
+        let scalar = Option::<Scalar>::from(Scalar::from_bytes_be(&field_element)).unwrap();
+        let point = G1Point::from(G1Projective::generator() * scalar);
+
+        let bytes = serialize_g1_compressed(&point);
+        let deserialized = deserialize_compressed_g1(&bytes).unwrap();
+        prop_assert_eq!(deserialized, point);
+        prop_assert_eq!(deserialized.to_compressed(), bytes);
+    }
+}
+
+proptest! {
+    #![proptest_config(expensive_config(4))]
+
+    #[test]
+    fn first_half_of_the_cells_is_the_blob(blob in strategies::blob()) {
+        let cells = ctx().compute_cells(&blob).unwrap();
+
+        let first_half: Vec<u8> = cells[..BYTES_PER_BLOB / BYTES_PER_CELL]
+            .iter()
+            .flat_map(|cell| cell.iter().copied())
+            .collect();
+        prop_assert_eq!(&first_half[..], &blob[..]);
+    }
+
+    #[test]
+    fn commitment_and_proofs_are_canonical_points(blob in strategies::blob()) {
+        let commitment = ctx().blob_to_kzg_commitment(&blob).unwrap();
+        let (_, proofs) = ctx().compute_cells_and_kzg_proofs(&blob).unwrap();
+
+        for bytes in std::iter::once(&commitment).chain(&proofs) {
+            let point = Option::<G1Point>::from(G1Point::from_compressed(bytes));
+            prop_assert!(point.is_some());
+            prop_assert_eq!(&point.unwrap().to_compressed(), bytes);
+        }
+    }
+
+    #[test]
+    fn point_not_in_subgroup_is_rejected_in_a_large_batch(
+        blob in strategies::blob(),
+        proof_index in 0..2 * CELLS_PER_EXT_BLOB,
+    ) {
+
+        let commitment = ctx().blob_to_kzg_commitment(&blob).unwrap();
+        let (cells, proofs) = ctx().compute_cells_and_kzg_proofs(&blob).unwrap();
+
+        // Every cell is verified twice, so that there are enough proofs to check them together
+        let cell_indices: Vec<CellIndex> = CellIndex::all().chain(CellIndex::all()).collect();
+        let mut batch_proofs: Vec<_> =
+            cell_indices.iter().map(|&i| &proofs[i.as_usize()]).collect();
+        let verify = |batch_proofs| {
+            ctx().verify_cell_kzg_proof_batch(
+                vec![&commitment; cell_indices.len()],
+                cell_indices.clone(),
+                cell_indices.iter().map(|&i| &*cells[i.as_usize()]).collect(),
+                batch_proofs,
+            )
+        };
+        prop_assert!(verify(batch_proofs.clone()).is_ok());
+
+        batch_proofs[proof_index] = &POINT_NOT_IN_SUBGROUP;
+        let result = verify(batch_proofs);
+        prop_assert!(matches!(result, Err(Error::Serialization(_))), "{:?}", result);
+    }
+}
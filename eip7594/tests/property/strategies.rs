@@ -0,0 +1,54 @@
+use bls12_381::reduce_bytes_to_scalar_bias;
+use proptest::{collection::vec, prelude::*, sample::subsequence};
+use rust_eth_kzg::{
+    constants::{
+        BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_BLOB,
+    },
+    CellIndex,
+};
+use std::ops::RangeInclusive;
+
+/// The most field elements of a blob that are set to a non-zero value.
+///
+/// Keeping this small keeps the blobs readable after shrinking, while still covering
+/// blobs that are not sparse in the evaluation form of the polynomial.
+const MAX_NON_ZERO_FIELD_ELEMENTS: usize = 16;
+
+/// Returns the big-endian encoding of an arbitrary field element, which is always canonical.
+pub fn field_element() -> impl Strategy<Value = [u8; BYTES_PER_FIELD_ELEMENT]> {
+    any::<[u8; BYTES_PER_FIELD_ELEMENT]>()
+        .prop_map(|bytes| reduce_bytes_to_scalar_bias(bytes).to_bytes_be())
+}
+
+/// Returns a blob where a few field elements are arbitrary, and the rest are zero.
+///
+/// The blob shrinks towards the zero blob.
+pub fn blob() -> impl Strategy<Value = Box<[u8; BYTES_PER_BLOB]>> {
+    vec(
+        (0..FIELD_ELEMENTS_PER_BLOB, field_element()),
+        0..=MAX_NON_ZERO_FIELD_ELEMENTS,
+    )
+    .prop_map(|field_elements| {
+        let mut blob = Box::new([0u8; BYTES_PER_BLOB]);
+        for (index, field_element) in field_elements {
+            let start = index * BYTES_PER_FIELD_ELEMENT;
+            blob[start..start + BYTES_PER_FIELD_ELEMENT].copy_from_slice(&field_element);
+        }
+        blob
+    })
+}
+
+/// Returns distinct cell indices in an arbitrary order, where the number of indices is in `len`.
+pub fn cell_indices(len: RangeInclusive<usize>) -> impl Strategy<Value = Vec<CellIndex>> {
+    subsequence(CellIndex::all().collect::<Vec<_>>(), len).prop_shuffle()
+}
+
+/// Returns an arbitrary cell index.
+pub fn cell_index() -> impl Strategy<Value = CellIndex> {
+    (0..CELLS_PER_EXT_BLOB).prop_map(|index| CellIndex::try_from(index).unwrap())
+}
+
+/// Returns an arbitrary number of bytes to split a blob at, for streaming it in chunks.
+pub fn chunk_sizes() -> impl Strategy<Value = Vec<usize>> {
+    vec(1..=2 * BYTES_PER_FIELD_ELEMENT, 1..=8)
+}
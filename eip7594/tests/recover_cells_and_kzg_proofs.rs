@@ -1,81 +1,9 @@
-use common::collect_test_files;
-use serde_::TestVector;
+use rust_eth_kzg::test_utils::{
+    collect_test_files, RecoverCellsAndKZGProofsTestVector as TestVector,
+};
+use rust_eth_kzg::{Cell, CellIndex, CellRef};
 use std::fs;
 
-mod common;
-
-mod serde_ {
-    use crate::common::{bytes_from_hex, UnsafeBytes};
-
-    use serde::Deserialize;
-
-    #[derive(Deserialize)]
-    struct YamlInput {
-        cell_indices: Vec<u64>,
-        cells: Vec<String>,
-    }
-
-    type YamlOutput = (Vec<String>, Vec<String>);
-
-    #[derive(Debug, Clone)]
-    pub struct KZGProofsAndCells {
-        pub proofs: Vec<UnsafeBytes>,
-        pub cells: Vec<UnsafeBytes>,
-    }
-
-    #[derive(Deserialize)]
-    struct YamlTestVector {
-        input: YamlInput,
-        output: Option<YamlOutput>,
-    }
-
-    pub struct TestVector {
-        pub input_cell_indices: Vec<u64>,
-        pub input_cells: Vec<UnsafeBytes>,
-        pub proofs_and_cells: Option<KZGProofsAndCells>,
-    }
-
-    impl TestVector {
-        pub fn from_str(yaml_data: &str) -> Self {
-            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
-            Self::from(yaml_test_vector)
-        }
-    }
-
-    impl From<YamlTestVector> for TestVector {
-        fn from(yaml_test_vector: YamlTestVector) -> Self {
-            let cell_indices = yaml_test_vector.input.cell_indices;
-
-            let input_cells: Vec<_> = yaml_test_vector
-                .input
-                .cells
-                .iter()
-                .map(|cell| bytes_from_hex(cell))
-                .collect();
-            let output = match yaml_test_vector.output {
-                Some((cells, kzg_proofs)) => {
-                    let kzg_proofs: Vec<_> = kzg_proofs
-                        .iter()
-                        .map(|proof| bytes_from_hex(proof))
-                        .collect();
-                    let cells: Vec<_> = cells.iter().map(|cell| bytes_from_hex(cell)).collect();
-                    Some((kzg_proofs, cells))
-                }
-                None => None,
-            };
-
-            Self {
-                input_cell_indices: cell_indices,
-                input_cells,
-                proofs_and_cells: output.map(|out| KZGProofsAndCells {
-                    proofs: out.0,
-                    cells: out.1,
-                }),
-            }
-        }
-    }
-}
-
 const TEST_DIR: &str = "../test_vectors/recover_cells_and_kzg_proofs";
 #[test]
 fn test_recover_cells_and_kzg_proofs() {
@@ -85,7 +13,7 @@ fn test_recover_cells_and_kzg_proofs() {
 
     for test_file in test_files {
         let yaml_data = fs::read_to_string(&test_file).unwrap();
-        let test = TestVector::from_str(&yaml_data);
+        let test = yaml_data.parse::<TestVector>().unwrap();
 
         let input_cells: Result<_, _> = test
             .input_cells
@@ -102,7 +30,16 @@ fn test_recover_cells_and_kzg_proofs() {
             }
         };
 
-        match ctx.recover_cells_and_kzg_proofs(test.input_cell_indices, input_cells) {
+        // An out of range cell index is rejected when it is converted to a `CellIndex`
+        let input_cell_indices = match CellIndex::try_from_u64s(&test.input_cell_indices) {
+            Ok(input_cell_indices) => input_cell_indices,
+            Err(_) => {
+                assert!(test.proofs_and_cells.is_none());
+                continue;
+            }
+        };
+
+        match ctx.recover_cells_and_kzg_proofs(input_cell_indices, input_cells) {
             Ok((cells, proofs)) => {
                 let expected_proofs_and_cells = test.proofs_and_cells.unwrap();
 
@@ -127,3 +64,67 @@ fn test_recover_cells_and_kzg_proofs() {
         };
     }
 }
+
+#[test]
+fn test_recover_cells_and_kzg_proofs_batch() {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
+
+    let ctx = rust_eth_kzg::DASContext::default();
+
+    let mut valid_inputs: Vec<(Vec<CellIndex>, Vec<Cell>)> = Vec::new();
+    let mut invalid_inputs = Vec::new();
+    for test_file in test_files {
+        let yaml_data = fs::read_to_string(&test_file).unwrap();
+        let test = yaml_data.parse::<TestVector>().unwrap();
+
+        let input_cells: Result<Vec<Cell>, _> = test
+            .input_cells
+            .iter()
+            .map(|cell| cell.clone().into_boxed_slice().try_into())
+            .collect();
+        let Ok(input_cells) = input_cells else {
+            continue;
+        };
+        let Ok(input_cell_indices) = CellIndex::try_from_u64s(&test.input_cell_indices) else {
+            continue;
+        };
+        if test.proofs_and_cells.is_some() {
+            valid_inputs.push((input_cell_indices, input_cells));
+        } else {
+            invalid_inputs.push((input_cell_indices, input_cells));
+        }
+    }
+
+    let results = ctx
+        .recover_cells_and_kzg_proofs_batch(as_refs(&valid_inputs))
+        .unwrap();
+    assert_eq!(results.len(), valid_inputs.len());
+    for ((cell_indices, cells), result) in as_refs(&valid_inputs).into_iter().zip(results) {
+        assert_eq!(
+            result,
+            ctx.recover_cells_and_kzg_proofs(cell_indices, cells)
+                .unwrap()
+        );
+    }
+
+    // A single invalid blob causes the whole batch to fail
+    if let Some(invalid_input) = invalid_inputs.first() {
+        let mut inputs = valid_inputs.clone();
+        inputs.push(invalid_input.clone());
+        assert!(ctx
+            .recover_cells_and_kzg_proofs_batch(as_refs(&inputs))
+            .is_err());
+    }
+}
+
+fn as_refs(inputs: &[(Vec<CellIndex>, Vec<Cell>)]) -> Vec<(Vec<CellIndex>, Vec<CellRef<'_>>)> {
+    inputs
+        .iter()
+        .map(|(cell_indices, cells)| {
+            (
+                cell_indices.clone(),
+                cells.iter().map(|cell| cell.as_ref()).collect(),
+            )
+        })
+        .collect()
+}
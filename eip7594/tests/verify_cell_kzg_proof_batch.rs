@@ -1,90 +1,201 @@
-use common::collect_test_files;
-use serde_::TestVector;
-use std::fs;
+use bls12_381::{ff::Field, group::prime::PrimeCurveAffine};
+use rust_eth_kzg::test_utils::{
+    collect_test_files, VerifyCellKZGProofBatchTestVector as TestVector,
+};
+use rust_eth_kzg::{
+    AggregateVerifier, Bytes48Ref, CellIndex, CellRef, Error, G1Point, Scalar, VerifierError,
+};
+use std::{cell::RefCell, fs};
 
-mod common;
-
-mod serde_ {
-    use crate::common::{bytes_from_hex, UnsafeBytes};
+const TEST_DIR: &str = "../test_vectors/verify_cell_kzg_proof_batch";
+#[test]
+fn test_verify_cell_kzg_proof_batch() {
+    let ctx = rust_eth_kzg::DASContext::default();
+    run_test_vectors(|commitments, cell_indices, cells, proofs| {
+        ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs)
+    });
+}
 
-    use serde::Deserialize;
+#[test]
+fn test_verifier_context_verify_cell_kzg_proof_batch() {
+    let ctx = rust_eth_kzg::VerifierContext::default();
+    run_test_vectors(|commitments, cell_indices, cells, proofs| {
+        ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs)
+    });
+}
 
-    #[derive(Deserialize)]
-    struct YamlInput {
-        commitments: Vec<String>,
-        cell_indices: Vec<u64>,
-        cells: Vec<String>,
-        proofs: Vec<String>,
-    }
+#[test]
+fn test_verify_cell_kzg_proof_batch_unchecked() {
+    let ctx = rust_eth_kzg::DASContext::default();
+    run_test_vectors(|commitments, cell_indices, cells, proofs| {
+        let result = ctx.verify_cell_kzg_proof_batch_unchecked(
+            commitments.clone(),
+            cell_indices.clone(),
+            cells.clone(),
+            proofs.clone(),
+        );
+        // The inputs that pass the subgroup checks are verified in the same way without them
+        match ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs) {
+            Ok(()) => assert!(result.is_ok()),
+            Err(err) if err.invalid_proof() => {
+                assert!(result.as_ref().unwrap_err().invalid_proof())
+            }
+            Err(err) => return Err(err),
+        }
+        result
+    });
+}
 
-    type YamlOutput = bool;
+#[test]
+fn test_verify_cell_kzg_proof() {
+    let ctx = rust_eth_kzg::DASContext::default();
+    let num_verified = std::cell::Cell::new(0);
+    run_test_vectors(|commitments, cell_indices, cells, proofs| {
+        if cells.len() == 1 && commitments.len() == 1 && proofs.len() == 1 {
+            num_verified.set(num_verified.get() + 1);
+            return ctx.verify_cell_kzg_proof(commitments[0], cell_indices[0], cells[0], proofs[0]);
+        }
 
-    #[derive(Deserialize)]
-    struct YamlTestVector {
-        input: YamlInput,
-        output: Option<YamlOutput>,
-    }
+        // Every cell of a valid batch is valid on its own
+        let result = ctx.verify_cell_kzg_proof_batch(
+            commitments.clone(),
+            cell_indices.clone(),
+            cells.clone(),
+            proofs.clone(),
+        );
+        if result.is_ok() {
+            for k in 0..cells.len() {
+                ctx.verify_cell_kzg_proof(commitments[k], cell_indices[k], cells[k], proofs[k])
+                    .unwrap();
+            }
+        }
+        result
+    });
+    assert!(num_verified.get() > 0);
+}
 
-    pub struct TestVector {
-        pub commitments: Vec<UnsafeBytes>,
-        pub cell_indices: Vec<u64>,
-        pub cells: Vec<UnsafeBytes>,
-        pub proofs: Vec<UnsafeBytes>,
-        pub output: Option<bool>,
-    }
+#[test]
+fn test_verify_cell_kzg_proof_batch_scalars() {
+    let ctx = rust_eth_kzg::DASContext::default();
+    let num_verified = std::cell::Cell::new(0);
+    run_test_vectors(|commitments, cell_indices, cells, proofs| {
+        let points = |bytes: &[Bytes48Ref]| -> Option<Vec<G1Point>> {
+            bytes
+                .iter()
+                .map(|bytes| G1Point::from_compressed(bytes).into())
+                .collect()
+        };
+        let field_elements: Option<Vec<Vec<Scalar>>> = cells
+            .iter()
+            .map(|cell| {
+                cell.chunks_exact(32)
+                    .map(|bytes| Scalar::from_bytes_be(bytes.try_into().unwrap()).into())
+                    .collect()
+            })
+            .collect();
 
-    impl TestVector {
-        pub fn from_str(yaml_data: &str) -> Self {
-            let yaml_test_vector: YamlTestVector = serde_yaml::from_str(yaml_data).unwrap();
-            Self::from(yaml_test_vector)
+        match (points(&commitments), field_elements, points(&proofs)) {
+            (Some(commitments), Some(field_elements), Some(proofs)) => {
+                num_verified.set(num_verified.get() + 1);
+                let cells: Vec<&[Scalar]> = field_elements.iter().map(Vec::as_slice).collect();
+                ctx.verify_cell_kzg_proof_batch_scalars(
+                    &commitments,
+                    &cell_indices,
+                    &cells,
+                    &proofs,
+                )
+            }
+            // The inputs that cannot be deserialized are rejected by the function that takes bytes
+            _ => ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs),
         }
-    }
+    });
+    assert!(num_verified.get() > 0);
 
-    impl From<YamlTestVector> for TestVector {
-        fn from(yaml_test_vector: YamlTestVector) -> Self {
-            let commitments = yaml_test_vector
-                .input
-                .commitments
-                .into_iter()
-                .map(|commitment| bytes_from_hex(&commitment))
-                .collect();
-            let cells = yaml_test_vector
-                .input
-                .cells
-                .into_iter()
-                .map(|cell| bytes_from_hex(&cell))
-                .collect();
-            let proofs: Vec<_> = yaml_test_vector
-                .input
-                .proofs
-                .into_iter()
-                .map(|proof| bytes_from_hex(&proof))
-                .collect();
-            let cell_indices = yaml_test_vector.input.cell_indices;
-
-            let output = yaml_test_vector.output;
-
-            Self {
-                commitments,
-                cell_indices,
-                cells,
-                proofs,
-                output,
+    // A cell must have the right number of field elements
+    let result = ctx.verify_cell_kzg_proof_batch_scalars(
+        &[G1Point::generator()],
+        &[CellIndex::try_from(0u64).unwrap()],
+        &[&[Scalar::ONE]],
+        &[G1Point::generator()],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::Verifier(VerifierError::CellHasInvalidLength { .. }))
+    ));
+}
+
+#[test]
+fn test_verify_column() {
+    let ctx = rust_eth_kzg::DASContext::default();
+    let num_columns_verified = std::cell::Cell::new(0);
+    run_test_vectors(|commitments, cell_indices, cells, proofs| {
+        // Only the test vectors where every cell is in the same column can be verified as a column
+        let same_column = cell_indices.len() == cells.len()
+            && cell_indices.windows(2).all(|pair| pair[0] == pair[1]);
+        match cell_indices.first() {
+            Some(&column_index) if same_column => {
+                num_columns_verified.set(num_columns_verified.get() + 1);
+                ctx.verify_column(commitments, column_index, cells, proofs)
             }
+            _ => ctx.verify_cell_kzg_proof_batch(commitments, cell_indices, cells, proofs),
         }
-    }
+    });
+    assert!(num_columns_verified.get() > 0);
 }
 
-const TEST_DIR: &str = "../test_vectors/verify_cell_kzg_proof_batch";
 #[test]
-fn test_verify_cell_kzg_proof_batch() {
-    let test_files = collect_test_files(TEST_DIR).unwrap();
-
+fn test_aggregate_verifier() {
     let ctx = rust_eth_kzg::DASContext::default();
+    let valid = RefCell::new(AggregateVerifier::new());
+    let all = RefCell::new(AggregateVerifier::new());
+    run_test_vectors(|commitments, cell_indices, cells, proofs| {
+        // Each test vector on its own is accepted and rejected in the same way as a batch
+        let mut aggregate = AggregateVerifier::new();
+        aggregate.add_batch(
+            commitments.clone(),
+            cell_indices.clone(),
+            cells.clone(),
+            proofs.clone(),
+        )?;
+        let result = ctx.verify_aggregate(&aggregate);
+
+        if result.is_ok() {
+            valid.borrow_mut().add_batch(
+                commitments.clone(),
+                cell_indices.clone(),
+                cells.clone(),
+                proofs.clone(),
+            )?;
+        }
+        all.borrow_mut()
+            .add_batch(commitments, cell_indices, cells, proofs)?;
+        result
+    });
+
+    // Together, the valid test vectors are valid, and a single invalid one makes the aggregate invalid
+    let valid = valid.into_inner();
+    let all = all.into_inner();
+    assert!(valid.num_batches() > 1);
+    assert!(all.num_batches() > valid.num_batches());
+    ctx.verify_aggregate(&valid).unwrap();
+    assert!(ctx.verify_aggregate(&all).unwrap_err().invalid_proof());
+
+    assert!(ctx.verify_aggregate(&AggregateVerifier::new()).is_ok());
+}
+
+fn run_test_vectors(
+    verify: impl Fn(
+        Vec<Bytes48Ref>,
+        Vec<CellIndex>,
+        Vec<CellRef>,
+        Vec<Bytes48Ref>,
+    ) -> Result<(), rust_eth_kzg::Error>,
+) {
+    let test_files = collect_test_files(TEST_DIR).unwrap();
 
     for test_file in test_files {
         let yaml_data = fs::read_to_string(&test_file).unwrap();
-        let test = TestVector::from_str(&yaml_data);
+        let test = yaml_data.parse::<TestVector>().unwrap();
 
         let cells: Result<_, _> = test
             .cells
@@ -131,7 +242,16 @@ fn test_verify_cell_kzg_proof_batch() {
             }
         };
 
-        match ctx.verify_cell_kzg_proof_batch(commitments, test.cell_indices, cells, proofs) {
+        // An out of range cell index is rejected when it is converted to a `CellIndex`
+        let cell_indices = match CellIndex::try_from_u64s(&test.cell_indices) {
+            Ok(cell_indices) => cell_indices,
+            Err(_) => {
+                assert!(test.output.is_none());
+                continue;
+            }
+        };
+
+        match verify(commitments, cell_indices, cells, proofs) {
             Ok(_) => {
                 // We arrive at this point if the proof verified as true
                 assert!(test.output.unwrap())
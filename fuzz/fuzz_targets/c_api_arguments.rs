@@ -0,0 +1,108 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use c_eth_kzg::{
+    eth_kzg_das_context_new, eth_kzg_free_error_message, eth_kzg_recover_cells_and_proofs_flat,
+    eth_kzg_verify_cell_kzg_proof_batch_flat, CResult, CResultCode, CResultStatus, DASContext,
+    BYTES_PER_CELL, BYTES_PER_COMMITMENT, BYTES_PER_PROOF, CELLS_PER_EXT_BLOB,
+};
+use libfuzzer_sys::fuzz_target;
+use std::{ptr, sync::OnceLock};
+
+/// The arguments of the flat functions of the C API.
+///
+/// The lengths are chosen independently of the buffers, but never exceed them, since a length
+/// that is larger than its buffer is undefined behavior for a C caller.
+#[derive(Arbitrary, Debug)]
+struct Input {
+    commitments: Vec<u8>,
+    cell_indices: Vec<u64>,
+    cells: Vec<u8>,
+    proofs: Vec<u8>,
+    num_commitments: u8,
+    num_cells: u8,
+    num_proofs: u8,
+    out_cells_length: u8,
+    out_proofs_length: u8,
+}
+
+/// The context pointer, which is created once and shared by all of the runs.
+struct Context(*const DASContext);
+
+// Safety: The context is never freed and is safe to use from multiple threads.
+unsafe impl Send for Context {}
+unsafe impl Sync for Context {}
+
+fn ctx() -> *const DASContext {
+    static CTX: OnceLock<Context> = OnceLock::new();
+    CTX.get_or_init(|| {
+        let mut code = CResultCode::Ok;
+        let ctx = unsafe { eth_kzg_das_context_new(false, 1, &mut code) };
+        assert!(!ctx.is_null(), "{code:?}");
+        Context(ctx)
+    })
+    .0
+}
+
+/// Returns the number of items of `item_size` bytes to pass for a buffer, which is at most the
+/// number of items in the buffer.
+fn num_items(buffer: &[u8], item_size: usize, requested: u8) -> u64 {
+    (requested as usize).min(buffer.len() / item_size) as u64
+}
+
+/// Returns a pointer to the buffer, which is null when the buffer is empty, as a C caller may pass.
+fn ptr_or_null<T>(buffer: &[T]) -> *const T {
+    if buffer.is_empty() {
+        ptr::null()
+    } else {
+        buffer.as_ptr()
+    }
+}
+
+/// Checks that the arguments were not able to cause an internal error, and frees the error message.
+fn check(result: CResult) {
+    if matches!(result.status, CResultStatus::Err) {
+        assert_ne!(result.code, CResultCode::Internal);
+        unsafe { eth_kzg_free_error_message(result.error_msg) };
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let num_commitments = num_items(
+        &input.commitments,
+        BYTES_PER_COMMITMENT,
+        input.num_commitments,
+    );
+    let num_cells = num_items(&input.cells, BYTES_PER_CELL, input.num_cells);
+    let num_proofs = num_items(&input.proofs, BYTES_PER_PROOF, input.num_proofs);
+
+    let mut verified = false;
+    check(eth_kzg_verify_cell_kzg_proof_batch_flat(
+        ctx(),
+        num_commitments,
+        ptr_or_null(&input.commitments),
+        input.cell_indices.len() as u64,
+        ptr_or_null(&input.cell_indices),
+        num_cells,
+        ptr_or_null(&input.cells),
+        num_proofs,
+        ptr_or_null(&input.proofs),
+        &mut verified,
+    ));
+
+    let out_cells_length = (input.out_cells_length as usize).min(CELLS_PER_EXT_BLOB);
+    let out_proofs_length = (input.out_proofs_length as usize).min(CELLS_PER_EXT_BLOB);
+    let mut out_cells = vec![0u8; out_cells_length * BYTES_PER_CELL];
+    let mut out_proofs = vec![0u8; out_proofs_length * BYTES_PER_PROOF];
+    check(eth_kzg_recover_cells_and_proofs_flat(
+        ctx(),
+        num_cells,
+        ptr_or_null(&input.cells),
+        input.cell_indices.len() as u64,
+        ptr_or_null(&input.cell_indices),
+        out_cells_length as u64,
+        out_cells.as_mut_ptr(),
+        out_proofs_length as u64,
+        out_proofs.as_mut_ptr(),
+    ));
+});
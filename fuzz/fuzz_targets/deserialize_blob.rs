@@ -0,0 +1,25 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eth_kzg::constants::BYTES_PER_BLOB;
+use rust_eth_kzg_fuzz::{ctx, to_array};
+
+fuzz_target!(|data: &[u8]| {
+    let blob = to_array::<BYTES_PER_BLOB>(data);
+    let commitment = ctx().blob_to_kzg_commitment(&blob);
+
+    // Streaming the same bytes must accept and reject the blob in the same way
+    let mut committer = ctx().blob_committer().unwrap();
+    let streamed = committer.update(data).and_then(|()| {
+        if data.len() < BYTES_PER_BLOB {
+            committer.update(&blob[data.len()..])?;
+        }
+        committer.finalize()
+    });
+
+    if data.len() > BYTES_PER_BLOB {
+        assert!(streamed.is_err());
+    } else {
+        assert_eq!(commitment.ok(), streamed.ok());
+    }
+});
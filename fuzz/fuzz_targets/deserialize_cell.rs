@@ -0,0 +1,40 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eth_kzg::{
+    constants::{BYTES_PER_CELL, BYTES_PER_COMMITMENT, CELLS_PER_EXT_BLOB},
+    CellIndex, Error, VerifierError,
+};
+use rust_eth_kzg_fuzz::{ctx, to_array};
+
+/// The compressed encoding of the point at infinity, which is the commitment to the zero blob and
+/// the proof of each of its cells.
+const POINT_AT_INFINITY: [u8; BYTES_PER_COMMITMENT] = {
+    let mut bytes = [0u8; BYTES_PER_COMMITMENT];
+    bytes[0] = 0xc0;
+    bytes
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((index_bytes, cell_bytes)) = data.split_first_chunk::<8>() else {
+        return;
+    };
+    let cell_index = u64::from_le_bytes(*index_bytes);
+    let cell = to_array::<BYTES_PER_CELL>(cell_bytes);
+
+    let result = CellIndex::try_from(cell_index)
+        .map_err(|err| Error::from(VerifierError::from(err)))
+        .and_then(|cell_index| {
+            ctx().verify_cell_kzg_proof_batch(
+                vec![&POINT_AT_INFINITY],
+                vec![cell_index],
+                vec![&cell],
+                vec![&POINT_AT_INFINITY],
+            )
+        });
+
+    // The only cell of the zero blob is the zero cell, so every other cell must be rejected
+    let is_zero_cell = cell.iter().all(|&byte| byte == 0);
+    let in_range = cell_index < CELLS_PER_EXT_BLOB as u64;
+    assert_eq!(result.is_ok(), is_zero_cell && in_range, "{result:?}");
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+use bls12_381::G1Point;
+use libfuzzer_sys::fuzz_target;
+use rust_eth_kzg::{
+    constants::{BYTES_PER_CELL, BYTES_PER_COMMITMENT},
+    Error,
+};
+use rust_eth_kzg_fuzz::{ctx, to_array};
+
+fuzz_target!(|data: &[u8]| {
+    let commitment = to_array::<BYTES_PER_COMMITMENT>(data);
+    let proof =
+        to_array::<BYTES_PER_COMMITMENT>(data.get(BYTES_PER_COMMITMENT..).unwrap_or_default());
+    let cell = [0u8; BYTES_PER_CELL];
+
+    let result =
+        ctx().verify_cell_kzg_proof_batch(vec![&commitment], vec![0], vec![&cell], vec![&proof]);
+
+    // A point must be rejected during deserialization exactly when it is not a valid point in G1
+    let is_valid_point = |bytes: &[u8; BYTES_PER_COMMITMENT]| -> bool {
+        G1Point::from_compressed(bytes).is_some().into()
+    };
+    let rejected = matches!(result, Err(Error::Serialization(_)));
+    assert_eq!(
+        rejected,
+        !(is_valid_point(&commitment) && is_valid_point(&proof)),
+        "{result:?}"
+    );
+});
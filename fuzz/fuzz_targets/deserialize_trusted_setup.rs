@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eth_kzg::TrustedSetup;
+
+fuzz_target!(|json: &str| {
+    let checked = TrustedSetup::try_from_json(json);
+    let unchecked = TrustedSetup::try_from_json_unchecked(json);
+
+    // The checks only reject more trusted setups
+    if let Ok(checked) = checked {
+        assert_eq!(Some(checked), unchecked.ok());
+    }
+});
@@ -0,0 +1,62 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use java_eth_kzg::{
+    arguments::{copy_to_output, slice_to_array_ref, split_flat_slice, to_cell_indices},
+    errors::Operation,
+};
+use libfuzzer_sys::fuzz_target;
+use rust_eth_kzg::constants::{BYTES_PER_CELL, BYTES_PER_COMMITMENT, CELLS_PER_EXT_BLOB};
+
+/// The arguments that are passed from Java, after they have been copied out of the JVM.
+#[derive(Arbitrary, Debug)]
+struct Input {
+    cell_indices: Vec<i64>,
+    bytes: Vec<u8>,
+    items: Vec<Vec<u8>>,
+    output_length: u16,
+}
+
+fuzz_target!(|input: Input| {
+    // A cell index is only accepted if it is in range, so that a negative index is not converted
+    // to a large `u64`
+    let cell_indices = to_cell_indices(&input.cell_indices, Operation::Recovery);
+    let in_range = |&index: &i64| (0..CELLS_PER_EXT_BLOB as i64).contains(&index);
+    assert_eq!(
+        cell_indices.is_ok(),
+        input.cell_indices.iter().all(in_range)
+    );
+    if let Ok(cell_indices) = cell_indices {
+        assert!(cell_indices
+            .iter()
+            .map(|cell_index| cell_index.as_u64())
+            .eq(input.cell_indices.iter().map(|&index| index as u64)));
+    }
+
+    check_split::<BYTES_PER_CELL>(&input.bytes);
+    check_split::<BYTES_PER_COMMITMENT>(&input.bytes);
+
+    let commitment = slice_to_array_ref::<BYTES_PER_COMMITMENT>(&input.bytes, "commitment");
+    assert_eq!(
+        commitment.is_ok(),
+        input.bytes.len() == BYTES_PER_COMMITMENT
+    );
+
+    let mut output = vec![0u8; input.output_length as usize];
+    let concatenation = input.items.concat();
+    let copied = copy_to_output(&mut output, &input.items, "output");
+    assert_eq!(copied.is_ok(), output.len() == concatenation.len());
+    if copied.is_ok() {
+        assert_eq!(output, concatenation);
+    }
+});
+
+/// Checks that a flat slice is only split if it contains a whole number of items, and that the
+/// items cover the whole slice.
+fn check_split<const N: usize>(bytes: &[u8]) {
+    let items = split_flat_slice::<N>(bytes, "items");
+    assert_eq!(items.is_ok(), bytes.len().is_multiple_of(N));
+    if let Ok(items) = items {
+        assert!(items.into_iter().flatten().eq(bytes));
+    }
+}
@@ -0,0 +1,133 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_eth_kzg::{
+    constants::{
+        BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB,
+        FIELD_ELEMENTS_PER_CELL,
+    },
+    Cell, CellIndex, CellsAndKZGProofs, Error, RecoveryError,
+};
+use rust_eth_kzg_fuzz::ctx;
+use std::{collections::HashSet, sync::OnceLock};
+
+/// The least number of cells that a blob can be recovered from.
+const MIN_CELLS: usize = CELLS_PER_EXT_BLOB / 2;
+
+/// An erasure pattern, which decides which cells are given to recovery and in which order.
+#[derive(Arbitrary, Debug)]
+struct Input {
+    /// The cells that are given, as a bit for each cell index.
+    present: u128,
+    /// Cell indices that are given after the present cells, which are out of range or
+    /// duplicates of other indices, unless they are the only occurrence of an index.
+    extra_indices: Vec<u8>,
+    /// Swaps of the given cells, which change the order that they are given in.
+    swaps: Vec<(u8, u8)>,
+    /// A change to one of the given cells, after which it is no longer a cell of the blob.
+    corruption: Option<Corruption>,
+    /// Whether the last cell is dropped, so that there is one more cell index than cells.
+    drop_last_cell: bool,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Corruption {
+    /// The position of the cell in the list of given cells.
+    position: u8,
+    /// The field element in the cell that is changed.
+    field_element: u8,
+    /// The bits of the last byte of the field element that are flipped.
+    ///
+    /// Only the last byte is changed, so that the field element stays canonical.
+    mask: u8,
+}
+
+/// Returns the cells and proofs of a fixed blob, which are computed once, since it is expensive.
+fn cells_and_proofs() -> &'static CellsAndKZGProofs {
+    static CELLS_AND_PROOFS: OnceLock<CellsAndKZGProofs> = OnceLock::new();
+    CELLS_AND_PROOFS.get_or_init(|| {
+        let mut blob = vec![0u8; BYTES_PER_BLOB];
+        for (i, field_element) in blob.chunks_exact_mut(BYTES_PER_FIELD_ELEMENT).enumerate() {
+            let value = (i as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            field_element[BYTES_PER_FIELD_ELEMENT - 8..].copy_from_slice(&value.to_be_bytes());
+        }
+        ctx()
+            .compute_cells_and_kzg_proofs(blob.as_slice().try_into().unwrap())
+            .unwrap()
+    })
+}
+
+fuzz_target!(|input: Input| {
+    let (cells, proofs) = cells_and_proofs();
+    let zero_cell = Cell::new([0u8; BYTES_PER_CELL]);
+
+    let mut cell_indices: Vec<u64> = (0..CELLS_PER_EXT_BLOB as u64)
+        .filter(|&index| input.present & (1 << index) != 0)
+        .chain(input.extra_indices.iter().map(|&index| u64::from(index)))
+        .collect();
+    if cell_indices.is_empty() {
+        return;
+    }
+    for &(i, j) in &input.swaps {
+        let len = cell_indices.len();
+        cell_indices.swap(i as usize % len, j as usize % len);
+    }
+
+    let mut given_cells: Vec<Cell> = cell_indices
+        .iter()
+        .map(|&index| cells.get(index as usize).unwrap_or(&zero_cell).clone())
+        .collect();
+    let corrupted = match &input.corruption {
+        Some(corruption) if corruption.mask != 0 => {
+            let cell = &mut given_cells[corruption.position as usize % cell_indices.len()];
+            let field_element = corruption.field_element as usize % FIELD_ELEMENTS_PER_CELL;
+            cell[(field_element + 1) * BYTES_PER_FIELD_ELEMENT - 1] ^= corruption.mask;
+            true
+        }
+        _ => false,
+    };
+    if input.drop_last_cell {
+        given_cells.pop();
+    }
+
+    // An out of range index is rejected when it is converted to a `CellIndex`
+    let result = CellIndex::try_from_u64s(&cell_indices)
+        .map_err(|err| Error::from(RecoveryError::from(err)))
+        .and_then(|cell_indices| {
+            ctx().recover_cells_and_kzg_proofs(
+                cell_indices,
+                given_cells.iter().map(|cell| &**cell).collect(),
+            )
+        });
+
+    let num_unique = cell_indices.iter().collect::<HashSet<_>>().len();
+    let is_valid_pattern = !input.drop_last_cell
+        && cell_indices
+            .iter()
+            .all(|&index| index < CELLS_PER_EXT_BLOB as u64)
+        && num_unique == cell_indices.len()
+        && num_unique >= MIN_CELLS;
+    if !is_valid_pattern {
+        assert!(result.is_err(), "accepted an invalid erasure pattern");
+        return;
+    }
+
+    // With exactly half of the cells, any cells are the cells of some blob. With more than half,
+    // a corrupted cell is not consistent with the other cells, and must be rejected
+    if corrupted && num_unique > MIN_CELLS {
+        assert!(
+            result.is_err(),
+            "accepted cells that are not from the same blob"
+        );
+        return;
+    }
+    let (recovered_cells, recovered_proofs) = result.expect("could not recover a valid pattern");
+    for (&index, cell) in cell_indices.iter().zip(&given_cells) {
+        assert_eq!(&recovered_cells[index as usize], cell);
+    }
+    if !corrupted {
+        assert_eq!(&recovered_cells, cells);
+        assert_eq!(&recovered_proofs, proofs);
+    }
+});
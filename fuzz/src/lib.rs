@@ -0,0 +1,21 @@
+//! Helpers that are shared by the fuzz targets.
+
+use rust_eth_kzg::DASContext;
+use std::sync::OnceLock;
+
+/// Returns a context that is shared by all of the runs of a fuzz target, since it is expensive to create.
+pub fn ctx() -> &'static DASContext {
+    static CTX: OnceLock<DASContext> = OnceLock::new();
+    CTX.get_or_init(DASContext::default)
+}
+
+/// Copies `data` into an array of `N` bytes, truncating it or padding it with zeros.
+///
+/// This lets every input exercise the parsing of an item, instead of only the inputs
+/// that have the right length.
+pub fn to_array<const N: usize>(data: &[u8]) -> Box<[u8; N]> {
+    let mut array = Box::new([0u8; N]);
+    let len = data.len().min(N);
+    array[..len].copy_from_slice(&data[..len]);
+    array
+}
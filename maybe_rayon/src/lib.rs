@@ -1,3 +1,8 @@
+#[cfg(all(feature = "singlethreaded", feature = "multithreaded"))]
+compile_error!(
+    "the `singlethreaded` and `multithreaded` features cannot be enabled at the same time"
+);
+
 #[cfg(feature = "multithreaded")]
 mod multi_threaded;
 #[cfg(not(feature = "multithreaded"))]
@@ -11,6 +16,8 @@ pub use single_threaded::*;
 pub mod prelude {
     pub use crate::MaybeParallelRefExt;
     pub use crate::MaybeParallelRefMutExt;
+    pub use crate::MaybeParallelSliceExt;
+    pub use crate::MaybeParallelSliceMutExt;
     pub use crate::*;
     #[cfg(feature = "multithreaded")]
     pub use rayon::prelude::*;
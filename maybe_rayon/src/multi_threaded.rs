@@ -1,7 +1,10 @@
+pub use rayon::current_num_threads;
 pub use rayon::iter::IntoParallelIterator;
 pub use rayon::iter::IntoParallelRefIterator;
 pub use rayon::iter::IntoParallelRefMutIterator;
 pub use rayon::iter::ParallelIterator;
+pub use rayon::slice::ParallelSlice;
+pub use rayon::slice::ParallelSliceMut;
 
 pub trait MaybeParallelExt: IntoParallelIterator {
     fn maybe_into_par_iter(self) -> <Self as IntoParallelIterator>::Iter
@@ -13,17 +16,37 @@ pub trait MaybeParallelExt: IntoParallelIterator {
 }
 
 pub trait MaybeParallelRefExt: for<'a> IntoParallelRefIterator<'a> {
-    fn maybe_par_iter(&self) -> <Self as IntoParallelRefIterator>::Iter {
+    fn maybe_par_iter(&self) -> <Self as IntoParallelRefIterator<'_>>::Iter {
         self.par_iter()
     }
 }
 
 pub trait MaybeParallelRefMutExt: for<'a> IntoParallelRefMutIterator<'a> {
-    fn maybe_par_iter_mut(&mut self) -> <Self as IntoParallelRefMutIterator>::Iter {
+    fn maybe_par_iter_mut(&mut self) -> <Self as IntoParallelRefMutIterator<'_>>::Iter {
         self.par_iter_mut()
     }
 }
 
+pub trait MaybeParallelSliceExt<T: Sync> {
+    fn maybe_par_chunks(&self, chunk_size: usize) -> rayon::slice::Chunks<'_, T>;
+}
+
+impl<T: Sync> MaybeParallelSliceExt<T> for [T] {
+    fn maybe_par_chunks(&self, chunk_size: usize) -> rayon::slice::Chunks<'_, T> {
+        self.par_chunks(chunk_size)
+    }
+}
+
+pub trait MaybeParallelSliceMutExt<T: Send> {
+    fn maybe_par_chunks_mut(&mut self, chunk_size: usize) -> rayon::slice::ChunksMut<'_, T>;
+}
+
+impl<T: Send> MaybeParallelSliceMutExt<T> for [T] {
+    fn maybe_par_chunks_mut(&mut self, chunk_size: usize) -> rayon::slice::ChunksMut<'_, T> {
+        self.par_chunks_mut(chunk_size)
+    }
+}
+
 impl<T: IntoParallelIterator> MaybeParallelExt for T {}
 impl<T: for<'a> IntoParallelRefIterator<'a>> MaybeParallelRefExt for T {}
 impl<T: for<'a> IntoParallelRefMutIterator<'a>> MaybeParallelRefMutExt for T {}
@@ -1,6 +1,12 @@
 pub use std::iter::IntoIterator;
 pub use std::iter::Iterator;
 
+/// The number of threads that work is split across, which is always one
+/// when running single threaded.
+pub const fn current_num_threads() -> usize {
+    1
+}
+
 pub trait MaybeParallelExt: IntoIterator {
     fn maybe_into_par_iter(self) -> <Self as IntoIterator>::IntoIter
     where
@@ -26,6 +32,26 @@ pub trait MaybeParallelRefMutExt {
     fn maybe_par_iter_mut(&mut self) -> Self::Iter<'_>;
 }
 
+pub trait MaybeParallelSliceExt<T> {
+    fn maybe_par_chunks(&self, chunk_size: usize) -> std::slice::Chunks<'_, T>;
+}
+
+impl<T> MaybeParallelSliceExt<T> for [T] {
+    fn maybe_par_chunks(&self, chunk_size: usize) -> std::slice::Chunks<'_, T> {
+        self.chunks(chunk_size)
+    }
+}
+
+pub trait MaybeParallelSliceMutExt<T> {
+    fn maybe_par_chunks_mut(&mut self, chunk_size: usize) -> std::slice::ChunksMut<'_, T>;
+}
+
+impl<T> MaybeParallelSliceMutExt<T> for [T] {
+    fn maybe_par_chunks_mut(&mut self, chunk_size: usize) -> std::slice::ChunksMut<'_, T> {
+        self.chunks_mut(chunk_size)
+    }
+}
+
 impl<T: IntoIterator> MaybeParallelExt for T {}
 
 impl<T: IntoIterator> MaybeParallelRefExt for T